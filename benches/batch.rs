@@ -0,0 +1,39 @@
+//! Cost of validating the recipients of a large /token/mint/batch request, with and without
+//! the per-request public key cache
+//!
+//! Run with `cargo bench --bench batch`. Results are in the README.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use solana_http_server::utils::validation::{self, PubkeyCache};
+use solana_sdk::pubkey::Pubkey;
+
+const RECIPIENTS: usize = 1000;
+
+fn validate_recipients(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate_1000_recipients");
+    // All distinct, then a few wallets each minted to many times
+    for distinct in [RECIPIENTS, 10] {
+        let wallets: Vec<String> = (0..distinct).map(|_| Pubkey::new_unique().to_string()).collect();
+        let destinations: Vec<&String> = wallets.iter().cycle().take(RECIPIENTS).collect();
+
+        group.bench_with_input(BenchmarkId::new("uncached", distinct), &destinations, |b, destinations| {
+            b.iter(|| {
+                for destination in destinations {
+                    validation::validate_pubkey(destination, "destination").unwrap();
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("cached", distinct), &destinations, |b, destinations| {
+            b.iter(|| {
+                let mut cache = PubkeyCache::default();
+                for destination in destinations {
+                    cache.validate(destination, "destination").unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, validate_recipients);
+criterion_main!(benches);
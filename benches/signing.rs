@@ -0,0 +1,45 @@
+//! Throughput of the offline key and signature operations behind /keypair, /message/sign and
+//! /message/verify
+//!
+//! Run with `cargo bench --bench signing`. Baseline numbers are in the README.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use solana_http_server::services::solana::SolanaService;
+use std::hint::black_box;
+
+const MESSAGE: &str = "Hello, Solana!";
+
+fn generate_keypair(c: &mut Criterion) {
+    let service = SolanaService::new();
+    c.bench_function("generate_keypair", |b| b.iter(|| service.generate_keypair().unwrap()));
+}
+
+fn sign_message(c: &mut Criterion) {
+    let service = SolanaService::new();
+    let keypair = service.generate_keypair().unwrap();
+    c.bench_function("sign_message", |b| {
+        b.iter(|| service.sign_message(black_box(MESSAGE), None, &keypair.secret).unwrap())
+    });
+}
+
+fn verify_message(c: &mut Criterion) {
+    let service = SolanaService::new();
+    let keypair = service.generate_keypair().unwrap();
+    let signed = service.sign_message(MESSAGE, None, &keypair.secret).unwrap();
+
+    let mut group = c.benchmark_group("verify_message");
+    for (name, strict) in [("lenient", false), ("strict", true)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let verified = service
+                    .verify_message(black_box(MESSAGE), None, &signed.signature, &signed.pubkey, strict)
+                    .unwrap();
+                assert!(verified.valid);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generate_keypair, sign_message, verify_message);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+//! Exposes the resolved versions of the Solana crates the server is compiled against, for
+//! GET /version
+//!
+//! Cargo.toml only holds version requirements, so the versions are read from Cargo.lock. A
+//! build without a lock file reports "unknown".
+
+use std::path::Path;
+
+/// Dependencies whose versions are exported, with the environment variable each is set in
+const REPORTED: [(&str, &str); 2] = [("solana-sdk", "SOLANA_SDK_VERSION"), ("spl-token", "SPL_TOKEN_VERSION")];
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let lock_path = Path::new(&manifest_dir).join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_path.display());
+
+    let lock = std::fs::read_to_string(&lock_path)
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok());
+    let package_name = std::env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME is set by cargo");
+
+    for (dependency, env_var) in REPORTED {
+        let version = lock
+            .as_ref()
+            .and_then(|lock| locked_version(lock, &package_name, dependency))
+            .unwrap_or_else(|| "unknown".to_string());
+        println!("cargo:rustc-env={}={}", env_var, version);
+    }
+}
+
+/// The version of `dependency` that `package` depends on in the lock file
+///
+/// A dependency entry names its version ("spl-token 4.0.0") only when several versions of the
+/// crate are locked; otherwise the single locked package is the one used.
+fn locked_version(lock: &toml::Table, package: &str, dependency: &str) -> Option<String> {
+    let packages = lock.get("package")?.as_array()?;
+    let field = |entry: &toml::Value, key: &str| entry.get(key).and_then(|value| value.as_str()).map(str::to_string);
+
+    let root = packages.iter().find(|entry| field(entry, "name").as_deref() == Some(package))?;
+    let entry = root
+        .get("dependencies")?
+        .as_array()?
+        .iter()
+        .filter_map(|entry| entry.as_str())
+        .find(|entry| entry.split(' ').next() == Some(dependency))?;
+    if let Some(version) = entry.split(' ').nth(1) {
+        return Some(version.to_string());
+    }
+
+    packages
+        .iter()
+        .find(|entry| field(entry, "name").as_deref() == Some(dependency))
+        .and_then(|entry| field(entry, "version"))
+}
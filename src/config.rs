@@ -0,0 +1,1560 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::HeaderValue;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    system_program,
+};
+use utoipa::{OpenApi, ToSchema};
+use zeroize::Zeroizing;
+
+use crate::services::rpc::{PoolConfig, RetryPolicy, RpcClientKind, DEFAULT_RPC_URL};
+use crate::utils::validation;
+
+/// Environment variable naming the TOML or JSON config file to load
+pub const CONFIG_FILE_ENV: &str = "CONFIG_FILE";
+
+/// Most retries SOLANA_RPC_MAX_RETRIES / `rpc.max_retries` may ask for
+const MAX_RPC_RETRIES: u32 = 10;
+
+/// Shortest `hmac.secret` accepted: as long as the SHA-256 output, as HMAC recommends
+const MIN_HMAC_SECRET_LEN: usize = 32;
+
+/// Routes given longer than the default `request_timeout` unless configured otherwise: the
+/// airdrop routes make several RPC calls of up to 30s each, and /keypair/funded then waits up
+/// to 30s for confirmation. Cutting them off could lose a keypair the airdrop already funded.
+const DEFAULT_ROUTE_TIMEOUTS: [(&str, Duration); 2] = [
+    ("/keypair/funded", Duration::from_secs(120)),
+    ("/rpc/airdrop", Duration::from_secs(120)),
+];
+
+/// A public Solana cluster, used to pick a default RPC URL and reported in responses so
+/// clients can check which network they are talking to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cluster {
+    MainnetBeta,
+    Devnet,
+    Testnet,
+    /// A solana-test-validator on this machine
+    Localnet,
+}
+
+impl Cluster {
+    /// The cluster's public RPC endpoint
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            Cluster::MainnetBeta => "https://api.mainnet-beta.solana.com",
+            Cluster::Devnet => DEFAULT_RPC_URL,
+            Cluster::Testnet => "https://api.testnet.solana.com",
+            Cluster::Localnet => "http://127.0.0.1:8899",
+        }
+    }
+
+    /// The cluster an RPC URL belongs to, when it is a public endpoint or a local validator
+    pub fn from_rpc_url(url: &str) -> Option<Self> {
+        let url = url.trim_end_matches('/');
+        let host = url.split_once("://").map_or(url, |(_, rest)| rest);
+        if host.starts_with("127.0.0.1:") || host.starts_with("localhost:") {
+            return Some(Cluster::Localnet);
+        }
+        [Cluster::MainnetBeta, Cluster::Devnet, Cluster::Testnet]
+            .into_iter()
+            .find(|cluster| cluster.rpc_url() == url)
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Cluster::MainnetBeta => "mainnet-beta",
+            Cluster::Devnet => "devnet",
+            Cluster::Testnet => "testnet",
+            Cluster::Localnet => "localnet",
+        })
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "mainnet-beta" | "mainnet" => Ok(Cluster::MainnetBeta),
+            "devnet" => Ok(Cluster::Devnet),
+            "testnet" => Ok(Cluster::Testnet),
+            "localnet" | "localhost" => Ok(Cluster::Localnet),
+            other => Err(format!(
+                "unknown cluster '{}': expected mainnet-beta, devnet, testnet or localnet",
+                other
+            )),
+        }
+    }
+}
+
+/// How log lines are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// One line per event, with timestamp, level, target and fields
+    #[default]
+    Full,
+    /// Like Full, but shorter
+    Compact,
+    /// Multi-line and easy to read, for development
+    Pretty,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "full" => Ok(LogFormat::Full),
+            "compact" => Ok(LogFormat::Compact),
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("unknown log format '{}': expected full, compact, pretty or json", other)),
+        }
+    }
+}
+
+/// How binary values in responses (instruction data, messages and transactions) are encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinaryEncoding {
+    /// Standard base64, with padding
+    #[default]
+    Base64,
+    /// URL-safe base64 without padding, as JWT and other web tooling use
+    Base64Url,
+}
+
+impl BinaryEncoding {
+    pub fn encode(self, bytes: impl AsRef<[u8]>) -> String {
+        match self {
+            BinaryEncoding::Base64 => general_purpose::STANDARD.encode(bytes),
+            BinaryEncoding::Base64Url => general_purpose::URL_SAFE_NO_PAD.encode(bytes),
+        }
+    }
+}
+
+impl FromStr for BinaryEncoding {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "base64" => Ok(BinaryEncoding::Base64),
+            "base64url" => Ok(BinaryEncoding::Base64Url),
+            other => Err(format!("unknown binary encoding '{}': expected base64 or base64url", other)),
+        }
+    }
+}
+
+/// Where the audit log of key generation, signing and verification is written
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AuditSink {
+    /// No audit log is kept
+    #[default]
+    Off,
+    /// Alongside the request log on stdout
+    Stdout,
+    /// Appended to a file, which is created if it doesn't exist
+    File(PathBuf),
+}
+
+impl FromStr for AuditSink {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim() {
+            "" => Err("audit_log must be off, stdout or a file path".to_string()),
+            "off" => Ok(AuditSink::Off),
+            "stdout" => Ok(AuditSink::Stdout),
+            path => Ok(AuditSink::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Logging settings, applied when the tracing subscriber is installed at startup
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    /// An EnvFilter directive such as "info" or "info,solana_http_server=debug"
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self { level: "info".to_string(), format: LogFormat::default() }
+    }
+}
+
+/// Settings for the Solana RPC and WebSocket endpoints
+#[derive(Debug, Clone)]
+pub struct RpcConfig {
+    pub url: String,
+    /// Endpoints tried in order when `url` fails with a transient error
+    pub fallback_urls: Vec<String>,
+    pub ws_url: Option<String>,
+    pub client: RpcClientKind,
+    pub commitment: CommitmentConfig,
+    pub retry: RetryPolicy,
+    pub pool: PoolConfig,
+    /// False when rpc.url is set to an empty string, for deployments that only serve the
+    /// offline routes; the RPC-backed routes then return 503
+    pub enabled: bool,
+    /// Whether an endpoint was chosen (an RPC URL or a cluster) rather than defaulted
+    configured: bool,
+}
+
+impl RpcConfig {
+    /// Whether an RPC endpoint was configured explicitly, for reads that have an offline fallback
+    pub fn is_configured(&self) -> bool {
+        self.configured
+    }
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_RPC_URL.to_string(),
+            fallback_urls: Vec::new(),
+            ws_url: None,
+            client: RpcClientKind::default(),
+            commitment: CommitmentConfig::confirmed(),
+            retry: RetryPolicy::default(),
+            pool: PoolConfig::default(),
+            enabled: true,
+            configured: false,
+        }
+    }
+}
+
+/// Limits on the token parameters clients may request, for operators of hosted instances, and
+/// the defaults used when a request leaves them out
+#[derive(Debug, Clone)]
+pub struct TokenPolicy {
+    /// Most decimals /token/create accepts
+    pub max_decimals: u8,
+    /// Largest amount, in base units, a single mint_to instruction may mint
+    pub max_mint_amount: u64,
+    /// Decimals /token/create and /token/create/new use when a request has none; without one,
+    /// requests must send `decimals`
+    pub default_decimals: Option<u8>,
+    /// Freeze authority /token/create and /token/create/new give a mint when a request sends
+    /// neither `freezeAuthority` nor `disable_freeze_authority`
+    pub default_freeze_authority: DefaultFreezeAuthority,
+}
+
+impl Default for TokenPolicy {
+    fn default() -> Self {
+        Self {
+            max_decimals: validation::MAX_DECIMALS,
+            max_mint_amount: u64::MAX,
+            default_decimals: None,
+            default_freeze_authority: DefaultFreezeAuthority::default(),
+        }
+    }
+}
+
+/// Freeze authority of a new mint whose request doesn't choose one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefaultFreezeAuthority {
+    /// The mint authority can also freeze accounts
+    #[default]
+    MintAuthority,
+    /// No account can ever freeze the mint's token accounts
+    None,
+    /// A fixed account, such as an issuer's compliance key
+    Account(Pubkey),
+}
+
+impl DefaultFreezeAuthority {
+    /// The freeze authority for a mint created by `mint_authority`
+    pub fn resolve(self, mint_authority: Pubkey) -> Option<Pubkey> {
+        match self {
+            DefaultFreezeAuthority::MintAuthority => Some(mint_authority),
+            DefaultFreezeAuthority::None => None,
+            DefaultFreezeAuthority::Account(account) => Some(account),
+        }
+    }
+}
+
+impl FromStr for DefaultFreezeAuthority {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim() {
+            "mint-authority" | "mint_authority" => Ok(DefaultFreezeAuthority::MintAuthority),
+            "none" => Ok(DefaultFreezeAuthority::None),
+            other => match Pubkey::from_str(other) {
+                Ok(account) if account == Pubkey::default() => {
+                    Err(format!("freeze authority can't be {}, the all-zeros address; use none for no freeze authority", account))
+                }
+                Ok(account) => Ok(DefaultFreezeAuthority::Account(account)),
+                Err(_) => Err(format!("unknown freeze authority '{}': expected mint-authority, none or a public key", other)),
+            },
+        }
+    }
+}
+
+/// Settings for POST /relay, which pays the network fees of client transactions
+#[derive(Clone)]
+pub struct RelayConfig {
+    /// Signs relayed transactions as their fee payer; /relay returns 503 without it
+    pub keypair: Option<Arc<Keypair>>,
+    /// Programs a relayed transaction may call; a transaction calling any other is rejected
+    pub allowed_programs: Vec<Pubkey>,
+}
+
+impl RelayConfig {
+    /// Programs relayed by default: plain transfers, token and associated token account
+    /// instructions, and memos. The compute budget program is left out, since a priority fee
+    /// set with it is paid by the relayer.
+    pub fn default_allowed_programs() -> Vec<Pubkey> {
+        vec![
+            system_program::id(),
+            spl_token::id(),
+            spl_token_2022::id(),
+            spl_associated_token_account::id(),
+            spl_memo::id(),
+        ]
+    }
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        Self { keypair: None, allowed_programs: Self::default_allowed_programs() }
+    }
+}
+
+// Shows only the relayer's public key, so logging the configuration can't leak the secret
+impl fmt::Debug for RelayConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelayConfig")
+            .field("relayer", &self.keypair.as_ref().map(|keypair| keypair.pubkey()))
+            .field("allowed_programs", &self.allowed_programs)
+            .finish()
+    }
+}
+
+/// Settings for HMAC request signing, the alternative to API keys for server-to-server callers
+#[derive(Clone)]
+pub struct HmacConfig {
+    /// Shared secret requests are signed with; None turns signing off
+    pub secret: Option<Zeroizing<String>>,
+    /// How far a request's X-Timestamp may be from the server's clock before it is rejected
+    pub max_skew: Duration,
+}
+
+impl HmacConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.secret.is_some()
+    }
+}
+
+impl Default for HmacConfig {
+    fn default() -> Self {
+        Self { secret: None, max_skew: Duration::from_secs(5 * 60) }
+    }
+}
+
+// Never shows the secret, so logging the configuration can't leak it
+impl fmt::Debug for HmacConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacConfig")
+            .field("enabled", &self.is_enabled())
+            .field("max_skew", &self.max_skew)
+            .finish()
+    }
+}
+
+/// Server configuration, validated at startup
+///
+/// Built from defaults, then the file named by CONFIG_FILE (TOML, or JSON for a `.json` file),
+/// then environment variables, each overriding the last.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub bind_address: SocketAddr,
+    /// The configured cluster, or the one the RPC URL belongs to; None for other RPC providers
+    pub cluster: Option<Cluster>,
+    pub rpc: RpcConfig,
+    /// Origins allowed to make cross-origin requests; empty allows any origin
+    pub cors_allowed_origins: Vec<HeaderValue>,
+    /// Requests each client may make per minute; None disables rate limiting
+    pub rate_limit_per_minute: Option<u32>,
+    /// Requests to the RPC-backed and other expensive routes that may run at once, across all
+    /// clients; None leaves them unlimited
+    pub max_concurrent_expensive_requests: Option<usize>,
+    /// Keys accepted in the X-API-Key header; empty disables authentication
+    pub api_keys: Vec<String>,
+    pub hmac: HmacConfig,
+    /// Whether the routes that generate secret keys (/keypair, /keypair/from-seed,
+    /// /keypair/derive/batch and /keypair/funded) are served
+    pub enable_keypair_generation: bool,
+    pub token: TokenPolicy,
+    /// Programs the transaction signing, bundling and relay endpoints may assemble or sign
+    /// instructions for; empty allows any program
+    pub allowed_programs: Vec<Pubkey>,
+    pub relay: RelayConfig,
+    /// Encoding of instruction data, messages and transactions in responses
+    pub binary_encoding: BinaryEncoding,
+    /// Whether responses are wrapped in `{ success, data }` unless a request asks for `?raw=true`
+    pub response_envelope: bool,
+    /// How long Idempotency-Key responses are replayed
+    pub idempotency_ttl: Duration,
+    /// Longest a request may take before it is abandoned with a 504
+    pub request_timeout: Duration,
+    /// Routes, by path, given a timeout other than `request_timeout`, starting from
+    /// DEFAULT_ROUTE_TIMEOUTS
+    pub route_timeouts: BTreeMap<String, Duration>,
+    /// How long a /auth/challenge nonce can be redeemed at /auth/verify
+    pub challenge_ttl: Duration,
+    pub log: LogConfig,
+    /// Where key generation, signing and verification events are recorded
+    pub audit_log: AuditSink,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            bind_address: SocketAddr::from(([0, 0, 0, 0], 8080)),
+            // The default RPC URL is devnet's
+            cluster: Some(Cluster::Devnet),
+            rpc: RpcConfig::default(),
+            cors_allowed_origins: Vec::new(),
+            rate_limit_per_minute: None,
+            max_concurrent_expensive_requests: None,
+            api_keys: Vec::new(),
+            hmac: HmacConfig::default(),
+            enable_keypair_generation: true,
+            token: TokenPolicy::default(),
+            allowed_programs: Vec::new(),
+            relay: RelayConfig::default(),
+            binary_encoding: BinaryEncoding::default(),
+            response_envelope: true,
+            idempotency_ttl: Duration::from_secs(24 * 60 * 60),
+            request_timeout: Duration::from_secs(30),
+            route_timeouts: DEFAULT_ROUTE_TIMEOUTS.iter().map(|(route, timeout)| (route.to_string(), *timeout)).collect(),
+            challenge_ttl: Duration::from_secs(5 * 60),
+            audit_log: AuditSink::default(),
+            log: LogConfig::default(),
+        }
+    }
+}
+
+/// Why the configuration couldn't be loaded
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {message}")]
+    Read { path: String, message: String },
+
+    #[error("Invalid config file {path}: {message}")]
+    Parse { path: String, message: String },
+
+    #[error("Invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Invalid(Vec<String>),
+}
+
+/// The config file's layout; every field is optional so a file only needs what it changes
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    bind_address: Option<String>,
+    cluster: Option<String>,
+    rpc: FileRpcConfig,
+    cors: FileCorsConfig,
+    rate_limit: FileRateLimitConfig,
+    max_concurrent_expensive_requests: Option<usize>,
+    api_keys: Option<Vec<String>>,
+    hmac: FileHmacConfig,
+    enable_keypair_generation: Option<bool>,
+    idempotency: FileIdempotencyConfig,
+    auth: FileAuthConfig,
+    token: FileTokenConfig,
+    allowed_programs: Option<Vec<String>>,
+    relay: FileRelayConfig,
+    binary_encoding: Option<String>,
+    response_envelope: Option<bool>,
+    request_timeout_secs: Option<u64>,
+    route_timeout_secs: Option<BTreeMap<String, u64>>,
+    log: FileLogConfig,
+    audit_log: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileRpcConfig {
+    url: Option<String>,
+    fallback_urls: Option<Vec<String>>,
+    ws_url: Option<String>,
+    client: Option<String>,
+    commitment: Option<String>,
+    max_retries: Option<u32>,
+    retry_base_ms: Option<u64>,
+    pool_size: Option<usize>,
+    pool_timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileCorsConfig {
+    allowed_origins: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileRateLimitConfig {
+    requests_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileTokenConfig {
+    max_decimals: Option<u8>,
+    max_mint_amount: Option<u64>,
+    default_decimals: Option<u8>,
+    default_freeze_authority: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileRelayConfig {
+    /// A solana-keygen keypair file; the secret itself is never put in the config
+    keypair_path: Option<String>,
+    allowed_programs: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileHmacConfig {
+    secret: Option<String>,
+    max_skew_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileIdempotencyConfig {
+    ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileAuthConfig {
+    challenge_ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileLogConfig {
+    level: Option<String>,
+    format: Option<String>,
+}
+
+impl Config {
+    /// Loads the configuration from CONFIG_FILE (if set) and the process environment
+    pub fn load() -> Result<Self, ConfigError> {
+        let env = |name: &str| std::env::var(name).ok();
+        let file = match env(CONFIG_FILE_ENV) {
+            Some(path) => {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|e| ConfigError::Read { path: path.clone(), message: e.to_string() })?;
+                Some((path, contents))
+            }
+            None => None,
+        };
+
+        Self::from_sources(file.as_ref().map(|(path, contents)| (path.as_str(), contents.as_str())), env)
+    }
+
+    /// How long a request to `route` may run: its entry in `route_timeouts`, or `request_timeout`
+    pub fn timeout_for(&self, route: &str) -> Duration {
+        self.route_timeouts.get(route).copied().unwrap_or(self.request_timeout)
+    }
+
+    /// Builds the configuration from an optional (path, contents) config file and an
+    /// environment lookup, so tests don't have to touch the process environment
+    fn from_sources(
+        file: Option<(&str, &str)>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let mut raw = match file {
+            Some((path, contents)) => parse_file(path, contents)?,
+            None => FileConfig::default(),
+        };
+
+        let mut errors = Vec::new();
+        raw.apply_env(&env, &mut errors);
+        let config = raw.validate(&mut errors);
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+}
+
+fn parse_file(path: &str, contents: &str) -> Result<FileConfig, ConfigError> {
+    let is_json = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    let parsed = if is_json {
+        serde_json::from_str(contents).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    };
+
+    parsed.map_err(|message| ConfigError::Parse { path: path.to_string(), message })
+}
+
+/// Parses a list of program ids, recording an error naming `setting` for each invalid entry
+fn parse_program_ids(setting: &str, programs: &[String], errors: &mut Vec<String>) -> Vec<Pubkey> {
+    programs
+        .iter()
+        .filter_map(|program| {
+            Pubkey::from_str(program)
+                .map_err(|_| errors.push(format!("{} entries must be program ids, got '{}'", setting, program)))
+                .ok()
+        })
+        .collect()
+}
+
+/// Splits a comma-separated environment value, dropping empty entries
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect()
+}
+
+impl FileConfig {
+    /// Overrides file values with any of the supported environment variables that are set
+    fn apply_env(&mut self, env: &impl Fn(&str) -> Option<String>, errors: &mut Vec<String>) {
+        fn number<T: FromStr>(name: &str, value: String, errors: &mut Vec<String>) -> Option<T> {
+            let parsed = value.trim().parse().ok();
+            if parsed.is_none() {
+                errors.push(format!("{} must be a non-negative integer, got '{}'", name, value));
+            }
+            parsed
+        }
+        fn boolean(name: &str, value: String, errors: &mut Vec<String>) -> Option<bool> {
+            match value.trim().to_lowercase().as_str() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => {
+                    errors.push(format!("{} must be true or false, got '{}'", name, value));
+                    None
+                }
+            }
+        }
+
+        if let Some(value) = env("BIND_ADDRESS") {
+            self.bind_address = Some(value);
+        }
+        if let Some(value) = env("SOLANA_CLUSTER") {
+            self.cluster = Some(value);
+        }
+        if let Some(value) = env("SOLANA_RPC_URL") {
+            self.rpc.url = Some(value);
+        }
+        if let Some(value) = env("SOLANA_RPC_FALLBACK_URLS") {
+            self.rpc.fallback_urls = Some(split_list(&value));
+        }
+        if let Some(value) = env("SOLANA_WS_URL") {
+            self.rpc.ws_url = Some(value);
+        }
+        if let Some(value) = env("SOLANA_RPC_CLIENT") {
+            self.rpc.client = Some(value);
+        }
+        if let Some(value) = env("SOLANA_COMMITMENT") {
+            self.rpc.commitment = Some(value);
+        }
+        if let Some(value) = env("SOLANA_RPC_MAX_RETRIES") {
+            self.rpc.max_retries = number("SOLANA_RPC_MAX_RETRIES", value, errors).or(self.rpc.max_retries);
+        }
+        if let Some(value) = env("SOLANA_RPC_RETRY_BASE_MS") {
+            self.rpc.retry_base_ms = number("SOLANA_RPC_RETRY_BASE_MS", value, errors).or(self.rpc.retry_base_ms);
+        }
+        if let Some(value) = env("SOLANA_RPC_POOL_SIZE") {
+            self.rpc.pool_size = number("SOLANA_RPC_POOL_SIZE", value, errors).or(self.rpc.pool_size);
+        }
+        if let Some(value) = env("SOLANA_RPC_POOL_TIMEOUT_MS") {
+            self.rpc.pool_timeout_ms = number("SOLANA_RPC_POOL_TIMEOUT_MS", value, errors).or(self.rpc.pool_timeout_ms);
+        }
+        if let Some(value) = env("CORS_ALLOWED_ORIGINS") {
+            self.cors.allowed_origins = Some(split_list(&value));
+        }
+        if let Some(value) = env("RATE_LIMIT_PER_MINUTE") {
+            self.rate_limit.requests_per_minute =
+                number("RATE_LIMIT_PER_MINUTE", value, errors).or(self.rate_limit.requests_per_minute);
+        }
+        if let Some(value) = env("MAX_CONCURRENT_EXPENSIVE_REQUESTS") {
+            self.max_concurrent_expensive_requests = number("MAX_CONCURRENT_EXPENSIVE_REQUESTS", value, errors)
+                .or(self.max_concurrent_expensive_requests);
+        }
+        if let Some(value) = env("API_KEYS") {
+            self.api_keys = Some(split_list(&value));
+        }
+        if let Some(value) = env("HMAC_SECRET") {
+            self.hmac.secret = Some(value);
+        }
+        if let Some(value) = env("HMAC_MAX_SKEW_SECS") {
+            self.hmac.max_skew_secs = number("HMAC_MAX_SKEW_SECS", value, errors).or(self.hmac.max_skew_secs);
+        }
+        if let Some(value) = env("ENABLE_KEYPAIR_GENERATION") {
+            self.enable_keypair_generation = boolean("ENABLE_KEYPAIR_GENERATION", value, errors).or(self.enable_keypair_generation);
+        }
+        if let Some(value) = env("TOKEN_MAX_DECIMALS") {
+            self.token.max_decimals = number("TOKEN_MAX_DECIMALS", value, errors).or(self.token.max_decimals);
+        }
+        if let Some(value) = env("TOKEN_MAX_MINT_AMOUNT") {
+            self.token.max_mint_amount = number("TOKEN_MAX_MINT_AMOUNT", value, errors).or(self.token.max_mint_amount);
+        }
+        if let Some(value) = env("TOKEN_DEFAULT_DECIMALS") {
+            self.token.default_decimals = number("TOKEN_DEFAULT_DECIMALS", value, errors).or(self.token.default_decimals);
+        }
+        if let Some(value) = env("TOKEN_DEFAULT_FREEZE_AUTHORITY") {
+            self.token.default_freeze_authority = Some(value);
+        }
+        if let Some(value) = env("ALLOWED_PROGRAMS") {
+            self.allowed_programs = Some(split_list(&value));
+        }
+        if let Some(value) = env("RELAY_KEYPAIR_PATH") {
+            self.relay.keypair_path = Some(value);
+        }
+        if let Some(value) = env("RELAY_ALLOWED_PROGRAMS") {
+            self.relay.allowed_programs = Some(split_list(&value));
+        }
+        if let Some(value) = env("BINARY_ENCODING") {
+            self.binary_encoding = Some(value);
+        }
+        if let Some(value) = env("IDEMPOTENCY_TTL_SECS") {
+            self.idempotency.ttl_secs = number("IDEMPOTENCY_TTL_SECS", value, errors).or(self.idempotency.ttl_secs);
+        }
+        if let Some(value) = env("AUTH_CHALLENGE_TTL_SECS") {
+            self.auth.challenge_ttl_secs = number("AUTH_CHALLENGE_TTL_SECS", value, errors).or(self.auth.challenge_ttl_secs);
+        }
+        if let Some(value) = env("AUDIT_LOG") {
+            self.audit_log = Some(value);
+        }
+        if let Some(value) = env("RESPONSE_ENVELOPE") {
+            self.response_envelope = boolean("RESPONSE_ENVELOPE", value, errors).or(self.response_envelope);
+        }
+        if let Some(value) = env("REQUEST_TIMEOUT_SECS") {
+            self.request_timeout_secs = number("REQUEST_TIMEOUT_SECS", value, errors).or(self.request_timeout_secs);
+        }
+        if let Some(value) = env("ROUTE_TIMEOUT_SECS") {
+            let mut timeouts = BTreeMap::new();
+            for entry in split_list(&value) {
+                match entry.split_once('=') {
+                    Some((route, secs)) => {
+                        if let Some(secs) = number("ROUTE_TIMEOUT_SECS", secs.to_string(), errors) {
+                            timeouts.insert(route.trim().to_string(), secs);
+                        }
+                    }
+                    None => errors.push(format!("ROUTE_TIMEOUT_SECS entries must look like /route=seconds, got '{}'", entry)),
+                }
+            }
+            self.route_timeout_secs = Some(timeouts);
+        }
+        if let Some(value) = env("RUST_LOG") {
+            self.log.level = Some(value);
+        }
+        if let Some(value) = env("LOG_FORMAT") {
+            self.log.format = Some(value);
+        }
+    }
+
+    /// Checks every value, recording a message for each problem so they can all be reported at
+    /// once; invalid values fall back to their defaults in the returned (discarded) Config
+    fn validate(self, errors: &mut Vec<String>) -> Config {
+        let defaults = Config::default();
+
+        let bind_address = match self.bind_address {
+            Some(address) => address.trim().parse().unwrap_or_else(|_| {
+                errors.push(format!("bind_address must be an IP address and port such as 0.0.0.0:8080, got '{}'", address));
+                defaults.bind_address
+            }),
+            None => defaults.bind_address,
+        };
+
+        let cluster = self.cluster.and_then(|cluster| {
+            cluster.parse::<Cluster>().map_err(|e| errors.push(format!("cluster: {}", e))).ok()
+        });
+
+        let rpc = self.rpc.validate(cluster, errors);
+
+        // Catch a cluster name that contradicts the RPC URL, e.g. "devnet" with a mainnet URL
+        let url_cluster = Cluster::from_rpc_url(&rpc.url);
+        let cluster = match (cluster, url_cluster) {
+            (Some(cluster), Some(url_cluster)) if cluster != url_cluster => {
+                errors.push(format!("cluster is {} but rpc.url {} is a {} endpoint", cluster, rpc.url, url_cluster));
+                Some(cluster)
+            }
+            (cluster, url_cluster) => cluster.or(url_cluster),
+        };
+
+        // Fallbacks must serve the same cluster, or a read could silently switch networks
+        if let Some(cluster) = cluster {
+            for url in &rpc.fallback_urls {
+                match Cluster::from_rpc_url(url) {
+                    Some(url_cluster) if url_cluster != cluster => errors.push(format!(
+                        "cluster is {} but rpc.fallback_urls entry {} is a {} endpoint",
+                        cluster, url, url_cluster
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        let cors_allowed_origins = self.cors.allowed_origins.unwrap_or_default().into_iter()
+            // "*" is the same as leaving the list empty
+            .filter(|origin| origin != "*")
+            .filter_map(|origin| {
+                let valid = (origin.starts_with("http://") || origin.starts_with("https://")) && !origin.ends_with('/');
+                match HeaderValue::from_str(&origin) {
+                    Ok(value) if valid => Some(value),
+                    _ => {
+                        errors.push(format!(
+                            "cors.allowed_origins entries must look like https://example.com (no path or trailing slash), got '{}'",
+                            origin
+                        ));
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        let rate_limit_per_minute = match self.rate_limit.requests_per_minute {
+            Some(0) => {
+                errors.push("rate_limit.requests_per_minute must be greater than 0; leave it unset to disable rate limiting".to_string());
+                None
+            }
+            limit => limit,
+        };
+        let max_concurrent_expensive_requests = match self.max_concurrent_expensive_requests {
+            Some(0) => {
+                errors.push(
+                    "max_concurrent_expensive_requests must be greater than 0; leave it unset for no limit".to_string(),
+                );
+                None
+            }
+            limit => limit,
+        };
+
+        let request_timeout = match self.request_timeout_secs {
+            Some(0) => {
+                errors.push("request_timeout_secs must be greater than 0".to_string());
+                defaults.request_timeout
+            }
+            secs => secs.map_or(defaults.request_timeout, Duration::from_secs),
+        };
+        // The defaults never shorten a route below a longer request_timeout, and configured
+        // timeouts override them
+        let mut route_timeouts: BTreeMap<String, Duration> = DEFAULT_ROUTE_TIMEOUTS
+            .iter()
+            .map(|(route, timeout)| (route.to_string(), request_timeout.max(*timeout)))
+            .collect();
+        if let Some(timeouts) = self.route_timeout_secs {
+            let routes = crate::openapi::ApiDoc::openapi().paths.paths;
+            let configured = timeouts
+                .into_iter()
+                .filter(|(route, secs)| {
+                    if !routes.contains_key(route) {
+                        errors.push(format!("route_timeout_secs: {} is not a route", route));
+                    } else if *secs == 0 {
+                        errors.push(format!("route_timeout_secs for {} must be greater than 0", route));
+                    }
+                    routes.contains_key(route) && *secs > 0
+                })
+                .map(|(route, secs)| (route, Duration::from_secs(secs)));
+            route_timeouts.extend(configured);
+        }
+        let challenge_ttl = match self.auth.challenge_ttl_secs {
+            Some(0) => {
+                errors.push("auth.challenge_ttl_secs must be greater than 0".to_string());
+                defaults.challenge_ttl
+            }
+            secs => secs.map_or(defaults.challenge_ttl, Duration::from_secs),
+        };
+
+        let api_keys = self.api_keys.unwrap_or_default();
+        for (index, key) in api_keys.iter().enumerate() {
+            if key.is_empty() || key.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                errors.push(format!("api_keys[{}] must be non-empty and contain no whitespace", index));
+            }
+        }
+
+        let max_decimals = match self.token.max_decimals {
+            Some(max) if max > validation::MAX_DECIMALS => {
+                errors.push(format!("token.max_decimals must be at most {}, got {}", validation::MAX_DECIMALS, max));
+                defaults.token.max_decimals
+            }
+            max => max.unwrap_or(defaults.token.max_decimals),
+        };
+        let token = TokenPolicy {
+            max_decimals,
+            default_decimals: match self.token.default_decimals {
+                Some(decimals) if decimals > max_decimals => {
+                    errors.push(format!(
+                        "token.default_decimals must be at most token.max_decimals ({}), got {}",
+                        max_decimals, decimals
+                    ));
+                    defaults.token.default_decimals
+                }
+                decimals => decimals,
+            },
+            default_freeze_authority: self.token.default_freeze_authority.map_or(
+                defaults.token.default_freeze_authority,
+                |authority| {
+                    authority.parse().unwrap_or_else(|e| {
+                        errors.push(format!("token.default_freeze_authority: {}", e));
+                        defaults.token.default_freeze_authority
+                    })
+                },
+            ),
+            max_mint_amount: match self.token.max_mint_amount {
+                Some(0) => {
+                    errors.push("token.max_mint_amount must be greater than 0".to_string());
+                    defaults.token.max_mint_amount
+                }
+                max => max.unwrap_or(defaults.token.max_mint_amount),
+            },
+        };
+
+        let allowed_programs = parse_program_ids("allowed_programs", &self.allowed_programs.unwrap_or_default(), errors);
+        let relay = self.relay.validate(errors);
+        let hmac = self.hmac.validate(errors);
+        if relay.keypair.is_some() && !rpc.enabled {
+            errors.push("relay.keypair_path requires rpc.url; relayed transactions are submitted over RPC".to_string());
+        }
+
+        let binary_encoding = self.binary_encoding.map_or(defaults.binary_encoding, |encoding| {
+            encoding.parse().unwrap_or_else(|e| {
+                errors.push(format!("binary_encoding: {}", e));
+                defaults.binary_encoding
+            })
+        });
+
+        // Opened once here so an unwritable path stops startup, rather than losing every event
+        let audit_log = self.audit_log.map_or(defaults.audit_log.clone(), |sink| match sink.parse() {
+            Ok(AuditSink::File(path)) => match std::fs::OpenOptions::new().create(true).append(true).open(&path) {
+                Ok(_) => AuditSink::File(path),
+                Err(e) => {
+                    errors.push(format!("audit_log: can't open {}: {}", path.display(), e));
+                    defaults.audit_log.clone()
+                }
+            },
+            Ok(sink) => sink,
+            Err(e) => {
+                errors.push(e);
+                defaults.audit_log.clone()
+            }
+        });
+
+        let log = LogConfig {
+            level: match self.log.level {
+                Some(level) => match tracing_subscriber::EnvFilter::try_new(&level) {
+                    Ok(_) => level,
+                    Err(e) => {
+                        errors.push(format!("log.level must be a filter such as info or info,solana_http_server=debug, got '{}': {}", level, e));
+                        defaults.log.level
+                    }
+                },
+                None => defaults.log.level,
+            },
+            format: self.log.format.map_or(defaults.log.format, |format| {
+                format.parse().unwrap_or_else(|e| {
+                    errors.push(format!("log.format: {}", e));
+                    defaults.log.format
+                })
+            }),
+        };
+
+        Config {
+            bind_address,
+            cluster,
+            rpc,
+            cors_allowed_origins,
+            rate_limit_per_minute,
+            max_concurrent_expensive_requests,
+            api_keys,
+            enable_keypair_generation: self.enable_keypair_generation.unwrap_or(defaults.enable_keypair_generation),
+            token,
+            allowed_programs,
+            relay,
+            hmac,
+            binary_encoding,
+            response_envelope: self.response_envelope.unwrap_or(defaults.response_envelope),
+            idempotency_ttl: self.idempotency.ttl_secs.map_or(defaults.idempotency_ttl, Duration::from_secs),
+            request_timeout,
+            route_timeouts,
+            challenge_ttl,
+            log,
+            audit_log,
+        }
+    }
+}
+
+impl FileRpcConfig {
+    fn validate(self, cluster: Option<Cluster>, errors: &mut Vec<String>) -> RpcConfig {
+        let defaults = RpcConfig::default();
+        let has_scheme = |url: &str, schemes: [&str; 2]| schemes.iter().any(|scheme| url.starts_with(scheme));
+
+        // An empty URL turns RPC off rather than falling back to the cluster's or the default one
+        let enabled = !self.url.as_deref().is_some_and(|url| url.trim().is_empty());
+        let configured = enabled && (self.url.is_some() || cluster.is_some());
+        let url = match self.url {
+            Some(_) if !enabled => String::new(),
+            Some(url) if has_scheme(&url, ["http://", "https://"]) => url,
+            Some(url) => {
+                errors.push(format!("rpc.url must be an http:// or https:// URL, got '{}'", url));
+                defaults.url.clone()
+            }
+            None => cluster.map_or(defaults.url.clone(), |cluster| cluster.rpc_url().to_string()),
+        };
+
+        let fallback_urls: Vec<String> = self.fallback_urls.unwrap_or_default().into_iter().filter(|url| {
+            let valid = has_scheme(url, ["http://", "https://"]);
+            if !valid {
+                errors.push(format!("rpc.fallback_urls entries must be http:// or https:// URLs, got '{}'", url));
+            }
+            valid
+        }).collect();
+        if !enabled && !fallback_urls.is_empty() {
+            errors.push("rpc.fallback_urls requires rpc.url; an empty rpc.url turns RPC off".to_string());
+        }
+
+        let ws_url = self.ws_url.filter(|ws_url| {
+            let valid = has_scheme(ws_url, ["ws://", "wss://"]);
+            if !valid {
+                errors.push(format!("rpc.ws_url must be a ws:// or wss:// URL, got '{}'", ws_url));
+            }
+            valid
+        });
+
+        let client = self.client.map_or(defaults.client, |client| {
+            client.parse().unwrap_or_else(|e| {
+                errors.push(format!("rpc.client: {}", e));
+                defaults.client
+            })
+        });
+
+        let commitment = self.commitment.map_or(defaults.commitment, |commitment| {
+            validation::validate_commitment(&commitment).unwrap_or_else(|e| {
+                errors.push(format!("rpc.commitment: {}", e));
+                defaults.commitment
+            })
+        });
+
+        let max_retries = match self.max_retries {
+            Some(retries) if retries > MAX_RPC_RETRIES => {
+                errors.push(format!("rpc.max_retries must be at most {}, got {}", MAX_RPC_RETRIES, retries));
+                defaults.retry.max_retries
+            }
+            retries => retries.unwrap_or(defaults.retry.max_retries),
+        };
+
+        let pool_size = match self.pool_size {
+            Some(0) => {
+                errors.push("rpc.pool_size must be greater than 0".to_string());
+                defaults.pool.size
+            }
+            size => size.unwrap_or(defaults.pool.size),
+        };
+
+        RpcConfig {
+            url,
+            fallback_urls,
+            ws_url,
+            client,
+            commitment,
+            retry: RetryPolicy {
+                max_retries,
+                base_delay: self.retry_base_ms.map_or(defaults.retry.base_delay, Duration::from_millis),
+                max_delay: defaults.retry.max_delay,
+            },
+            pool: PoolConfig {
+                size: pool_size,
+                acquire_timeout: self.pool_timeout_ms.map_or(defaults.pool.acquire_timeout, Duration::from_millis),
+            },
+            enabled,
+            configured,
+        }
+    }
+}
+
+impl FileRelayConfig {
+    fn validate(self, errors: &mut Vec<String>) -> RelayConfig {
+        let keypair = self.keypair_path.and_then(|path| match read_keypair_file(&path) {
+            Ok(keypair) => Some(Arc::new(keypair)),
+            Err(e) => {
+                errors.push(format!("relay.keypair_path {} is not a readable solana-keygen keypair file: {}", path, e));
+                None
+            }
+        });
+
+        let allowed_programs = match self.allowed_programs {
+            Some(programs) if programs.is_empty() => {
+                errors.push("relay.allowed_programs must list at least one program id".to_string());
+                RelayConfig::default_allowed_programs()
+            }
+            Some(programs) => parse_program_ids("relay.allowed_programs", &programs, errors),
+            None => RelayConfig::default_allowed_programs(),
+        };
+
+        RelayConfig { keypair, allowed_programs }
+    }
+}
+
+impl FileHmacConfig {
+    fn validate(self, errors: &mut Vec<String>) -> HmacConfig {
+        let defaults = HmacConfig::default();
+        let secret = self.secret.map(Zeroizing::new);
+        if secret.as_ref().is_some_and(|secret| secret.len() < MIN_HMAC_SECRET_LEN) {
+            errors.push(format!("hmac.secret must be at least {} characters", MIN_HMAC_SECRET_LEN));
+        }
+        let max_skew = match self.max_skew_secs {
+            Some(0) => {
+                errors.push("hmac.max_skew_secs must be greater than 0".to_string());
+                defaults.max_skew
+            }
+            secs => secs.map_or(defaults.max_skew, Duration::from_secs),
+        };
+
+        HmacConfig { secret, max_skew }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Builds a Config from an optional file and a fixed set of environment variables
+    fn load(file: Option<(&str, &str)>, env: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let env: HashMap<String, String> = env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Config::from_sources(file, |name| env.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = load(None, &[]).unwrap();
+        assert_eq!(config.bind_address, SocketAddr::from(([0, 0, 0, 0], 8080)));
+        assert_eq!(config.rpc.url, DEFAULT_RPC_URL);
+        assert_eq!(config.cluster, Some(Cluster::Devnet));
+        assert!(!config.rpc.is_configured());
+        assert_eq!(config.rpc.commitment, CommitmentConfig::confirmed());
+        assert!(config.api_keys.is_empty());
+        assert!(config.enable_keypair_generation);
+        assert!(config.rate_limit_per_minute.is_none());
+        assert!(config.max_concurrent_expensive_requests.is_none());
+    }
+
+    #[test]
+    fn test_toml_file_with_env_overrides() {
+        let file = r#"
+            bind_address = "127.0.0.1:9000"
+            cluster = "testnet"
+            api_keys = ["from-file"]
+            enable_keypair_generation = false
+            max_concurrent_expensive_requests = 32
+
+            [rpc]
+            client = "blocking"
+            commitment = "finalized"
+            max_retries = 5
+            pool_size = 8
+
+            [cors]
+            allowed_origins = ["https://app.example.com"]
+
+            [rate_limit]
+            requests_per_minute = 120
+
+            [token]
+            max_decimals = 6
+        "#;
+        let config = load(Some(("server.toml", file)), &[
+            ("SOLANA_COMMITMENT", "processed"),
+            ("API_KEYS", "key-one, key-two"),
+            ("MAX_CONCURRENT_EXPENSIVE_REQUESTS", "16"),
+        ]).unwrap();
+
+        assert_eq!(config.bind_address, "127.0.0.1:9000".parse::<SocketAddr>().unwrap());
+        assert_eq!(config.cluster, Some(Cluster::Testnet));
+        // No rpc.url, so the cluster's endpoint is used
+        assert_eq!(config.rpc.url, "https://api.testnet.solana.com");
+        assert!(config.rpc.is_configured());
+        assert_eq!(config.rpc.client, RpcClientKind::Blocking);
+        assert_eq!(config.rpc.commitment, CommitmentConfig::processed());
+        assert_eq!(config.rpc.retry.max_retries, 5);
+        assert_eq!(config.rpc.pool.size, 8);
+        assert_eq!(config.rpc.pool.acquire_timeout, PoolConfig::default().acquire_timeout);
+        assert_eq!(config.cors_allowed_origins, [HeaderValue::from_static("https://app.example.com")]);
+        assert_eq!(config.rate_limit_per_minute, Some(120));
+        assert_eq!(config.max_concurrent_expensive_requests, Some(16));
+        assert_eq!(config.api_keys, ["key-one", "key-two"]);
+        assert!(!config.enable_keypair_generation);
+        assert_eq!(config.token.max_decimals, 6);
+        assert_eq!(config.token.max_mint_amount, u64::MAX);
+    }
+
+    #[test]
+    fn test_json_file() {
+        let file = r#"{ "rpc": { "url": "http://127.0.0.1:8899" }, "idempotency": { "ttl_secs": 60 } }"#;
+        let config = load(Some(("server.json", file)), &[]).unwrap();
+        assert_eq!(config.rpc.url, "http://127.0.0.1:8899");
+        assert_eq!(config.cluster, Some(Cluster::Localnet));
+        assert!(config.rpc.is_configured());
+        assert_eq!(config.idempotency_ttl, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_unknown_file_keys_are_rejected() {
+        let result = load(Some(("server.toml", "bind_adress = \"0.0.0.0:80\"")), &[]);
+        assert!(matches!(result, Err(ConfigError::Parse { message, .. }) if message.contains("bind_adress")));
+    }
+
+    #[test]
+    fn test_every_invalid_value_is_reported() {
+        let result = load(None, &[
+            ("BIND_ADDRESS", "localhost"),
+            ("SOLANA_CLUSTER", "moonnet"),
+            ("SOLANA_RPC_CLIENT", "threaded"),
+            ("SOLANA_COMMITMENT", "max"),
+            ("SOLANA_RPC_MAX_RETRIES", "50"),
+            ("SOLANA_RPC_RETRY_BASE_MS", "soon"),
+            ("SOLANA_RPC_POOL_SIZE", "0"),
+            ("SOLANA_RPC_POOL_TIMEOUT_MS", "-1"),
+            ("CORS_ALLOWED_ORIGINS", "app.example.com"),
+            ("RATE_LIMIT_PER_MINUTE", "0"),
+            ("MAX_CONCURRENT_EXPENSIVE_REQUESTS", "0"),
+            ("ENABLE_KEYPAIR_GENERATION", "no"),
+            ("TOKEN_MAX_DECIMALS", "12"),
+            ("TOKEN_MAX_MINT_AMOUNT", "0"),
+            ("RUST_LOG", "info,solana_http_server=verbose"),
+            ("LOG_FORMAT", "xml"),
+        ]);
+
+        let Err(ConfigError::Invalid(errors)) = result else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors.len(), 16, "{:#?}", errors);
+        assert!(errors.iter().any(|e| e.starts_with("bind_address")));
+        assert!(errors.iter().any(|e| e.contains("moonnet")));
+        assert!(errors.iter().any(|e| e.contains("SOLANA_RPC_RETRY_BASE_MS")));
+    }
+
+    #[test]
+    fn test_log_settings() {
+        let config = load(None, &[]).unwrap();
+        assert_eq!(config.log.level, "info");
+        assert_eq!(config.log.format, LogFormat::Full);
+
+        let file = "[log]\nlevel = \"warn\"\nformat = \"pretty\"\n";
+        let config = load(Some(("server.toml", file)), &[]).unwrap();
+        assert_eq!(config.log.level, "warn");
+        assert_eq!(config.log.format, LogFormat::Pretty);
+
+        // The environment overrides the file, as for every other setting
+        let config = load(Some(("server.toml", file)), &[
+            ("RUST_LOG", "info,solana_http_server=debug"),
+            ("LOG_FORMAT", " JSON "),
+        ]).unwrap();
+        assert_eq!(config.log.level, "info,solana_http_server=debug");
+        assert_eq!(config.log.format, LogFormat::Json);
+
+        assert_eq!("compact".parse::<LogFormat>().unwrap(), LogFormat::Compact);
+        assert!(matches!("xml".parse::<LogFormat>(), Err(e) if e.contains("expected full, compact, pretty or json")));
+    }
+
+    #[test]
+    fn test_empty_rpc_url_disables_rpc() {
+        assert!(load(None, &[]).unwrap().rpc.enabled);
+
+        let config = load(None, &[("SOLANA_RPC_URL", ""), ("SOLANA_CLUSTER", "devnet")]).unwrap();
+        assert!(!config.rpc.enabled);
+        assert!(!config.rpc.is_configured());
+        assert_eq!(config.rpc.url, "");
+        // The cluster is still reported, but doesn't pick an endpoint
+        assert_eq!(config.cluster, Some(Cluster::Devnet));
+
+        let config = load(Some(("server.toml", "[rpc]\nurl = \" \"\n")), &[]).unwrap();
+        assert!(!config.rpc.enabled);
+        assert_eq!(config.cluster, None);
+    }
+
+    #[test]
+    fn test_relay_settings() {
+        let config = load(None, &[]).unwrap();
+        assert!(config.relay.keypair.is_none());
+        assert_eq!(config.relay.allowed_programs, RelayConfig::default_allowed_programs());
+
+        let relayer = Keypair::new();
+        let path = std::env::temp_dir().join(format!("relayer-{}.json", relayer.pubkey()));
+        solana_sdk::signature::write_keypair_file(&relayer, &path).unwrap();
+        let config = load(None, &[
+            ("RELAY_KEYPAIR_PATH", path.to_str().unwrap()),
+            ("RELAY_ALLOWED_PROGRAMS", &format!("{}, ", spl_memo::id())),
+        ]);
+        let without_rpc = load(None, &[("RELAY_KEYPAIR_PATH", path.to_str().unwrap()), ("SOLANA_RPC_URL", "")]);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(without_rpc, Err(ConfigError::Invalid(errors)) if errors[0].starts_with("relay.keypair_path requires rpc.url")));
+        let config = config.unwrap();
+        assert_eq!(config.relay.keypair.as_ref().unwrap().pubkey(), relayer.pubkey());
+        assert_eq!(config.relay.allowed_programs, [spl_memo::id()]);
+        // Only the public key is ever shown
+        assert!(!format!("{:?}", config).contains(&relayer.to_base58_string()));
+
+        let invalid = |env: &[(&str, &str)]| match load(None, env) {
+            Err(ConfigError::Invalid(errors)) => errors,
+            other => panic!("expected an invalid configuration, got {:?}", other),
+        };
+        let errors = invalid(&[("RELAY_KEYPAIR_PATH", "/nonexistent/relayer.json")]);
+        assert!(errors[0].starts_with("relay.keypair_path /nonexistent/relayer.json is not a readable"), "{:?}", errors);
+        let errors = invalid(&[("RELAY_ALLOWED_PROGRAMS", "not-a-program")]);
+        assert_eq!(errors, ["relay.allowed_programs entries must be program ids, got 'not-a-program'"]);
+        let errors = invalid(&[("RELAY_ALLOWED_PROGRAMS", ",")]);
+        assert_eq!(errors, ["relay.allowed_programs must list at least one program id"]);
+    }
+
+    #[test]
+    fn test_response_envelope() {
+        assert!(load(None, &[]).unwrap().response_envelope);
+        let config = load(Some(("server.toml", "response_envelope = false")), &[]).unwrap();
+        assert!(!config.response_envelope);
+        let config = load(Some(("server.toml", "response_envelope = false")), &[("RESPONSE_ENVELOPE", "true")]).unwrap();
+        assert!(config.response_envelope);
+
+        let result = load(None, &[("RESPONSE_ENVELOPE", "no")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == ["RESPONSE_ENVELOPE must be true or false, got 'no'"]));
+    }
+
+    #[test]
+    fn test_hmac_config() {
+        let config = load(None, &[]).unwrap();
+        assert!(!config.hmac.is_enabled());
+        assert_eq!(config.hmac.max_skew, Duration::from_secs(300));
+
+        let secret = "s".repeat(32);
+        let file = format!("[hmac]\nsecret = \"{}\"\nmax_skew_secs = 30", secret);
+        let config = load(Some(("server.toml", &file)), &[]).unwrap();
+        assert_eq!(config.hmac.secret.as_deref().map(String::as_str), Some(secret.as_str()));
+        assert_eq!(config.hmac.max_skew, Duration::from_secs(30));
+        assert!(!format!("{:?}", config).contains(&secret));
+
+        let config = load(None, &[("HMAC_SECRET", &secret), ("HMAC_MAX_SKEW_SECS", "60")]).unwrap();
+        assert!(config.hmac.is_enabled());
+        assert_eq!(config.hmac.max_skew, Duration::from_secs(60));
+
+        let result = load(None, &[("HMAC_SECRET", "short"), ("HMAC_MAX_SKEW_SECS", "0")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == [
+            "hmac.secret must be at least 32 characters",
+            "hmac.max_skew_secs must be greater than 0",
+        ]));
+    }
+
+    #[test]
+    fn test_challenge_ttl() {
+        assert_eq!(load(None, &[]).unwrap().challenge_ttl, Duration::from_secs(300));
+        let config = load(Some(("server.toml", "[auth]\nchallenge_ttl_secs = 60")), &[]).unwrap();
+        assert_eq!(config.challenge_ttl, Duration::from_secs(60));
+        let config = load(None, &[("AUTH_CHALLENGE_TTL_SECS", "30")]).unwrap();
+        assert_eq!(config.challenge_ttl, Duration::from_secs(30));
+
+        let result = load(None, &[("AUTH_CHALLENGE_TTL_SECS", "0")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == ["auth.challenge_ttl_secs must be greater than 0"]));
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        assert_eq!(load(None, &[]).unwrap().request_timeout, Duration::from_secs(30));
+        let config = load(Some(("server.toml", "request_timeout_secs = 5")), &[]).unwrap();
+        assert_eq!(config.request_timeout, Duration::from_secs(5));
+        let config = load(Some(("server.toml", "request_timeout_secs = 5")), &[("REQUEST_TIMEOUT_SECS", "90")]).unwrap();
+        assert_eq!(config.request_timeout, Duration::from_secs(90));
+
+        let result = load(None, &[("REQUEST_TIMEOUT_SECS", "0")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == ["request_timeout_secs must be greater than 0"]));
+    }
+
+    #[test]
+    fn test_route_timeouts() {
+        let file = ("server.toml", "request_timeout_secs = 10\n[route_timeout_secs]\n\"/rpc/simulate\" = 60\n\"/message/sign\" = 2");
+        let config = load(Some(file), &[]).unwrap();
+        assert_eq!(config.timeout_for("/rpc/simulate"), Duration::from_secs(60));
+        assert_eq!(config.timeout_for("/message/sign"), Duration::from_secs(2));
+        assert_eq!(config.timeout_for("/message/verify"), Duration::from_secs(10));
+        // The airdrop routes keep their longer defaults
+        assert_eq!(config.timeout_for("/keypair/funded"), Duration::from_secs(120));
+        assert_eq!(config.timeout_for("/rpc/airdrop"), Duration::from_secs(120));
+
+        // The environment replaces the file's table rather than merging into it
+        let config = load(Some(file), &[("ROUTE_TIMEOUT_SECS", "/keypair/funded=90, /batch=5")]).unwrap();
+        assert_eq!(config.route_timeouts.len(), 3);
+        assert_eq!(config.timeout_for("/keypair/funded"), Duration::from_secs(90));
+        assert_eq!(config.timeout_for("/rpc/simulate"), Duration::from_secs(10));
+
+        // A longer default request timeout applies to the airdrop routes too
+        let config = load(None, &[("REQUEST_TIMEOUT_SECS", "300")]).unwrap();
+        assert_eq!(config.timeout_for("/keypair/funded"), Duration::from_secs(300));
+
+        let result = load(None, &[("ROUTE_TIMEOUT_SECS", "/rpc/simulate=0,/rpc/simulat=5,/batch,/rpc/send=soon")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == [
+            "ROUTE_TIMEOUT_SECS entries must look like /route=seconds, got '/batch'",
+            "ROUTE_TIMEOUT_SECS must be a non-negative integer, got 'soon'",
+            "route_timeout_secs: /rpc/simulat is not a route",
+            "route_timeout_secs for /rpc/simulate must be greater than 0",
+        ]));
+    }
+
+    #[test]
+    fn test_token_defaults() {
+        let config = load(None, &[]).unwrap();
+        assert_eq!(config.token.default_decimals, None);
+        assert_eq!(config.token.default_freeze_authority, DefaultFreezeAuthority::MintAuthority);
+
+        let file = "[token]\ndefault_decimals = 6\ndefault_freeze_authority = \"none\"\n";
+        let config = load(Some(("server.toml", file)), &[]).unwrap();
+        assert_eq!(config.token.default_decimals, Some(6));
+        assert_eq!(config.token.default_freeze_authority, DefaultFreezeAuthority::None);
+
+        let account = Pubkey::new_unique();
+        let config = load(None, &[("TOKEN_DEFAULT_FREEZE_AUTHORITY", &account.to_string())]).unwrap();
+        assert_eq!(config.token.default_freeze_authority, DefaultFreezeAuthority::Account(account));
+
+        // A default the policy would reject, or an unknown authority, stops startup
+        let result = load(None, &[
+            ("TOKEN_MAX_DECIMALS", "6"),
+            ("TOKEN_DEFAULT_DECIMALS", "9"),
+            ("TOKEN_DEFAULT_FREEZE_AUTHORITY", "issuer"),
+        ]);
+        let Err(ConfigError::Invalid(errors)) = result else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors, [
+            "token.default_decimals must be at most token.max_decimals (6), got 9",
+            "token.default_freeze_authority: unknown freeze authority 'issuer': expected mint-authority, none or a public key",
+        ]);
+
+        let result = load(None, &[("TOKEN_DEFAULT_FREEZE_AUTHORITY", "11111111111111111111111111111111")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == [
+            "token.default_freeze_authority: freeze authority can't be 11111111111111111111111111111111, the all-zeros address; use none for no freeze authority",
+        ]));
+    }
+
+    #[test]
+    fn test_audit_log() {
+        assert_eq!(load(None, &[]).unwrap().audit_log, AuditSink::Off);
+        let config = load(Some(("server.toml", "audit_log = \"stdout\"")), &[]).unwrap();
+        assert_eq!(config.audit_log, AuditSink::Stdout);
+
+        let path = std::env::temp_dir().join(format!("config-audit-{}.jsonl", std::process::id()));
+        let config = load(None, &[("AUDIT_LOG", path.to_str().unwrap())]).unwrap();
+        assert_eq!(config.audit_log, AuditSink::File(path.clone()));
+        std::fs::remove_file(&path).unwrap();
+
+        let result = load(None, &[("AUDIT_LOG", "/nonexistent-dir/audit.jsonl")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors[0].starts_with("audit_log: can't open /nonexistent-dir/audit.jsonl")));
+    }
+
+    #[test]
+    fn test_binary_encoding() {
+        assert_eq!(load(None, &[]).unwrap().binary_encoding, BinaryEncoding::Base64);
+        let config = load(Some(("server.toml", "binary_encoding = \"base64url\"")), &[]).unwrap();
+        assert_eq!(config.binary_encoding, BinaryEncoding::Base64Url);
+        let config = load(Some(("server.toml", "binary_encoding = \"base64url\"")), &[("BINARY_ENCODING", "base64")]).unwrap();
+        assert_eq!(config.binary_encoding, BinaryEncoding::Base64);
+
+        // Bytes that encode to `+`, `/` and padding in standard base64
+        assert_eq!(BinaryEncoding::Base64.encode([0xfb, 0xff]), "+/8=");
+        assert_eq!(BinaryEncoding::Base64Url.encode([0xfb, 0xff]), "-_8");
+
+        let result = load(None, &[("BINARY_ENCODING", "hex")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == ["binary_encoding: unknown binary encoding 'hex': expected base64 or base64url"]));
+    }
+
+    #[test]
+    fn test_allowed_programs() {
+        assert!(load(None, &[]).unwrap().allowed_programs.is_empty(), "every program is allowed by default");
+
+        let file = format!("allowed_programs = [\"{}\"]", spl_token::id());
+        let config = load(Some(("server.toml", &file)), &[]).unwrap();
+        assert_eq!(config.allowed_programs, [spl_token::id()]);
+        let config = load(Some(("server.toml", &file)), &[("ALLOWED_PROGRAMS", &format!("{},{}", system_program::id(), spl_memo::id()))]).unwrap();
+        assert_eq!(config.allowed_programs, [system_program::id(), spl_memo::id()]);
+
+        let result = load(None, &[("ALLOWED_PROGRAMS", "Tokenkeg")]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors == ["allowed_programs entries must be program ids, got 'Tokenkeg'"]));
+    }
+
+    #[test]
+    fn test_rpc_fallback_urls() {
+        assert!(load(None, &[]).unwrap().rpc.fallback_urls.is_empty());
+
+        let file = "[rpc]\nurl = \"https://rpc.example.com\"\nfallback_urls = [\"https://api.devnet.solana.com\"]\n";
+        let config = load(Some(("server.toml", file)), &[]).unwrap();
+        assert_eq!(config.rpc.fallback_urls, ["https://api.devnet.solana.com"]);
+
+        // The environment takes a comma-separated list, kept in order
+        let config = load(Some(("server.toml", file)), &[
+            ("SOLANA_RPC_FALLBACK_URLS", "https://b.example.com, https://a.example.com,"),
+        ]).unwrap();
+        assert_eq!(config.rpc.fallback_urls, ["https://b.example.com", "https://a.example.com"]);
+
+        let invalid = |env: &[(&str, &str)]| match load(None, env) {
+            Err(ConfigError::Invalid(errors)) => errors,
+            other => panic!("expected validation errors, got {:?}", other.map(|_| ())),
+        };
+        let errors = invalid(&[("SOLANA_RPC_FALLBACK_URLS", "rpc.example.com")]);
+        assert!(errors[0].starts_with("rpc.fallback_urls entries"), "{:?}", errors);
+        // A fallback on another cluster would switch networks mid-outage
+        let errors = invalid(&[
+            ("SOLANA_CLUSTER", "devnet"),
+            ("SOLANA_RPC_FALLBACK_URLS", "https://api.mainnet-beta.solana.com"),
+        ]);
+        assert!(errors[0].contains("is a mainnet-beta endpoint"), "{:?}", errors);
+        let errors = invalid(&[("SOLANA_RPC_URL", ""), ("SOLANA_RPC_FALLBACK_URLS", "https://rpc.example.com")]);
+        assert!(errors[0].contains("requires rpc.url"), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_cluster_names() {
+        assert_eq!("mainnet".parse::<Cluster>().unwrap(), Cluster::MainnetBeta);
+        assert_eq!(" Devnet ".parse::<Cluster>().unwrap(), Cluster::Devnet);
+        assert_eq!(Cluster::MainnetBeta.to_string(), "mainnet-beta");
+        assert!("moonnet".parse::<Cluster>().is_err());
+        assert_eq!(serde_json::to_value(Cluster::MainnetBeta).unwrap(), "mainnet-beta");
+    }
+
+    #[test]
+    fn test_cluster_from_rpc_url() {
+        assert_eq!(Cluster::from_rpc_url("https://api.mainnet-beta.solana.com/"), Some(Cluster::MainnetBeta));
+        assert_eq!(Cluster::from_rpc_url("http://localhost:8899"), Some(Cluster::Localnet));
+        assert_eq!(Cluster::from_rpc_url("https://mainnet.helius-rpc.com/?api-key=abc"), None);
+
+        // A custom provider keeps the configured cluster
+        let config = load(None, &[
+            ("SOLANA_CLUSTER", "mainnet-beta"),
+            ("SOLANA_RPC_URL", "https://mainnet.helius-rpc.com/?api-key=abc"),
+        ]).unwrap();
+        assert_eq!(config.cluster, Some(Cluster::MainnetBeta));
+        assert!(load(None, &[("SOLANA_RPC_URL", "https://rpc.example.com")]).unwrap().cluster.is_none());
+
+        let result = load(None, &[
+            ("SOLANA_CLUSTER", "devnet"),
+            ("SOLANA_RPC_URL", "https://api.mainnet-beta.solana.com"),
+        ]);
+        assert!(matches!(result, Err(ConfigError::Invalid(errors)) if errors[0].starts_with("cluster is devnet")));
+    }
+}
@@ -0,0 +1,964 @@
+//! Sample requests and responses for every JSON route, served at GET /examples
+//!
+//! Requests are built from the request models using fixed sample keys, so they always pass
+//! validation. For routes that work offline, the response is what the service returns for that
+//! request. For routes that need the cluster, or that generate fresh keys, the response is an
+//! illustrative value of the response model. The router tests send every sample request through
+//! the real handlers to keep the two in sync.
+
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+use solana_sdk::{
+    hash::Hash, message::Message, signature::Signer, signer::keypair::keypair_from_seed,
+    system_instruction, transaction::Transaction,
+};
+
+use crate::config::Cluster;
+use crate::models::{
+    AccountInfoRequest, AccountInfoResponse, AirdropRequest, AuthChallengeResponse, AuthVerifyRequest, AuthVerifyResponse, AssociatedTokenAddressRequest, AssociatedTokenAddressBatchRequest, AssociatedTokenAddressBatchResponse, MintAssociatedTokenAddress, DerivePdaBatchRequest, DerivePdaBatchResponse, DerivePdaRequest, OnCurveRequest, PdaBatchResult, AirdropResponse, ApiResponse, BatchCall, BatchResult,
+    BurnCloseRequest, CreateStakeAccountRequest, CreateNewTokenRequest, CreateTokenRequest, DeactivateStakeRequest, DelegateStakeRequest, ExportKeypairRequest, FundedKeypairRequest, FundedKeypairResponse,
+    HealthResponse, DeepHealthResponse, ImportKeypairRequest, InspectKeypairRequest, RotateKeypairRequest, SignedMessage, KeypairFromSeedRequest, DeriveKeypairBatchRequest, DeriveKeypairBatchResponse, KeypairResponse, MintBatchRequest, MintInfoRequest,
+    MintInfoResponse, MintRecipient, MintTokenRequest, NormalizePubkeyRequest, Pagination, PartialSignTransactionRequest, PublicKeyResponse, RelayTransactionRequest, RelayTransactionResponse, RentRequest,
+    RentResponse, RouteExample, SendSolRequest, SendTokenRequest, SendTokenWithMemoRequest, SendTransactionRequest, SendTransactionResponse,
+    SignMessageBatchRequest, SignMessageRequest, SignTransactionRequest, SignatureStatusRequest, SignatureStatusResponse,
+    SimulateTransactionRequest, SimulateTransactionResponse, SiwsBuildResponse, SiwsVerifyRequest,
+    SiwsVerifyResponse, SyncNativeRequest, TokenAccountBalance, CreateTransferFeeMintRequest, TransferWithFeeRequest, InitializeMetadataPointerRequest, InitializeTokenMetadataRequest, UpdateTokenMetadataRequest, CreateCloseableMintRequest, CloseMintRequest, TransactionSignatureRequest, TokenAccountInfoRequest, TokenAccountInfoResponse,
+    TokenAccountsRequest, TokenAccountsResponse, UnwrapSolRequest, VerifyMessageRequest, VerifyTransactionRequest,
+    VersionResponse, VoteAccountInfo, VoteAccountsRequest, VoteAccountsResponse, WithdrawStakeRequest, WrapSolRequest,
+};
+use crate::services::entropy::{check_keypair_entropy, DEFAULT_ENTROPY_SAMPLE};
+use crate::services::siws::SiwsMessage;
+use crate::services::solana::{SolanaService, TransferFeeSettings};
+use crate::utils::errors::{serialization_error, AppError, Result};
+
+/// Routes whose sample response is illustrative: they read from or submit to the cluster
+pub const RPC_ROUTES: [&str; 12] = [
+    "/health/deep",
+    "/keypair/funded",
+    "/rpc/token-accounts",
+    "/rpc/account",
+    "/rpc/mint-info",
+    "/rpc/token-account-info",
+    "/rpc/send",
+    "/rpc/airdrop",
+    "/relay",
+    "/rpc/simulate",
+    "/rpc/signature-status",
+    "/rpc/vote-accounts",
+];
+
+/// Routes whose responses come from freshly generated keys or nonces, so only their shape is fixed
+pub const RANDOM_ROUTES: [&str; 5] =
+    ["/keypair", "/keypair/public-only", "/keypair/entropy-check", "/token/create/new", "/auth/challenge"];
+
+/// Routes whose sample request names something a running server issued (a challenge nonce),
+/// so replaying it against another server is rejected
+pub const STATEFUL_ROUTES: [&str; 1] = ["/auth/verify"];
+
+/// Builds the sample request and response of every route, keyed by "METHOD /path"
+pub fn route_examples() -> Result<BTreeMap<String, RouteExample>> {
+    let solana = SolanaService::new();
+    let wallet = sample_keypair(1)?;
+    let recipient = sample_keypair(2)?.pubkey();
+    let mint = sample_keypair(3)?.pubkey();
+    let wallet_secret = wallet.to_base58_string();
+    let wallet_ata = spl_associated_token_account::get_associated_token_address(&wallet.pubkey(), &mint);
+    let blockhash = Hash::new_from_array([9; 32]);
+
+    let transfer = Message::new_with_blockhash(
+        &[system_instruction::transfer(&wallet.pubkey(), &recipient, 1_000_000)],
+        Some(&wallet.pubkey()),
+        &blockhash,
+    );
+    let unsigned = Transaction::new_unsigned(transfer.clone());
+    let signed = solana.sign_transaction(transfer.clone(), std::slice::from_ref(&wallet_secret))?;
+
+    let siws = SiwsMessage {
+        domain: "example.com".to_string(),
+        address: wallet.pubkey().to_string(),
+        statement: Some("Sign in to Example".to_string()),
+        uri: Some("https://example.com/login".to_string()),
+        version: Some("1".to_string()),
+        nonce: Some("k3JcU8rWq0bZ2mLx".to_string()),
+        issued_at: Some("2024-01-01T00:00:00.000Z".to_string()),
+        ..Default::default()
+    };
+    let siws_text = siws.to_text();
+
+    let mut examples = BTreeMap::new();
+    let mut add = |method: &str, path: &str, example: RouteExample| {
+        examples.insert(format!("{} {}", method, path), example);
+    };
+
+    add("GET", "/health", no_body(HealthResponse { status: "ok".to_string(), cluster: Some(Cluster::Devnet) })?);
+    add(
+        "GET",
+        "/health/deep",
+        no_body(DeepHealthResponse {
+            status: "ok".to_string(),
+            cluster: Some(Cluster::Devnet),
+            rpc: "ok".to_string(),
+            slot: Some(281_234_567),
+            error: None,
+        })?,
+    );
+    add(
+        "GET",
+        "/version",
+        no_body(VersionResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            solana_sdk: env!("SOLANA_SDK_VERSION").to_string(),
+            spl_token: env!("SPL_TOKEN_VERSION").to_string(),
+        })?,
+    );
+
+    // Keypairs
+    add(
+        "POST",
+        "/keypair",
+        no_body(KeypairResponse { pubkey: wallet.pubkey().to_string(), secret: wallet_secret.clone() })?,
+    );
+    add("POST", "/keypair/public-only", no_body(PublicKeyResponse { pubkey: wallet.pubkey().to_string() })?);
+    add("GET", "/keypair/entropy-check", no_body(check_keypair_entropy(DEFAULT_ENTROPY_SAMPLE))?);
+    add(
+        "POST",
+        "/keypair/from-seed",
+        with_body(
+            KeypairFromSeedRequest { seed: general_purpose::STANDARD.encode([1u8; 32]) },
+            solana.keypair_from_seed(&[1; 32])?,
+        )?,
+    );
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    add(
+        "POST",
+        "/keypair/derive/batch",
+        with_body(
+            DeriveKeypairBatchRequest { mnemonic: phrase.to_string(), passphrase: String::new(), count: 2, start_index: 0 },
+            DeriveKeypairBatchResponse {
+                keypairs: solana.derive_keypairs(&crate::utils::validation::validate_mnemonic(phrase)?, "", 0..2)?,
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/keypair/export",
+        with_body(ExportKeypairRequest { secret: wallet_secret.clone() }, solana.export_keypair(&wallet_secret)?)?,
+    );
+    add(
+        "POST",
+        "/keypair/import",
+        with_body(
+            ImportKeypairRequest { keypair: wallet.to_bytes().to_vec() },
+            solana.import_keypair(&wallet.to_bytes())?,
+        )?,
+    );
+    add(
+        "POST",
+        "/keypair/inspect",
+        with_body(InspectKeypairRequest { secret: wallet_secret.clone() }, solana.inspect_keypair(&wallet_secret)?)?,
+    );
+    let rotated_secret = sample_keypair(8)?.to_base58_string();
+    let attestation = "wallet attests: account 42".to_string();
+    let attestation_signature = solana.sign_message(&attestation, None, &wallet_secret)?.signature;
+    add(
+        "POST",
+        "/keypair/rotate",
+        with_body(
+            RotateKeypairRequest {
+                old_secret: wallet_secret.clone(),
+                new_secret: rotated_secret.clone(),
+                messages: vec![SignedMessage { message: attestation.clone(), signature: attestation_signature.clone() }],
+                prefix: None,
+            },
+            solana.rotate_signatures(
+                &[(attestation.as_str(), crate::utils::validation::validate_signature(&attestation_signature)?)],
+                None,
+                &wallet_secret,
+                &rotated_secret,
+            )?,
+        )?,
+    );
+    add(
+        "POST",
+        "/keypair/funded",
+        with_body(
+            FundedKeypairRequest { sol: 1.0 },
+            FundedKeypairResponse {
+                pubkey: wallet.pubkey().to_string(),
+                secret: wallet_secret.clone(),
+                lamports: 1_000_000_000,
+                signature: signed.signature.clone(),
+                confirmed: true,
+                cluster: Some(Cluster::Devnet),
+            },
+        )?,
+    );
+
+    // RPC lookups and submissions
+    add(
+        "POST",
+        "/rpc/token-accounts",
+        with_body(
+            TokenAccountsRequest {
+                owner: wallet.pubkey().to_string(),
+                mint: Some(mint.to_string()),
+                offset: 0,
+                limit: None,
+                commitment: None,
+            },
+            TokenAccountsResponse {
+                owner: wallet.pubkey().to_string(),
+                accounts: vec![TokenAccountBalance {
+                    account: wallet_ata.to_string(),
+                    mint: mint.to_string(),
+                    amount: 5_000_000,
+                    decimals: 6,
+                }],
+                pagination: Pagination { offset: 0, limit: 100, total: 1, next_offset: None },
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/account",
+        with_body(
+            AccountInfoRequest { pubkey: wallet.pubkey().to_string(), encoding: "base64".to_string(), commitment: None },
+            AccountInfoResponse {
+                pubkey: wallet.pubkey().to_string(),
+                lamports: 1_000_000_000,
+                owner: solana_sdk::system_program::id().to_string(),
+                executable: false,
+                encoding: "base64".to_string(),
+                data: serde_json::json!(["", "base64"]),
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/mint-info",
+        with_body(
+            MintInfoRequest { mint: mint.to_string(), commitment: None },
+            MintInfoResponse {
+                mint: mint.to_string(),
+                supply: 1_000_000_000,
+                decimals: 6,
+                mint_authority: Some(wallet.pubkey().to_string()),
+                freeze_authority: None,
+                is_initialized: true,
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/token-account-info",
+        with_body(
+            TokenAccountInfoRequest { account: wallet_ata.to_string(), commitment: None },
+            TokenAccountInfoResponse {
+                account: wallet_ata.to_string(),
+                mint: mint.to_string(),
+                owner: wallet.pubkey().to_string(),
+                amount: 5_000_000,
+                delegate: None,
+                delegated_amount: 0,
+                state: "initialized".to_string(),
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/rent",
+        with_body(
+            RentRequest { data_len: 165, commitment: None },
+            RentResponse { data_len: 165, lamports: solana.rent_exempt_minimum(165), source: "local".to_string() },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/send",
+        with_body(
+            SendTransactionRequest { transaction: signed.transaction.clone() },
+            SendTransactionResponse { signature: signed.signature.clone(), cluster: Some(Cluster::Devnet) },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/airdrop",
+        with_body(
+            AirdropRequest { pubkey: wallet.pubkey().to_string(), sol: 1.0 },
+            AirdropResponse {
+                pubkey: wallet.pubkey().to_string(),
+                lamports: 1_000_000_000,
+                signature: signed.signature.clone(),
+                cluster: Some(Cluster::Devnet),
+            },
+        )?,
+    );
+    // The wallet signs a transfer whose fee payer is the relayer, which adds its signature
+    let relayer = sample_keypair(7)?;
+    let mut sponsored = Transaction::new_unsigned(Message::new_with_blockhash(
+        &[system_instruction::transfer(&wallet.pubkey(), &recipient, 1_000_000)],
+        Some(&relayer.pubkey()),
+        &blockhash,
+    ));
+    sponsored.partial_sign(&[&wallet], blockhash);
+    let relayed = solana.sign_relayed_transaction(sponsored.clone(), &relayer, &[solana_sdk::system_program::id()])?;
+    add(
+        "POST",
+        "/relay",
+        with_body(
+            RelayTransactionRequest {
+                transaction: general_purpose::STANDARD.encode(bincode::serialize(&sponsored).map_err(serialization_error)?),
+            },
+            RelayTransactionResponse {
+                signature: relayed.signatures[0].to_string(),
+                fee_payer: relayer.pubkey().to_string(),
+                cluster: Some(Cluster::Devnet),
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/simulate",
+        with_body(
+            SimulateTransactionRequest {
+                transaction: signed.transaction.clone(),
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: None,
+            },
+            SimulateTransactionResponse {
+                err: None,
+                logs: vec![
+                    "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+                    "Program 11111111111111111111111111111111 success".to_string(),
+                ],
+                units_consumed: Some(150),
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/signature-status",
+        with_body(
+            SignatureStatusRequest { signature: signed.signature.clone() },
+            SignatureStatusResponse {
+                signature: signed.signature.clone(),
+                found: true,
+                confirmation_status: Some("finalized".to_string()),
+                slot: Some(250_000_000),
+                confirmations: None,
+                err: None,
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/rpc/vote-accounts",
+        with_body(
+            VoteAccountsRequest { vote_pubkey: None, offset: 0, limit: Some(1), commitment: None },
+            VoteAccountsResponse {
+                current: vec![VoteAccountInfo {
+                    vote_pubkey: sample_keypair(5)?.pubkey().to_string(),
+                    node_pubkey: sample_keypair(6)?.pubkey().to_string(),
+                    activated_stake: 1_250_000_000_000_000,
+                    commission: 5,
+                    epoch_vote_account: true,
+                    last_vote: 250_000_000,
+                    root_slot: 249_999_968,
+                }],
+                delinquent: vec![],
+                total_current: 1_400,
+                total_delinquent: 60,
+                pagination: Pagination { offset: 0, limit: 1, total: 1_400, next_offset: Some(1) },
+            },
+        )?,
+    );
+
+    // Token instructions
+    add(
+        "POST",
+        "/token/create",
+        with_body(
+            CreateTokenRequest {
+                mint_authority: wallet.pubkey().to_string(),
+                mint: mint.to_string(),
+                decimals: Some(6),
+                freeze_authority: None,
+                disable_freeze_authority: false,
+            },
+            solana.create_token_mint(&wallet.pubkey().to_string(), &mint.to_string(), Some(&wallet.pubkey().to_string()), 6)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/create/new",
+        with_body(
+            CreateNewTokenRequest {
+                mint_authority: wallet.pubkey().to_string(),
+                payer: None,
+                decimals: Some(6),
+                freeze_authority: None,
+                disable_freeze_authority: false,
+            },
+            solana.create_new_token_mint(&wallet.pubkey(), &wallet.pubkey(), Some(&wallet.pubkey()), 6)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/mint",
+        with_body(
+            MintTokenRequest {
+                mint: mint.to_string(),
+                destination: recipient.to_string(),
+                authority: wallet.pubkey().to_string(),
+                amount: 1_000_000,
+            },
+            solana.mint_token(&mint.to_string(), &recipient.to_string(), &wallet.pubkey().to_string(), 1_000_000)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/mint/batch",
+        with_body(
+            MintBatchRequest {
+                mint: mint.to_string(),
+                authority: wallet.pubkey().to_string(),
+                recipients: vec![
+                    MintRecipient { destination: recipient.to_string(), amount: 1_000_000 },
+                    MintRecipient { destination: wallet.pubkey().to_string(), amount: 2_000_000 },
+                ],
+                fee_payer: None,
+                recent_blockhash: None,
+            },
+            solana.mint_token_batch(
+                &mint,
+                &wallet.pubkey(),
+                &[(recipient, 1_000_000), (wallet.pubkey(), 2_000_000)],
+                None,
+            )?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/burn-close",
+        with_body(
+            BurnCloseRequest {
+                mint: mint.to_string(),
+                account: wallet_ata.to_string(),
+                authority: wallet.pubkey().to_string(),
+                destination: wallet.pubkey().to_string(),
+                amount: 5_000_000,
+            },
+            solana.burn_and_close(
+                &mint.to_string(),
+                &wallet_ata.to_string(),
+                &wallet.pubkey().to_string(),
+                &wallet.pubkey().to_string(),
+                5_000_000,
+            )?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/wrap",
+        with_body(
+            WrapSolRequest { owner: wallet.pubkey().to_string(), lamports: 1_000_000_000 },
+            solana.wrap_sol(&wallet.pubkey(), 1_000_000_000)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/unwrap",
+        with_body(
+            UnwrapSolRequest { owner: wallet.pubkey().to_string(), account: None },
+            solana.unwrap_sol(&wallet.pubkey(), None)?,
+        )?,
+    );
+    let wrapped_account = spl_associated_token_account::get_associated_token_address(
+        &wallet.pubkey(),
+        &spl_token::native_mint::id(),
+    );
+    add(
+        "POST",
+        "/token/sync-native",
+        with_body(SyncNativeRequest { account: wrapped_account.to_string() }, solana.sync_native(&wrapped_account)?)?,
+    );
+
+
+    // Address derivation: an associated token account is itself a PDA of the ATA program
+    let ata_seeds = [wallet.pubkey(), spl_token::id(), mint].map(|key| format!("pubkey:{}", key));
+    add(
+        "POST",
+        "/pda/derive",
+        with_body(
+            DerivePdaRequest { program_id: spl_associated_token_account::id().to_string(), seeds: ata_seeds.to_vec() },
+            solana.derive_pda(
+                &spl_associated_token_account::id(),
+                &[wallet.pubkey(), spl_token::id(), mint].map(|key| key.to_bytes().to_vec()),
+            )?,
+        )?,
+    );
+    let vault_seeds = vec!["vault".to_string(), format!("pubkey:{}", wallet.pubkey())];
+    let vault = solana.derive_pda(&spl_token::id(), &[b"vault".to_vec(), wallet.pubkey().to_bytes().to_vec()])?;
+    add(
+        "POST",
+        "/pda/derive/batch",
+        with_body(
+            DerivePdaBatchRequest {
+                entries: vec![
+                    DerivePdaRequest { program_id: spl_token::id().to_string(), seeds: vault_seeds },
+                    DerivePdaRequest { program_id: spl_token::id().to_string(), seeds: vec!["x".repeat(33)] },
+                ],
+            },
+            DerivePdaBatchResponse {
+                results: vec![
+                    PdaBatchResult { address: Some(vault.address), bump: Some(vault.bump), error: None },
+                    PdaBatchResult {
+                        address: None,
+                        bump: None,
+                        error: Some("seeds[0] is 33 bytes; seeds are at most 32 bytes".to_string()),
+                    },
+                ],
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/token/ata",
+        with_body(
+            AssociatedTokenAddressRequest { wallet: wallet.pubkey().to_string(), mint: mint.to_string(), token_program: None },
+            solana.derive_ata(&wallet.pubkey(), &mint, &spl_token::id()),
+        )?,
+    );
+    add(
+        "POST",
+        "/token/ata/batch",
+        with_body(
+            AssociatedTokenAddressBatchRequest {
+                wallet: wallet.pubkey().to_string(),
+                mints: vec![mint.to_string(), spl_token::native_mint::id().to_string()],
+                token_program: None,
+            },
+            AssociatedTokenAddressBatchResponse {
+                wallet: wallet.pubkey().to_string(),
+                accounts: [mint, spl_token::native_mint::id()]
+                    .iter()
+                    .map(|account_mint| MintAssociatedTokenAddress {
+                        mint: account_mint.to_string(),
+                        address: solana.derive_ata(&wallet.pubkey(), account_mint, &spl_token::id()).address,
+                    })
+                    .collect(),
+            },
+        )?,
+    );
+    add(
+        "POST",
+        "/pubkey/on-curve",
+        with_body(OnCurveRequest { pubkey: wallet_ata.to_string() }, solana.on_curve(&wallet_ata))?,
+    );
+    add(
+        "POST",
+        "/pubkey/normalize",
+        with_body(
+            NormalizePubkeyRequest {
+                pubkey: wallet.pubkey().to_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
+                public_key_encoding: None,
+            },
+            solana.normalize_pubkey(&wallet.pubkey(), "hex"),
+        )?,
+    );
+
+    // Token-2022 transfer-fee instructions: 0.5% per transfer, capped at 5 tokens
+    let fee = TransferFeeSettings {
+        basis_points: 50,
+        maximum_fee: 5_000_000,
+        config_authority: wallet.pubkey(),
+        withdraw_withheld_authority: wallet.pubkey(),
+    };
+    add(
+        "POST",
+        "/token/transfer-fee/create-mint",
+        with_body(
+            CreateTransferFeeMintRequest {
+                mint_authority: wallet.pubkey().to_string(),
+                mint: mint.to_string(),
+                decimals: 6,
+                freeze_authority: None,
+                transfer_fee_basis_points: fee.basis_points,
+                maximum_fee: fee.maximum_fee,
+                transfer_fee_config_authority: None,
+                withdraw_withheld_authority: None,
+            },
+            solana.create_transfer_fee_mint(&mint, &wallet.pubkey(), None, 6, fee)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/transfer-fee/transfer",
+        with_body(
+            TransferWithFeeRequest {
+                destination: recipient.to_string(),
+                mint: mint.to_string(),
+                owner: wallet.pubkey().to_string(),
+                amount: 1_000_000,
+                decimals: 6,
+                fee: None,
+                transfer_fee_basis_points: Some(fee.basis_points),
+                maximum_fee: Some(fee.maximum_fee),
+            },
+            solana.transfer_with_fee(
+                &wallet.pubkey(),
+                &recipient,
+                &mint,
+                1_000_000,
+                6,
+                solana.transfer_fee(1_000_000, fee.basis_points, fee.maximum_fee)?,
+            )?,
+        )?,
+    );
+
+    // Token-2022 metadata stored in the mint itself
+    add(
+        "POST",
+        "/token/metadata/pointer",
+        with_body(
+            InitializeMetadataPointerRequest {
+                mint: mint.to_string(),
+                authority: Some(wallet.pubkey().to_string()),
+                metadata_address: None,
+            },
+            solana.initialize_metadata_pointer(&mint, Some(&wallet.pubkey()), None)?,
+        )?,
+    );
+    let uri = "https://example.com/token.json";
+    add(
+        "POST",
+        "/token/metadata/initialize",
+        with_body(
+            InitializeTokenMetadataRequest {
+                mint: mint.to_string(),
+                mint_authority: wallet.pubkey().to_string(),
+                update_authority: None,
+                name: "Example Token".to_string(),
+                symbol: "EXMPL".to_string(),
+                uri: uri.to_string(),
+            },
+            solana.initialize_token_metadata(
+                &mint,
+                &wallet.pubkey(),
+                &wallet.pubkey(),
+                "Example Token".to_string(),
+                "EXMPL".to_string(),
+                uri.to_string(),
+            )?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/metadata/update",
+        with_body(
+            UpdateTokenMetadataRequest {
+                mint: mint.to_string(),
+                update_authority: wallet.pubkey().to_string(),
+                field: "uri".to_string(),
+                value: "https://example.com/token-v2.json".to_string(),
+            },
+            solana.update_token_metadata(&mint, &wallet.pubkey(), "uri", "https://example.com/token-v2.json".to_string())?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/close-mint/create-mint",
+        with_body(
+            CreateCloseableMintRequest {
+                mint_authority: wallet.pubkey().to_string(),
+                mint: mint.to_string(),
+                decimals: 6,
+                freeze_authority: None,
+                close_authority: None,
+            },
+            solana.create_closeable_mint(&mint, &wallet.pubkey(), None, 6, &wallet.pubkey())?,
+        )?,
+    );
+    add(
+        "POST",
+        "/token/close-mint",
+        with_body(
+            CloseMintRequest {
+                mint: mint.to_string(),
+                destination: wallet.pubkey().to_string(),
+                close_authority: wallet.pubkey().to_string(),
+            },
+            solana.close_mint(&mint, &wallet.pubkey(), &wallet.pubkey())?,
+        )?,
+    );
+
+    // Stake instructions
+    let stake_account = sample_keypair(4)?.pubkey();
+    let vote_account = sample_keypair(5)?.pubkey();
+    add(
+        "POST",
+        "/stake/create",
+        with_body(
+            CreateStakeAccountRequest {
+                from: wallet.pubkey().to_string(),
+                stake_account: stake_account.to_string(),
+                lamports: 1_000_000_000,
+                staker: None,
+                withdrawer: None,
+            },
+            solana.create_stake_account(&wallet.pubkey(), &stake_account, &wallet.pubkey(), &wallet.pubkey(), 1_000_000_000)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/stake/delegate",
+        with_body(
+            DelegateStakeRequest {
+                stake_account: stake_account.to_string(),
+                vote_account: vote_account.to_string(),
+                staker: wallet.pubkey().to_string(),
+            },
+            solana.delegate_stake(&stake_account, &vote_account, &wallet.pubkey())?,
+        )?,
+    );
+    add(
+        "POST",
+        "/stake/deactivate",
+        with_body(
+            DeactivateStakeRequest { stake_account: stake_account.to_string(), staker: wallet.pubkey().to_string() },
+            solana.deactivate_stake(&stake_account, &wallet.pubkey())?,
+        )?,
+    );
+    add(
+        "POST",
+        "/stake/withdraw",
+        with_body(
+            WithdrawStakeRequest {
+                stake_account: stake_account.to_string(),
+                withdrawer: wallet.pubkey().to_string(),
+                destination: wallet.pubkey().to_string(),
+                lamports: 1_000_000_000,
+            },
+            solana.withdraw_stake(&stake_account, &wallet.pubkey(), &wallet.pubkey(), 1_000_000_000)?,
+        )?,
+    );
+
+    // Messages and sign-in
+    let signed_message = solana.sign_message("Hello, Solana!", None, &wallet_secret)?;
+    add(
+        "POST",
+        "/message/sign",
+        with_body(
+            SignMessageRequest {
+                message: "Hello, Solana!".to_string(),
+                secret: wallet_secret.clone(),
+                prefix: None,
+                allow_empty: false,
+            },
+            signed_message.clone(),
+        )?,
+    );
+    let batch_messages = vec!["Hello, Solana!".to_string(), "Goodbye, Solana!".to_string()];
+    add(
+        "POST",
+        "/message/sign/batch",
+        with_body(
+            SignMessageBatchRequest {
+                secret: wallet_secret.clone(),
+                messages: batch_messages.clone(),
+                prefix: None,
+                allow_empty: false,
+            },
+            solana.sign_messages(&batch_messages, None, &wallet_secret)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/message/verify",
+        with_body(
+            VerifyMessageRequest {
+                message: "Hello, Solana!".to_string(),
+                signature: signed_message.signature.clone(),
+                pubkey: signed_message.pubkey.clone(),
+                prefix: None,
+                public_key_encoding: None,
+                signature_encoding: None,
+                strict: false,
+                allow_empty: false,
+            },
+            solana.verify_message("Hello, Solana!", None, &signed_message.signature, &signed_message.pubkey, false)?,
+        )?,
+    );
+    let challenge = "Xq7pLm2RtY9vBc4K".to_string();
+    add("POST", "/auth/challenge", no_body(AuthChallengeResponse { nonce: challenge.clone(), expires_in_secs: 300 })?);
+    add(
+        "POST",
+        "/auth/verify",
+        with_body(
+            AuthVerifyRequest {
+                pubkey: wallet.pubkey().to_string(),
+                signature: solana.sign_message(&challenge, None, &wallet_secret)?.signature,
+                nonce: challenge,
+            },
+            AuthVerifyResponse { verified: true, pubkey: wallet.pubkey().to_string() },
+        )?,
+    );
+    add(
+        "POST",
+        "/siws/build",
+        with_body(siws.clone(), SiwsBuildResponse { message: siws_text.clone(), fields: siws.clone() })?,
+    );
+    add(
+        "POST",
+        "/siws/verify",
+        with_body(
+            SiwsVerifyRequest {
+                message: siws_text.clone(),
+                signature: wallet.sign_message(siws_text.as_bytes()).to_string(),
+                domain: Some(siws.domain.clone()),
+                nonce: siws.nonce.clone(),
+            },
+            SiwsVerifyResponse { valid: true, reason: None, fields: siws.clone() },
+        )?,
+    );
+
+    // Transactions
+    add(
+        "POST",
+        "/transaction/sign",
+        with_body(
+            SignTransactionRequest {
+                message: general_purpose::STANDARD.encode(transfer.serialize()),
+                secrets: vec![wallet_secret.clone()],
+            },
+            signed.clone(),
+        )?,
+    );
+    add(
+        "POST",
+        "/transaction/sign/partial",
+        with_body(
+            PartialSignTransactionRequest {
+                transaction: encode_transaction(&unsigned)?,
+                secrets: vec![wallet_secret.clone()],
+            },
+            solana.partial_sign_transaction(unsigned.clone(), std::slice::from_ref(&wallet_secret))?,
+        )?,
+    );
+    let signed_transaction = bincode::deserialize::<Transaction>(
+        &general_purpose::STANDARD.decode(&signed.transaction).map_err(serialization_error)?,
+    )
+    .map_err(serialization_error)?;
+    add(
+        "POST",
+        "/transaction/verify",
+        with_body(
+            VerifyTransactionRequest { transaction: signed.transaction.clone() },
+            solana.verify_transaction(&signed_transaction)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/transaction/signature",
+        with_body(
+            TransactionSignatureRequest { transaction: signed.transaction.clone() },
+            solana.transaction_signature(&signed_transaction)?,
+        )?,
+    );
+
+    // Transfers
+    let send_sol = SendSolRequest { from: wallet.pubkey().to_string(), to: recipient.to_string(), lamports: 1_000_000 };
+    let sent_sol = solana.send_sol(&send_sol.from, &send_sol.to, send_sol.lamports)?;
+    add("POST", "/send/sol", with_body(send_sol.clone(), sent_sol.clone())?);
+    add(
+        "POST",
+        "/send/token",
+        with_body(
+            SendTokenRequest {
+                destination: recipient.to_string(),
+                mint: mint.to_string(),
+                owner: wallet.pubkey().to_string(),
+                amount: 1_000_000,
+                create_ata_if_missing: false,
+            },
+            solana.send_token(&recipient.to_string(), &mint.to_string(), &wallet.pubkey().to_string(), 1_000_000, false)?,
+        )?,
+    );
+    add(
+        "POST",
+        "/send/token-with-memo",
+        with_body(
+            SendTokenWithMemoRequest {
+                destination: recipient.to_string(),
+                mint: mint.to_string(),
+                owner: wallet.pubkey().to_string(),
+                amount: 1_000_000,
+                memo: "deposit 1234".to_string(),
+                create_ata_if_missing: false,
+            },
+            solana.send_token_with_memo(&recipient, &mint, &wallet.pubkey(), 1_000_000, "deposit 1234", false)?,
+        )?,
+    );
+
+    // Batch
+    add(
+        "POST",
+        "/batch",
+        with_body(
+            vec![
+                BatchCall {
+                    id: Some(serde_json::json!(1)),
+                    method: "send/sol".to_string(),
+                    params: to_value(&send_sol)?,
+                },
+                BatchCall {
+                    id: Some(serde_json::json!(2)),
+                    method: "token/sync-native".to_string(),
+                    params: serde_json::json!({ "account": "invalid" }),
+                },
+            ],
+            vec![
+                BatchResult { id: Some(serde_json::json!(1)), status: 200, success: true, data: Some(to_value(&sent_sol)?), error: None },
+                BatchResult {
+                    id: Some(serde_json::json!(2)),
+                    status: 400,
+                    success: false,
+                    data: None,
+                    error: Some(
+                        crate::utils::validation::validate_pubkey("invalid", "account")
+                            .err()
+                            .map(|e| e.into_parts().1)
+                            .unwrap_or_default(),
+                    ),
+                },
+            ],
+        )?,
+    );
+
+    Ok(examples)
+}
+
+/// The keypair derived from a seed of 32 copies of `byte`
+fn sample_keypair(byte: u8) -> Result<solana_sdk::signature::Keypair> {
+    keypair_from_seed(&[byte; 32]).map_err(|e| AppError::InvalidKeypair(e.to_string()))
+}
+
+fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    Ok(general_purpose::STANDARD.encode(bincode::serialize(transaction).map_err(serialization_error)?))
+}
+
+fn to_value(value: &impl Serialize) -> Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(serialization_error)
+}
+
+/// The example of a route that takes no request body
+fn no_body(response: impl Serialize) -> Result<RouteExample> {
+    Ok(RouteExample { request: None, response: to_value(&ApiResponse::success(response))? })
+}
+
+fn with_body(request: impl Serialize, response: impl Serialize) -> Result<RouteExample> {
+    Ok(RouteExample { request: Some(to_value(&request)?), response: to_value(&ApiResponse::success(response))? })
+}
@@ -1,30 +1,152 @@
 use axum::{
-    extract::{Json as ExtractJson, rejection::JsonRejection},
-    response::Json,
+    extract::{Json as ExtractJson, Query, State, rejection::JsonRejection, ws::WebSocketUpgrade},
+    response::{IntoResponse, Json, Response},
     async_trait,
     extract::FromRequest,
-    http::Request,
+    http::{header, Request, StatusCode},
 };
-use tracing::{info, error};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use futures::StreamExt;
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signer};
+use tracing::{info, warn, error};
 use serde::de::DeserializeOwned;
+use utoipa::OpenApi;
 
+use crate::config::{DefaultFreezeAuthority, TokenPolicy};
+use crate::state::AppState;
 use crate::models::{
     ApiResponse, 
+    ApiErrorResponse,
     KeypairResponse,
+    PublicKeyResponse,
+    KeypairFromSeedRequest,
+    DeriveKeypairBatchRequest,
+    DeriveKeypairBatchResponse,
+    KeypairQrQuery,
+    DerivePdaRequest,
+    DerivePdaQuery,
+    PdaResponse,
+    DerivePdaBatchRequest,
+    DerivePdaBatchResponse,
+    PdaBatchResult,
+    StreamQuery,
+    AssociatedTokenAddressRequest,
+    AssociatedTokenAddressQuery,
+    AssociatedTokenAddressResponse,
+    AssociatedTokenAddressBatchRequest,
+    AssociatedTokenAddressBatchResponse,
+    MintAssociatedTokenAddress,
+    OnCurveRequest,
+    NormalizePubkeyRequest,
+    NormalizePubkeyResponse,
+    OnCurveQuery,
+    OnCurveResponse,
+    EntropyCheckQuery,
+    EntropyCheckResponse,
+    ExportKeypairRequest,
+    ExportKeypairResponse,
+    ImportKeypairRequest,
+    InspectKeypairRequest,
+    InspectKeypairResponse,
+    RotateKeypairRequest,
+    RotateKeypairResponse,
+    FundedKeypairRequest,
+    FundedKeypairResponse,
+    TokenAccountsRequest,
+    TokenAccountsResponse,
+    AccountInfoRequest,
+    AccountInfoResponse,
+    AccountSubscribeQuery,
+    MintInfoRequest,
+    MintInfoResponse,
+    TokenAccountInfoRequest,
+    TokenAccountInfoResponse,
+    RentRequest,
+    RentResponse,
+    SendTransactionRequest,
+    SendTransactionResponse,
+    RelayTransactionRequest,
+    RelayTransactionResponse,
+    AirdropRequest,
+    AirdropResponse,
+    SimulateTransactionRequest,
+    SimulateTransactionResponse,
+    SignatureStatusRequest,
+    SignatureStatusResponse,
+    VoteAccountsRequest,
+    VoteAccountsResponse,
+    HealthResponse,
+    DeepHealthResponse,
+    VersionResponse,
+    RouteExample,
     CreateTokenRequest,
+    CreateNewTokenRequest,
+    CreateNewTokenResponse,
     MintTokenRequest,
+    MintBatchRequest,
+    MintBatchResponse,
     TokenInstructionResponse,
+    MintRecipient,
+    BurnCloseRequest,
+    BurnCloseResponse,
+    WrapSolRequest,
+    UnwrapSolRequest,
+    SyncNativeRequest,
+    CreateTransferFeeMintRequest,
+    CreateTransferFeeMintResponse,
+    CreateCloseableMintRequest,
+    CreateCloseableMintResponse,
+    CloseMintRequest,
+    TransferWithFeeRequest,
+    TransferWithFeeResponse,
+    InitializeMetadataPointerRequest,
+    InitializeTokenMetadataRequest,
+    UpdateTokenMetadataRequest,
+    CreateStakeAccountRequest,
+    CreateStakeAccountResponse,
+    DelegateStakeRequest,
+    DeactivateStakeRequest,
+    WithdrawStakeRequest,
+    WrappedSolResponse,
     SignMessageRequest,
+    SignMessageBatchRequest,
+    SignMessageBatchResponse,
+    SignTransactionRequest,
+    SignTransactionResponse,
+    PartialSignTransactionRequest,
+    PartialSignTransactionResponse,
+    VerifyTransactionRequest,
+    TransactionSignatureRequest,
+    TransactionSignatureResponse,
+    VerifyTransactionResponse,
     SignMessageResponse,
     VerifyMessageRequest,
     VerifyMessageResponse,
+    SiwsBuildResponse,
+    AuthChallengeResponse,
+    AuthVerifyRequest,
+    AuthVerifyResponse,
+    SiwsVerifyRequest,
+    SiwsVerifyResponse,
     SendSolRequest,
     SendSolResponse,
     SendTokenRequest,
+    SendTokenWithMemoRequest,
+    SendTokenWithMemoResponse,
+    BatchCall,
+    BatchResult,
     SendTokenResponse,
 };
-use crate::services::solana::SolanaService;
-use crate::utils::errors::{AppError, Result};
+use crate::services::rpc::CONFIRMATION_TIMEOUT;
+use crate::services::siws::SiwsMessage;
+use crate::services::solana::TransferFeeSettings;
+use crate::utils::audit::AuditResult;
+use crate::utils::errors::{AppError, Result, serialization_error};
+use crate::utils::challenge::RedeemError;
+use crate::utils::ndjson::ndjson_response;
+use crate::utils::pagination::paginate;
 use crate::utils::validation;
 
 /// Custom JSON extractor that handles deserialization errors properly
@@ -41,11 +163,20 @@ where
     async fn from_request(req: Request<axum::body::Body>, state: &S) -> Result<Self> {
         match ExtractJson::<T>::from_request(req, state).await {
             Ok(json) => Ok(JsonExtractor(json.0)),
+            // A body sent without the JSON content type is the client's framing mistake, not
+            // invalid input, so it gets its own status
+            Err(JsonRejection::MissingJsonContentType(_)) => Err(AppError::UnsupportedMediaType(
+                "Content-Type must be application/json".to_string(),
+            )),
             Err(rejection) => {
                 let error_message = match rejection {
                     JsonRejection::JsonDataError(err) => {
-                        let err_str = err.to_string();
-                        if err_str.contains("missing field") || 
+                        let err_str = err.body_text();
+                        if let Some(message) = integer_range_error(&err_str) {
+                            message
+                        } else if let Some(message) = unknown_field_error(&err_str) {
+                            message
+                        } else if err_str.contains("missing field") || 
                            err_str.contains("missing field `") ||
                            err_str.contains("expected value") ||
                            err_str.contains("Failed to deserialize") {
@@ -57,9 +188,6 @@ where
                     JsonRejection::JsonSyntaxError(_) => {
                         "Invalid JSON syntax".to_string()
                     }
-                    JsonRejection::MissingJsonContentType(_) => {
-                        "Missing Content-Type: application/json header".to_string()
-                    }
                     _ => "Invalid request body".to_string(),
                 };
                 Err(AppError::ValidationError(error_message))
@@ -68,274 +196,4546 @@ where
     }
 }
 
+/// Turns serde's error for a negative, fractional, too-large or non-numeric integer field into a
+/// clear message naming the rejected value (e.g. an amount above u64::MAX, or `"100 SOL"`)
+/// instead of wrapping or a generic "Invalid JSON data"
+fn integer_range_error(err_str: &str) -> Option<String> {
+    let (max, kind) = if err_str.contains("expected u64") {
+        (u64::MAX.to_string(), "u64")
+    } else if err_str.contains("expected u8") {
+        (u8::MAX.to_string(), "u8")
+    } else {
+        return None;
+    };
+
+    // axum reports "Failed to deserialize the JSON body into the target type: <field>: <error>"
+    let field = err_str
+        .split(": ")
+        .nth(1)
+        .filter(|field| !field.contains(' '))
+        .unwrap_or("value");
+
+    let message = format!("{} must be an integer between 0 and {} ({})", field, max, kind);
+    Some(match rejected_value(err_str) {
+        Some(value) => format!("{}, got {}", message, value),
+        None => message,
+    })
+}
+
+/// The value serde rejected, from the "invalid type: floating point `1.5`, expected u64" part of
+/// its error: the number itself, a string with its quotes, or a kind like `null` or `map`
+fn rejected_value(err_str: &str) -> Option<&str> {
+    let start = ["invalid type: ", "invalid value: "]
+        .iter()
+        .find_map(|prefix| err_str.find(prefix).map(|start| start + prefix.len()))?;
+    let unexpected = &err_str[start..start + err_str[start..].rfind(", expected")?];
+    Some(match (unexpected.find('`'), unexpected.rfind('`')) {
+        (Some(open), Some(close)) if open < close => &unexpected[open + 1..close],
+        _ => unexpected.strip_prefix("string ").unwrap_or(unexpected),
+    })
+}
+
+/// Turns serde's error for a field the request type doesn't have (request types deny unknown
+/// fields, so a typo like "mintauthority" isn't silently ignored) into a message naming it and
+/// the accepted fields
+fn unknown_field_error(err_str: &str) -> Option<String> {
+    let start = err_str.find("unknown field `")?;
+    // Drop serde's " at line 1 column 17" position suffix
+    let message = err_str[start..].split(" at line ").next().unwrap_or_default();
+    Some(format!("U{}", &message[1..]))
+}
+
+/// Handler for GET /openapi.json
+/// Serves the OpenAPI 3 document describing every route
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "meta",
+    responses(
+        (status = 200, description = "OpenAPI 3 document", body = Object),
+    )
+)]
+pub async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(crate::openapi::ApiDoc::openapi())
+}
+
+/// Handler for GET /examples
+/// Serves a sample request and response for every JSON route
+#[utoipa::path(
+    get,
+    path = "/examples",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Sample request and response of each route, keyed by \"METHOD /path\"", body = ApiResponse<BTreeMap<String, RouteExample>>),
+    )
+)]
+pub async fn examples_handler() -> Result<Json<ApiResponse<BTreeMap<String, RouteExample>>>> {
+    match crate::examples::route_examples() {
+        Ok(examples) => Ok(Json(ApiResponse::success(examples))),
+        Err(e) => {
+            error!("Failed to build route examples: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Fallback for paths no route matches, so unknown routes get the standard error envelope
+pub async fn not_found_handler() -> AppError {
+    AppError::NotFound("Not Found".to_string())
+}
+
+/// Handler for GET /health
+/// Reports that the server is up and which cluster it is configured for
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Server is up", body = ApiResponse<HealthResponse>),
+    )
+)]
+pub async fn health_handler(State(state): State<AppState>) -> Json<ApiResponse<HealthResponse>> {
+    Json(ApiResponse::success(HealthResponse {
+        status: "ok".to_string(),
+        cluster: state.config.cluster,
+    }))
+}
+
+/// Handler for GET /health/deep
+/// Reports whether the configured RPC node is reachable and healthy, and the slot it has reached
+///
+/// Answers 503 when the check fails, so readiness probes stop routing to a server that can't
+/// reach the cluster. The body keeps the same shape either way.
+#[utoipa::path(
+    get,
+    path = "/health/deep",
+    tag = "meta",
+    responses(
+        (status = 200, description = "The RPC node is healthy, or RPC is disabled", body = ApiResponse<DeepHealthResponse>),
+        (status = 503, description = "No RPC endpoint is reachable and healthy", body = ApiResponse<DeepHealthResponse>),
+    )
+)]
+pub async fn deep_health_handler(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<DeepHealthResponse>>) {
+    let health = |rpc: &str, slot, error: Option<String>| DeepHealthResponse {
+        status: if error.is_some() { "degraded" } else { "ok" }.to_string(),
+        cluster: state.config.cluster,
+        rpc: rpc.to_string(),
+        slot,
+        error,
+    };
+    // An offline-only server is ready without a cluster to reach
+    if !state.config.rpc.enabled {
+        return (StatusCode::OK, Json(ApiResponse::success(health("disabled", None, None))));
+    }
+
+    // A node that is behind still reports its slot, so the slot is fetched either way
+    let (slot, error) = match tokio::join!(state.rpc.get_health(), state.rpc.get_slot()) {
+        (Ok(()), Ok(slot)) => (Some(slot), None),
+        (Err(e), slot) => (slot.ok(), Some(e)),
+        (Ok(()), Err(e)) => (None, Some(e)),
+    };
+    match error {
+        None => (StatusCode::OK, Json(ApiResponse::success(health("ok", slot, None)))),
+        Some(e) => {
+            warn!("Deep health check failed: {}", e);
+            let response = health("degraded", slot, Some(e.into_parts().1));
+            (StatusCode::SERVICE_UNAVAILABLE, Json(ApiResponse { success: false, ..ApiResponse::success(response) }))
+        }
+    }
+}
+
+/// Handler for GET /version
+/// Reports the server version and the Solana crate versions it was built with
+#[utoipa::path(
+    get,
+    path = "/version",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Server and dependency versions", body = ApiResponse<VersionResponse>),
+    )
+)]
+pub async fn version_handler() -> Json<ApiResponse<VersionResponse>> {
+    Json(ApiResponse::success(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        solana_sdk: env!("SOLANA_SDK_VERSION").to_string(),
+        spl_token: env!("SPL_TOKEN_VERSION").to_string(),
+    }))
+}
+
 /// Handler for POST /keypair
 /// Generates a new Solana keypair
-pub async fn generate_keypair_handler() -> Result<Json<ApiResponse<KeypairResponse>>> {
+#[utoipa::path(
+    post,
+    path = "/keypair",
+    tag = "keypair",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<KeypairResponse>),
+    )
+)]
+pub async fn generate_keypair_handler(State(state): State<AppState>) -> Result<Json<ApiResponse<KeypairResponse>>> {
     info!("Handling keypair generation request");
 
-    let solana_service = SolanaService::new();
+    let solana_service = &state.solana;
     
     match solana_service.generate_keypair() {
         Ok(keypair_response) => {
             info!("Successfully generated new keypair");
+            state.audit.record("keypair.generate", &[&keypair_response.pubkey], AuditResult::Success);
             Ok(Json(ApiResponse::success(keypair_response)))
         }
         Err(e) => {
             error!("Failed to generate keypair: {}", e);
+            state.audit.record("keypair.generate", &[], AuditResult::failure(&e));
             Err(e)
         }
     }
 }
 
-/// Handler for POST /token/create
-/// Creates an SPL token mint instruction
-pub async fn create_token_handler(
-    JsonExtractor(request): JsonExtractor<CreateTokenRequest>,
-) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
-    info!("Handling token creation request for mint: {}", request.mint);
+/// Handler for POST /keypair/public-only
+/// Generates a keypair and returns only its public key, discarding the secret
+#[utoipa::path(
+    post,
+    path = "/keypair/public-only",
+    tag = "keypair",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PublicKeyResponse>),
+    )
+)]
+pub async fn public_only_keypair_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<PublicKeyResponse>>> {
+    info!("Handling public-only keypair generation request");
 
-    // Comprehensive validation using validation module
-    let mint_authority = validation::validate_pubkey(&request.mint_authority, "mintAuthority")?;
-    let mint = validation::validate_pubkey(&request.mint, "mint")?;
-    let decimals = validation::validate_decimals(request.decimals)?;
+    match state.solana.generate_public_key_only() {
+        Ok(public_key_response) => {
+            info!("Successfully generated public key: {}", public_key_response.pubkey);
+            state.audit.record("keypair.generate_public_only", &[&public_key_response.pubkey], AuditResult::Success);
+            Ok(Json(ApiResponse::success(public_key_response)))
+        }
+        Err(e) => {
+            error!("Failed to generate public key: {}", e);
+            state.audit.record("keypair.generate_public_only", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
 
-    let solana_service = SolanaService::new();
+/// Handler for GET /keypair/qr
+/// Renders a public key as a scannable SVG QR code
+#[utoipa::path(
+    get,
+    path = "/keypair/qr",
+    tag = "keypair",
+    params(KeypairQrQuery),
+    responses(
+        (status = 200, description = "QR code encoding the base58 address", content_type = "image/svg+xml", body = String),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn keypair_qr_handler(Query(query): Query<KeypairQrQuery>) -> Result<Response> {
+    info!("Handling QR code request for: {}", query.pubkey);
 
-    match solana_service.create_token_mint(
-        &mint_authority.to_string(),
-        &mint.to_string(),
-        decimals,
-    ) {
-        Ok(token_response) => {
-            info!("Successfully created token mint instruction for mint: {}", request.mint);
-            Ok(Json(ApiResponse::success(token_response)))
+    let pubkey = validation::validate_pubkey(&query.pubkey, "pubkey")?;
+
+    match crate::services::qr::pubkey_svg(&pubkey) {
+        Ok(svg) => {
+            info!("Successfully rendered QR code for: {}", pubkey);
+            Ok(([(header::CONTENT_TYPE, "image/svg+xml")], svg).into_response())
         }
         Err(e) => {
-            error!("Failed to create token mint instruction: {}", e);
+            error!("Failed to render QR code: {}", e);
             Err(e)
         }
     }
 }
 
-/// Handler for POST /token/mint
-/// Creates an SPL token mint_to instruction
-pub async fn mint_token_handler(
-    JsonExtractor(request): JsonExtractor<MintTokenRequest>,
-) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
-    info!("Handling token minting request for mint: {}", request.mint);
+/// Handler for GET /keypair/entropy-check
+/// Generates a sample of keypairs and runs basic statistical checks on their seeds
+#[utoipa::path(
+    get,
+    path = "/keypair/entropy-check",
+    tag = "keypair",
+    params(EntropyCheckQuery),
+    responses(
+        (status = 200, description = "Pass/fail summary of the checks; no keys are returned", body = ApiResponse<EntropyCheckResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn entropy_check_handler(
+    Query(query): Query<EntropyCheckQuery>,
+) -> Result<Json<ApiResponse<EntropyCheckResponse>>> {
+    info!("Handling entropy check request");
 
-    // Comprehensive validation using validation module
-    let mint = validation::validate_pubkey(&request.mint, "mint")?;
-    let destination = validation::validate_pubkey(&request.destination, "destination")?;
-    let authority = validation::validate_pubkey(&request.authority, "authority")?;
-    let amount = validation::validate_positive_amount(request.amount, "amount")?;
+    let sample = validation::validate_entropy_sample(query.sample)?;
 
-    let solana_service = SolanaService::new();
+    // Generating up to a thousand keypairs is CPU-bound, so keep it off the async workers
+    let report = tokio::task::spawn_blocking(move || crate::services::entropy::check_keypair_entropy(sample))
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Entropy check failed: {}", e)))?;
 
-    match solana_service.mint_token(
-        &mint.to_string(),
-        &destination.to_string(),
-        &authority.to_string(),
-        amount,
-    ) {
-        Ok(token_response) => {
-            info!("Successfully created token mint_to instruction for mint: {}", request.mint);
-            Ok(Json(ApiResponse::success(token_response)))
+    if report.passed {
+        info!("Entropy check over {} keypairs passed", report.sample_size);
+    } else {
+        error!("Entropy check over {} keypairs FAILED: {:?}", report.sample_size, report.checks);
+    }
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Handler for POST /keypair/from-seed
+/// Derives the same keypair every time for a given 32-byte seed
+#[utoipa::path(
+    post,
+    path = "/keypair/from-seed",
+    tag = "keypair",
+    request_body = KeypairFromSeedRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<KeypairResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn keypair_from_seed_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<KeypairFromSeedRequest>,
+) -> Result<Json<ApiResponse<KeypairResponse>>> {
+    info!("Handling keypair from seed request");
+
+    let seed = validation::validate_seed(&request.seed)?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.keypair_from_seed(&seed) {
+        Ok(keypair_response) => {
+            info!("Successfully derived keypair: {}", keypair_response.pubkey);
+            state.audit.record("keypair.from_seed", &[&keypair_response.pubkey], AuditResult::Success);
+            Ok(Json(ApiResponse::success(keypair_response)))
         }
         Err(e) => {
-            error!("Failed to create token mint_to instruction: {}", e);
+            error!("Failed to derive keypair from seed: {}", e);
+            state.audit.record("keypair.from_seed", &[], AuditResult::failure(&e));
             Err(e)
         }
     }
 }
 
-/// Handler for POST /message/sign
-/// Signs a message with the provided secret key
-pub async fn sign_message_handler(
-    JsonExtractor(request): JsonExtractor<SignMessageRequest>,
-) -> Result<Json<ApiResponse<SignMessageResponse>>> {
-    info!("Handling message signing request");
+/// Handler for POST /keypair/derive/batch
+/// Derives `count` sequential accounts m/44'/501'/i'/0' of a BIP39 mnemonic, starting at `start_index`
+#[utoipa::path(
+    post,
+    path = "/keypair/derive/batch",
+    tag = "keypair",
+    request_body = DeriveKeypairBatchRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<DeriveKeypairBatchResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn derive_keypair_batch_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<DeriveKeypairBatchRequest>,
+) -> Result<Json<ApiResponse<DeriveKeypairBatchResponse>>> {
+    info!("Handling derive keypair batch request for {} accounts", request.count);
+
+    let mnemonic = validation::validate_mnemonic(&request.mnemonic)?;
+    let accounts = validation::validate_derivation_range(request.start_index, request.count)?;
+
+    match state.solana.derive_keypairs(&mnemonic, &request.passphrase, accounts) {
+        Ok(keypairs) => {
+            info!("Successfully derived {} keypairs", keypairs.len());
+            let pubkeys: Vec<&str> = keypairs.iter().map(|keypair| keypair.pubkey.as_str()).collect();
+            state.audit.record("keypair.derive_batch", &pubkeys, AuditResult::Success);
+            Ok(Json(ApiResponse::success(DeriveKeypairBatchResponse { keypairs })))
+        }
+        Err(e) => {
+            error!("Failed to derive keypairs from mnemonic: {}", e);
+            state.audit.record("keypair.derive_batch", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /keypair/export
+/// Converts a base58 secret key into the solana-keygen JSON byte array format
+#[utoipa::path(
+    post,
+    path = "/keypair/export",
+    tag = "keypair",
+    request_body = ExportKeypairRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ExportKeypairResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn export_keypair_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<ExportKeypairRequest>,
+) -> Result<Json<ApiResponse<ExportKeypairResponse>>> {
+    info!("Handling keypair export request");
 
-    // Comprehensive validation using validation module
-    validation::validate_non_empty_string(&request.message, "message")?;
     validation::validate_secret_key(&request.secret)?;
 
-    let solana_service = SolanaService::new();
+    let solana_service = &state.solana;
 
-    match solana_service.sign_message(&request.message, &request.secret) {
-        Ok(sign_response) => {
-            info!("Successfully signed message");
-            Ok(Json(ApiResponse::success(sign_response)))
+    match solana_service.export_keypair(&request.secret) {
+        Ok(export_response) => {
+            info!("Successfully exported keypair: {}", export_response.pubkey);
+            Ok(Json(ApiResponse::success(export_response)))
         }
         Err(e) => {
-            error!("Failed to sign message: {}", e);
+            error!("Failed to export keypair: {}", e);
             Err(e)
         }
     }
 }
 
-/// Handler for POST /message/verify
-/// Verifies a message signature
-pub async fn verify_message_handler(
-    JsonExtractor(request): JsonExtractor<VerifyMessageRequest>,
-) -> Result<Json<ApiResponse<VerifyMessageResponse>>> {
-    info!("Handling message verification request");
+/// Handler for POST /keypair/import
+/// Converts a solana-keygen JSON byte array into a base58 keypair
+#[utoipa::path(
+    post,
+    path = "/keypair/import",
+    tag = "keypair",
+    request_body = ImportKeypairRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<KeypairResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn import_keypair_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<ImportKeypairRequest>,
+) -> Result<Json<ApiResponse<KeypairResponse>>> {
+    info!("Handling keypair import request");
 
-    // Comprehensive validation using validation module
-    validation::validate_non_empty_string(&request.message, "message")?;
-    let _signature_bytes = validation::validate_signature_format(&request.signature)?;
-    let pubkey = validation::validate_pubkey(&request.pubkey, "pubkey")?;
+    validation::validate_keypair_bytes(&request.keypair)?;
 
-    let solana_service = SolanaService::new();
+    let solana_service = &state.solana;
 
-    match solana_service.verify_message(
-        &request.message,
-        &request.signature,
-        &pubkey.to_string(),
-    ) {
-        Ok(verify_response) => {
-            info!("Successfully verified message signature: {}", verify_response.valid);
-            Ok(Json(ApiResponse::success(verify_response)))
+    match solana_service.import_keypair(&request.keypair) {
+        Ok(keypair_response) => {
+            info!("Successfully imported keypair: {}", keypair_response.pubkey);
+            Ok(Json(ApiResponse::success(keypair_response)))
         }
         Err(e) => {
-            error!("Failed to verify message signature: {}", e);
+            error!("Failed to import keypair: {}", e);
             Err(e)
         }
     }
 }
 
-/// Handler for POST /send/sol
-/// Creates a SOL transfer instruction
-pub async fn send_sol_handler(
-    JsonExtractor(request): JsonExtractor<SendSolRequest>,
-) -> Result<Json<ApiResponse<SendSolResponse>>> {
-    info!("Handling SOL transfer request from {} to {}", request.from, request.to);
+/// Handler for POST /keypair/inspect
+/// Reports a secret key's length and public keys, for diagnosing malformed keys
+#[utoipa::path(
+    post,
+    path = "/keypair/inspect",
+    tag = "keypair",
+    request_body = InspectKeypairRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<InspectKeypairResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn inspect_keypair_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<InspectKeypairRequest>,
+) -> Result<Json<ApiResponse<InspectKeypairResponse>>> {
+    info!("Handling keypair inspect request");
 
-    // Comprehensive validation using validation module
-    let from = validation::validate_pubkey(&request.from, "sender")?;
-    let to = validation::validate_pubkey(&request.to, "recipient")?;
-    let lamports = validation::validate_positive_amount(request.lamports, "lamports")?;
+    if request.secret.is_empty() {
+        return Err(AppError::ValidationError("secret is required".to_string()));
+    }
+
+    match state.solana.inspect_keypair(&request.secret) {
+        Ok(inspect_response) => {
+            info!("Inspected a {}-byte secret key (valid: {})", inspect_response.byte_length, inspect_response.valid);
+            Ok(Json(ApiResponse::success(inspect_response)))
+        }
+        Err(e) => {
+            error!("Failed to inspect keypair: {}", e);
+            Err(e)
+        }
+    }
+}
 
-    let solana_service = SolanaService::new();
+/// Handler for POST /keypair/rotate
+/// Checks messages were signed by the old key and signs them again with the new one
+#[utoipa::path(
+    post,
+    path = "/keypair/rotate",
+    tag = "keypair",
+    request_body = RotateKeypairRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RotateKeypairResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn rotate_keypair_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<RotateKeypairRequest>,
+) -> Result<Json<ApiResponse<RotateKeypairResponse>>> {
+    info!("Handling keypair rotation request with {} messages", request.messages.len());
 
-    match solana_service.send_sol(&from.to_string(), &to.to_string(), lamports) {
-        Ok(sol_response) => {
-            info!("Successfully created SOL transfer instruction");
-            Ok(Json(ApiResponse::success(sol_response)))
+    validation::validate_secret_key(&request.old_secret)?;
+    validation::validate_secret_key(&request.new_secret)?;
+    validation::validate_batch_len(request.messages.len(), "messages")?;
+    let messages = request.messages
+        .iter()
+        .enumerate()
+        .map(|(index, signed)| {
+            check_message(&signed.message, false, &format!("messages[{}].message", index))?;
+            Ok((signed.message.as_str(), validation::validate_signature(&signed.signature)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    validation::validate_message_prefix(request.prefix.as_deref())?;
+
+    match state.solana.rotate_signatures(&messages, request.prefix.as_deref(), &request.old_secret, &request.new_secret) {
+        Ok(rotate_response) => {
+            info!("Re-signed {} messages from {} under {}", rotate_response.signatures.len(), rotate_response.old_pubkey, rotate_response.new_pubkey);
+            state.audit.record(
+                "keypair.rotate",
+                &[&rotate_response.old_pubkey, &rotate_response.new_pubkey],
+                AuditResult::Success,
+            );
+            Ok(Json(ApiResponse::success(rotate_response)))
         }
         Err(e) => {
-            error!("Failed to create SOL transfer instruction: {}", e);
+            error!("Failed to rotate signatures: {}", e);
+            state.audit.record("keypair.rotate", &[], AuditResult::failure(&e));
             Err(e)
         }
     }
 }
 
-/// Handler for POST /send/token
-/// Creates an SPL token transfer instruction
-pub async fn send_token_handler(
-    JsonExtractor(request): JsonExtractor<SendTokenRequest>,
-) -> Result<Json<ApiResponse<SendTokenResponse>>> {
-    info!("Handling token transfer request for mint: {}", request.mint);
+/// How long before its route's timeout /keypair/funded gives up waiting for confirmation, so
+/// the response carrying the new secret key is sent before the request is abandoned
+const TIMEOUT_MARGIN: Duration = Duration::from_secs(1);
+
+/// Handler for POST /keypair/funded
+/// Generates a new keypair and funds it with an airdrop (devnet/testnet only)
+#[utoipa::path(
+    post,
+    path = "/keypair/funded",
+    tag = "keypair",
+    request_body = FundedKeypairRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<FundedKeypairResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn funded_keypair_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<FundedKeypairRequest>,
+) -> Result<Json<ApiResponse<FundedKeypairResponse>>> {
+    info!("Handling funded keypair request for {} SOL", request.sol);
+
+    let lamports = validation::validate_sol_amount(request.sol)?;
+    // The confirmation wait has to end before the route times out, or the keypair is lost with it
+    let deadline = Instant::now() + state.config.timeout_for("/keypair/funded").saturating_sub(TIMEOUT_MARGIN);
+
+    let solana_service = &state.solana;
+    let rpc_service = &state.rpc;
+
+    let mut keypair = solana_service
+        .generate_keypair()
+        .inspect_err(|e| state.audit.record("keypair.generate_funded", &[], AuditResult::failure(e)))?;
+    state.audit.record("keypair.generate_funded", &[&keypair.pubkey], AuditResult::Success);
+    let pubkey = Pubkey::from_str(&keypair.pubkey)
+        .map_err(|e| AppError::InternalServerError(format!("Generated invalid public key: {}", e)))?;
+
+    let signature = match rpc_service.request_airdrop(&pubkey, lamports).await {
+        Ok(signature) => signature,
+        Err(e) => {
+            error!("Failed to request airdrop: {}", e);
+            return Err(e);
+        }
+    };
+
+    // The airdrop may already have landed, so a failed confirmation check must not lose the keypair
+    let wait = CONFIRMATION_TIMEOUT.min(deadline.saturating_duration_since(Instant::now()));
+    let confirmed = match rpc_service.wait_for_confirmation(&signature, wait).await {
+        Ok(confirmed) => confirmed,
+        Err(e) => {
+            warn!("Failed to confirm airdrop {}: {}", signature, e);
+            false
+        }
+    };
+    info!("Airdrop {} to {} confirmed: {}", signature, keypair.pubkey, confirmed);
+
+    // Taken rather than moved, since KeypairResponse zeroizes its secret on drop
+    Ok(Json(ApiResponse::success(FundedKeypairResponse {
+        pubkey: std::mem::take(&mut keypair.pubkey),
+        secret: std::mem::take(&mut keypair.secret),
+        lamports,
+        signature: signature.to_string(),
+        confirmed,
+        cluster: state.config.cluster,
+    })))
+}
+
+/// Handler for POST /rpc/token-accounts
+/// Lists the SPL token accounts held by a wallet
+#[utoipa::path(
+    post,
+    path = "/rpc/token-accounts",
+    tag = "rpc",
+    request_body = TokenAccountsRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenAccountsResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn token_accounts_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<TokenAccountsRequest>,
+) -> Result<Json<ApiResponse<TokenAccountsResponse>>> {
+    info!("Handling token accounts request for owner: {}", request.owner);
 
-    // Comprehensive validation using validation module
-    let destination = validation::validate_pubkey(&request.destination, "destination")?;
-    let mint = validation::validate_pubkey(&request.mint, "mint")?;
     let owner = validation::validate_pubkey(&request.owner, "owner")?;
-    let amount = validation::validate_positive_amount(request.amount, "amount")?;
+    let mint = request.mint
+        .as_deref()
+        .map(|mint| validation::validate_pubkey(mint, "mint"))
+        .transpose()?;
+    let limit = validation::validate_page_limit(request.limit)?;
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
 
-    let solana_service = SolanaService::new();
+    let rpc_service = state.rpc.with_commitment(commitment);
 
-    match solana_service.send_token(
-        &destination.to_string(),
-        &mint.to_string(),
-        &owner.to_string(),
-        amount,
-    ) {
-        Ok(token_response) => {
-            info!("Successfully created token transfer instruction");
-            Ok(Json(ApiResponse::success(token_response)))
+    match rpc_service.get_token_accounts_by_owner(&owner, mint).await {
+        Ok(mut accounts) => {
+            info!("Found {} token accounts for owner: {}", accounts.len(), request.owner);
+            // The node returns accounts in no particular order, so sort them for stable pages
+            accounts.sort_by(|a, b| a.mint.cmp(&b.mint).then_with(|| a.account.cmp(&b.account)));
+            let (accounts, pagination) = paginate(accounts, request.offset, limit);
+            Ok(Json(ApiResponse::success(TokenAccountsResponse {
+                owner: owner.to_string(),
+                accounts,
+                pagination,
+            })))
         }
         Err(e) => {
-            error!("Failed to create token transfer instruction: {}", e);
+            error!("Failed to fetch token accounts: {}", e);
             Err(e)
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::{CreateTokenRequest, MintTokenRequest, SignMessageRequest, VerifyMessageRequest, SendSolRequest, SendTokenRequest};
+/// Handler for POST /rpc/account
+/// Fetches an account's lamports, owner, executable flag and data
+#[utoipa::path(
+    post,
+    path = "/rpc/account",
+    tag = "rpc",
+    request_body = AccountInfoRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AccountInfoResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 404, description = "Account not found", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn account_info_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<AccountInfoRequest>,
+) -> Result<Json<ApiResponse<AccountInfoResponse>>> {
+    info!("Handling account info request for: {}", request.pubkey);
 
-    #[tokio::test]
-    async fn test_generate_keypair_handler() {
-        let result = generate_keypair_handler().await;
-        assert!(result.is_ok());
-        
-        let response = result.unwrap();
-        assert!(response.0.success);
-        assert!(!response.0.data.pubkey.is_empty());
-        assert!(!response.0.data.secret.is_empty());
-    }
+    let pubkey = validation::validate_pubkey(&request.pubkey, "pubkey")?;
+    let encoding = validation::validate_account_encoding(&request.encoding)?;
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
 
-    #[tokio::test]
-    async fn test_create_token_handler_validation() {
-        let invalid_request = CreateTokenRequest {
-            mint_authority: "".to_string(),
-            mint: "".to_string(),
-            decimals: 9,
-        };
-        
-        let result = create_token_handler(JsonExtractor(invalid_request)).await;
-        assert!(result.is_err());
-    }
+    let rpc_service = state.rpc.with_commitment(commitment);
 
-    #[tokio::test]
-    async fn test_mint_token_handler_validation() {
-        let invalid_request = MintTokenRequest {
-            mint: "".to_string(),
-            destination: "".to_string(),
-            authority: "".to_string(),
-            amount: 0,
-        };
-        
-        let result = mint_token_handler(JsonExtractor(invalid_request)).await;
-        assert!(result.is_err());
+    match rpc_service.get_account_info(&pubkey, encoding).await {
+        Ok(account_response) => {
+            info!("Successfully fetched account: {}", request.pubkey);
+            Ok(Json(ApiResponse::success(account_response)))
+        }
+        Err(e) => {
+            error!("Failed to fetch account {}: {}", request.pubkey, e);
+            Err(e)
+        }
     }
+}
 
-    #[tokio::test]
-    async fn test_sign_message_handler_validation() {
-        let invalid_request = SignMessageRequest {
-            message: "".to_string(),
-            secret: "".to_string(),
-        };
-        
-        let result = sign_message_handler(JsonExtractor(invalid_request)).await;
-        assert!(result.is_err());
-    }
+/// Handler for POST /rpc/mint-info
+/// Fetches an SPL token mint's supply, decimals and authorities
+#[utoipa::path(
+    post,
+    path = "/rpc/mint-info",
+    tag = "rpc",
+    request_body = MintInfoRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<MintInfoResponse>),
+        (status = 400, description = "Invalid request, or the account is not a mint", body = ApiErrorResponse),
+        (status = 404, description = "Account not found", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn mint_info_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<MintInfoRequest>,
+) -> Result<Json<ApiResponse<MintInfoResponse>>> {
+    info!("Handling mint info request for: {}", request.mint);
 
-    #[tokio::test]
-    async fn test_verify_message_handler_validation() {
-        let invalid_request = VerifyMessageRequest {
-            message: "".to_string(),
-            signature: "".to_string(),
-            pubkey: "".to_string(),
-        };
-        
-        let result = verify_message_handler(JsonExtractor(invalid_request)).await;
-        assert!(result.is_err());
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
+
+    let rpc_service = state.rpc.with_commitment(commitment);
+
+    match rpc_service.get_mint_info(&mint).await {
+        Ok(mint_response) => {
+            info!("Successfully fetched mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(mint_response)))
+        }
+        Err(e) => {
+            error!("Failed to fetch mint {}: {}", request.mint, e);
+            Err(e)
+        }
     }
+}
 
-    #[tokio::test]
-    async fn test_send_sol_handler_validation() {
-        let invalid_request = SendSolRequest {
-            from: "".to_string(),
-            to: "".to_string(),
-            lamports: 0,
-        };
-        
-        let result = send_sol_handler(JsonExtractor(invalid_request)).await;
-        assert!(result.is_err());
+/// Handler for POST /rpc/token-account-info
+/// Fetches an SPL token account's owner, mint, balance, delegate and state
+#[utoipa::path(
+    post,
+    path = "/rpc/token-account-info",
+    tag = "rpc",
+    request_body = TokenAccountInfoRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenAccountInfoResponse>),
+        (status = 400, description = "Invalid request, or the account is not a token account", body = ApiErrorResponse),
+        (status = 404, description = "Account not found", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn token_account_info_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<TokenAccountInfoRequest>,
+) -> Result<Json<ApiResponse<TokenAccountInfoResponse>>> {
+    info!("Handling token account info request for: {}", request.account);
+
+    let account = validation::validate_pubkey(&request.account, "account")?;
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
+
+    let rpc_service = state.rpc.with_commitment(commitment);
+
+    match rpc_service.get_token_account_info(&account).await {
+        Ok(token_account_response) => {
+            info!("Successfully fetched token account: {}", request.account);
+            Ok(Json(ApiResponse::success(token_account_response)))
+        }
+        Err(e) => {
+            error!("Failed to fetch token account {}: {}", request.account, e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /rpc/rent
+/// Returns the rent-exempt minimum balance for an account size
+#[utoipa::path(
+    post,
+    path = "/rpc/rent",
+    tag = "rpc",
+    request_body = RentRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RentResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn rent_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<RentRequest>,
+) -> Result<Json<ApiResponse<RentResponse>>> {
+    info!("Handling rent exemption request for {} bytes", request.data_len);
+
+    let data_len = validation::validate_data_len(request.data_len)?;
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
+
+    // Without a configured RPC URL or cluster there's nothing to ask, so use the default rent parameters
+    let (lamports, source) = if state.config.rpc.is_configured() {
+        let rpc_service = state.rpc.with_commitment(commitment);
+        match rpc_service.get_minimum_balance_for_rent_exemption(data_len).await {
+            Ok(lamports) => (lamports, "rpc"),
+            Err(e) => {
+                error!("Failed to fetch rent exemption minimum: {}", e);
+                return Err(e);
+            }
+        }
+    } else {
+        (state.solana.rent_exempt_minimum(data_len), "local")
+    };
+
+    info!("Rent exempt minimum for {} bytes: {} lamports ({})", data_len, lamports, source);
+    Ok(Json(ApiResponse::success(RentResponse {
+        data_len: request.data_len,
+        lamports,
+        source: source.to_string(),
+    })))
+}
+
+/// Handler for POST /rpc/send
+/// Submits a signed transaction to the configured cluster
+#[utoipa::path(
+    post,
+    path = "/rpc/send",
+    tag = "rpc",
+    request_body = SendTransactionRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the original response when a request is retried")),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SendTransactionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 409, description = "Request with this Idempotency-Key still in progress", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn send_transaction_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SendTransactionRequest>,
+) -> Result<Json<ApiResponse<SendTransactionResponse>>> {
+    info!("Handling send transaction request");
+
+    let transaction = validation::validate_signed_transaction(&request.transaction)?;
+
+    let rpc_service = &state.rpc;
+    match rpc_service.send_transaction(&transaction).await {
+        Ok(signature) => {
+            info!("Successfully sent transaction: {}", signature);
+            Ok(Json(ApiResponse::success(SendTransactionResponse {
+                signature: signature.to_string(),
+                cluster: state.config.cluster,
+            })))
+        }
+        Err(e) => {
+            error!("Failed to send transaction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /relay
+/// Pays the fees of a client's transaction with the relayer key and submits it
+#[utoipa::path(
+    post,
+    path = "/relay",
+    tag = "relay",
+    request_body = RelayTransactionRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the original response when a request is retried")),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RelayTransactionResponse>),
+        (status = 400, description = "Invalid request, or a transaction the relayer won't pay for", body = ApiErrorResponse),
+        (status = 409, description = "Request with this Idempotency-Key still in progress", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "No relayer key configured, or all RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn relay_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<RelayTransactionRequest>,
+) -> Result<Json<ApiResponse<RelayTransactionResponse>>> {
+    info!("Handling relay request");
+
+    let Some(relayer) = state.config.relay.keypair.as_deref() else {
+        return Err(AppError::ServiceUnavailable(
+            "Relaying is not configured; set relay.keypair_path or RELAY_KEYPAIR_PATH".to_string(),
+        ));
+    };
+    let transaction = validation::validate_transaction(&request.transaction)?;
+    validation::validate_programs_allowed(&transaction.message, &state.config.allowed_programs)?;
+    let relayer_pubkey = relayer.pubkey().to_string();
+    let transaction = state
+        .solana
+        .sign_relayed_transaction(transaction, relayer, &state.config.relay.allowed_programs)
+        .inspect_err(|e| state.audit.record("transaction.relay_sign", &[&relayer_pubkey], AuditResult::failure(e)))?;
+    state.audit.record("transaction.relay_sign", &[&relayer_pubkey], AuditResult::Success);
+
+    match state.rpc.send_transaction(&transaction).await {
+        Ok(signature) => {
+            info!("Successfully relayed transaction: {}", signature);
+            Ok(Json(ApiResponse::success(RelayTransactionResponse {
+                signature: signature.to_string(),
+                fee_payer: relayer_pubkey,
+                cluster: state.config.cluster,
+            })))
+        }
+        Err(e) => {
+            error!("Failed to relay transaction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /rpc/airdrop
+/// Requests an airdrop to an existing wallet (devnet/testnet only)
+#[utoipa::path(
+    post,
+    path = "/rpc/airdrop",
+    tag = "rpc",
+    request_body = AirdropRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Replays the original response when a request is retried")),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AirdropResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 409, description = "Request with this Idempotency-Key still in progress", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn airdrop_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<AirdropRequest>,
+) -> Result<Json<ApiResponse<AirdropResponse>>> {
+    info!("Handling airdrop request for {} SOL to {}", request.sol, request.pubkey);
+
+    let pubkey = validation::validate_pubkey(&request.pubkey, "pubkey")?;
+    let lamports = validation::validate_sol_amount(request.sol)?;
+
+    let rpc_service = &state.rpc;
+    match rpc_service.request_airdrop(&pubkey, lamports).await {
+        Ok(signature) => {
+            info!("Requested airdrop {} to {}", signature, pubkey);
+            Ok(Json(ApiResponse::success(AirdropResponse {
+                pubkey: pubkey.to_string(),
+                lamports,
+                signature: signature.to_string(),
+                cluster: state.config.cluster,
+            })))
+        }
+        Err(e) => {
+            error!("Failed to request airdrop to {}: {}", pubkey, e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /rpc/simulate
+/// Simulates a transaction to see its logs, compute usage and any error before paying fees
+#[utoipa::path(
+    post,
+    path = "/rpc/simulate",
+    tag = "rpc",
+    request_body = SimulateTransactionRequest,
+    responses(
+        (status = 200, description = "Simulation ran; a failing transaction is reported in err", body = ApiResponse<SimulateTransactionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn simulate_transaction_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SimulateTransactionRequest>,
+) -> Result<Json<ApiResponse<SimulateTransactionResponse>>> {
+    info!(
+        "Handling simulate transaction request (sig_verify: {}, replace_recent_blockhash: {})",
+        request.sig_verify, request.replace_recent_blockhash
+    );
+
+    if request.sig_verify && request.replace_recent_blockhash {
+        return Err(AppError::ValidationError(
+            "sig_verify and replace_recent_blockhash cannot both be set".to_string(),
+        ));
+    }
+    let transaction = if request.sig_verify {
+        validation::validate_signed_transaction(&request.transaction)?
+    } else {
+        validation::validate_transaction(&request.transaction)?
+    };
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
+
+    let rpc_service = state.rpc.with_commitment(commitment);
+    match rpc_service
+        .simulate_transaction(&transaction, request.sig_verify, request.replace_recent_blockhash)
+        .await
+    {
+        Ok(simulation) => {
+            info!("Simulated transaction: err {:?}, {:?} units", simulation.err, simulation.units_consumed);
+            Ok(Json(ApiResponse::success(simulation)))
+        }
+        Err(e) => {
+            error!("Failed to simulate transaction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /rpc/signature-status
+/// Reports a submitted transaction's confirmation status, for clients polling until it lands
+#[utoipa::path(
+    post,
+    path = "/rpc/signature-status",
+    tag = "rpc",
+    request_body = SignatureStatusRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SignatureStatusResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn signature_status_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SignatureStatusRequest>,
+) -> Result<Json<ApiResponse<SignatureStatusResponse>>> {
+    info!("Handling signature status request for {}", request.signature);
+
+    let signature = validation::validate_signature(&request.signature)?;
+
+    let rpc_service = &state.rpc;
+    match rpc_service.get_signature_status(&signature).await {
+        Ok(status) => {
+            info!("Signature {} found: {}, status: {:?}", signature, status.found, status.confirmation_status);
+            Ok(Json(ApiResponse::success(status)))
+        }
+        Err(e) => {
+            error!("Failed to fetch signature status: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /rpc/vote-accounts
+/// Lists current and delinquent validators' vote accounts, for staking UIs picking a validator
+#[utoipa::path(
+    post,
+    path = "/rpc/vote-accounts",
+    tag = "rpc",
+    request_body = VoteAccountsRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<VoteAccountsResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 502, description = "RPC node error", body = ApiErrorResponse),
+        (status = 503, description = "All RPC connections are busy", body = ApiErrorResponse),
+    )
+)]
+pub async fn vote_accounts_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<VoteAccountsRequest>,
+) -> Result<Json<ApiResponse<VoteAccountsResponse>>> {
+    info!("Handling vote accounts request");
+
+    let vote_pubkey = request.vote_pubkey
+        .as_deref()
+        .map(|vote_pubkey| validation::validate_pubkey(vote_pubkey, "vote_pubkey"))
+        .transpose()?;
+    let limit = validation::validate_page_limit(request.limit)?;
+    let commitment = request.commitment.as_deref().map(validation::validate_commitment).transpose()?;
+
+    let rpc_service = state.rpc.with_commitment(commitment);
+
+    match rpc_service.get_vote_accounts(vote_pubkey.as_ref(), request.offset, limit).await {
+        Ok(accounts) => {
+            info!(
+                "Found {} current and {} delinquent vote accounts",
+                accounts.total_current, accounts.total_delinquent
+            );
+            Ok(Json(ApiResponse::success(accounts)))
+        }
+        Err(e) => {
+            error!("Failed to fetch vote accounts: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for GET /ws/account
+/// Upgrades to a WebSocket and streams accountSubscribe notifications for the pubkey
+#[utoipa::path(
+    get,
+    path = "/ws/account",
+    tag = "rpc",
+    params(AccountSubscribeQuery),
+    responses(
+        (status = 101, description = "Switching to a WebSocket that streams account notifications"),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn account_ws_handler(
+    State(state): State<AppState>,
+    ws: WebSocketUpgrade,
+    Query(query): Query<AccountSubscribeQuery>,
+) -> Result<Response> {
+    info!("Handling account subscription for: {}", query.pubkey);
+
+    // Validate before upgrading so a bad pubkey is still a 400, and a missing endpoint a 503
+    let pubkey = validation::validate_pubkey(&query.pubkey, "pubkey")?;
+    state.pubsub.ensure_configured()?;
+
+    let pubsub_service = state.pubsub.clone();
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        pubsub_service.forward_account_updates(socket, pubkey).await;
+    }))
+}
+
+/// Handler for POST /token/create
+/// Creates an SPL token mint instruction
+#[utoipa::path(
+    post,
+    path = "/token/create",
+    tag = "token",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn create_token_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<CreateTokenRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling token creation request for mint: {}", request.mint);
+
+    // Comprehensive validation using validation module
+    let mint_authority = validation::validate_authority(&request.mint_authority, "mintAuthority")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let decimals = resolve_decimals(request.decimals, &state.config.token)?;
+    let freeze_authority = resolve_freeze_authority(
+        &request.freeze_authority,
+        request.disable_freeze_authority,
+        mint_authority,
+        state.config.token.default_freeze_authority,
+    )?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.create_token_mint(
+        &mint_authority.to_string(),
+        &mint.to_string(),
+        freeze_authority.map(|pubkey| pubkey.to_string()).as_deref(),
+        decimals,
+    ) {
+        Ok(token_response) => {
+            info!("Successfully created token mint instruction for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(token_response)))
+        }
+        Err(e) => {
+            error!("Failed to create token mint instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Validates the decimals of a /token/create or /token/create/new request, falling back to the
+/// configured default when it has none
+fn resolve_decimals(decimals: Option<u8>, policy: &TokenPolicy) -> Result<u8> {
+    match decimals.or(policy.default_decimals) {
+        Some(decimals) => validation::validate_decimals(decimals, policy.max_decimals),
+        None => Err(AppError::ValidationError("decimals is required".to_string())),
+    }
+}
+
+/// Resolves a new mint's freeze authority from the `freezeAuthority` and
+/// `disable_freeze_authority` fields of /token/create and /token/create/new
+fn resolve_freeze_authority(
+    freeze_authority: &Option<Option<String>>,
+    disable_freeze_authority: bool,
+    mint_authority: Pubkey,
+    default: DefaultFreezeAuthority,
+) -> Result<Option<Pubkey>> {
+    match (freeze_authority, disable_freeze_authority) {
+        (Some(Some(_)), true) => Err(AppError::ValidationError(
+            "freezeAuthority cannot be set when disable_freeze_authority is true".to_string(),
+        )),
+        (_, true) | (Some(None), false) => Ok(None),
+        // Not sent: the configured default, by default freezing with the mint authority
+        (None, false) => Ok(default.resolve(mint_authority)),
+        (Some(Some(freeze_authority)), false) => {
+            Ok(Some(validation::validate_authority(freeze_authority, "freezeAuthority")?))
+        }
+    }
+}
+
+/// Handler for POST /token/create/new
+/// Generates a mint keypair and creates the create_account and initialize_mint instructions for it
+#[utoipa::path(
+    post,
+    path = "/token/create/new",
+    tag = "token",
+    request_body = CreateNewTokenRequest,
+    responses(
+        (status = 200, description = "The new mint, its secret key and the instructions creating it", body = ApiResponse<CreateNewTokenResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn create_new_token_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<CreateNewTokenRequest>,
+) -> Result<Json<ApiResponse<CreateNewTokenResponse>>> {
+    info!("Handling new token mint request for mint authority: {}", request.mint_authority);
+
+    let mint_authority = validation::validate_authority(&request.mint_authority, "mintAuthority")?;
+    let payer = match &request.payer {
+        Some(payer) => validation::validate_pubkey(payer, "payer")?,
+        None => mint_authority,
+    };
+    let decimals = resolve_decimals(request.decimals, &state.config.token)?;
+    let freeze_authority = resolve_freeze_authority(
+        &request.freeze_authority,
+        request.disable_freeze_authority,
+        mint_authority,
+        state.config.token.default_freeze_authority,
+    )?;
+
+    match state.solana.create_new_token_mint(&payer, &mint_authority, freeze_authority.as_ref(), decimals) {
+        Ok(response) => {
+            info!("Successfully created instructions for new token mint: {}", response.mint);
+            state.audit.record("keypair.generate_mint", &[&response.mint], AuditResult::Success);
+            Ok(Json(ApiResponse::success(response)))
+        }
+        Err(e) => {
+            error!("Failed to create new token mint instructions: {}", e);
+            state.audit.record("keypair.generate_mint", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/mint
+/// Creates an SPL token mint_to instruction
+#[utoipa::path(
+    post,
+    path = "/token/mint",
+    tag = "token",
+    request_body = MintTokenRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn mint_token_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<MintTokenRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling token minting request for mint: {}", request.mint);
+
+    // Comprehensive validation using validation module
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let authority = validation::validate_authority(&request.authority, "authority")?;
+    let amount = validation::validate_mint_amount(request.amount, state.config.token.max_mint_amount, "amount")?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.mint_token(
+        &mint.to_string(),
+        &destination.to_string(),
+        &authority.to_string(),
+        amount,
+    ) {
+        Ok(token_response) => {
+            info!("Successfully created token mint_to instruction for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(token_response)))
+        }
+        Err(e) => {
+            error!("Failed to create token mint_to instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/mint/batch
+/// Creates mint_to instructions for many recipients, as one JSON body or, with ?stream=ndjson,
+/// one instruction per line
+#[utoipa::path(
+    post,
+    path = "/token/mint/batch",
+    tag = "token",
+    params(StreamQuery),
+    request_body = MintBatchRequest,
+    responses(
+        (status = 200, description = "Success. With ?stream=ndjson, one TokenInstructionResponse per line",
+            content((ApiResponse<MintBatchResponse> = "application/json"), (TokenInstructionResponse = "application/x-ndjson"))),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn mint_batch_stream_handler(
+    state: State<AppState>,
+    Query(query): Query<StreamQuery>,
+    JsonExtractor(request): JsonExtractor<MintBatchRequest>,
+) -> Result<Response> {
+    if !validation::validate_stream(query.stream.as_deref())? {
+        return mint_batch_handler(state, JsonExtractor(request)).await.map(IntoResponse::into_response);
+    }
+    info!("Streaming batch mint for mint {} with {} recipients", request.mint, request.recipients.len());
+
+    let mut pubkeys = validation::PubkeyCache::default();
+    let mint = pubkeys.validate(&request.mint, "mint")?;
+    let authority = validation::validate_authority(&request.authority, "authority")?;
+    validation::validate_batch_len_up_to(request.recipients.len(), validation::MAX_STREAMED_BATCH_SIZE, "recipients")?;
+    validation::validate_program_allowed(&spl_token::id(), &state.config.allowed_programs)?;
+    // Bundling packs every instruction into messages at once, which streaming is meant to avoid
+    if request.fee_payer.is_some() || request.recent_blockhash.is_some() {
+        return Err(AppError::ValidationError(
+            "fee_payer and recent_blockhash can't be used with stream=ndjson".to_string(),
+        ));
+    }
+    let recipients = validate_mint_recipients(&mut pubkeys, &request.recipients, state.config.token.max_mint_amount)?;
+
+    let solana = state.solana.clone();
+    let instructions = recipients
+        .into_iter()
+        .map(move |(destination, amount)| solana.mint_to_response(&mint, &destination, &authority, amount));
+    Ok(ndjson_response(futures::stream::iter(instructions)))
+}
+
+/// The JSON form of POST /token/mint/batch, also run by /batch
+/// Creates mint_to instructions for many recipients, optionally bundled into transaction messages
+pub async fn mint_batch_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<MintBatchRequest>,
+) -> Result<Json<ApiResponse<MintBatchResponse>>> {
+    info!("Handling batch mint request for mint {} with {} recipients", request.mint, request.recipients.len());
+
+    // Recipients often repeat, so each key is parsed once
+    let mut pubkeys = validation::PubkeyCache::default();
+    let mint = pubkeys.validate(&request.mint, "mint")?;
+    let authority = validation::validate_authority(&request.authority, "authority")?;
+    validation::validate_batch_len(request.recipients.len(), "recipients")?;
+    validation::validate_program_allowed(&spl_token::id(), &state.config.allowed_programs)?;
+    let recipients = validate_mint_recipients(&mut pubkeys, &request.recipients, state.config.token.max_mint_amount)?;
+    let fee_payer = request.fee_payer.as_deref().map(|fee_payer| pubkeys.validate(fee_payer, "fee_payer")).transpose()?;
+    let recent_blockhash = match request.recent_blockhash.as_deref() {
+        Some(blockhash) => validation::validate_blockhash(blockhash, "recent_blockhash")?,
+        None => Hash::default(),
+    };
+
+    let solana_service = &state.solana;
+
+    match solana_service.mint_token_batch(&mint, &authority, &recipients, fee_payer.as_ref().map(|fee_payer| (fee_payer, &recent_blockhash))) {
+        Ok(batch_response) => {
+            info!("Successfully created {} mint_to instructions for mint: {}", batch_response.instructions.len(), request.mint);
+            Ok(Json(ApiResponse::success(batch_response)))
+        }
+        Err(e) => {
+            error!("Failed to create batch mint_to instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Validates each recipient's destination and amount, naming the failing entry by its index
+fn validate_mint_recipients(
+    pubkeys: &mut validation::PubkeyCache,
+    recipients: &[MintRecipient],
+    max_mint_amount: u64,
+) -> Result<Vec<(Pubkey, u64)>> {
+    recipients
+        .iter()
+        .enumerate()
+        .map(|(index, recipient)| {
+            Ok((
+                pubkeys.validate(&recipient.destination, &format!("recipients[{}].destination", index))?,
+                validation::validate_mint_amount(recipient.amount, max_mint_amount, &format!("recipients[{}].amount", index))?,
+            ))
+        })
+        .collect()
+}
+
+/// Handler for POST /token/burn-close
+/// Creates burn and close_account instructions to empty and close a token account
+#[utoipa::path(
+    post,
+    path = "/token/burn-close",
+    tag = "token",
+    request_body = BurnCloseRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<BurnCloseResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn burn_close_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<BurnCloseRequest>,
+) -> Result<Json<ApiResponse<BurnCloseResponse>>> {
+    info!("Handling token burn-close request for account: {}", request.account);
+
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let account = validation::validate_pubkey(&request.account, "account")?;
+    let authority = validation::validate_authority(&request.authority, "authority")?;
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let amount = validation::validate_positive_amount(request.amount, "amount")?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.burn_and_close(
+        &mint.to_string(),
+        &account.to_string(),
+        &authority.to_string(),
+        &destination.to_string(),
+        amount,
+    ) {
+        Ok(burn_close_response) => {
+            info!("Successfully created burn-close instructions for account: {}", request.account);
+            Ok(Json(ApiResponse::success(burn_close_response)))
+        }
+        Err(e) => {
+            error!("Failed to create burn-close instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/wrap
+/// Creates the instructions that wrap native SOL into the owner's wrapped SOL token account
+#[utoipa::path(
+    post,
+    path = "/token/wrap",
+    tag = "token",
+    request_body = WrapSolRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<WrappedSolResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn wrap_sol_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<WrapSolRequest>,
+) -> Result<Json<ApiResponse<WrappedSolResponse>>> {
+    info!("Handling wrap SOL request for owner: {}", request.owner);
+
+    let owner = validation::validate_pubkey(&request.owner, "owner")?;
+    let lamports = validation::validate_positive_amount(request.lamports, "lamports")?;
+
+    match state.solana.wrap_sol(&owner, lamports) {
+        Ok(wrap_response) => {
+            info!("Successfully created wrap SOL instructions for account: {}", wrap_response.account);
+            Ok(Json(ApiResponse::success(wrap_response)))
+        }
+        Err(e) => {
+            error!("Failed to create wrap SOL instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/unwrap
+/// Creates the close_account instruction that turns a wrapped SOL account back into SOL
+#[utoipa::path(
+    post,
+    path = "/token/unwrap",
+    tag = "token",
+    request_body = UnwrapSolRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<WrappedSolResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn unwrap_sol_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<UnwrapSolRequest>,
+) -> Result<Json<ApiResponse<WrappedSolResponse>>> {
+    info!("Handling unwrap SOL request for owner: {}", request.owner);
+
+    let owner = validation::validate_pubkey(&request.owner, "owner")?;
+    let account = request
+        .account
+        .as_deref()
+        .map(|account| validation::validate_pubkey(account, "account"))
+        .transpose()?;
+
+    match state.solana.unwrap_sol(&owner, account.as_ref()) {
+        Ok(unwrap_response) => {
+            info!("Successfully created unwrap SOL instruction for account: {}", unwrap_response.account);
+            Ok(Json(ApiResponse::success(unwrap_response)))
+        }
+        Err(e) => {
+            error!("Failed to create unwrap SOL instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/sync-native
+/// Creates a sync_native instruction to update a wrapped SOL account's token balance
+#[utoipa::path(
+    post,
+    path = "/token/sync-native",
+    tag = "token",
+    request_body = SyncNativeRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn sync_native_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SyncNativeRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling sync_native request for account: {}", request.account);
+
+    let account = validation::validate_pubkey(&request.account, "account")?;
+
+    match state.solana.sync_native(&account) {
+        Ok(instruction_response) => {
+            info!("Successfully created sync_native instruction for account: {}", request.account);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create sync_native instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Cache-Control for GET routes whose response is a pure function of the query string
+const DETERMINISTIC_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Sends a GET route's result with a Cache-Control that lets browsers and proxies keep it,
+/// since the same query always gets the same answer
+fn cacheable<T: serde::Serialize>(result: Result<Json<ApiResponse<T>>>) -> Result<Response> {
+    result.map(|json| ([(header::CACHE_CONTROL, DETERMINISTIC_CACHE_CONTROL)], json).into_response())
+}
+
+/// Handler for POST /pda/derive
+/// Finds the program-derived address and bump seed for a program id and seeds
+#[utoipa::path(
+    post,
+    path = "/pda/derive",
+    tag = "address",
+    request_body = DerivePdaRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PdaResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn derive_pda_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<DerivePdaRequest>,
+) -> Result<Json<ApiResponse<PdaResponse>>> {
+    info!("Handling PDA derivation for program: {}", request.program_id);
+
+    let program_id = validation::validate_pubkey(&request.program_id, "program_id")?;
+    let seeds = validation::validate_pda_seeds(&request.seeds)?;
+
+    match state.solana.derive_pda(&program_id, &seeds) {
+        Ok(pda_response) => {
+            info!("Derived PDA {} (bump {}) for program {}", pda_response.address, pda_response.bump, program_id);
+            Ok(Json(ApiResponse::success(pda_response)))
+        }
+        Err(e) => {
+            error!("Failed to derive PDA: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for GET /pda/derive
+/// The query-string form of POST /pda/derive, cacheable since the result never changes
+#[utoipa::path(
+    get,
+    path = "/pda/derive",
+    tag = "address",
+    params(DerivePdaQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PdaResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn derive_pda_query_handler(
+    state: State<AppState>,
+    Query(query): Query<DerivePdaQuery>,
+) -> Result<Response> {
+    cacheable(derive_pda_handler(state, JsonExtractor(query.into())).await)
+}
+
+/// Handler for POST /pda/derive/batch
+/// Derives many program-derived addresses at once, as one JSON body or, with ?stream=ndjson,
+/// one result per line
+#[utoipa::path(
+    post,
+    path = "/pda/derive/batch",
+    tag = "address",
+    params(StreamQuery),
+    request_body = DerivePdaBatchRequest,
+    responses(
+        (status = 200, description = "One result per entry; failed entries have an error instead of an address. With ?stream=ndjson, one PdaBatchResult per line",
+            content((ApiResponse<DerivePdaBatchResponse> = "application/json"), (PdaBatchResult = "application/x-ndjson"))),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn derive_pda_batch_stream_handler(
+    state: State<AppState>,
+    Query(query): Query<StreamQuery>,
+    JsonExtractor(request): JsonExtractor<DerivePdaBatchRequest>,
+) -> Result<Response> {
+    if !validation::validate_stream(query.stream.as_deref())? {
+        return derive_pda_batch_handler(state, JsonExtractor(request)).await.map(IntoResponse::into_response);
+    }
+    info!("Streaming batch PDA derivation for {} entries", request.entries.len());
+
+    validation::validate_batch_len_up_to(request.entries.len(), validation::MAX_STREAMED_BATCH_SIZE, "entries")?;
+
+    let results = derive_pda_results(state.0, request.entries);
+    Ok(ndjson_response(futures::stream::iter(results.map(Ok))))
+}
+
+/// The JSON form of POST /pda/derive/batch, also run by /batch
+/// Derives many program-derived addresses at once, reporting each entry's failure separately
+pub async fn derive_pda_batch_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<DerivePdaBatchRequest>,
+) -> Result<Json<ApiResponse<DerivePdaBatchResponse>>> {
+    info!("Handling batch PDA derivation for {} entries", request.entries.len());
+
+    validation::validate_batch_len(request.entries.len(), "entries")?;
+
+    let results: Vec<PdaBatchResult> = derive_pda_results(state, request.entries).collect();
+
+    let failed = results.iter().filter(|result| result.error.is_some()).count();
+    info!("Derived {} PDAs ({} entries failed)", results.len() - failed, failed);
+    Ok(Json(ApiResponse::success(DerivePdaBatchResponse { results })))
+}
+
+/// Derives each entry's address and bump, or reports why it failed, one entry at a time as the
+/// results are consumed
+fn derive_pda_results(state: AppState, entries: Vec<DerivePdaRequest>) -> impl Iterator<Item = PdaBatchResult> {
+    // PDA tables usually share a handful of programs, so each distinct id is parsed once
+    let mut program_ids: HashMap<String, std::result::Result<Pubkey, String>> = HashMap::new();
+    entries.into_iter().map(move |entry| {
+        let program_id = program_ids
+            .entry(entry.program_id.clone())
+            .or_insert_with(|| validation::validate_pubkey(&entry.program_id, "program_id").map_err(|e| e.into_parts().1))
+            .clone();
+        let derived = program_id.and_then(|program_id| {
+            validation::validate_pda_seeds(&entry.seeds)
+                .and_then(|seeds| state.solana.derive_pda(&program_id, &seeds))
+                .map_err(|e| e.into_parts().1)
+        });
+        match derived {
+            Ok(pda) => PdaBatchResult { address: Some(pda.address), bump: Some(pda.bump), error: None },
+            Err(message) => PdaBatchResult { address: None, bump: None, error: Some(message) },
+        }
+    })
+}
+
+/// Handler for POST /token/ata
+/// Derives a wallet's associated token account address for a mint
+#[utoipa::path(
+    post,
+    path = "/token/ata",
+    tag = "token",
+    request_body = AssociatedTokenAddressRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AssociatedTokenAddressResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn associated_token_address_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<AssociatedTokenAddressRequest>,
+) -> Result<Json<ApiResponse<AssociatedTokenAddressResponse>>> {
+    info!("Handling associated token address request for wallet: {}", request.wallet);
+
+    let wallet = validation::validate_pubkey(&request.wallet, "wallet")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let token_program = validation::validate_token_program(request.token_program.as_deref())?;
+
+    let ata_response = state.solana.derive_ata(&wallet, &mint, &token_program);
+    info!("Derived associated token account {} for wallet {}", ata_response.address, wallet);
+    Ok(Json(ApiResponse::success(ata_response)))
+}
+
+/// Handler for GET /token/ata
+/// The query-string form of POST /token/ata, cacheable since the result never changes
+#[utoipa::path(
+    get,
+    path = "/token/ata",
+    tag = "token",
+    params(AssociatedTokenAddressQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AssociatedTokenAddressResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn associated_token_address_query_handler(
+    state: State<AppState>,
+    Query(query): Query<AssociatedTokenAddressQuery>,
+) -> Result<Response> {
+    cacheable(associated_token_address_handler(state, JsonExtractor(query.into())).await)
+}
+
+/// Handler for POST /token/ata/batch
+/// Derives a wallet's associated token account addresses for many mints at once
+#[utoipa::path(
+    post,
+    path = "/token/ata/batch",
+    tag = "token",
+    request_body = AssociatedTokenAddressBatchRequest,
+    responses(
+        (status = 200, description = "One account per mint, in request order", body = ApiResponse<AssociatedTokenAddressBatchResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn associated_token_address_batch_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<AssociatedTokenAddressBatchRequest>,
+) -> Result<Json<ApiResponse<AssociatedTokenAddressBatchResponse>>> {
+    info!("Handling batch associated token address request for wallet {} and {} mints", request.wallet, request.mints.len());
+
+    let wallet = validation::validate_pubkey(&request.wallet, "wallet")?;
+    validation::validate_batch_len(request.mints.len(), "mints")?;
+    let token_program = validation::validate_token_program(request.token_program.as_deref())?;
+    let mut pubkeys = validation::PubkeyCache::default();
+    let mints = request
+        .mints
+        .iter()
+        .enumerate()
+        .map(|(index, mint)| pubkeys.validate(mint, &format!("mints[{}]", index)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let accounts: Vec<MintAssociatedTokenAddress> = mints
+        .iter()
+        .map(|mint| MintAssociatedTokenAddress {
+            mint: mint.to_string(),
+            address: state.solana.derive_ata(&wallet, mint, &token_program).address,
+        })
+        .collect();
+    info!("Derived {} associated token accounts for wallet {}", accounts.len(), wallet);
+    Ok(Json(ApiResponse::success(AssociatedTokenAddressBatchResponse { wallet: wallet.to_string(), accounts })))
+}
+
+/// Handler for POST /pubkey/on-curve
+/// Reports whether an address is an ed25519 point (a wallet) or off the curve (a PDA)
+#[utoipa::path(
+    post,
+    path = "/pubkey/on-curve",
+    tag = "address",
+    request_body = OnCurveRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<OnCurveResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn on_curve_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<OnCurveRequest>,
+) -> Result<Json<ApiResponse<OnCurveResponse>>> {
+    info!("Handling on-curve check for: {}", request.pubkey);
+
+    let pubkey = validation::validate_pubkey(&request.pubkey, "pubkey")?;
+
+    let on_curve_response = state.solana.on_curve(&pubkey);
+    info!("{} is {}", pubkey, if on_curve_response.on_curve { "on the curve" } else { "off the curve" });
+    Ok(Json(ApiResponse::success(on_curve_response)))
+}
+
+/// Handler for GET /pubkey/on-curve
+/// The query-string form of POST /pubkey/on-curve, cacheable since the result never changes
+#[utoipa::path(
+    get,
+    path = "/pubkey/on-curve",
+    tag = "address",
+    params(OnCurveQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<OnCurveResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn on_curve_query_handler(
+    state: State<AppState>,
+    Query(query): Query<OnCurveQuery>,
+) -> Result<Response> {
+    cacheable(on_curve_handler(state, JsonExtractor(OnCurveRequest { pubkey: query.pubkey })).await)
+}
+
+/// Handler for POST /pubkey/normalize
+/// Reads a public key in base58, hex or base64 and returns it in all three
+#[utoipa::path(
+    post,
+    path = "/pubkey/normalize",
+    tag = "address",
+    request_body = NormalizePubkeyRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<NormalizePubkeyResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn normalize_pubkey_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<NormalizePubkeyRequest>,
+) -> Result<Json<ApiResponse<NormalizePubkeyResponse>>> {
+    info!("Handling pubkey normalize request for: {}", request.pubkey);
+
+    let (pubkey, input_encoding) = match request.public_key_encoding.as_deref() {
+        None => validation::detect_encoded_pubkey(&request.pubkey, "pubkey")?,
+        Some(encoding) => (
+            validation::validate_encoded_pubkey(&request.pubkey, Some(encoding), "pubkey")?,
+            validation::normalize_input(encoding),
+        ),
+    };
+
+    info!("Read {} as {}", pubkey, input_encoding);
+    Ok(Json(ApiResponse::success(state.solana.normalize_pubkey(&pubkey, input_encoding))))
+}
+
+/// Handler for POST /token/transfer-fee/create-mint
+/// Creates the instructions initializing a Token-2022 mint that charges a fee on every transfer
+#[utoipa::path(
+    post,
+    path = "/token/transfer-fee/create-mint",
+    tag = "token",
+    request_body = CreateTransferFeeMintRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CreateTransferFeeMintResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn create_transfer_fee_mint_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<CreateTransferFeeMintRequest>,
+) -> Result<Json<ApiResponse<CreateTransferFeeMintResponse>>> {
+    info!("Handling transfer-fee mint creation request for mint: {}", request.mint);
+
+    let mint_authority = validation::validate_authority(&request.mint_authority, "mint_authority")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let decimals = validation::validate_decimals(request.decimals, state.config.token.max_decimals)?;
+    let freeze_authority = request
+        .freeze_authority
+        .as_deref()
+        .map(|freeze_authority| validation::validate_authority(freeze_authority, "freeze_authority"))
+        .transpose()?;
+    validation::validate_transfer_fee(request.transfer_fee_basis_points, request.maximum_fee)?;
+    let authority_or_mint_authority = |authority: &Option<String>, field_name| match authority {
+        Some(authority) => validation::validate_authority(authority, field_name),
+        None => Ok(mint_authority),
+    };
+    let fee = TransferFeeSettings {
+        basis_points: request.transfer_fee_basis_points,
+        maximum_fee: request.maximum_fee,
+        config_authority: authority_or_mint_authority(
+            &request.transfer_fee_config_authority,
+            "transfer_fee_config_authority",
+        )?,
+        withdraw_withheld_authority: authority_or_mint_authority(
+            &request.withdraw_withheld_authority,
+            "withdraw_withheld_authority",
+        )?,
+    };
+
+    match state.solana.create_transfer_fee_mint(&mint, &mint_authority, freeze_authority.as_ref(), decimals, fee) {
+        Ok(mint_response) => {
+            info!("Successfully created transfer-fee mint instructions for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(mint_response)))
+        }
+        Err(e) => {
+            error!("Failed to create transfer-fee mint instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/transfer-fee/transfer
+/// Creates a Token-2022 transfer_checked_with_fee instruction
+#[utoipa::path(
+    post,
+    path = "/token/transfer-fee/transfer",
+    tag = "token",
+    request_body = TransferWithFeeRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TransferWithFeeResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn transfer_with_fee_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<TransferWithFeeRequest>,
+) -> Result<Json<ApiResponse<TransferWithFeeResponse>>> {
+    info!("Handling transfer-fee token transfer request for mint: {}", request.mint);
+
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let owner = validation::validate_authority(&request.owner, "owner")?;
+    let amount = validation::validate_positive_amount(request.amount, "amount")?;
+    let fee = match (request.fee, request.transfer_fee_basis_points, request.maximum_fee) {
+        (Some(fee), None, None) => fee,
+        (None, Some(basis_points), Some(maximum_fee)) => {
+            validation::validate_transfer_fee(basis_points, maximum_fee)?;
+            state.solana.transfer_fee(amount, basis_points, maximum_fee)?
+        }
+        _ => {
+            return Err(AppError::ValidationError(
+                "give either fee, or both transfer_fee_basis_points and maximum_fee".to_string(),
+            ));
+        }
+    };
+    if fee > amount {
+        return Err(AppError::ValidationError(format!("fee must be at most amount ({}), got {}", amount, fee)));
+    }
+
+    match state.solana.transfer_with_fee(&owner, &destination, &mint, amount, request.decimals, fee) {
+        Ok(transfer_response) => {
+            info!("Successfully created transfer_checked_with_fee instruction with fee {}", transfer_response.fee);
+            Ok(Json(ApiResponse::success(transfer_response)))
+        }
+        Err(e) => {
+            error!("Failed to create transfer_checked_with_fee instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/metadata/pointer
+/// Creates a Token-2022 metadata-pointer initialize instruction
+#[utoipa::path(
+    post,
+    path = "/token/metadata/pointer",
+    tag = "token",
+    request_body = InitializeMetadataPointerRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn metadata_pointer_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<InitializeMetadataPointerRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling metadata pointer request for mint: {}", request.mint);
+
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let authority = request
+        .authority
+        .as_deref()
+        .map(|authority| validation::validate_authority(authority, "authority"))
+        .transpose()?;
+    let metadata_address = request
+        .metadata_address
+        .as_deref()
+        .map(|metadata_address| validation::validate_pubkey(metadata_address, "metadata_address"))
+        .transpose()?;
+
+    match state.solana.initialize_metadata_pointer(&mint, authority.as_ref(), metadata_address.as_ref()) {
+        Ok(instruction_response) => {
+            info!("Successfully created metadata pointer instruction for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create metadata pointer instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/metadata/initialize
+/// Creates a token-metadata initialize instruction storing a name, symbol and uri in the mint
+#[utoipa::path(
+    post,
+    path = "/token/metadata/initialize",
+    tag = "token",
+    request_body = InitializeTokenMetadataRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn initialize_token_metadata_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<InitializeTokenMetadataRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling token metadata initialize request for mint: {}", request.mint);
+
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let mint_authority = validation::validate_authority(&request.mint_authority, "mint_authority")?;
+    let update_authority = match &request.update_authority {
+        Some(update_authority) => validation::validate_authority(update_authority, "update_authority")?,
+        None => mint_authority,
+    };
+    validation::validate_non_empty_string(&request.name, "name")?;
+    validation::validate_metadata_field("name", &request.name)?;
+    validation::validate_metadata_field("symbol", &request.symbol)?;
+    validation::validate_metadata_field("uri", &request.uri)?;
+
+    match state.solana.initialize_token_metadata(
+        &mint,
+        &mint_authority,
+        &update_authority,
+        request.name,
+        request.symbol,
+        request.uri,
+    ) {
+        Ok(instruction_response) => {
+            info!("Successfully created token metadata initialize instruction for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create token metadata initialize instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/metadata/update
+/// Creates a token-metadata update_field instruction
+#[utoipa::path(
+    post,
+    path = "/token/metadata/update",
+    tag = "token",
+    request_body = UpdateTokenMetadataRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn update_token_metadata_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<UpdateTokenMetadataRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling token metadata update request for mint: {} field: {}", request.mint, request.field);
+
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let update_authority = validation::validate_authority(&request.update_authority, "update_authority")?;
+    validation::validate_metadata_field(&request.field, &request.value)?;
+
+    match state.solana.update_token_metadata(&mint, &update_authority, &request.field, request.value) {
+        Ok(instruction_response) => {
+            info!("Successfully created token metadata update instruction for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create token metadata update instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/close-mint/create-mint
+/// Creates the instructions initializing a Token-2022 mint that its close authority can close
+#[utoipa::path(
+    post,
+    path = "/token/close-mint/create-mint",
+    tag = "token",
+    request_body = CreateCloseableMintRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CreateCloseableMintResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn create_closeable_mint_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<CreateCloseableMintRequest>,
+) -> Result<Json<ApiResponse<CreateCloseableMintResponse>>> {
+    info!("Handling closeable mint creation request for mint: {}", request.mint);
+
+    let mint_authority = validation::validate_authority(&request.mint_authority, "mint_authority")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let decimals = validation::validate_decimals(request.decimals, state.config.token.max_decimals)?;
+    let freeze_authority = request
+        .freeze_authority
+        .as_deref()
+        .map(|freeze_authority| validation::validate_authority(freeze_authority, "freeze_authority"))
+        .transpose()?;
+    let close_authority = match request.close_authority.as_deref() {
+        Some(close_authority) => validation::validate_authority(close_authority, "close_authority")?,
+        None => mint_authority,
+    };
+    // The mint can't sign for itself once it exists, so it could never be closed
+    if close_authority == mint {
+        return Err(AppError::ValidationError("close_authority must differ from mint".to_string()));
+    }
+
+    match state.solana.create_closeable_mint(&mint, &mint_authority, freeze_authority.as_ref(), decimals, &close_authority) {
+        Ok(mint_response) => {
+            info!("Successfully created closeable mint instructions for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(mint_response)))
+        }
+        Err(e) => {
+            error!("Failed to create closeable mint instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /token/close-mint
+/// Creates the Token-2022 close_account instruction that closes a mint with zero supply
+#[utoipa::path(
+    post,
+    path = "/token/close-mint",
+    tag = "token",
+    request_body = CloseMintRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn close_mint_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<CloseMintRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling close mint request for mint: {}", request.mint);
+
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let close_authority = validation::validate_authority(&request.close_authority, "close_authority")?;
+    if destination == mint {
+        return Err(AppError::ValidationError("destination must differ from the mint being closed".to_string()));
+    }
+    if close_authority == mint {
+        return Err(AppError::ValidationError("close_authority must differ from mint".to_string()));
+    }
+
+    match state.solana.close_mint(&mint, &destination, &close_authority) {
+        Ok(instruction_response) => {
+            info!("Successfully created close mint instruction for mint: {}", request.mint);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create close mint instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Rejects a stake request that names the stake account in a role it can't also play
+fn check_not_stake_account(stake_account: &Pubkey, other: &Pubkey, field_name: &str) -> Result<()> {
+    if stake_account == other {
+        return Err(AppError::ValidationError(format!("{} must differ from stake_account", field_name)));
+    }
+    Ok(())
+}
+
+/// Handler for POST /stake/create
+/// Creates the instructions for a new, initialized stake account funded with rent plus the stake
+#[utoipa::path(
+    post,
+    path = "/stake/create",
+    tag = "stake",
+    request_body = CreateStakeAccountRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CreateStakeAccountResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn create_stake_account_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<CreateStakeAccountRequest>,
+) -> Result<Json<ApiResponse<CreateStakeAccountResponse>>> {
+    info!("Handling stake account creation request for: {}", request.stake_account);
+
+    let from = validation::validate_pubkey(&request.from, "from")?;
+    let stake_account = validation::validate_pubkey(&request.stake_account, "stake_account")?;
+    let lamports = validation::validate_positive_amount(request.lamports, "lamports")?;
+    let staker = match &request.staker {
+        Some(staker) => validation::validate_authority(staker, "staker")?,
+        None => from,
+    };
+    let withdrawer = match &request.withdrawer {
+        Some(withdrawer) => validation::validate_authority(withdrawer, "withdrawer")?,
+        None => from,
+    };
+    // create_account needs a fresh account, so the funding wallet can't be the stake account
+    check_not_stake_account(&stake_account, &from, "from")?;
+
+    match state.solana.create_stake_account(&from, &stake_account, &staker, &withdrawer, lamports) {
+        Ok(stake_response) => {
+            info!("Successfully created stake account instructions for: {}", stake_response.stake_account);
+            Ok(Json(ApiResponse::success(stake_response)))
+        }
+        Err(e) => {
+            error!("Failed to create stake account instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /stake/delegate
+/// Creates the instruction delegating a stake account to a validator's vote account
+#[utoipa::path(
+    post,
+    path = "/stake/delegate",
+    tag = "stake",
+    request_body = DelegateStakeRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn delegate_stake_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<DelegateStakeRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling stake delegation request for: {}", request.stake_account);
+
+    let stake_account = validation::validate_pubkey(&request.stake_account, "stake_account")?;
+    let vote_account = validation::validate_pubkey(&request.vote_account, "vote_account")?;
+    let staker = validation::validate_authority(&request.staker, "staker")?;
+    check_not_stake_account(&stake_account, &vote_account, "vote_account")?;
+
+    match state.solana.delegate_stake(&stake_account, &vote_account, &staker) {
+        Ok(instruction_response) => {
+            info!("Successfully created delegate instruction for: {} to {}", request.stake_account, request.vote_account);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create delegate instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /stake/deactivate
+/// Creates the instruction deactivating a delegated stake account
+#[utoipa::path(
+    post,
+    path = "/stake/deactivate",
+    tag = "stake",
+    request_body = DeactivateStakeRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn deactivate_stake_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<DeactivateStakeRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling stake deactivation request for: {}", request.stake_account);
+
+    let stake_account = validation::validate_pubkey(&request.stake_account, "stake_account")?;
+    let staker = validation::validate_authority(&request.staker, "staker")?;
+
+    match state.solana.deactivate_stake(&stake_account, &staker) {
+        Ok(instruction_response) => {
+            info!("Successfully created deactivate instruction for: {}", request.stake_account);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create deactivate instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /stake/withdraw
+/// Creates the instruction withdrawing lamports from a stake account
+#[utoipa::path(
+    post,
+    path = "/stake/withdraw",
+    tag = "stake",
+    request_body = WithdrawStakeRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TokenInstructionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn withdraw_stake_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<WithdrawStakeRequest>,
+) -> Result<Json<ApiResponse<TokenInstructionResponse>>> {
+    info!("Handling stake withdrawal request for: {}", request.stake_account);
+
+    let stake_account = validation::validate_pubkey(&request.stake_account, "stake_account")?;
+    let withdrawer = validation::validate_authority(&request.withdrawer, "withdrawer")?;
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let lamports = validation::validate_positive_amount(request.lamports, "lamports")?;
+    check_not_stake_account(&stake_account, &destination, "destination")?;
+
+    match state.solana.withdraw_stake(&stake_account, &withdrawer, &destination, lamports) {
+        Ok(instruction_response) => {
+            info!("Successfully created withdraw instruction for: {}", request.stake_account);
+            Ok(Json(ApiResponse::success(instruction_response)))
+        }
+        Err(e) => {
+            error!("Failed to create withdraw instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Validates a message to sign or verify, logging a warning for one that is only whitespace
+///
+/// Such a message is still signed exactly as sent: trimming it would change the signed bytes
+/// without the client knowing.
+fn check_message(message: &str, allow_empty: bool, field_name: &str) -> Result<()> {
+    validation::validate_signable_message(message, allow_empty, field_name)?;
+    if validation::is_blank_message(message) {
+        warn!("{} is only whitespace ({} bytes); signing it unchanged", field_name, message.len());
+    }
+    Ok(())
+}
+
+/// Handler for POST /message/sign
+/// Signs a message with the provided secret key
+#[utoipa::path(
+    post,
+    path = "/message/sign",
+    tag = "message",
+    request_body = SignMessageRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SignMessageResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn sign_message_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SignMessageRequest>,
+) -> Result<Json<ApiResponse<SignMessageResponse>>> {
+    info!("Handling message signing request");
+
+    // Comprehensive validation using validation module
+    check_message(&request.message, request.allow_empty, "message")?;
+    validation::validate_secret_key(&request.secret)?;
+    validation::validate_message_prefix(request.prefix.as_deref())?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.sign_message(&request.message, request.prefix.as_deref(), &request.secret) {
+        Ok(sign_response) => {
+            info!("Successfully signed message");
+            state.audit.record("message.sign", &[&sign_response.pubkey], AuditResult::Success);
+            Ok(Json(ApiResponse::success(sign_response)))
+        }
+        Err(e) => {
+            error!("Failed to sign message: {}", e);
+            state.audit.record("message.sign", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /message/sign/batch
+/// Signs many messages with one secret key, which is decoded only once
+#[utoipa::path(
+    post,
+    path = "/message/sign/batch",
+    tag = "message",
+    request_body = SignMessageBatchRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SignMessageBatchResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn sign_message_batch_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SignMessageBatchRequest>,
+) -> Result<Json<ApiResponse<SignMessageBatchResponse>>> {
+    info!("Handling batch message signing request with {} messages", request.messages.len());
+
+    validation::validate_batch_len(request.messages.len(), "messages")?;
+    for (index, message) in request.messages.iter().enumerate() {
+        check_message(message, request.allow_empty, &format!("messages[{}]", index))?;
+    }
+    validation::validate_secret_key(&request.secret)?;
+    validation::validate_message_prefix(request.prefix.as_deref())?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.sign_messages(&request.messages, request.prefix.as_deref(), &request.secret) {
+        Ok(batch_response) => {
+            info!("Successfully signed {} messages", batch_response.signatures.len());
+            state.audit.record("message.sign_batch", &[&batch_response.pubkey], AuditResult::Success);
+            Ok(Json(ApiResponse::success(batch_response)))
+        }
+        Err(e) => {
+            error!("Failed to sign messages: {}", e);
+            state.audit.record("message.sign_batch", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// The accounts that must sign `message`, for the audit log
+fn required_signers(message: &solana_sdk::message::Message) -> Vec<String> {
+    message
+        .account_keys
+        .iter()
+        .take(message.header.num_required_signatures as usize)
+        .map(Pubkey::to_string)
+        .collect()
+}
+
+fn pubkey_refs(pubkeys: &[String]) -> Vec<&str> {
+    pubkeys.iter().map(String::as_str).collect()
+}
+
+/// Handler for POST /transaction/sign
+/// Signs a transaction message with all of its required signers
+#[utoipa::path(
+    post,
+    path = "/transaction/sign",
+    tag = "transaction",
+    request_body = SignTransactionRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SignTransactionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn sign_transaction_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SignTransactionRequest>,
+) -> Result<Json<ApiResponse<SignTransactionResponse>>> {
+    info!("Handling transaction signing request with {} secrets", request.secrets.len());
+
+    let message = validation::validate_message(&request.message)?;
+    validation::validate_programs_allowed(&message, &state.config.allowed_programs)?;
+    if request.secrets.is_empty() {
+        return Err(AppError::ValidationError("secrets must not be empty".to_string()));
+    }
+    for secret in &request.secrets {
+        validation::validate_secret_key(secret)?;
+    }
+
+    let signers = required_signers(&message);
+    let solana_service = &state.solana;
+
+    match solana_service.sign_transaction(message, &request.secrets) {
+        Ok(sign_response) => {
+            info!("Successfully signed transaction: {}", sign_response.signature);
+            state.audit.record("transaction.sign", &pubkey_refs(&signers), AuditResult::Success);
+            Ok(Json(ApiResponse::success(sign_response)))
+        }
+        Err(e) => {
+            error!("Failed to sign transaction: {}", e);
+            state.audit.record("transaction.sign", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /transaction/sign/partial
+/// Adds signatures to a partially signed transaction and reports the signers still missing
+#[utoipa::path(
+    post,
+    path = "/transaction/sign/partial",
+    tag = "transaction",
+    request_body = PartialSignTransactionRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PartialSignTransactionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn partial_sign_transaction_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<PartialSignTransactionRequest>,
+) -> Result<Json<ApiResponse<PartialSignTransactionResponse>>> {
+    info!("Handling partial transaction signing request with {} secrets", request.secrets.len());
+
+    let transaction = validation::validate_transaction(&request.transaction)?;
+    validation::validate_programs_allowed(&transaction.message, &state.config.allowed_programs)?;
+    if request.secrets.is_empty() {
+        return Err(AppError::ValidationError("secrets must not be empty".to_string()));
+    }
+    for secret in &request.secrets {
+        validation::validate_secret_key(secret)?;
+    }
+
+    let signers = required_signers(&transaction.message);
+    let solana_service = &state.solana;
+
+    match solana_service.partial_sign_transaction(transaction, &request.secrets) {
+        Ok(sign_response) => {
+            info!("Successfully added signatures; {} signer(s) still missing", sign_response.missing.len());
+            // Signers still missing are the ones these secrets didn't cover
+            let signed: Vec<&str> = signers
+                .iter()
+                .filter(|signer| !sign_response.missing.contains(signer))
+                .map(String::as_str)
+                .collect();
+            state.audit.record("transaction.sign_partial", &signed, AuditResult::Success);
+            Ok(Json(ApiResponse::success(sign_response)))
+        }
+        Err(e) => {
+            error!("Failed to partially sign transaction: {}", e);
+            state.audit.record("transaction.sign_partial", &[], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /transaction/verify
+/// Checks that every required signature on a transaction is present and valid
+#[utoipa::path(
+    post,
+    path = "/transaction/verify",
+    tag = "transaction",
+    request_body = VerifyTransactionRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<VerifyTransactionResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn verify_transaction_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<VerifyTransactionRequest>,
+) -> Result<Json<ApiResponse<VerifyTransactionResponse>>> {
+    info!("Handling transaction verification request");
+
+    let transaction = validation::validate_transaction(&request.transaction)?;
+    let signers = required_signers(&transaction.message);
+
+    let solana_service = &state.solana;
+
+    match solana_service.verify_transaction(&transaction) {
+        Ok(verify_response) => {
+            info!("Transaction signatures complete: {}", verify_response.complete);
+            state.audit.record("transaction.verify", &pubkey_refs(&signers), AuditResult::verified(verify_response.complete));
+            Ok(Json(ApiResponse::success(verify_response)))
+        }
+        Err(e) => {
+            error!("Failed to verify transaction: {}", e);
+            state.audit.record("transaction.verify", &pubkey_refs(&signers), AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /transaction/signature
+/// Returns the signature a signed transaction will be known by, without submitting it
+#[utoipa::path(
+    post,
+    path = "/transaction/signature",
+    tag = "transaction",
+    request_body = TransactionSignatureRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TransactionSignatureResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn transaction_signature_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<TransactionSignatureRequest>,
+) -> Result<Json<ApiResponse<TransactionSignatureResponse>>> {
+    info!("Handling transaction signature request");
+
+    let transaction = validation::validate_transaction(&request.transaction)?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.transaction_signature(&transaction) {
+        Ok(signature_response) => {
+            info!("Transaction signature: {}", signature_response.signature);
+            Ok(Json(ApiResponse::success(signature_response)))
+        }
+        Err(e) => {
+            error!("Failed to read transaction signature: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /message/verify
+/// Verifies a message signature
+#[utoipa::path(
+    post,
+    path = "/message/verify",
+    tag = "message",
+    request_body = VerifyMessageRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<VerifyMessageResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn verify_message_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<VerifyMessageRequest>,
+) -> Result<Json<ApiResponse<VerifyMessageResponse>>> {
+    info!("Handling message verification request");
+
+    // Comprehensive validation using validation module
+    check_message(&request.message, request.allow_empty, "message")?;
+    let signature = validation::validate_encoded_signature(&request.signature, request.signature_encoding.as_deref())?;
+    let pubkey = validation::validate_encoded_pubkey(&request.pubkey, request.public_key_encoding.as_deref(), "pubkey")?;
+    validation::validate_message_prefix(request.prefix.as_deref())?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.verify_message(
+        &request.message,
+        request.prefix.as_deref(),
+        &signature.to_string(),
+        &pubkey.to_string(),
+        request.strict,
+    ) {
+        Ok(verify_response) => {
+            info!("Successfully verified message signature: {}", verify_response.valid);
+            state.audit.record("message.verify", &[&verify_response.pubkey], AuditResult::verified(verify_response.valid));
+            Ok(Json(ApiResponse::success(verify_response)))
+        }
+        Err(e) => {
+            error!("Failed to verify message signature: {}", e);
+            state.audit.record("message.verify", &[&pubkey.to_string()], AuditResult::failure(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /auth/challenge
+/// Issues a random single-use nonce for a wallet to sign, proving it holds its key
+#[utoipa::path(
+    post,
+    path = "/auth/challenge",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AuthChallengeResponse>),
+        (status = 503, description = "Too many challenges are outstanding", body = ApiErrorResponse),
+    )
+)]
+pub async fn auth_challenge_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<AuthChallengeResponse>>> {
+    info!("Handling auth challenge request");
+
+    match state.challenges.issue() {
+        Some(nonce) => {
+            info!("Issued auth challenge");
+            Ok(Json(ApiResponse::success(AuthChallengeResponse {
+                nonce,
+                expires_in_secs: state.challenges.ttl().as_secs(),
+            })))
+        }
+        None => {
+            warn!("Refusing auth challenge: too many are outstanding");
+            Err(AppError::ServiceUnavailable(
+                "Too many outstanding challenges; try again later".to_string(),
+            ))
+        }
+    }
+}
+
+/// Handler for POST /auth/verify
+/// Checks a wallet's signature of an issued nonce, then redeems the nonce so it can't be replayed
+#[utoipa::path(
+    post,
+    path = "/auth/verify",
+    tag = "auth",
+    request_body = AuthVerifyRequest,
+    responses(
+        (status = 200, description = "The wallet signed the nonce", body = ApiResponse<AuthVerifyResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 401, description = "Wrong signature, or an unknown, used or expired nonce", body = ApiErrorResponse),
+    )
+)]
+pub async fn auth_verify_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<AuthVerifyRequest>,
+) -> Result<Json<ApiResponse<AuthVerifyResponse>>> {
+    info!("Handling auth verify request for: {}", request.pubkey);
+
+    let pubkey = validation::validate_pubkey(&request.pubkey, "pubkey")?;
+    let signature = validation::validate_signature(&request.signature)?;
+    validation::validate_non_empty_string(&request.nonce, "nonce")?;
+
+    // The signature is checked first, so a mistyped one doesn't use up the nonce
+    let pubkey_string = pubkey.to_string();
+    let verified = state
+        .solana
+        .verify_message(&request.nonce, None, &signature.to_string(), &pubkey_string, true)
+        .inspect_err(|e| state.audit.record("auth.verify", &[&pubkey_string], AuditResult::failure(e)))?;
+    if !verified.valid {
+        warn!("Auth signature for {} did not verify", pubkey);
+        state.audit.record("auth.verify", &[&pubkey_string], AuditResult::Invalid);
+        return Err(AppError::Unauthorized(format!("signature is not {}'s signature of the nonce", pubkey)));
+    }
+
+    match state.challenges.redeem(&request.nonce) {
+        Ok(()) => {
+            info!("Verified ownership of {}", pubkey);
+            state.audit.record("auth.verify", &[&pubkey_string], AuditResult::Success);
+            Ok(Json(ApiResponse::success(AuthVerifyResponse { verified: true, pubkey: pubkey_string })))
+        }
+        Err(e) => {
+            warn!("Auth nonce for {} was not redeemable: {:?}", pubkey, e);
+            let error = AppError::Unauthorized(match e {
+                RedeemError::Expired => "nonce has expired; request a new challenge".to_string(),
+                RedeemError::Unknown => "nonce was not issued by this server or has already been used".to_string(),
+            });
+            state.audit.record("auth.verify", &[&pubkey_string], AuditResult::failure(&error));
+            Err(error)
+        }
+    }
+}
+
+/// Handler for POST /siws/build
+/// Builds the text of a Sign-In With Solana message from its fields
+#[utoipa::path(
+    post,
+    path = "/siws/build",
+    tag = "siws",
+    request_body = SiwsMessage,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SiwsBuildResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn siws_build_handler(
+    JsonExtractor(request): JsonExtractor<SiwsMessage>,
+) -> Result<Json<ApiResponse<SiwsBuildResponse>>> {
+    info!("Handling SIWS message build request for {}", request.domain);
+
+    let fields = request.with_defaults(chrono::Utc::now());
+    fields.validate()?;
+
+    let message = fields.to_text();
+    info!("Successfully built SIWS message");
+    Ok(Json(ApiResponse::success(SiwsBuildResponse { message, fields })))
+}
+
+/// Handler for POST /siws/verify
+/// Parses a signed Sign-In With Solana message and checks its signature, domain, nonce and
+/// validity window
+#[utoipa::path(
+    post,
+    path = "/siws/verify",
+    tag = "siws",
+    request_body = SiwsVerifyRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SiwsVerifyResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn siws_verify_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SiwsVerifyRequest>,
+) -> Result<Json<ApiResponse<SiwsVerifyResponse>>> {
+    info!("Handling SIWS message verification request");
+
+    let fields = SiwsMessage::parse(&request.message)?;
+    validation::validate_signature_format(&request.signature)?;
+
+    let verified = match state.solana.verify_message(&request.message, None, &request.signature, &fields.address, true) {
+        Ok(verified) => verified,
+        Err(e) => {
+            error!("Failed to verify SIWS message signature: {}", e);
+            state.audit.record("siws.verify", &[&fields.address], AuditResult::failure(&e));
+            return Err(e);
+        }
+    };
+    let reason = if verified.valid {
+        fields
+            .check(request.domain.as_deref(), request.nonce.as_deref(), chrono::Utc::now())
+            .err()
+    } else {
+        Some("Signature does not match the message and address".to_string())
+    };
+
+    info!("Verified SIWS message for {}: {}", fields.address, reason.is_none());
+    state.audit.record("siws.verify", &[&fields.address], AuditResult::verified(reason.is_none()));
+    Ok(Json(ApiResponse::success(SiwsVerifyResponse {
+        valid: reason.is_none(),
+        reason,
+        fields,
+    })))
+}
+
+/// Handler for POST /batch
+/// Runs several operations in one request, returning each one's result in order, as one JSON
+/// body or, with ?stream=ndjson, one result per line as each is ready
+#[utoipa::path(
+    post,
+    path = "/batch",
+    tag = "batch",
+    params(StreamQuery),
+    request_body = Vec<BatchCall>,
+    responses(
+        (status = 200, description = "Every call ran; each result reports its own status. With ?stream=ndjson, one BatchResult per line",
+            content((ApiResponse<Vec<BatchResult>> = "application/json"), (BatchResult = "application/x-ndjson"))),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn batch_stream_handler(
+    state: State<AppState>,
+    Query(query): Query<StreamQuery>,
+    JsonExtractor(calls): JsonExtractor<Vec<BatchCall>>,
+) -> Result<Response> {
+    if !validation::validate_stream(query.stream.as_deref())? {
+        return batch_handler(state, JsonExtractor(calls)).await.map(IntoResponse::into_response);
+    }
+    info!("Streaming batch request with {} calls", calls.len());
+
+    // Calls can reach the cluster, so a streamed batch is held to the usual size
+    validation::validate_batch_len(calls.len(), "batch")?;
+
+    let state = state.0;
+    let results = futures::stream::iter(calls)
+        .map(move |call| run_batch_call(state.clone(), call))
+        .buffered(validation::MAX_BATCH_SIZE);
+    Ok(ndjson_response(results.map(Ok)))
+}
+
+/// The JSON form of POST /batch
+/// Runs several operations in one request, returning each one's result in order
+pub async fn batch_handler(
+    State(state): State<AppState>,
+    JsonExtractor(calls): JsonExtractor<Vec<BatchCall>>,
+) -> Result<Json<ApiResponse<Vec<BatchResult>>>> {
+    info!("Handling batch request with {} calls", calls.len());
+
+    validation::validate_batch_len(calls.len(), "batch")?;
+
+    // Calls are independent, so RPC-backed ones can wait on the cluster concurrently
+    let results = futures::future::join_all(calls.into_iter().map(|call| run_batch_call(state.clone(), call))).await;
+
+    let failed = results.iter().filter(|result| !result.success).count();
+    info!("Batch finished: {} succeeded, {} failed", results.len() - failed, failed);
+    Ok(Json(ApiResponse::success(results)))
+}
+
+/// Runs one batch call, reporting its failure in its result
+async fn run_batch_call(state: AppState, call: BatchCall) -> BatchResult {
+    let id = call.id.clone();
+    match dispatch_batch_call(state, call).await {
+        Ok(data) => BatchResult { id, status: 200, success: true, data: Some(data), error: None },
+        Err(e) => {
+            let (status, message) = e.into_parts();
+            BatchResult { id, status: status.as_u16(), success: false, data: None, error: Some(message) }
+        }
+    }
+}
+
+/// Runs one batch call through the handler for its route
+///
+/// Only endpoints without side effects are available: the submitting ones (/rpc/send,
+/// /rpc/airdrop, /keypair/funded) need their own request for Idempotency-Key protection, and
+/// the WebSocket stream can't be batched.
+async fn dispatch_batch_call(app_state: AppState, call: BatchCall) -> Result<serde_json::Value> {
+    let params = call.params;
+    let keypair_generation = app_state.config.enable_keypair_generation;
+    let state = || State(app_state.clone());
+
+    match call.method.as_str() {
+        "keypair" if keypair_generation => batch_response(generate_keypair_handler(state()).await),
+        "keypair/from-seed" if keypair_generation => batch_call(params, |request| keypair_from_seed_handler(state(), request)).await,
+        "keypair/derive/batch" if keypair_generation => batch_call(params, |request| derive_keypair_batch_handler(state(), request)).await,
+        "keypair/public-only" => batch_response(public_only_keypair_handler(state()).await),
+        "keypair/export" => batch_call(params, |request| export_keypair_handler(state(), request)).await,
+        "keypair/import" => batch_call(params, |request| import_keypair_handler(state(), request)).await,
+        "keypair/inspect" => batch_call(params, |request| inspect_keypair_handler(state(), request)).await,
+        "keypair/rotate" => batch_call(params, |request| rotate_keypair_handler(state(), request)).await,
+        "rpc/token-accounts" => batch_call(params, |request| token_accounts_handler(state(), request)).await,
+        "rpc/account" => batch_call(params, |request| account_info_handler(state(), request)).await,
+        "rpc/mint-info" => batch_call(params, |request| mint_info_handler(state(), request)).await,
+        "rpc/token-account-info" => batch_call(params, |request| token_account_info_handler(state(), request)).await,
+        "rpc/rent" => batch_call(params, |request| rent_handler(state(), request)).await,
+        "rpc/simulate" => batch_call(params, |request| simulate_transaction_handler(state(), request)).await,
+        "rpc/signature-status" => batch_call(params, |request| signature_status_handler(state(), request)).await,
+        "rpc/vote-accounts" => batch_call(params, |request| vote_accounts_handler(state(), request)).await,
+        "token/create" => batch_call(params, |request| create_token_handler(state(), request)).await,
+        "token/create/new" if keypair_generation => batch_call(params, |request| create_new_token_handler(state(), request)).await,
+        "token/mint" => batch_call(params, |request| mint_token_handler(state(), request)).await,
+        "token/mint/batch" => batch_call(params, |request| mint_batch_handler(state(), request)).await,
+        "token/burn-close" => batch_call(params, |request| burn_close_handler(state(), request)).await,
+        "token/wrap" => batch_call(params, |request| wrap_sol_handler(state(), request)).await,
+        "token/unwrap" => batch_call(params, |request| unwrap_sol_handler(state(), request)).await,
+        "token/sync-native" => batch_call(params, |request| sync_native_handler(state(), request)).await,
+        "pda/derive" => batch_call(params, |request| derive_pda_handler(state(), request)).await,
+        "pda/derive/batch" => batch_call(params, |request| derive_pda_batch_handler(state(), request)).await,
+        "token/ata" => batch_call(params, |request| associated_token_address_handler(state(), request)).await,
+        "token/ata/batch" => batch_call(params, |request| associated_token_address_batch_handler(state(), request)).await,
+        "pubkey/on-curve" => batch_call(params, |request| on_curve_handler(state(), request)).await,
+        "pubkey/normalize" => batch_call(params, |request| normalize_pubkey_handler(state(), request)).await,
+        "token/transfer-fee/create-mint" => {
+            batch_call(params, |request| create_transfer_fee_mint_handler(state(), request)).await
+        }
+        "token/transfer-fee/transfer" => batch_call(params, |request| transfer_with_fee_handler(state(), request)).await,
+        "token/metadata/pointer" => batch_call(params, |request| metadata_pointer_handler(state(), request)).await,
+        "token/metadata/initialize" => {
+            batch_call(params, |request| initialize_token_metadata_handler(state(), request)).await
+        }
+        "token/metadata/update" => batch_call(params, |request| update_token_metadata_handler(state(), request)).await,
+        "token/close-mint/create-mint" => batch_call(params, |request| create_closeable_mint_handler(state(), request)).await,
+        "token/close-mint" => batch_call(params, |request| close_mint_handler(state(), request)).await,
+        "stake/create" => batch_call(params, |request| create_stake_account_handler(state(), request)).await,
+        "stake/delegate" => batch_call(params, |request| delegate_stake_handler(state(), request)).await,
+        "stake/deactivate" => batch_call(params, |request| deactivate_stake_handler(state(), request)).await,
+        "stake/withdraw" => batch_call(params, |request| withdraw_stake_handler(state(), request)).await,
+        "message/sign" => batch_call(params, |request| sign_message_handler(state(), request)).await,
+        "message/sign/batch" => batch_call(params, |request| sign_message_batch_handler(state(), request)).await,
+        "message/verify" => batch_call(params, |request| verify_message_handler(state(), request)).await,
+        "siws/build" => batch_call(params, siws_build_handler).await,
+        "siws/verify" => batch_call(params, |request| siws_verify_handler(state(), request)).await,
+        "transaction/sign" => batch_call(params, |request| sign_transaction_handler(state(), request)).await,
+        "transaction/sign/partial" => batch_call(params, |request| partial_sign_transaction_handler(state(), request)).await,
+        "transaction/verify" => batch_call(params, |request| verify_transaction_handler(state(), request)).await,
+        "transaction/signature" => batch_call(params, |request| transaction_signature_handler(state(), request)).await,
+        "send/sol" => batch_call(params, |request| send_sol_handler(state(), request)).await,
+        "send/token" => batch_call(params, |request| send_token_handler(state(), request)).await,
+        "send/token-with-memo" => batch_call(params, |request| send_token_with_memo_handler(state(), request)).await,
+        other => Err(AppError::NotFound(format!("Unknown batch method: {}", other))),
+    }
+}
+
+/// Calls `handler` with `params` as its request body
+async fn batch_call<T, R, F, Fut>(params: serde_json::Value, handler: F) -> Result<serde_json::Value>
+where
+    T: DeserializeOwned,
+    R: serde::Serialize,
+    F: FnOnce(JsonExtractor<T>) -> Fut,
+    Fut: std::future::Future<Output = Result<Json<ApiResponse<R>>>>,
+{
+    // An omitted params is treated as an empty body, so requests with only optional fields work
+    let params = if params.is_null() { serde_json::json!({}) } else { params };
+    let request = serde_json::from_value(params)
+        .map_err(|e| AppError::ValidationError(format!("Invalid params: {}", e)))?;
+    batch_response(handler(JsonExtractor(request)).await)
+}
+
+/// The `data` of a handler's response, as JSON
+fn batch_response<R: serde::Serialize>(response: Result<Json<ApiResponse<R>>>) -> Result<serde_json::Value> {
+    let Json(response) = response?;
+    serde_json::to_value(response.data).map_err(serialization_error)
+}
+
+/// Handler for POST /send/sol
+/// Creates a SOL transfer instruction
+#[utoipa::path(
+    post,
+    path = "/send/sol",
+    tag = "send",
+    request_body = SendSolRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SendSolResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn send_sol_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SendSolRequest>,
+) -> Result<Json<ApiResponse<SendSolResponse>>> {
+    info!("Handling SOL transfer request from {} to {}", request.from, request.to);
+
+    // Comprehensive validation using validation module
+    let from = validation::validate_pubkey(&request.from, "sender")?;
+    let to = validation::validate_pubkey(&request.to, "recipient")?;
+    // Clients match on the exact "Amount must be greater than 0" message for this endpoint
+    let lamports = validation::validate_positive_amount(request.lamports, "Amount")?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.send_sol(&from.to_string(), &to.to_string(), lamports) {
+        Ok(sol_response) => {
+            info!("Successfully created SOL transfer instruction");
+            Ok(Json(ApiResponse::success(sol_response)))
+        }
+        Err(e) => {
+            error!("Failed to create SOL transfer instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /send/token
+/// Creates an SPL token transfer instruction
+#[utoipa::path(
+    post,
+    path = "/send/token",
+    tag = "send",
+    request_body = SendTokenRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SendTokenResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn send_token_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SendTokenRequest>,
+) -> Result<Json<ApiResponse<SendTokenResponse>>> {
+    info!("Handling token transfer request for mint: {}", request.mint);
+
+    // Comprehensive validation using validation module
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let owner = validation::validate_pubkey(&request.owner, "owner")?;
+    let amount = validation::validate_positive_amount(request.amount, "amount")?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.send_token(
+        &destination.to_string(),
+        &mint.to_string(),
+        &owner.to_string(),
+        amount,
+        request.create_ata_if_missing,
+    ) {
+        Ok(token_response) => {
+            info!("Successfully created token transfer instruction");
+            Ok(Json(ApiResponse::success(token_response)))
+        }
+        Err(e) => {
+            error!("Failed to create token transfer instruction: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Handler for POST /send/token-with-memo
+/// Creates a memo instruction followed by an SPL token transfer instruction
+#[utoipa::path(
+    post,
+    path = "/send/token-with-memo",
+    tag = "send",
+    request_body = SendTokenWithMemoRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<SendTokenWithMemoResponse>),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+    )
+)]
+pub async fn send_token_with_memo_handler(
+    State(state): State<AppState>,
+    JsonExtractor(request): JsonExtractor<SendTokenWithMemoRequest>,
+) -> Result<Json<ApiResponse<SendTokenWithMemoResponse>>> {
+    info!("Handling token transfer with memo request for mint: {}", request.mint);
+
+    let destination = validation::validate_pubkey(&request.destination, "destination")?;
+    let mint = validation::validate_pubkey(&request.mint, "mint")?;
+    let owner = validation::validate_pubkey(&request.owner, "owner")?;
+    let amount = validation::validate_positive_amount(request.amount, "amount")?;
+    validation::validate_memo(&request.memo)?;
+
+    let solana_service = &state.solana;
+
+    match solana_service.send_token_with_memo(&destination, &mint, &owner, amount, &request.memo, request.create_ata_if_missing) {
+        Ok(token_response) => {
+            info!("Successfully created token transfer with memo instructions");
+            Ok(Json(ApiResponse::success(token_response)))
+        }
+        Err(e) => {
+            error!("Failed to create token transfer with memo instructions: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::config::Config;
+
+    /// Shared services for the default configuration, as handlers receive them from the router
+    fn test_state() -> State<AppState> {
+        state_for(Config::default())
+    }
+
+    fn state_for(config: Config) -> State<AppState> {
+        State(AppState::new(Arc::new(config)))
+    }
+    use crate::models::{KeypairFromSeedRequest, DeriveKeypairBatchRequest, ExportKeypairRequest, ImportKeypairRequest, InspectKeypairRequest, DerivePdaRequest, DerivePdaBatchRequest, RotateKeypairRequest, SignedMessage, FundedKeypairRequest, TokenAccountsRequest, AccountInfoRequest, MintInfoRequest, TokenAccountInfoRequest, RentRequest, CreateTokenRequest, MintTokenRequest, MintBatchRequest, BurnCloseRequest, WrapSolRequest, UnwrapSolRequest, SyncNativeRequest, SignMessageRequest, SignMessageBatchRequest, VerifyMessageRequest, SendSolRequest, SendTokenRequest, SendTokenWithMemoRequest};
+
+    async fn extract<T: DeserializeOwned>(body: &str) -> Result<T> {
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap();
+        JsonExtractor::<T>::from_request(request, &()).await.map(|JsonExtractor(value)| value)
+    }
+
+    #[tokio::test]
+    async fn test_amount_boundaries() {
+        let mint = "11111111111111111111111111111112";
+
+        // u64::MAX itself is accepted as-is
+        let body = format!(r#"{{"mint":"{0}","destination":"{0}","authority":"{0}","amount":18446744073709551615}}"#, mint);
+        let request: MintTokenRequest = extract(&body).await.unwrap();
+        assert_eq!(request.amount, u64::MAX);
+
+        // The same amount as a string, for clients whose numbers can't hold it exactly
+        let body = format!(r#"{{"mint":"{0}","destination":"{0}","authority":"{0}","amount":"18446744073709551615"}}"#, mint);
+        let request: MintTokenRequest = extract(&body).await.unwrap();
+        assert_eq!(request.amount, u64::MAX);
+
+        // One past u64::MAX and negative amounts are rejected rather than wrapped
+        for (amount, got) in [
+            ("18446744073709551616", "1.8446744073709552e+19"),
+            ("-1", "-1"),
+            ("1.5", "1.5"),
+            (r#""18446744073709551616""#, r#""18446744073709551616""#),
+            (r#""1e3""#, r#""1e3""#),
+        ] {
+            let body = format!(r#"{{"mint":"{0}","destination":"{0}","authority":"{0}","amount":{1}}}"#, mint, amount);
+            match extract::<MintTokenRequest>(&body).await {
+                Err(AppError::ValidationError(message)) => {
+                    assert_eq!(message, format!("amount must be an integer between 0 and 18446744073709551615 (u64), got {}", got));
+                }
+                other => panic!("expected a validation error for {}, got {:?}", amount, other.map(|r| r.amount)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_amounts_accept_numeric_strings() {
+        let key = "11111111111111111111111111111112";
+        let send_sol = |lamports: &str| format!(r#"{{"from":"{0}","to":"{0}","lamports":{1}}}"#, key, lamports);
+
+        let request: SendSolRequest = extract(&send_sol(r#""100""#)).await.unwrap();
+        assert_eq!(request.lamports, 100);
+        let request: SendSolRequest = extract(&send_sol("100")).await.unwrap();
+        assert_eq!(request.lamports, 100);
+
+        for (lamports, got) in [
+            (r#""100 SOL""#, r#""100 SOL""#),
+            (r#""0.5""#, r#""0.5""#),
+            ("0.5", "0.5"),
+            (r#""-5""#, r#""-5""#),
+            ("-5", "-5"),
+            ("true", "true"),
+            ("null", "null"),
+        ] {
+            match extract::<SendSolRequest>(&send_sol(lamports)).await {
+                Err(AppError::ValidationError(message)) => assert_eq!(
+                    message,
+                    format!("lamports must be an integer between 0 and 18446744073709551615 (u64), got {}", got)
+                ),
+                other => panic!("expected a validation error for {}, got {:?}", lamports, other.map(|r| r.lamports)),
+            }
+        }
+
+        // Nested and optional amounts too
+        let body = format!(r#"{{"mint":"{0}","authority":"{0}","recipients":[{{"destination":"{0}","amount":"7"}}]}}"#, key);
+        let request: MintBatchRequest = extract(&body).await.unwrap();
+        assert_eq!(request.recipients[0].amount, 7);
+
+        let transfer = |fee: &str| {
+            format!(r#"{{"destination":"{0}","mint":"{0}","owner":"{0}","amount":"10","decimals":0,"fee":{1}}}"#, key, fee)
+        };
+        let request: TransferWithFeeRequest = extract(&transfer(r#""3""#)).await.unwrap();
+        assert_eq!((request.amount, request.fee), (10, Some(3)));
+        let request: TransferWithFeeRequest = extract(&transfer("null")).await.unwrap();
+        assert_eq!(request.fee, None);
+        match extract::<TransferWithFeeRequest>(&transfer("-3")).await {
+            Err(AppError::ValidationError(message)) => {
+                assert_eq!(message, "fee must be an integer between 0 and 18446744073709551615 (u64), got -3");
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|r| r.fee)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_fields_are_rejected() {
+        let key = "11111111111111111111111111111112";
+
+        // A mistyped field name is reported instead of being ignored
+        let body = format!(r#"{{"mintAuth":"{0}","mint":"{0}","decimals":6}}"#, key);
+        let (status, message) = extract::<CreateTokenRequest>(&body).await.unwrap_err().into_parts();
+        assert_eq!(status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            message,
+            "Unknown field `mintAuth`, expected one of `mintAuthority`, `mint_authority`, `mintauthority`, `mint`, \
+             `decimals`, `freezeAuthority`, `freeze_authority`, `freezeauthority`, `disableFreezeAuthority`, \
+             `disable_freeze_authority`"
+        );
+
+        // Extra fields are rejected even when every required field is present
+        let body = format!(r#"{{"mint":"{0}","destination":"{0}","authority":"{0}","amount":1,"memo":"hi"}}"#, key);
+        match extract::<MintTokenRequest>(&body).await {
+            Err(AppError::ValidationError(message)) => assert!(message.starts_with("Unknown field `memo`"), "{}", message),
+            other => panic!("expected a validation error, got {:?}", other.map(|r| r.amount)),
+        }
+
+        // Nested request types are checked too
+        let body = format!(r#"{{"mint":"{0}","authority":"{0}","recipients":[{{"destination":"{0}","amount":1,"ammount":2}}]}}"#, key);
+        assert!(matches!(extract::<MintBatchRequest>(&body).await, Err(AppError::ValidationError(m)) if m.contains("`ammount`")));
+    }
+
+    #[tokio::test]
+    async fn test_field_name_aliases() {
+        let key = "11111111111111111111111111111112";
+
+        for (mint_authority, freeze_authority) in [
+            ("mintAuthority", "freezeAuthority"),
+            ("mint_authority", "freeze_authority"),
+            ("mintauthority", "freezeauthority"),
+        ] {
+            let body = format!(r#"{{"{1}":"{0}","mint":"{0}","decimals":6,"{2}":null}}"#, key, mint_authority, freeze_authority);
+            let request: CreateTokenRequest = extract(&body).await.unwrap();
+            assert_eq!(request.mint_authority, key, "{}", mint_authority);
+            assert_eq!(request.freeze_authority, Some(None), "{}", freeze_authority);
+        }
+
+        // Other multi-word fields accept camelCase as well as snake_case
+        for field in ["data_len", "dataLen"] {
+            let request: RentRequest = extract(&format!(r#"{{"{}":165}}"#, field)).await.unwrap();
+            assert_eq!(request.data_len, 165);
+        }
+        let request: SimulateTransactionRequest =
+            extract(r#"{"transaction":"AA==","sigVerify":true,"replaceRecentBlockhash":false}"#).await.unwrap();
+        assert!(request.sig_verify && !request.replace_recent_blockhash);
+        let body = format!(r#"{{"mint":"{0}","authority":"{0}","recipients":[],"recentBlockhash":"{0}"}}"#, key);
+        let request: MintBatchRequest = extract(&body).await.unwrap();
+        assert_eq!(request.recent_blockhash.as_deref(), Some(key));
+        let body = r#"{"message":"hi","signature":"sig","pubkey":"key","publicKeyEncoding":"hex"}"#;
+        let request: VerifyMessageRequest = extract(body).await.unwrap();
+        assert_eq!(request.public_key_encoding.as_deref(), Some("hex"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_keypair_handler() {
+        let result = generate_keypair_handler(test_state()).await;
+        assert!(result.is_ok());
+        
+        let response = result.unwrap();
+        assert!(response.0.success);
+        assert!(!response.0.data.pubkey.is_empty());
+        assert!(!response.0.data.secret.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_public_only_keypair_handler() {
+        let Json(response) = public_only_keypair_handler(test_state()).await.unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["data"].as_object().unwrap().keys().collect::<Vec<_>>(), ["pubkey"]);
+        assert!(validation::validate_pubkey(&response.data.pubkey, "pubkey").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_export_import_keypair_handlers() {
+        let keypair = generate_keypair_handler(test_state()).await.unwrap().0.data;
+
+        let exported = export_keypair_handler(test_state(), JsonExtractor(ExportKeypairRequest {
+            secret: keypair.secret.clone(),
+        })).await.unwrap().0.data;
+        assert_eq!(exported.keypair.len(), 64);
+
+        let imported = import_keypair_handler(test_state(), JsonExtractor(ImportKeypairRequest {
+            keypair: exported.keypair.clone(),
+        })).await.unwrap().0.data;
+        assert_eq!(imported.pubkey, keypair.pubkey);
+        assert_eq!(imported.secret, keypair.secret);
+    }
+
+    #[tokio::test]
+    async fn test_inspect_keypair_handler() {
+        let keypair = generate_keypair_handler(test_state()).await.unwrap().0.data;
+        let request = InspectKeypairRequest { secret: keypair.secret.clone() };
+
+        let Json(response) = inspect_keypair_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert!(response.data.valid);
+        assert_eq!(response.data.derived_pubkey, Some(keypair.pubkey.clone()));
+        // No secret bytes are returned, in any encoding
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains(&keypair.secret));
+        let secret_bytes = bs58::decode(&keypair.secret).into_vec().unwrap();
+        assert!(!json.contains(&bs58::encode(&secret_bytes[..32]).into_string()));
+
+        let result = inspect_keypair_handler(test_state(), JsonExtractor(InspectKeypairRequest { secret: String::new() })).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "secret is required"));
+    }
+
+    #[tokio::test]
+    async fn test_keypair_from_seed_handler() {
+        let request = KeypairFromSeedRequest {
+            seed: "00".repeat(32),
+        };
+        let first = keypair_from_seed_handler(test_state(), JsonExtractor(request.clone())).await.unwrap();
+        let second = keypair_from_seed_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert_eq!(first.0.data.pubkey, second.0.data.pubkey);
+
+        let invalid_request = KeypairFromSeedRequest {
+            seed: "00".repeat(31),
+        };
+        let result = keypair_from_seed_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_derive_keypair_batch_handler() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let body = format!(r#"{{"mnemonic":"{}","count":2,"startIndex":7}}"#, phrase);
+        let request: DeriveKeypairBatchRequest = extract(&body).await.unwrap();
+        let response = derive_keypair_batch_handler(test_state(), JsonExtractor(request)).await.unwrap().0.data;
+        let paths: Vec<&str> = response.keypairs.iter().map(|keypair| keypair.derivation_path.as_str()).collect();
+        assert_eq!(paths, ["m/44'/501'/7'/0'", "m/44'/501'/8'/0'"]);
+
+        let body = format!(r#"{{"mnemonic":"{}","count":2,"account":7}}"#, phrase);
+        match extract::<DeriveKeypairBatchRequest>(&body).await {
+            Err(AppError::ValidationError(message)) => assert!(message.starts_with("Unknown field `account`"), "{}", message),
+            other => panic!("expected an unknown field error, got {:?}", other.map(|request| request.count)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_keypair_handler_validation() {
+        let invalid_request = ImportKeypairRequest {
+            keypair: vec![0u8; 10],
+        };
+
+        let result = import_keypair_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_health_handler_reports_cluster() {
+        let Json(response) = health_handler(test_state()).await;
+        assert_eq!(response.data.status, "ok");
+        assert_eq!(response.data.cluster, Some(crate::config::Cluster::Devnet));
+    }
+
+    #[tokio::test]
+    async fn test_deep_health_handler() {
+        let mut config = Config::default();
+        config.rpc.enabled = false;
+        let (status, Json(response)) = deep_health_handler(state_for(config)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((response.data.status.as_str(), response.data.rpc.as_str(), response.data.slot), ("ok", "disabled", None));
+
+        // Nothing listens on port 1, so the node is unreachable
+        let mut config = Config::default();
+        config.rpc.url = "http://127.0.0.1:1".to_string();
+        config.rpc.retry.max_retries = 0;
+        let (status, Json(response)) = deep_health_handler(state_for(config)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!response.success);
+        assert_eq!((response.data.status.as_str(), response.data.rpc.as_str(), response.data.slot), ("degraded", "degraded", None));
+        assert!(response.data.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_version_handler_reports_locked_versions() {
+        let Json(response) = version_handler().await;
+        assert_eq!(response.data.version, env!("CARGO_PKG_VERSION"));
+        assert!(response.data.solana_sdk.starts_with("1."), "{}", response.data.solana_sdk);
+        assert!(response.data.spl_token.starts_with("4."), "{}", response.data.spl_token);
+    }
+
+    #[tokio::test]
+    async fn test_funded_keypair_handler_validation() {
+        let invalid_request = FundedKeypairRequest { sol: 0.0 };
+
+        let result = funded_keypair_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_token_accounts_handler_validation() {
+        let invalid_request = TokenAccountsRequest {
+            owner: "11111111111111111111111111111112".to_string(),
+            mint: Some("not-a-mint".to_string()),
+            offset: 0,
+            limit: None,
+            commitment: None,
+        };
+
+        let result = token_accounts_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+
+        // The page size is checked before the node is asked
+        let invalid_request = TokenAccountsRequest {
+            owner: "11111111111111111111111111111112".to_string(),
+            mint: None,
+            offset: 0,
+            limit: Some(validation::MAX_PAGE_LIMIT + 1),
+            commitment: None,
+        };
+        let result = token_accounts_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "limit must be between 1 and 500"));
+    }
+
+    #[tokio::test]
+    async fn test_account_info_handler_validation() {
+        let invalid_request = AccountInfoRequest {
+            pubkey: "11111111111111111111111111111112".to_string(),
+            encoding: "base58".to_string(),
+            commitment: None,
+        };
+
+        let result = account_info_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_mint_info_handler_validation() {
+        let invalid_request = MintInfoRequest {
+            mint: "".to_string(),
+            commitment: None,
+        };
+
+        let result = mint_info_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let invalid_commitment = MintInfoRequest {
+            mint: "11111111111111111111111111111112".to_string(),
+            commitment: Some("recent".to_string()),
+        };
+        let result = mint_info_handler(test_state(), JsonExtractor(invalid_commitment)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("commitment")));
+    }
+
+    #[tokio::test]
+    async fn test_token_account_info_handler_validation() {
+        let invalid_request = TokenAccountInfoRequest {
+            account: "invalid".to_string(),
+            commitment: None,
+        };
+
+        let result = token_account_info_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rent_handler_validation() {
+        let invalid_request = RentRequest {
+            data_len: u64::MAX,
+            commitment: None,
+        };
+
+        let result = rent_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_handler_validation() {
+        let invalid_request = SendTransactionRequest {
+            transaction: "not a transaction".to_string(),
+        };
+
+        let result = send_transaction_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::DeserializationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_airdrop_handler_validation() {
+        let invalid_request = AirdropRequest {
+            pubkey: Pubkey::new_unique().to_string(),
+            sol: 0.0,
+        };
+
+        let result = airdrop_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_transaction_handler_validation() {
+        use crate::models::SimulateTransactionRequest;
+        use solana_sdk::{message::Message, transaction::Transaction};
+        use base64::{Engine as _, engine::general_purpose};
+
+        let payer = Pubkey::new_unique();
+        let instruction = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let unsigned = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+        let unsigned = general_purpose::STANDARD.encode(bincode::serialize(&unsigned).unwrap());
+        let request = |sig_verify, replace_recent_blockhash, commitment: Option<&str>| SimulateTransactionRequest {
+            transaction: unsigned.clone(),
+            sig_verify,
+            replace_recent_blockhash,
+            commitment: commitment.map(str::to_string),
+        };
+
+        for invalid in [
+            request(true, true, None),
+            // Signatures are only required when they are verified
+            request(true, false, None),
+            request(false, true, Some("max")),
+            SimulateTransactionRequest { transaction: "not base64!".to_string(), ..request(false, false, None) },
+        ] {
+            let result = simulate_transaction_handler(test_state(), JsonExtractor(invalid)).await;
+            assert!(matches!(result, Err(AppError::ValidationError(_) | AppError::DeserializationError(_))), "{:?}", result.err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signature_status_handler_validation() {
+        use crate::models::SignatureStatusRequest;
+
+        for signature in ["", "not-base58!", &Pubkey::new_unique().to_string()] {
+            let request = SignatureStatusRequest { signature: signature.to_string() };
+            let result = signature_status_handler(test_state(), JsonExtractor(request)).await;
+            assert!(matches!(result, Err(AppError::ValidationError(_) | AppError::InvalidSignature(_))), "{:?}", signature);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mint_batch_handler_validation() {
+        use crate::models::MintRecipient;
+
+        let request = |destination: &str, amount| MintBatchRequest {
+            mint: Pubkey::new_unique().to_string(),
+            authority: Pubkey::new_unique().to_string(),
+            recipients: vec![
+                MintRecipient { destination: Pubkey::new_unique().to_string(), amount: 10 },
+                MintRecipient { destination: destination.to_string(), amount },
+            ],
+            fee_payer: None,
+            recent_blockhash: None,
+        };
+
+        let result = mint_batch_handler(test_state(), JsonExtractor(request("bad", 10))).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(message)) if message.contains("recipients[1].destination")));
+
+        let result = mint_batch_handler(test_state(), JsonExtractor(request(&Pubkey::new_unique().to_string(), 0))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "recipients[1].amount must be greater than 0"));
+
+        let mut empty = request(&Pubkey::new_unique().to_string(), 10);
+        empty.recipients.clear();
+        assert!(matches!(mint_batch_handler(test_state(), JsonExtractor(empty)).await, Err(AppError::ValidationError(_))));
+
+        let mut bundled = request(&Pubkey::new_unique().to_string(), 10);
+        bundled.fee_payer = Some(Pubkey::new_unique().to_string());
+        let response = mint_batch_handler(test_state(), JsonExtractor(bundled)).await.unwrap();
+        assert_eq!(response.0.data.instructions.len(), 2);
+        assert_eq!(response.0.data.messages.as_ref().map(Vec::len), Some(1));
+
+        let mut bad_fee_payer = request(&Pubkey::new_unique().to_string(), 10);
+        bad_fee_payer.fee_payer = Some("bad".to_string());
+        let result = mint_batch_handler(test_state(), JsonExtractor(bad_fee_payer)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(message)) if message.contains("fee_payer")));
+    }
+
+    #[tokio::test]
+    async fn test_mint_batch_fee_payer_is_first_account() {
+        use base64::{Engine as _, engine::general_purpose};
+        use solana_sdk::message::Message;
+
+        let [mint, authority, destination, fee_payer] = [(); 4].map(|_| Pubkey::new_unique());
+        // `payer` is still accepted for the fee payer
+        for key in ["fee_payer", "feePayer", "payer"] {
+            let request: MintBatchRequest = serde_json::from_value(serde_json::json!({
+                "mint": mint.to_string(),
+                "authority": authority.to_string(),
+                "recipients": [{ "destination": destination.to_string(), "amount": 10 }],
+                key: fee_payer.to_string(),
+            }))
+            .unwrap();
+            let Json(response) = mint_batch_handler(test_state(), JsonExtractor(request)).await.unwrap();
+            let messages = response.data.messages.unwrap();
+            let message: Message = bincode::deserialize(&general_purpose::STANDARD.decode(&messages[0]).unwrap()).unwrap();
+
+            // The fee payer comes first and signs, ahead of the authority that signs the mint_to
+            assert_eq!(message.account_keys[0], fee_payer, "{}", key);
+            assert_eq!(message.account_keys[1], authority);
+            assert_eq!(message.header.num_required_signatures, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_handler_validation() {
+        use base64::Engine as _;
+
+        let payer = Pubkey::new_unique();
+        let instruction = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let message = solana_sdk::message::Message::new(&[instruction], Some(&payer));
+        let request = |secrets: Vec<String>| SignTransactionRequest {
+            message: base64::engine::general_purpose::STANDARD.encode(message.serialize()),
+            secrets,
+        };
+
+        let result = sign_transaction_handler(test_state(), JsonExtractor(request(vec![]))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "secrets must not be empty"));
+
+        let result = sign_transaction_handler(test_state(), JsonExtractor(request(vec!["not-a-secret".to_string()]))).await;
+        assert!(matches!(result, Err(AppError::InvalidSecretKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_records_key_operations_without_secrets() {
+        use base64::{Engine as _, engine::general_purpose};
+        use crate::utils::audit::AuditLog;
+        use serde_json::json;
+
+        let (audit, buffer) = AuditLog::in_memory();
+        let mut app_state = AppState::new(Arc::new(Config::default()));
+        app_state.audit = Arc::new(audit);
+        let state = || State(app_state.clone());
+
+        let generated = generate_keypair_handler(state()).await.unwrap().0.data;
+        let seed = general_purpose::STANDARD.encode([7u8; 32]);
+        let derived = keypair_from_seed_handler(state(), JsonExtractor(KeypairFromSeedRequest { seed: seed.clone() })).await.unwrap().0.data;
+        let signed = sign_message_handler(state(), JsonExtractor(SignMessageRequest {
+            message: "hello".to_string(),
+            secret: generated.secret.clone(),
+            prefix: None,
+            allow_empty: false,
+        })).await.unwrap().0.data;
+        let verified = verify_message_handler(state(), JsonExtractor(VerifyMessageRequest {
+            message: "tampered".to_string(),
+            signature: signed.signature.clone(),
+            pubkey: generated.pubkey.clone(),
+            prefix: None,
+            public_key_encoding: None,
+            signature_encoding: None,
+            strict: false,
+            allow_empty: false,
+        })).await.unwrap().0.data;
+        assert!(!verified.valid);
+
+        let payer = Pubkey::from_str(&derived.pubkey).unwrap();
+        let instruction = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let message = solana_sdk::message::Message::new(&[instruction], Some(&payer));
+        let signed_transaction = sign_transaction_handler(state(), JsonExtractor(SignTransactionRequest {
+            message: general_purpose::STANDARD.encode(message.serialize()),
+            secrets: vec![derived.secret.clone()],
+        })).await.unwrap().0.data;
+        assert!(!signed_transaction.signature.is_empty());
+        let new_mint = create_new_token_handler(state(), JsonExtractor(CreateNewTokenRequest {
+            mint_authority: generated.pubkey.clone(),
+            payer: None,
+            decimals: Some(6),
+            freeze_authority: None,
+            disable_freeze_authority: false,
+        })).await.unwrap().0.data;
+
+        let events: Vec<serde_json::Value> = {
+            let buffer = buffer.lock().unwrap();
+            let log = std::str::from_utf8(&buffer).unwrap();
+            for secret in [&generated.secret, &derived.secret, &new_mint.mint_secret, &seed] {
+                assert!(!log.contains(secret.as_str()), "audit log contains a secret: {}", log);
+            }
+            log.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+        };
+        let summary: Vec<(&str, &serde_json::Value, &str)> = events
+            .iter()
+            .map(|event| (event["operation"].as_str().unwrap(), &event["pubkeys"], event["result"].as_str().unwrap()))
+            .collect();
+        assert_eq!(summary, [
+            ("keypair.generate", &json!([generated.pubkey]), "success"),
+            ("keypair.from_seed", &json!([derived.pubkey]), "success"),
+            ("message.sign", &json!([generated.pubkey]), "success"),
+            ("message.verify", &json!([generated.pubkey]), "invalid"),
+            ("transaction.sign", &json!([derived.pubkey]), "success"),
+            ("keypair.generate_mint", &json!([new_mint.mint]), "success"),
+        ]);
+
+        // Failures are recorded with the status the client got
+        let result = sign_message_handler(state(), JsonExtractor(SignMessageRequest {
+            message: "hello".to_string(),
+            secret: bs58::encode([1u8; 64]).into_string(),
+            prefix: None,
+            allow_empty: false,
+        })).await;
+        assert!(result.is_err());
+        let buffer = buffer.lock().unwrap();
+        let last: serde_json::Value = serde_json::from_str(std::str::from_utf8(&buffer).unwrap().lines().last().unwrap()).unwrap();
+        assert_eq!((&last["operation"], &last["result"], &last["status"]), (&json!("message.sign"), &json!("failure"), &json!(400)));
+    }
+
+    #[tokio::test]
+    async fn test_partial_sign_transaction_handler_validation() {
+        let result = partial_sign_transaction_handler(test_state(), JsonExtractor(PartialSignTransactionRequest {
+            transaction: "not base64!".to_string(),
+            secrets: vec![],
+        })).await;
+        assert!(matches!(result, Err(AppError::DeserializationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_transaction_handler_validation() {
+        let result = verify_transaction_handler(test_state(), JsonExtractor(VerifyTransactionRequest {
+            transaction: "".to_string(),
+        })).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "transaction is required"));
+    }
+
+    #[tokio::test]
+    async fn test_create_token_handler_validation() {
+        let invalid_request = CreateTokenRequest {
+            mint_authority: "".to_string(),
+            mint: "".to_string(),
+            decimals: Some(9),
+            freeze_authority: None,
+            disable_freeze_authority: false,
+        };
+        
+        let result = create_token_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_token_handler_freeze_authority() {
+        let request = |freeze_authority, disable_freeze_authority| CreateTokenRequest {
+            mint_authority: "11111111111111111111111111111112".to_string(),
+            mint: "11111111111111111111111111111113".to_string(),
+            decimals: Some(6),
+            freeze_authority,
+            disable_freeze_authority,
+        };
+
+        let invalid = create_token_handler(test_state(), JsonExtractor(request(Some(Some("invalid".to_string())), false))).await;
+        assert!(matches!(invalid, Err(AppError::InvalidPublicKey(_))));
+        // The all-zeros address is the system program's; a mint needing it to freeze could never freeze
+        let zeros = create_token_handler(test_state(), JsonExtractor(request(Some(Some(Pubkey::default().to_string())), false))).await;
+        assert!(matches!(zeros, Err(AppError::ValidationError(message)) if message.starts_with("freezeAuthority can't be 11111111111111111111111111111111")));
+        let zeros = CreateTokenRequest { mint_authority: Pubkey::default().to_string(), ..request(None, false) };
+        let result = create_token_handler(test_state(), JsonExtractor(zeros)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("mintAuthority can't be")));
+
+        // Default keeps a freeze authority; null removes it (shorter instruction data)
+        let default = create_token_handler(test_state(), JsonExtractor(request(None, false))).await.unwrap();
+        let disabled = create_token_handler(test_state(), JsonExtractor(request(Some(None), false))).await.unwrap();
+        assert!(disabled.0.data.instruction_data.len() < default.0.data.instruction_data.len());
+
+        // The flag removes it too, alone or alongside an explicit null
+        for freeze_authority in [None, Some(None)] {
+            let flagged = create_token_handler(test_state(), JsonExtractor(request(freeze_authority, true))).await.unwrap();
+            assert_eq!(flagged.0.data.instruction_data, disabled.0.data.instruction_data);
+        }
+
+        // but contradicts an explicit freeze authority
+        let conflicting = request(Some(Some("11111111111111111111111111111114".to_string())), true);
+        let result = create_token_handler(test_state(), JsonExtractor(conflicting)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("disable_freeze_authority")));
+    }
+
+    #[tokio::test]
+    async fn test_create_token_handler_uses_configured_defaults() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let request = |decimals| CreateTokenRequest {
+            mint_authority: "11111111111111111111111111111112".to_string(),
+            mint: "11111111111111111111111111111113".to_string(),
+            decimals,
+            freeze_authority: None,
+            disable_freeze_authority: false,
+        };
+        // InitializeMint data: tag, decimals, mint authority, then COption<freeze authority>
+        let data = |response: Json<ApiResponse<TokenInstructionResponse>>| {
+            general_purpose::STANDARD.decode(&response.0.data.instruction_data).unwrap()
+        };
+
+        // Without defaults, decimals is required and the mint authority can freeze
+        let result = create_token_handler(test_state(), JsonExtractor(request(None))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "decimals is required"));
+        let data_with_freeze = data(create_token_handler(test_state(), JsonExtractor(request(Some(6)))).await.unwrap());
+        assert_eq!(data_with_freeze[34], 1);
+
+        let freeze_account = Pubkey::new_unique();
+        for (default_freeze_authority, freeze_authority) in [
+            (DefaultFreezeAuthority::None, None),
+            (DefaultFreezeAuthority::Account(freeze_account), Some(freeze_account)),
+        ] {
+            let state = || state_for(Config {
+                token: TokenPolicy { default_decimals: Some(2), default_freeze_authority, ..TokenPolicy::default() },
+                ..Config::default()
+            });
+            let defaulted = data(create_token_handler(state(), JsonExtractor(request(None))).await.unwrap());
+            assert_eq!(defaulted[1], 2);
+            match freeze_authority {
+                Some(account) => assert_eq!(defaulted[35..], account.to_bytes()),
+                None => assert_eq!(defaulted[34..], [0]),
+            }
+
+            // A request's own decimals still win, and are checked against the maximum
+            let explicit = data(create_token_handler(state(), JsonExtractor(request(Some(6)))).await.unwrap());
+            assert_eq!(explicit[1], 6);
+            let result = create_token_handler(state(), JsonExtractor(request(Some(10)))).await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_new_token_handler() {
+        let mint_authority = "11111111111111111111111111111112";
+        let request = |payer: Option<&str>| CreateNewTokenRequest {
+            mint_authority: mint_authority.to_string(),
+            payer: payer.map(str::to_string),
+            decimals: Some(6),
+            freeze_authority: None,
+            disable_freeze_authority: false,
+        };
+
+        // The mint authority pays unless a payer is given
+        let response = create_new_token_handler(test_state(), JsonExtractor(request(None))).await.unwrap();
+        assert_eq!(response.0.data.instructions[0].accounts[0].pubkey, mint_authority);
+        assert_eq!(response.0.data.instructions[0].accounts[1].pubkey, response.0.data.mint);
+        let payer = "11111111111111111111111111111114";
+        let response = create_new_token_handler(test_state(), JsonExtractor(request(Some(payer)))).await.unwrap();
+        assert_eq!(response.0.data.instructions[0].accounts[0].pubkey, payer);
+
+        let result = create_new_token_handler(test_state(), JsonExtractor(request(Some("invalid")))).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+        let mut too_precise = request(None);
+        too_precise.decimals = Some(10);
+        assert!(matches!(
+            create_new_token_handler(test_state(), JsonExtractor(too_precise)).await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mint_token_handler_validation() {
+        let invalid_request = MintTokenRequest {
+            mint: "".to_string(),
+            destination: "".to_string(),
+            authority: "".to_string(),
+            amount: 0,
+        };
+        
+        let result = mint_token_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_burn_close_handler_validation() {
+        let key = "11111111111111111111111111111112".to_string();
+        let invalid_request = BurnCloseRequest {
+            mint: key.clone(),
+            account: key.clone(),
+            authority: key.clone(),
+            destination: "invalid".to_string(),
+            amount: 1,
+        };
+        let result = burn_close_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+
+        let zero_amount = BurnCloseRequest {
+            mint: key.clone(),
+            account: key.clone(),
+            authority: key.clone(),
+            destination: key,
+            amount: 0,
+        };
+        let result = burn_close_handler(test_state(), JsonExtractor(zero_amount)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_wrap_and_unwrap_sol_handler_validation() {
+        let key = "11111111111111111111111111111112".to_string();
+        let zero_lamports = WrapSolRequest { owner: key.clone(), lamports: 0 };
+        let result = wrap_sol_handler(test_state(), JsonExtractor(zero_lamports)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let invalid_account = UnwrapSolRequest { owner: key, account: Some("invalid".to_string()) };
+        let result = unwrap_sol_handler(test_state(), JsonExtractor(invalid_account)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stake_handlers_validation() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let [stake_account, wallet, vote] = [(); 3].map(|_| Pubkey::new_unique().to_string());
+        let create = |from: &str, lamports| CreateStakeAccountRequest {
+            from: from.to_string(),
+            stake_account: stake_account.clone(),
+            lamports,
+            staker: None,
+            withdrawer: None,
+        };
+
+        let Json(created) = create_stake_account_handler(test_state(), JsonExtractor(create(&wallet, 1_000_000_000))).await.unwrap();
+        assert_eq!(created.data.lamports, 1_000_000_000 + created.data.rent_exempt_lamports);
+        // The staker and withdrawer default to the funding wallet; Initialize data is the tag,
+        // then the staker and withdrawer
+        let initialize = general_purpose::STANDARD.decode(&created.data.instructions[1].instruction_data).unwrap();
+        let wallet_bytes = Pubkey::from_str(&wallet).unwrap().to_bytes();
+        assert_eq!(initialize[4..36], wallet_bytes);
+        assert_eq!(initialize[36..68], wallet_bytes);
+
+        let result = create_stake_account_handler(test_state(), JsonExtractor(create(&wallet, 0))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        let result = create_stake_account_handler(test_state(), JsonExtractor(create(&stake_account, 1))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "from must differ from stake_account"));
+
+        let delegate = |vote_account: &str| DelegateStakeRequest {
+            stake_account: stake_account.clone(),
+            vote_account: vote_account.to_string(),
+            staker: wallet.clone(),
+        };
+        assert!(delegate_stake_handler(test_state(), JsonExtractor(delegate(&vote))).await.is_ok());
+        let result = delegate_stake_handler(test_state(), JsonExtractor(delegate("invalid"))).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+        let result = delegate_stake_handler(test_state(), JsonExtractor(delegate(&stake_account))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("vote_account")));
+
+        let deactivate = DeactivateStakeRequest { stake_account: stake_account.clone(), staker: String::new() };
+        assert!(deactivate_stake_handler(test_state(), JsonExtractor(deactivate)).await.is_err());
+
+        let withdraw = |destination: &str, lamports| WithdrawStakeRequest {
+            stake_account: stake_account.clone(),
+            withdrawer: wallet.clone(),
+            destination: destination.to_string(),
+            lamports,
+        };
+        assert!(withdraw_stake_handler(test_state(), JsonExtractor(withdraw(&wallet, 1))).await.is_ok());
+        assert!(withdraw_stake_handler(test_state(), JsonExtractor(withdraw(&wallet, 0))).await.is_err());
+        assert!(withdraw_stake_handler(test_state(), JsonExtractor(withdraw(&stake_account, 1))).await.is_err());
+
+        // No key signs for the all-zeros address, so a stake account it withdraws from would be locked for good
+        let zeros = Pubkey::default().to_string();
+        let rejects_zeros = |result: Result<_>, field: &str| {
+            assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with(&format!("{} can't be {}", field, zeros))));
+        };
+        let mut request = create(&wallet, 1);
+        request.withdrawer = Some(zeros.clone());
+        rejects_zeros(create_stake_account_handler(test_state(), JsonExtractor(request)).await.map(|_| ()), "withdrawer");
+        let mut request = create(&wallet, 1);
+        request.staker = Some(zeros.clone());
+        rejects_zeros(create_stake_account_handler(test_state(), JsonExtractor(request)).await.map(|_| ()), "staker");
+        let request = DelegateStakeRequest { staker: zeros.clone(), ..delegate(&vote) };
+        rejects_zeros(delegate_stake_handler(test_state(), JsonExtractor(request)).await.map(|_| ()), "staker");
+        let request = DeactivateStakeRequest { stake_account: stake_account.clone(), staker: zeros.clone() };
+        rejects_zeros(deactivate_stake_handler(test_state(), JsonExtractor(request)).await.map(|_| ()), "staker");
+        let request = WithdrawStakeRequest { withdrawer: zeros.clone(), ..withdraw(&wallet, 1) };
+        rejects_zeros(withdraw_stake_handler(test_state(), JsonExtractor(request)).await.map(|_| ()), "withdrawer");
+    }
+
+    #[tokio::test]
+    async fn test_transfer_fee_handlers() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let [mint, authority, owner, destination] = [(); 4].map(|_| Pubkey::new_unique().to_string());
+        let create = |transfer_fee_basis_points, maximum_fee| CreateTransferFeeMintRequest {
+            mint_authority: authority.clone(),
+            mint: mint.clone(),
+            decimals: 6,
+            freeze_authority: None,
+            transfer_fee_basis_points,
+            maximum_fee,
+            transfer_fee_config_authority: None,
+            withdraw_withheld_authority: None,
+        };
+
+        let Json(created) = create_transfer_fee_mint_handler(test_state(), JsonExtractor(create(50, 5_000))).await.unwrap();
+        // The fee authorities default to the mint authority: the config data is the two tags,
+        // then each authority as an option flag and pubkey
+        let config = general_purpose::STANDARD.decode(&created.data.instructions[0].instruction_data).unwrap();
+        let authority_bytes = Pubkey::from_str(&authority).unwrap().to_bytes();
+        assert_eq!(config[2], 1);
+        assert_eq!(config[3..35], authority_bytes);
+        assert_eq!(config[36..68], authority_bytes);
+
+        let result = create_transfer_fee_mint_handler(test_state(), JsonExtractor(create(10_001, 5_000))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("transfer_fee_basis_points")));
+        let result = create_transfer_fee_mint_handler(test_state(), JsonExtractor(create(50, 0))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("maximum_fee")));
+
+        let transfer = |fee, transfer_fee_basis_points, maximum_fee| TransferWithFeeRequest {
+            destination: destination.clone(),
+            mint: mint.clone(),
+            owner: owner.clone(),
+            amount: 10_000,
+            decimals: 6,
+            fee,
+            transfer_fee_basis_points,
+            maximum_fee,
+        };
+        let Json(computed) = transfer_with_fee_handler(test_state(), JsonExtractor(transfer(None, Some(50), Some(30)))).await.unwrap();
+        assert_eq!(computed.data.fee, 30, "0.5% of 10_000 is capped at maximum_fee");
+        let Json(given) = transfer_with_fee_handler(test_state(), JsonExtractor(transfer(Some(7), None, None))).await.unwrap();
+        assert_eq!(given.data.fee, 7);
+
+        for (fee, basis_points, maximum_fee) in [(None, None, None), (Some(7), Some(50), Some(30)), (None, Some(50), None)] {
+            let result = transfer_with_fee_handler(test_state(), JsonExtractor(transfer(fee, basis_points, maximum_fee))).await;
+            assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("give either fee")));
+        }
+        let result = transfer_with_fee_handler(test_state(), JsonExtractor(transfer(Some(10_001), None, None))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "fee must be at most amount (10000), got 10001"));
+
+        let zeros = TransferWithFeeRequest { owner: Pubkey::default().to_string(), ..transfer(Some(7), None, None) };
+        let result = transfer_with_fee_handler(test_state(), JsonExtractor(zeros)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("owner can't be")));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_pubkey_handler() {
+        let pubkey = Pubkey::from([0x50; 32]);
+        let hex: String = pubkey.to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        let normalize = |pubkey: String, public_key_encoding: Option<&str>| {
+            normalize_pubkey_handler(
+                test_state(),
+                JsonExtractor(NormalizePubkeyRequest { pubkey, public_key_encoding: public_key_encoding.map(str::to_string) }),
+            )
+        };
+
+        let Json(response) = normalize(hex.clone(), None).await.unwrap();
+        let data = response.data;
+        assert_eq!((data.pubkey, data.input_encoding), (pubkey.to_string(), "hex".to_string()));
+        assert_eq!(data.hex, hex);
+        assert_eq!(validation::decode_base64(&data.base64).unwrap(), pubkey.to_bytes());
+
+        let result = normalize(hex[..62].to_string(), None).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "pubkey must be exactly 32 bytes, got 31 as hex"));
+
+        // A named encoding is the only one tried: hex text read as base64 is 48 bytes
+        let Json(response) = normalize(data.base64.clone(), Some("base64")).await.unwrap();
+        assert_eq!(response.data.pubkey, pubkey.to_string());
+        let result = normalize(hex, Some("base64")).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "pubkey must be exactly 32 bytes, got 48"));
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata_handlers() {
+        let [mint, authority] = [(); 2].map(|_| Pubkey::new_unique().to_string());
+        let initialize = |name: &str, symbol: &str| InitializeTokenMetadataRequest {
+            mint: mint.clone(),
+            mint_authority: authority.clone(),
+            update_authority: None,
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: "https://example.com/token.json".to_string(),
+        };
+
+        // The update authority defaults to the mint authority
+        let Json(response) = initialize_token_metadata_handler(test_state(), JsonExtractor(initialize("Token", "TKN"))).await.unwrap();
+        assert_eq!(response.data.accounts[1].pubkey, authority);
+        let result = initialize_token_metadata_handler(test_state(), JsonExtractor(initialize("", "TKN"))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        let result = initialize_token_metadata_handler(test_state(), JsonExtractor(initialize("Token", "TOOLONGSYMBOL"))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("symbol must be at most 10 bytes")));
+
+        let pointer = InitializeMetadataPointerRequest { mint: mint.clone(), authority: None, metadata_address: Some("invalid".to_string()) };
+        let result = metadata_pointer_handler(test_state(), JsonExtractor(pointer)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+
+        let update = |field: &str, value: String| UpdateTokenMetadataRequest {
+            mint: mint.clone(),
+            update_authority: authority.clone(),
+            field: field.to_string(),
+            value,
+        };
+        assert!(update_token_metadata_handler(test_state(), JsonExtractor(update("uri", "https://example.com/v2.json".to_string()))).await.is_ok());
+        let result = update_token_metadata_handler(test_state(), JsonExtractor(update("uri", "u".repeat(201)))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "uri must be at most 200 bytes, got 201"));
+    }
+
+    #[tokio::test]
+    async fn test_relay_handler_validation() {
+        use base64::{Engine as _, engine::general_purpose};
+        use solana_sdk::{message::Message, signature::Keypair, system_instruction, transaction::Transaction};
+
+        let user = Keypair::new();
+        let relayer = Arc::new(Keypair::new());
+        let request = |fee_payer: &Pubkey| {
+            let transfer = system_instruction::transfer(&user.pubkey(), &Pubkey::new_unique(), 1);
+            let mut transaction = Transaction::new_unsigned(Message::new(&[transfer], Some(fee_payer)));
+            transaction.partial_sign(&[&user], Hash::new_unique());
+            RelayTransactionRequest { transaction: general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap()) }
+        };
+
+        let result = relay_handler(test_state(), JsonExtractor(request(&relayer.pubkey()))).await;
+        assert!(matches!(result, Err(AppError::ServiceUnavailable(message)) if message.starts_with("Relaying is not configured")));
+
+        let mut config = Config::default();
+        config.relay.keypair = Some(relayer.clone());
+        let result = relay_handler(state_for(config.clone()), JsonExtractor(request(&user.pubkey()))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("Transaction fee payer must be the relayer")));
+
+        config.relay.allowed_programs = vec![spl_memo::id()];
+        let result = relay_handler(state_for(config), JsonExtractor(request(&relayer.pubkey()))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("which is not allowed for relaying")));
+    }
+
+    #[tokio::test]
+    async fn test_allowed_programs_handlers() {
+        use base64::{Engine as _, engine::general_purpose};
+        use solana_sdk::{message::Message, signature::Keypair, system_instruction, transaction::Transaction};
+
+        let config = Config { allowed_programs: vec![spl_memo::id()], ..Config::default() };
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let message = Message::new(&[transfer], Some(&payer.pubkey()));
+        let rejected = |error: Option<AppError>| {
+            matches!(error, Some(AppError::ValidationError(message)) if message.contains("is not in this server's allowed_programs"))
+        };
+
+        let request = SignTransactionRequest {
+            message: general_purpose::STANDARD.encode(message.serialize()),
+            secrets: vec![bs58::encode(payer.to_bytes()).into_string()],
+        };
+        assert!(sign_transaction_handler(test_state(), JsonExtractor(request.clone())).await.is_ok());
+        assert!(rejected(sign_transaction_handler(state_for(config.clone()), JsonExtractor(request)).await.err()));
+
+        let request = PartialSignTransactionRequest {
+            transaction: general_purpose::STANDARD.encode(bincode::serialize(&Transaction::new_unsigned(message.clone())).unwrap()),
+            secrets: vec![bs58::encode(payer.to_bytes()).into_string()],
+        };
+        assert!(rejected(partial_sign_transaction_handler(state_for(config.clone()), JsonExtractor(request)).await.err()));
+
+        let request: MintBatchRequest = serde_json::from_value(serde_json::json!({
+            "mint": Pubkey::new_unique().to_string(),
+            "authority": Pubkey::new_unique().to_string(),
+            "recipients": [{ "destination": Pubkey::new_unique().to_string(), "amount": 10 }],
+        }))
+        .unwrap();
+        assert!(rejected(mint_batch_handler(state_for(config.clone()), JsonExtractor(request)).await.err()));
+
+        // The relay checks both its own list and the server-wide one
+        let relayer = Arc::new(Keypair::new());
+        let mut config = config;
+        config.relay.keypair = Some(relayer.clone());
+        let mut transaction = Transaction::new_unsigned(Message::new(
+            &[system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+            Some(&relayer.pubkey()),
+        ));
+        transaction.partial_sign(&[&payer], Hash::new_unique());
+        let request = RelayTransactionRequest { transaction: general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap()) };
+        assert!(rejected(relay_handler(state_for(config), JsonExtractor(request)).await.err()));
+    }
+
+    #[tokio::test]
+    async fn test_close_mint_handlers() {
+        let [mint, authority, close_authority] = [(); 3].map(|_| Pubkey::new_unique().to_string());
+        let create = |close_authority: Option<&str>| CreateCloseableMintRequest {
+            mint_authority: authority.clone(),
+            mint: mint.clone(),
+            decimals: 6,
+            freeze_authority: None,
+            close_authority: close_authority.map(str::to_string),
+        };
+
+        let Json(created) = create_closeable_mint_handler(test_state(), JsonExtractor(create(None))).await.unwrap();
+        assert_eq!(created.data.instructions.len(), 2);
+        // Without a close authority the mint authority is used
+        let Json(explicit) = create_closeable_mint_handler(test_state(), JsonExtractor(create(Some(&authority)))).await.unwrap();
+        assert_eq!(explicit.data.instructions[0].instruction_data, created.data.instructions[0].instruction_data);
+
+        let result = create_closeable_mint_handler(test_state(), JsonExtractor(create(Some("bad")))).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(message)) if message.contains("close_authority")));
+        let result = create_closeable_mint_handler(test_state(), JsonExtractor(create(Some(&mint)))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "close_authority must differ from mint"));
+
+        let close = |destination: &str, close_authority: &str| CloseMintRequest {
+            mint: mint.clone(),
+            destination: destination.to_string(),
+            close_authority: close_authority.to_string(),
+        };
+        let Json(closed) = close_mint_handler(test_state(), JsonExtractor(close(&authority, &close_authority))).await.unwrap();
+        assert_eq!(closed.data.program_id, spl_token_2022::id().to_string());
+        assert!(closed.data.accounts[2].is_signer);
+
+        let result = close_mint_handler(test_state(), JsonExtractor(close(&mint, &close_authority))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "destination must differ from the mint being closed"));
+        let result = close_mint_handler(test_state(), JsonExtractor(close(&authority, ""))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_) | AppError::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_derive_pda_batch_handler() {
+        let program_id = Pubkey::new_unique();
+        let entry = |program_id: &str, seeds: &[&str]| DerivePdaRequest {
+            program_id: program_id.to_string(),
+            seeds: seeds.iter().map(|seed| seed.to_string()).collect(),
+        };
+        let request = DerivePdaBatchRequest {
+            entries: vec![
+                entry(&program_id.to_string(), &["vault", "hex:01"]),
+                entry("not-a-key", &["vault"]),
+                entry(&program_id.to_string(), &["hex:zz"]),
+                entry(&program_id.to_string(), &[]),
+            ],
+        };
+
+        let Json(response) = derive_pda_batch_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        let results = response.data.results;
+        assert_eq!(results.len(), 4);
+        // Each entry matches what /pda/derive returns for it alone
+        let single = test_state().solana.derive_pda(&program_id, &[b"vault".to_vec(), vec![1]]).unwrap();
+        assert_eq!((results[0].address.clone(), results[0].bump), (Some(single.address), Some(single.bump)));
+        assert!(results[1].error.as_deref().unwrap().contains("program_id"));
+        assert_eq!(results[2].error.as_deref(), Some("seeds[0] is not valid hex"));
+        assert!(results[3].address.is_some() && results[3].error.is_none());
+
+        let result = derive_pda_batch_handler(test_state(), JsonExtractor(DerivePdaBatchRequest { entries: Vec::new() })).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "entries must not be empty"));
+    }
+
+    #[tokio::test]
+    async fn test_associated_token_address_batch_handler() {
+        let (wallet, mints) = (Pubkey::new_unique(), [Pubkey::new_unique(), Pubkey::new_unique()]);
+        let request = |mints: Vec<String>, token_program: Option<String>| AssociatedTokenAddressBatchRequest {
+            wallet: wallet.to_string(),
+            mints,
+            token_program,
+        };
+
+        let response = associated_token_address_batch_handler(
+            test_state(),
+            JsonExtractor(request(mints.iter().map(Pubkey::to_string).collect(), Some(spl_token_2022::id().to_string()))),
+        )
+        .await
+        .unwrap()
+        .0
+        .data;
+        assert_eq!(response.wallet, wallet.to_string());
+        // Each account matches what /token/ata returns for its mint alone
+        for (account, mint) in response.accounts.iter().zip(&mints) {
+            assert_eq!(account.mint, mint.to_string());
+            assert_eq!(account.address, test_state().solana.derive_ata(&wallet, mint, &spl_token_2022::id()).address);
+        }
+
+        let result = associated_token_address_batch_handler(
+            test_state(),
+            JsonExtractor(request(vec![mints[0].to_string(), "not-a-key".to_string()], None)),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(message)) if message.contains("mints[1]")));
+        let result = associated_token_address_batch_handler(test_state(), JsonExtractor(request(Vec::new(), None))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "mints must not be empty"));
+    }
+
+    #[tokio::test]
+    async fn test_auth_challenge_flow() {
+        let state = test_state();
+        let wallet = state.solana.generate_keypair().unwrap();
+        let Json(challenge) = auth_challenge_handler(state.clone()).await.unwrap();
+        assert_eq!(challenge.data.expires_in_secs, 300);
+        let nonce = challenge.data.nonce;
+
+        let request = |signature: &str| AuthVerifyRequest {
+            pubkey: wallet.pubkey.clone(),
+            signature: signature.to_string(),
+            nonce: nonce.clone(),
+        };
+        // A signature of something else is rejected without using up the nonce
+        let wrong = state.solana.sign_message("not the nonce", None, &wallet.secret).unwrap().signature;
+        let result = auth_verify_handler(state.clone(), JsonExtractor(request(&wrong))).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(message)) if message.ends_with("signature of the nonce")));
+
+        let signature = state.solana.sign_message(&nonce, None, &wallet.secret).unwrap().signature;
+        let Json(verified) = auth_verify_handler(state.clone(), JsonExtractor(request(&signature))).await.unwrap();
+        assert!(verified.data.verified);
+        assert_eq!(verified.data.pubkey, wallet.pubkey);
+
+        // The nonce can't be replayed, and other servers' nonces aren't accepted
+        let result = auth_verify_handler(state.clone(), JsonExtractor(request(&signature))).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(message)) if message.contains("already been used")));
+        let result = auth_verify_handler(test_state(), JsonExtractor(request(&signature))).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let expiring = state_for(Config { challenge_ttl: std::time::Duration::ZERO, ..Config::default() });
+        let Json(challenge) = auth_challenge_handler(expiring.clone()).await.unwrap();
+        let signature = state.solana.sign_message(&challenge.data.nonce, None, &wallet.secret).unwrap().signature;
+        let result = auth_verify_handler(expiring, JsonExtractor(AuthVerifyRequest { nonce: challenge.data.nonce, ..request(&signature) })).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(message)) if message.starts_with("nonce has expired")));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_keypair_handler() {
+        let old_secret = test_state().solana.generate_keypair().unwrap().secret.clone();
+        let new_secret = test_state().solana.generate_keypair().unwrap().secret.clone();
+        let signature = test_state().solana.sign_message("attestation", None, &old_secret).unwrap().signature;
+        let request = |signature: &str, new_secret: &str| RotateKeypairRequest {
+            old_secret: old_secret.clone(),
+            new_secret: new_secret.to_string(),
+            messages: vec![SignedMessage { message: "attestation".to_string(), signature: signature.to_string() }],
+            prefix: None,
+        };
+
+        let Json(response) = rotate_keypair_handler(test_state(), JsonExtractor(request(&signature, &new_secret))).await.unwrap();
+        let Json(verified) = verify_message_handler(test_state(), JsonExtractor(VerifyMessageRequest {
+            message: "attestation".to_string(),
+            signature: response.data.signatures[0].clone(),
+            pubkey: response.data.new_pubkey.clone(),
+            prefix: None,
+            public_key_encoding: None,
+            signature_encoding: None,
+            strict: false,
+            allow_empty: false,
+        })).await.unwrap();
+        assert!(verified.data.valid);
+
+        let result = rotate_keypair_handler(test_state(), JsonExtractor(request("bad", &new_secret))).await;
+        assert!(matches!(result, Err(AppError::InvalidSignature(_))));
+        let result = rotate_keypair_handler(test_state(), JsonExtractor(request(&signature, "bad"))).await;
+        assert!(matches!(result, Err(AppError::InvalidSecretKey(_))));
+
+        // A signature by the new key is not one by the old key
+        let Json(wrong_key) = rotate_keypair_handler(test_state(), JsonExtractor(request(&signature, &new_secret))).await.unwrap();
+        let result = rotate_keypair_handler(test_state(), JsonExtractor(request(&wrong_key.data.signatures[0], &new_secret))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("messages[0].signature is not")));
+    }
+
+    #[tokio::test]
+    async fn test_sync_native_handler() {
+        let invalid_request = SyncNativeRequest { account: "invalid".to_string() };
+        let result = sync_native_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+
+        let request = SyncNativeRequest { account: "11111111111111111111111111111112".to_string() };
+        let Json(response) = sync_native_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert_eq!(response.data.instruction_data, "EQ==");
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_handler_validation() {
+        let invalid_request = SignMessageRequest {
+            message: "".to_string(),
+            secret: "".to_string(),
+            prefix: None,
+            allow_empty: false,
+        };
+        
+        let result = sign_message_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_message_prefix_handlers() {
+        let secret = test_state().solana.generate_keypair().unwrap().secret.clone();
+        let request: SignMessageRequest = extract(&format!(
+            r#"{{"message":"hello","secret":"{}","prefix":"example.com wants you to sign in:\n"}}"#,
+            secret
+        ))
+        .await
+        .unwrap();
+        let Json(signed) = sign_message_handler(test_state(), JsonExtractor(request)).await.unwrap();
+
+        let verify = |prefix: &str| {
+            format!(
+                r#"{{"message":"hello","signature":"{}","pubkey":"{}"{}}}"#,
+                signed.data.signature, signed.data.pubkey, prefix
+            )
+        };
+        let request: VerifyMessageRequest = extract(&verify(r#","prefix":"example.com wants you to sign in:\n""#)).await.unwrap();
+        let Json(response) = verify_message_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert!(response.data.valid);
+
+        let request: VerifyMessageRequest = extract(&verify("")).await.unwrap();
+        let Json(response) = verify_message_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert!(!response.data.valid);
+
+        let request: VerifyMessageRequest = extract(&verify(r#","prefix":"""#)).await.unwrap();
+        let result = verify_message_handler(test_state(), JsonExtractor(request)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_handler_empty_and_blank_messages() {
+        let secret = test_state().solana.generate_keypair().unwrap().secret.clone();
+        let sign = |message: &str, allow_empty| {
+            sign_message_handler(
+                test_state(),
+                JsonExtractor(SignMessageRequest { message: message.to_string(), secret: secret.clone(), prefix: None, allow_empty }),
+            )
+        };
+
+        let result = sign("", false).await;
+        assert!(matches!(result, Err(AppError::ValidationError(m)) if m.contains("set allow_empty")));
+
+        // Empty and whitespace-only messages are signed exactly as sent, never trimmed
+        for message in ["", " ", "\n\n", " padded "] {
+            let Json(signed) = sign(message, true).await.unwrap();
+            assert_eq!(signed.data.message, message);
+            let request: VerifyMessageRequest = extract(&serde_json::json!({
+                "message": message,
+                "signature": signed.data.signature,
+                "pubkey": signed.data.pubkey,
+                "allowEmpty": true,
+            }).to_string())
+            .await
+            .unwrap();
+            let Json(verified) = verify_message_handler(test_state(), JsonExtractor(request)).await.unwrap();
+            assert!(verified.data.valid, "{:?}", message);
+        }
+        assert_ne!(sign(" ", false).await.unwrap().0.data.signature, sign("", true).await.unwrap().0.data.signature);
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_batch_handler() {
+        let secret = test_state().solana.generate_keypair().unwrap().secret.clone();
+        let request = |messages: &[&str], prefix: Option<&str>| SignMessageBatchRequest {
+            secret: secret.clone(),
+            messages: messages.iter().map(|message| message.to_string()).collect(),
+            prefix: prefix.map(str::to_string),
+            allow_empty: false,
+        };
+
+        let Json(batch) = sign_message_batch_handler(test_state(), JsonExtractor(request(&["one", "two", "one"], Some("app:"))))
+            .await
+            .unwrap();
+        assert_eq!(batch.data.signatures.len(), 3);
+        assert_eq!(batch.data.signatures[0], batch.data.signatures[2]);
+        for (message, signature) in ["one", "two"].iter().zip(&batch.data.signatures) {
+            let single = SignMessageRequest {
+                message: message.to_string(),
+                secret: secret.clone(),
+                prefix: Some("app:".to_string()),
+                allow_empty: false,
+            };
+            let Json(signed) = sign_message_handler(test_state(), JsonExtractor(single)).await.unwrap();
+            assert_eq!(&signed.data.signature, signature);
+            assert_eq!(signed.data.pubkey, batch.data.pubkey);
+        }
+
+        let result = sign_message_batch_handler(test_state(), JsonExtractor(request(&["one", ""], None))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(m)) if m.starts_with("messages[1] must not be empty")));
+        let result = sign_message_batch_handler(test_state(), JsonExtractor(request(&[], None))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_siws_build_sign_and_verify() {
+        use solana_sdk::signature::{Keypair, Signer};
+
+        let keypair = Keypair::new();
+        let request: SiwsMessage = extract(&format!(
+            r#"{{"domain":"example.com","address":"{}","statement":"Sign in to Example","expirationTime":"2999-01-01T00:00:00Z"}}"#,
+            keypair.pubkey()
+        ))
+        .await
+        .unwrap();
+        let Json(built) = siws_build_handler(JsonExtractor(request)).await.unwrap();
+        assert!(built.data.message.starts_with("example.com wants you to sign in with your Solana account:\n"));
+        // A nonce and issue time are filled in when not given
+        let nonce = built.data.fields.nonce.clone().unwrap();
+        assert!(built.data.fields.issued_at.is_some());
+
+        let signature = keypair.sign_message(built.data.message.as_bytes()).to_string();
+        let verify = |message: &str, domain: &str, nonce: &str| SiwsVerifyRequest {
+            message: message.to_string(),
+            signature: signature.clone(),
+            domain: Some(domain.to_string()),
+            nonce: Some(nonce.to_string()),
+        };
+
+        let request = verify(&built.data.message, "example.com", &nonce);
+        let Json(response) = siws_verify_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert!(response.data.valid);
+        assert_eq!(response.data.reason, None);
+        assert_eq!(response.data.fields, built.data.fields);
+
+        let request = verify(&built.data.message, "example.com", "wrongnonce");
+        let Json(response) = siws_verify_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert!(!response.data.valid);
+        assert_eq!(response.data.reason.as_deref(), Some("Message nonce does not match"));
+
+        // Changing the statement invalidates the signature
+        let tampered = built.data.message.replace("Sign in to Example", "Sign in to Evil");
+        let Json(response) = siws_verify_handler(test_state(), JsonExtractor(verify(&tampered, "example.com", &nonce))).await.unwrap();
+        assert!(!response.data.valid);
+
+        let result = siws_verify_handler(test_state(), JsonExtractor(verify("not a siws message", "example.com", &nonce))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let request: SiwsMessage = extract(r#"{"domain":"example.com","address":"invalid"}"#).await.unwrap();
+        let result = siws_build_handler(JsonExtractor(request)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_message_handler_validation() {
+        let invalid_request = VerifyMessageRequest {
+            message: "".to_string(),
+            signature: "".to_string(),
+            pubkey: "".to_string(),
+            prefix: None,
+            public_key_encoding: None,
+            signature_encoding: None,
+            strict: false,
+            allow_empty: false,
+        };
+        
+        let result = verify_message_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_message_handler_raw_public_key() {
+        use solana_sdk::signature::{Keypair, Signer};
+
+        let keypair = Keypair::new();
+        let signature = keypair.sign_message(b"hello").to_string();
+        let hex: String = keypair.pubkey().to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        let request = |pubkey: &str, encoding: &str| VerifyMessageRequest {
+            message: "hello".to_string(),
+            signature: signature.clone(),
+            pubkey: pubkey.to_string(),
+            prefix: None,
+            public_key_encoding: Some(encoding.to_string()),
+            signature_encoding: None,
+            strict: true,
+            allow_empty: false,
+        };
+
+        let Json(response) = verify_message_handler(test_state(), JsonExtractor(request(&hex, "hex"))).await.unwrap();
+        assert!(response.data.valid);
+        // The key is reported as the Solana address it corresponds to
+        assert_eq!(response.data.pubkey, keypair.pubkey().to_string());
+
+        let result = verify_message_handler(test_state(), JsonExtractor(request(&hex[2..], "hex"))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_base64url_encoding() {
+        use base64::{Engine as _, engine::general_purpose};
+        use solana_sdk::signature::{Keypair, Signer};
+        use crate::config::BinaryEncoding;
+
+        // Signatures and public keys can be verified in unpadded base64url
+        let keypair = Keypair::new();
+        let request = VerifyMessageRequest {
+            message: "hello".to_string(),
+            signature: general_purpose::URL_SAFE_NO_PAD.encode(keypair.sign_message(b"hello").as_ref()),
+            pubkey: general_purpose::URL_SAFE_NO_PAD.encode(keypair.pubkey().to_bytes()),
+            prefix: None,
+            public_key_encoding: Some("base64url".to_string()),
+            signature_encoding: Some("base64url".to_string()),
+            strict: false,
+            allow_empty: false,
+        };
+        let Json(response) = verify_message_handler(test_state(), JsonExtractor(request.clone())).await.unwrap();
+        assert!(response.data.valid);
+        let result = verify_message_handler(test_state(), JsonExtractor(VerifyMessageRequest { signature_encoding: Some("base32".to_string()), ..request })).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("signature_encoding must be")));
+
+        // Instruction data follows the configured encoding; 0xff amount bytes encode to `/` in standard base64
+        let request = || MintTokenRequest {
+            mint: Pubkey::new_unique().to_string(),
+            destination: Pubkey::new_unique().to_string(),
+            authority: Pubkey::new_unique().to_string(),
+            amount: u64::MAX,
+        };
+        let Json(standard) = mint_token_handler(test_state(), JsonExtractor(request())).await.unwrap();
+        let state = state_for(Config { binary_encoding: BinaryEncoding::Base64Url, ..Config::default() });
+        let Json(url_safe) = mint_token_handler(state, JsonExtractor(request())).await.unwrap();
+        assert!(standard.data.instruction_data.contains('/'));
+        assert_eq!(url_safe.data.instruction_data, standard.data.instruction_data.replace('/', "_").trim_end_matches('='));
+    }
+
+    #[tokio::test]
+    async fn test_batch_handler_mixed_results() {
+        use crate::models::BatchCall;
+        use serde_json::json;
+
+        let call = |id: serde_json::Value, method: &str, params: serde_json::Value| BatchCall {
+            id: Some(id),
+            method: method.to_string(),
+            params,
+        };
+        let wallet = Pubkey::new_unique().to_string();
+        let calls = vec![
+            call(json!(1), "keypair", serde_json::Value::Null),
+            call(json!("mint"), "token/create", json!({ "mintAuthority": wallet, "mint": wallet, "decimals": 6 })),
+            call(json!(3), "token/create", json!({ "mintAuthority": wallet, "mint": wallet, "decimals": 10 })),
+            call(json!(4), "send/sol", json!({ "from": wallet })),
+            call(json!(5), "rpc/send", json!({})),
+            call(json!(6), "keypair/export", json!({ "secret": "" })),
+        ];
+
+        let Json(response) = batch_handler(test_state(), JsonExtractor(calls)).await.unwrap();
+        let results = response.data;
+        assert_eq!(results.iter().map(|r| r.id.clone().unwrap()).collect::<Vec<_>>(), [json!(1), json!("mint"), json!(3), json!(4), json!(5), json!(6)]);
+        assert_eq!(results.iter().map(|r| r.status).collect::<Vec<_>>(), [200, 200, 400, 400, 404, 400]);
+
+        assert!(results[0].data.as_ref().unwrap()["secret"].is_string());
+        assert_eq!(results[1].data.as_ref().unwrap()["program_id"], spl_token::id().to_string());
+        assert_eq!(results[2].error.as_deref(), Some("decimals must be between 0 and 9"));
+        assert!(results[3].error.as_ref().unwrap().starts_with("Invalid params: missing field"));
+        assert!(!results[4].success && results[4].data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_handler_validation() {
+        use crate::models::BatchCall;
+
+        let result = batch_handler(test_state(), JsonExtractor(vec![])).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        // Routes that are switched off aren't reachable through a batch either
+        let config = state_for(Config { enable_keypair_generation: false, ..Config::default() });
+        let calls = vec![BatchCall { id: None, method: "keypair".to_string(), params: serde_json::Value::Null }];
+        let Json(response) = batch_handler(config, JsonExtractor(calls)).await.unwrap();
+        assert_eq!(response.data[0].status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_send_sol_handler_validation() {
+        let invalid_request = SendSolRequest {
+            from: "".to_string(),
+            to: "".to_string(),
+            lamports: 0,
+        };
+        
+        let result = send_sol_handler(test_state(), JsonExtractor(invalid_request)).await;
+        assert!(result.is_err());
     }
 
     #[tokio::test]
@@ -345,9 +4745,89 @@ mod tests {
             mint: "".to_string(),
             owner: "".to_string(),
             amount: 0,
+            create_ata_if_missing: false,
         };
         
-        let result = send_token_handler(JsonExtractor(invalid_request)).await;
+        let result = send_token_handler(test_state(), JsonExtractor(invalid_request)).await;
         assert!(result.is_err());
     }
-} 
+
+    #[tokio::test]
+    async fn test_send_token_handler_creates_ata() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let (destination, mint, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let body = |create: bool| {
+            format!(
+                r#"{{"destination":"{}","mint":"{}","owner":"{}","amount":5,"createAtaIfMissing":{}}}"#,
+                destination, mint, owner, create
+            )
+        };
+
+        let request: SendTokenRequest = extract(&body(false)).await.unwrap();
+        let Json(response) = send_token_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        assert!(response.data.instructions.is_none());
+
+        let request: SendTokenRequest = extract(&body(true)).await.unwrap();
+        let Json(response) = send_token_handler(test_state(), JsonExtractor(request)).await.unwrap();
+        let instructions = response.data.instructions.unwrap();
+        assert_eq!(instructions.len(), 2);
+
+        // The create-ATA instruction comes first, paid by the owner, for the destination's ATA
+        let destination_ata = spl_associated_token_account::get_associated_token_address(&destination, &mint);
+        assert_eq!(instructions[0].program_id, spl_associated_token_account::id().to_string());
+        assert_eq!(instructions[0].instruction_data, general_purpose::STANDARD.encode([1]));
+        assert_eq!(instructions[0].accounts[0].pubkey, owner.to_string());
+        assert!(instructions[0].accounts[0].is_signer);
+        assert_eq!(instructions[0].accounts[1].pubkey, destination_ata.to_string());
+        assert_eq!(instructions[0].accounts[2].pubkey, destination.to_string());
+
+        // Then the transfer into that account, matching the top-level instruction
+        assert_eq!(instructions[1].program_id, spl_token::id().to_string());
+        assert_eq!(instructions[1].instruction_data, response.data.instruction_data);
+        assert_eq!(instructions[1].accounts[1].pubkey, destination_ata.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_send_token_with_memo_handler() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let (destination, mint, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let request = |memo: &str, create_ata_if_missing| SendTokenWithMemoRequest {
+            destination: destination.to_string(),
+            mint: mint.to_string(),
+            owner: owner.to_string(),
+            amount: 5,
+            memo: memo.to_string(),
+            create_ata_if_missing,
+        };
+
+        // The memo, signed by the owner, comes directly before the transfer
+        let Json(response) = send_token_with_memo_handler(test_state(), JsonExtractor(request("deposit 42", false))).await.unwrap();
+        let instructions = response.data.instructions;
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].program_id, spl_memo::id().to_string());
+        assert_eq!(instructions[0].instruction_data, general_purpose::STANDARD.encode("deposit 42"));
+        assert_eq!(instructions[0].accounts.len(), 1);
+        assert_eq!(instructions[0].accounts[0].pubkey, owner.to_string());
+        assert!(instructions[0].accounts[0].is_signer);
+        assert_eq!(instructions[1].program_id, spl_token::id().to_string());
+
+        let Json(response) = send_token_with_memo_handler(test_state(), JsonExtractor(request("deposit 42", true))).await.unwrap();
+        let programs: Vec<_> = response.data.instructions.iter().map(|instruction| instruction.program_id.clone()).collect();
+        assert_eq!(
+            programs,
+            [spl_associated_token_account::id(), spl_memo::id(), spl_token::id()].map(|id| id.to_string())
+        );
+
+        let result = send_token_with_memo_handler(test_state(), JsonExtractor(request("", false))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(m)) if m == "memo is required"));
+        let too_long = "m".repeat(validation::MAX_MEMO_LEN + 1);
+        let result = send_token_with_memo_handler(test_state(), JsonExtractor(request(&too_long, false))).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        let mut bad_owner = request("deposit 42", false);
+        bad_owner.owner = "invalid".to_string();
+        let result = send_token_with_memo_handler(test_state(), JsonExtractor(bad_owner)).await;
+        assert!(matches!(result, Err(AppError::InvalidPublicKey(_))));
+    }
+}
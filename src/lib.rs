@@ -0,0 +1,15 @@
+//! Solana HTTP server: an Axum REST API for keypairs, SPL token instructions, message signing
+//! and RPC lookups
+//!
+//! The binary in main.rs loads the configuration and serves `router::create_router`. The
+//! modules are exposed as a library so the benchmarks in benches/ can call the services directly.
+
+pub mod config;
+pub mod router;
+pub mod handlers;
+pub mod services;
+pub mod models;
+pub mod utils;
+pub mod openapi;
+pub mod examples;
+pub mod state;
@@ -1,31 +1,60 @@
 use std::net::SocketAddr;
-use tokio;
-use tracing::{info, warn};
-use tracing_subscriber;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
 
-mod router;
-mod handlers;
-mod services;
-mod models;
-mod utils;
+use solana_http_server::config::{Config, LogConfig, LogFormat};
+use solana_http_server::router::create_router;
+use solana_http_server::services::solana::SolanaService;
+use solana_sdk::signature::Signer;
 
-use router::create_router;
+/// How long `bench` generates keypairs for when no duration is given
+const DEFAULT_BENCH_SECONDS: u64 = 5;
 
 #[tokio::main]
 async fn main() {
-    // Initialize tracing subscriber for logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info".into())
-        )
-        .init();
+    // `solana-http-server bench [seconds]` reports keypair generation throughput instead of serving
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("bench") {
+        let seconds = match args.next().map(|seconds| seconds.parse::<u64>()) {
+            None => DEFAULT_BENCH_SECONDS,
+            Some(Ok(seconds)) if seconds > 0 => seconds,
+            Some(_) => {
+                eprintln!("usage: solana-http-server bench [seconds]");
+                std::process::exit(2);
+            }
+        };
+        run_keypair_bench(Duration::from_secs(seconds));
+        return;
+    }
+
+    // Load and validate the configuration before binding anything. Logging is set up from it
+    // first, falling back to the default level and format to report an invalid configuration.
+    let config = Config::load();
+    init_tracing(config.as_ref().map_or(&LogConfig::default(), |config| &config.log));
+    let config = config.unwrap_or_else(|e| {
+        error!("{}", e);
+        std::process::exit(1);
+    });
+    let addr = config.bind_address;
+    if !config.enable_keypair_generation {
+        info!("Keypair generation is disabled: /keypair, /keypair/from-seed, /keypair/derive/batch, /keypair/funded and /token/create/new are not served");
+    }
+    match &config.relay.keypair {
+        Some(relayer) => info!("Relaying transactions with fee payer {}", relayer.pubkey()),
+        None => info!("No relayer key configured: /relay returns 503"),
+    }
+    match config.cluster {
+        _ if !config.rpc.enabled => {
+            warn!("No RPC endpoint configured: RPC-backed routes return 503 RPC_NOT_CONFIGURED")
+        }
+        Some(cluster) => info!("Using RPC endpoint {} (cluster: {})", config.rpc.url, cluster),
+        None => info!("Using RPC endpoint {}", config.rpc.url),
+    }
 
     // Create the application router
-    let app = create_router();
+    let app = create_router(Arc::new(config));
 
-    // Define the server address
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
     info!("Starting Solana HTTP server on {}", addr);
 
     // Create a TCP listener
@@ -38,19 +67,124 @@ async fn main() {
 
     info!("Server listening on http://{}", addr);
     info!("Available endpoints:");
+    info!("  GET  /openapi.json    - OpenAPI 3 description of the API");
+    info!("  GET  /health          - Server status and configured cluster");
+    info!("  GET  /health/deep     - RPC node health and current slot, for readiness probes");
+    info!("  GET  /version         - Server, solana-sdk and spl-token versions");
+    info!("  GET  /examples        - Sample request and response for every route");
     info!("  POST /keypair         - Generate new Solana keypair");
+    info!("  POST /keypair/from-seed - Derive a keypair from a 32-byte seed");
+    info!("  POST /keypair/derive/batch - Derive sequential m/44'/501'/i'/0' keypairs from a mnemonic");
+    info!("  POST /keypair/public-only - Generate a keypair and return only its public key");
+    info!("  GET  /keypair/qr      - SVG QR code of a public key");
+    info!("  GET  /keypair/entropy-check - Statistical self-test of the keypair generator");
+    info!("  POST /keypair/export  - Export secret key as solana-keygen byte array");
+    info!("  POST /keypair/import  - Import solana-keygen byte array keypair");
+    info!("  POST /keypair/inspect - Decode a secret key's length and public keys");
+    info!("  POST /keypair/rotate  - Re-sign messages signed by an old key with a new key");
+    info!("  POST /keypair/funded  - Generate keypair and airdrop SOL (devnet)");
+    info!("  POST /rpc/token-accounts - List a wallet's SPL token accounts");
+    info!("  POST /rpc/account     - Look up an account's lamports, owner and data");
+    info!("  POST /rpc/mint-info   - Read an SPL token mint's supply, decimals and authorities");
+    info!("  POST /rpc/token-account-info - Read an SPL token account's balance, delegate and state");
+    info!("  POST /rpc/rent        - Rent-exempt minimum for an account size");
+    info!("  POST /rpc/send        - Submit a signed transaction (Idempotency-Key supported)");
+    info!("  POST /rpc/airdrop     - Airdrop SOL to a wallet (Idempotency-Key supported)");
+    info!("  POST /relay           - Pay a transaction's fees and submit it (Idempotency-Key supported)");
+    info!("  POST /rpc/simulate    - Simulate a transaction without submitting it");
+    info!("  POST /rpc/signature-status - Confirmation status of a submitted transaction");
+    info!("  POST /rpc/vote-accounts - Current and delinquent validators' vote accounts");
+    info!("  GET  /ws/account      - Stream account changes over a WebSocket");
     info!("  POST /token/create    - Create SPL token mint instruction");
+    info!("  POST /token/create/new - Generate a mint keypair and create the instructions for it");
     info!("  POST /token/mint      - Create SPL token mint_to instruction");
+    info!("  POST /token/mint/batch - Create mint_to instructions for many recipients (?stream=ndjson)");
+    info!("  POST /token/burn-close - Create burn + close_account instructions");
+    info!("  POST /token/wrap      - Create instructions to wrap SOL as wrapped SOL tokens");
+    info!("  POST /token/unwrap    - Create instruction to unwrap wrapped SOL back to SOL");
+    info!("  POST /token/sync-native - Create sync_native instruction for a wrapped SOL account");
+    info!("  POST /pda/derive      - Find a program-derived address and bump (also GET)");
+    info!("  POST /pda/derive/batch - Find many program-derived addresses at once (?stream=ndjson)");
+    info!("  POST /token/ata       - Derive an associated token account address (also GET)");
+    info!("  POST /token/ata/batch - Derive a wallet's associated token accounts for many mints");
+    info!("  POST /pubkey/on-curve - Check whether an address is on the ed25519 curve (also GET)");
+    info!("  POST /pubkey/normalize - Convert an address between base58, hex and base64");
+    info!("  POST /token/transfer-fee/create-mint - Initialize a Token-2022 mint with a transfer fee");
+    info!("  POST /token/transfer-fee/transfer - Create Token-2022 transfer_checked_with_fee instruction");
+    info!("  POST /token/metadata/pointer - Create Token-2022 metadata-pointer initialize instruction");
+    info!("  POST /token/metadata/initialize - Create token-metadata initialize instruction");
+    info!("  POST /token/metadata/update - Create token-metadata update_field instruction");
+    info!("  POST /token/close-mint/create-mint - Initialize a Token-2022 mint with a close authority");
+    info!("  POST /token/close-mint - Create Token-2022 close_account instruction for a mint");
+    info!("  POST /stake/create    - Create and initialize a funded stake account");
+    info!("  POST /stake/delegate  - Create stake delegate instruction");
+    info!("  POST /stake/deactivate - Create stake deactivate instruction");
+    info!("  POST /stake/withdraw  - Create stake withdraw instruction");
     info!("  POST /message/sign    - Sign message with secret key");
+    info!("  POST /message/sign/batch - Sign many messages with one secret key");
     info!("  POST /message/verify  - Verify message signature");
+    info!("  POST /auth/challenge  - Issue a single-use nonce for a wallet to sign");
+    info!("  POST /auth/verify     - Check a signed nonce and redeem it");
+    info!("  POST /siws/build      - Build a Sign-In With Solana message");
+    info!("  POST /siws/verify     - Verify a signed Sign-In With Solana message");
+    info!("  POST /transaction/sign - Sign a transaction message with its required signers");
+    info!("  POST /transaction/sign/partial - Add signatures to a partially signed transaction");
+    info!("  POST /transaction/verify - Check a transaction's signatures are present and valid");
+    info!("  POST /transaction/signature - Signature (transaction id) of a signed transaction");
     info!("  POST /send/sol        - Create SOL transfer instruction");
     info!("  POST /send/token      - Create SPL token transfer instruction");
+    info!("  POST /send/token-with-memo - Create memo + SPL token transfer instructions");
+    info!("  POST /batch           - Run several operations in one request (?stream=ndjson)");
 
-    // Start serving the application
-    axum::serve(listener, app)
+    // Start serving the application, keeping peer addresses for per-IP rate limiting
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap_or_else(|e| {
             warn!("Server error: {}", e);
             std::process::exit(1);
         });
-} 
+}
+
+/// Installs the global tracing subscriber with the configured filter and format
+fn init_tracing(log: &LogConfig) {
+    // The level was validated with the configuration, so it still parses here
+    let subscriber = tracing_subscriber::fmt().with_env_filter(tracing_subscriber::EnvFilter::new(&log.level));
+    match log.format {
+        LogFormat::Full => subscriber.init(),
+        LogFormat::Compact => subscriber.compact().init(),
+        LogFormat::Pretty => subscriber.pretty().init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
+
+/// Generates keypairs for `duration` on one thread, then on every available core, and prints
+/// keypairs/sec for each, for sizing deployments that serve /keypair
+fn run_keypair_bench(duration: Duration) {
+    let cores = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+    let runs = if cores > 1 { vec![1, cores] } else { vec![1] };
+    println!("Generating keypairs for {}s per run...", duration.as_secs());
+    for threads in runs {
+        let generated: u64 = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let service = SolanaService::new();
+                        let started = Instant::now();
+                        let mut generated = 0u64;
+                        while started.elapsed() < duration {
+                            service.generate_keypair().expect("keypair generation does not fail");
+                            generated += 1;
+                        }
+                        generated
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|worker| worker.join().expect("bench thread panicked")).sum()
+        });
+        println!(
+            "{:>3} thread(s): {:>10.0} keypairs/sec",
+            threads,
+            generated as f64 / duration.as_secs_f64()
+        );
+    }
+}
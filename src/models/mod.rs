@@ -1,17 +1,62 @@
+//! Request and response types
+//!
+//! Request fields are snake_case. Every multi-word field also accepts its camelCase spelling
+//! through a serde alias, so clients can use either convention. The exceptions are
+//! `mintAuthority` and `freezeAuthority` on /token/create, which have always been camelCase.
+//! They also accept snake_case and the all-lowercase spelling.
+
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use zeroize::Zeroize;
+
+use crate::config::Cluster;
+use crate::services::siws::SiwsMessage;
+
+/// Implements Zeroize for the listed secret fields, and a Drop that calls it, so secrets in
+/// requests and responses are wiped when the value is freed rather than lingering in heap memory
+macro_rules! zeroize_on_drop {
+    ($type:ident: $($field:ident),+) => {
+        impl Zeroize for $type {
+            fn zeroize(&mut self) {
+                $(self.$field.zeroize();)+
+            }
+        }
+
+        impl Drop for $type {
+            fn drop(&mut self) {
+                self.zeroize();
+            }
+        }
+    };
+}
 
 /// Standard API response wrapper for successful responses
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: T,
+    /// Diagnostics added by the router when the request has `?meta=true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ResponseMeta>,
+}
+
+/// Server-side timing for a response, requested with `?meta=true`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct ResponseMeta {
+    /// When the response was produced, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Time from the request reaching the router until the response was ready
+    pub latency_ms: f64,
 }
 
 /// Standard API response wrapper for error responses  
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct ApiErrorResponse {
     pub success: bool,
     pub error: String,
+    /// Machine-readable error class named after the HTTP status, e.g. "NOT_FOUND"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
@@ -19,6 +64,7 @@ impl<T> ApiResponse<T> {
         Self {
             success: true,
             data,
+            meta: None,
         }
     }
 }
@@ -28,37 +74,996 @@ impl ApiErrorResponse {
         Self {
             success: false,
             error: message.to_string(),
+            code: None,
         }
     }
+
+    /// Sets `code` from an HTTP status: its reason phrase in upper snake case
+    pub fn with_status_code(self, status: axum::http::StatusCode) -> Self {
+        let code = status
+            .canonical_reason()
+            .map(|reason| reason.to_uppercase().replace([' ', '-'], "_"));
+        Self { code, ..self }
+    }
 }
 
 /// Response for POST /keypair
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct KeypairResponse {
     pub pubkey: String,
     pub secret: String,
 }
 
+zeroize_on_drop!(KeypairResponse: secret);
+
+/// Response for POST /keypair/public-only
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct PublicKeyResponse {
+    /// Public key of a keypair whose secret was discarded
+    pub pubkey: String,
+}
+
+/// Request for POST /keypair/from-seed
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct KeypairFromSeedRequest {
+    /// 32 bytes as 64 hex characters or base64
+    pub seed: String,
+}
+
+zeroize_on_drop!(KeypairFromSeedRequest: seed);
+
+/// Request for POST /keypair/derive/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeriveKeypairBatchRequest {
+    /// BIP39 English mnemonic of 12 to 24 words, as wallets such as Phantom and solana-keygen show it
+    pub mnemonic: String,
+    /// Optional BIP39 passphrase; empty by default
+    #[serde(default)]
+    pub passphrase: String,
+    /// How many sequential accounts to derive, 1 to 100
+    pub count: u32,
+    /// First account index; defaults to 0
+    #[serde(default, alias = "startIndex")]
+    pub start_index: u32,
+}
+
+zeroize_on_drop!(DeriveKeypairBatchRequest: mnemonic, passphrase);
+
+/// Response for POST /keypair/derive/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DeriveKeypairBatchResponse {
+    /// One keypair per account index, in order
+    pub keypairs: Vec<DerivedKeypair>,
+}
+
+/// One keypair derived from a mnemonic
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DerivedKeypair {
+    pub index: u32,
+    /// e.g. m/44'/501'/0'/0'
+    pub derivation_path: String,
+    pub pubkey: String,
+    pub secret: String,
+}
+
+zeroize_on_drop!(DerivedKeypair: secret);
+
+/// Request for POST /keypair/export
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExportKeypairRequest {
+    pub secret: String,
+}
+
+zeroize_on_drop!(ExportKeypairRequest: secret);
+
+/// Response for POST /keypair/export
+/// `keypair` is the 64-byte array written by solana-keygen (e.g. ~/.config/solana/id.json)
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ExportKeypairResponse {
+    pub pubkey: String,
+    pub keypair: Vec<u8>,
+}
+
+zeroize_on_drop!(ExportKeypairResponse: keypair);
+
+/// Request for POST /keypair/import
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ImportKeypairRequest {
+    pub keypair: Vec<u8>,
+}
+
+zeroize_on_drop!(ImportKeypairRequest: keypair);
+
+/// Request for POST /keypair/inspect
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectKeypairRequest {
+    pub secret: String,
+}
+
+zeroize_on_drop!(InspectKeypairRequest: secret);
+
+/// Response for POST /keypair/inspect
+/// Describes a base58 secret key without returning any of its secret bytes
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct InspectKeypairResponse {
+    /// Decoded length in bytes; a usable secret key is 64 (secret half, then public half)
+    pub byte_length: usize,
+    /// Public key derived from the first 32 bytes, when there are at least 32
+    pub derived_pubkey: Option<String>,
+    /// Public key stored in the last 32 bytes of a 64-byte key
+    pub embedded_pubkey: Option<String>,
+    /// Whether the embedded public key is the one derived from the secret half
+    pub pubkey_matches: bool,
+    /// Whether the key is accepted by the signing endpoints
+    pub valid: bool,
+    /// Why the key is not valid
+    pub problem: Option<String>,
+}
+
+/// A message and its base58 signature, as returned by /message/sign
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SignedMessage {
+    pub message: String,
+    pub signature: String,
+}
+
+/// Request for POST /keypair/rotate
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RotateKeypairRequest {
+    /// Secret key the messages were signed with
+    #[serde(alias = "oldSecret")]
+    pub old_secret: String,
+    /// Secret key to sign them with instead
+    #[serde(alias = "newSecret")]
+    pub new_secret: String,
+    /// Messages with their signatures under the old key
+    pub messages: Vec<SignedMessage>,
+    /// The prefix the messages were signed with, used again for the new signatures
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+zeroize_on_drop!(RotateKeypairRequest: old_secret, new_secret);
+
+/// Response for POST /keypair/rotate
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct RotateKeypairResponse {
+    pub old_pubkey: String,
+    pub new_pubkey: String,
+    /// Base58-encoded signatures under the new key, one per message, in request order
+    pub signatures: Vec<String>,
+}
+
+/// Request for POST /keypair/funded
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FundedKeypairRequest {
+    pub sol: f64,
+}
+
+/// Response for POST /keypair/funded
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct FundedKeypairResponse {
+    pub pubkey: String,
+    pub secret: String,
+    pub lamports: u64,
+    pub signature: String,
+    pub confirmed: bool,
+    /// Cluster the airdrop was requested on; null for an RPC provider of unknown cluster
+    pub cluster: Option<Cluster>,
+}
+
+zeroize_on_drop!(FundedKeypairResponse: secret);
+
+/// Request for POST /rpc/token-accounts
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TokenAccountsRequest {
+    pub owner: String,
+    #[serde(default)]
+    pub mint: Option<String>,
+    /// Accounts to skip, for paging
+    #[serde(default)]
+    pub offset: usize,
+    /// Most accounts returned, from 1 to 500 (default 100)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+/// Where a page sits in a paged list
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct Pagination {
+    pub offset: usize,
+    pub limit: usize,
+    /// Size of the full list
+    pub total: usize,
+    /// Offset of the next page, or null on the last page
+    pub next_offset: Option<usize>,
+}
+
+/// A token account and its balance, as listed by POST /rpc/token-accounts
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TokenAccountBalance {
+    pub account: String,
+    pub mint: String,
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+/// Response for POST /rpc/token-accounts
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TokenAccountsResponse {
+    pub owner: String,
+    /// By mint, then account address
+    pub accounts: Vec<TokenAccountBalance>,
+    pub pagination: Pagination,
+}
+
+/// Request for POST /rpc/account
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountInfoRequest {
+    pub pubkey: String,
+    /// "base64" (default) or "jsonParsed"
+    #[serde(default = "default_account_encoding")]
+    pub encoding: String,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+fn default_account_encoding() -> String {
+    "base64".to_string()
+}
+
+/// Response for POST /rpc/account
+/// `data` is a base64 string, or the parsed account object for jsonParsed encoding
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AccountInfoResponse {
+    pub pubkey: String,
+    pub lamports: u64,
+    pub owner: String,
+    pub executable: bool,
+    pub encoding: String,
+    #[schema(value_type = Object)]
+    pub data: serde_json::Value,
+}
+
+/// Request for POST /rpc/mint-info
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MintInfoRequest {
+    pub mint: String,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+/// Response for POST /rpc/mint-info
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MintInfoResponse {
+    pub mint: String,
+    /// Total supply in base units
+    pub supply: u64,
+    pub decimals: u8,
+    /// None once minting has been disabled
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    pub is_initialized: bool,
+}
+
+/// Request for POST /rpc/token-account-info
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TokenAccountInfoRequest {
+    pub account: String,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+/// Response for POST /rpc/token-account-info
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TokenAccountInfoResponse {
+    pub account: String,
+    pub mint: String,
+    pub owner: String,
+    /// Balance in base units
+    pub amount: u64,
+    pub delegate: Option<String>,
+    /// How much of the balance the delegate may transfer
+    pub delegated_amount: u64,
+    /// "initialized" or "frozen"
+    pub state: String,
+}
+
+/// Request for POST /rpc/rent
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RentRequest {
+    #[serde(alias = "dataLen")]
+    pub data_len: u64,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+/// Response for POST /rpc/rent
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct RentResponse {
+    pub data_len: u64,
+    /// Minimum balance for an account of data_len bytes to be rent exempt
+    pub lamports: u64,
+    /// "rpc" when the cluster was asked, "local" when computed from default rent parameters
+    pub source: String,
+}
+
+/// Request for POST /rpc/send
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendTransactionRequest {
+    /// Base64-encoded, bincode-serialized signed transaction
+    pub transaction: String,
+}
+
+/// Response for POST /rpc/send
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SendTransactionResponse {
+    pub signature: String,
+    /// Cluster the transaction was submitted to; null for an RPC provider of unknown cluster
+    pub cluster: Option<Cluster>,
+}
+
+/// Request for POST /relay
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RelayTransactionRequest {
+    /// Base64-encoded, bincode-serialized transaction whose fee payer is the relayer, signed by
+    /// every other required signer
+    pub transaction: String,
+}
+
+/// Response for POST /relay
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct RelayTransactionResponse {
+    pub signature: String,
+    /// The relayer, which paid the transaction's fees
+    pub fee_payer: String,
+    /// Cluster the transaction was submitted to; null for an RPC provider of unknown cluster
+    pub cluster: Option<Cluster>,
+}
+
+/// Request for POST /rpc/airdrop
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AirdropRequest {
+    pub pubkey: String,
+    /// Amount of SOL to request
+    pub sol: f64,
+}
+
+/// Response for POST /rpc/airdrop
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AirdropResponse {
+    pub pubkey: String,
+    pub lamports: u64,
+    pub signature: String,
+    /// Cluster the airdrop was requested on; null for an RPC provider of unknown cluster
+    pub cluster: Option<Cluster>,
+}
+
+/// Request for POST /rpc/simulate
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SimulateTransactionRequest {
+    /// Base64-encoded, bincode-serialized transaction; signatures are only needed with sig_verify
+    pub transaction: String,
+    /// Verify the transaction's signatures during simulation
+    #[serde(default, alias = "sigVerify")]
+    pub sig_verify: bool,
+    /// Simulate against the latest blockhash instead of the transaction's own; can't be
+    /// combined with sig_verify, as replacing the blockhash invalidates the signatures
+    #[serde(default, alias = "replaceRecentBlockhash")]
+    pub replace_recent_blockhash: bool,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+/// Response for POST /rpc/simulate
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SimulateTransactionResponse {
+    /// Why the transaction would fail; null if the simulation succeeded
+    pub err: Option<String>,
+    /// Program log messages, in order
+    pub logs: Vec<String>,
+    /// Compute units the transaction used
+    pub units_consumed: Option<u64>,
+}
+
+/// Request for POST /rpc/signature-status
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureStatusRequest {
+    /// Base58 transaction signature, as returned by /rpc/send
+    pub signature: String,
+}
+
+/// Request for POST /rpc/vote-accounts
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VoteAccountsRequest {
+    /// Only return this vote account
+    #[serde(default, alias = "votePubkey")]
+    pub vote_pubkey: Option<String>,
+    /// Accounts to skip in each list, for paging
+    #[serde(default)]
+    pub offset: usize,
+    /// Most accounts returned in each list, from 1 to 500 (default 100)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// "processed", "confirmed" or "finalized"; defaults to SOLANA_COMMITMENT
+    #[serde(default)]
+    pub commitment: Option<String>,
+}
+
+/// A validator's vote account, as listed by POST /rpc/vote-accounts
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct VoteAccountInfo {
+    pub vote_pubkey: String,
+    /// The validator's identity
+    pub node_pubkey: String,
+    /// Lamports of stake delegated to the account and active this epoch
+    pub activated_stake: u64,
+    /// Percentage of rewards the validator keeps
+    pub commission: u8,
+    /// Whether the account is staked for the current epoch
+    pub epoch_vote_account: bool,
+    /// Most recent slot voted on
+    pub last_vote: u64,
+    pub root_slot: u64,
+}
+
+/// Response for POST /rpc/vote-accounts
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct VoteAccountsResponse {
+    /// Voting validators, by activated stake (largest first)
+    pub current: Vec<VoteAccountInfo>,
+    /// Validators that have stopped voting, by activated stake (largest first)
+    pub delinquent: Vec<VoteAccountInfo>,
+    /// Size of each full list before `offset` and `limit`
+    pub total_current: usize,
+    pub total_delinquent: usize,
+    /// Paging of the longer list, so `next_offset` is null once both lists are exhausted
+    pub pagination: Pagination,
+}
+
+/// Response for POST /rpc/signature-status
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SignatureStatusResponse {
+    pub signature: String,
+    /// False when the node has no record of the signature: not processed yet, or too old
+    pub found: bool,
+    /// "processed", "confirmed" or "finalized"
+    pub confirmation_status: Option<String>,
+    /// Slot the transaction was processed in
+    pub slot: Option<u64>,
+    /// Blocks confirmed since then; null once the transaction is finalized
+    pub confirmations: Option<usize>,
+    /// Why the transaction failed, if it landed with an error
+    pub err: Option<String>,
+}
+
+/// Response for GET /health
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct HealthResponse {
+    pub status: String,
+    /// Cluster the server is configured for; null for an RPC provider of unknown cluster
+    pub cluster: Option<Cluster>,
+}
+
+/// Response for GET /health/deep
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DeepHealthResponse {
+    /// "ok", or "degraded" when the RPC check failed
+    pub status: String,
+    pub cluster: Option<Cluster>,
+    /// "ok", "degraded" when no endpoint is reachable and healthy, or "disabled" when RPC is
+    /// turned off
+    pub rpc: String,
+    /// Slot the cluster has reached; null when RPC is disabled or the slot couldn't be fetched
+    pub slot: Option<u64>,
+    /// Why the RPC check failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for GET /version
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct VersionResponse {
+    /// Version of this server
+    pub version: String,
+    /// solana-sdk version compiled in, which determines transaction and instruction encoding
+    pub solana_sdk: String,
+    /// spl-token version compiled in, which determines token instruction encoding
+    pub spl_token: String,
+}
+
+/// Query parameters for GET /ws/account
+#[derive(Serialize, Deserialize, Debug, Clone, IntoParams)]
+pub struct AccountSubscribeQuery {
+    /// Defaulted so a missing pubkey is reported by validation like other endpoints
+    #[serde(default)]
+    pub pubkey: String,
+}
+
+/// Query parameters for GET /keypair/qr
+#[derive(Serialize, Deserialize, Debug, Clone, IntoParams)]
+pub struct KeypairQrQuery {
+    /// Address to encode; defaulted so a missing pubkey is reported by validation
+    #[serde(default)]
+    pub pubkey: String,
+}
+
+/// Query parameters for GET /keypair/entropy-check
+#[derive(Serialize, Deserialize, Debug, Clone, IntoParams)]
+pub struct EntropyCheckQuery {
+    /// Keypairs to generate, from 64 to 1024 (default 256)
+    pub sample: Option<usize>,
+}
+
+/// Response for GET /keypair/entropy-check
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct EntropyCheckResponse {
+    /// True when every check passed
+    pub passed: bool,
+    pub sample_size: usize,
+    pub checks: Vec<EntropyCheck>,
+}
+
+/// One statistical check of GET /keypair/entropy-check
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct EntropyCheck {
+    /// `unique_keys`, `byte_distribution` or `bit_balance`
+    pub name: String,
+    pub passed: bool,
+    /// The measured value and the bound it was checked against
+    pub detail: String,
+}
+
+/// Request for POST /pda/derive
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DerivePdaRequest {
+    #[serde(alias = "programId")]
+    pub program_id: String,
+    /// Up to 15 seeds of at most 32 bytes. Plain text is used as UTF-8; prefix a seed with
+    /// `utf8:`, `pubkey:`, `hex:` or `base64:` to choose how it is decoded
+    pub seeds: Vec<String>,
+}
+
+/// Query parameters for GET /pda/derive
+#[derive(Serialize, Deserialize, Debug, Clone, IntoParams)]
+pub struct DerivePdaQuery {
+    /// Defaulted so a missing program id is reported by validation
+    #[serde(default, alias = "programId")]
+    pub program_id: String,
+    /// Comma-separated seeds, written as for POST /pda/derive
+    #[serde(default)]
+    pub seeds: String,
+}
+
+impl From<DerivePdaQuery> for DerivePdaRequest {
+    fn from(query: DerivePdaQuery) -> Self {
+        let seeds = match query.seeds.as_str() {
+            "" => Vec::new(),
+            seeds => seeds.split(',').map(str::to_string).collect(),
+        };
+        Self { program_id: query.program_id, seeds }
+    }
+}
+
+/// Response for /pda/derive
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct PdaResponse {
+    pub address: String,
+    /// The bump seed `find_program_address` appended to move the address off the curve
+    pub bump: u8,
+}
+
+/// Query parameters for the batch endpoints that can stream their results
+#[derive(Serialize, Deserialize, Debug, Clone, Default, IntoParams)]
+pub struct StreamQuery {
+    /// "ndjson" to stream one result per line as newline-delimited JSON
+    #[serde(default)]
+    pub stream: Option<String>,
+}
+
+/// Request for POST /pda/derive/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DerivePdaBatchRequest {
+    /// 1 to 500 derivations, each as for POST /pda/derive
+    pub entries: Vec<DerivePdaRequest>,
+}
+
+/// Response for POST /pda/derive/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DerivePdaBatchResponse {
+    /// One result per entry, in request order
+    pub results: Vec<PdaBatchResult>,
+}
+
+/// One entry's outcome in POST /pda/derive/batch: its address and bump, or why it failed
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct PdaBatchResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bump: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Request for POST /token/ata
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AssociatedTokenAddressRequest {
+    pub wallet: String,
+    pub mint: String,
+    /// The token program or Token-2022; defaults to the token program
+    #[serde(default, alias = "tokenProgram")]
+    pub token_program: Option<String>,
+}
+
+/// Query parameters for GET /token/ata
+#[derive(Serialize, Deserialize, Debug, Clone, IntoParams)]
+pub struct AssociatedTokenAddressQuery {
+    #[serde(default)]
+    pub wallet: String,
+    #[serde(default)]
+    pub mint: String,
+    #[serde(default, alias = "tokenProgram")]
+    pub token_program: Option<String>,
+}
+
+impl From<AssociatedTokenAddressQuery> for AssociatedTokenAddressRequest {
+    fn from(query: AssociatedTokenAddressQuery) -> Self {
+        Self { wallet: query.wallet, mint: query.mint, token_program: query.token_program }
+    }
+}
+
+/// Response for /token/ata
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AssociatedTokenAddressResponse {
+    pub address: String,
+}
+
+/// Request for POST /token/ata/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AssociatedTokenAddressBatchRequest {
+    pub wallet: String,
+    /// 1 to 500 mints to derive the wallet's associated token accounts for
+    pub mints: Vec<String>,
+    /// The token program or Token-2022, for every mint; defaults to the token program
+    #[serde(default, alias = "tokenProgram")]
+    pub token_program: Option<String>,
+}
+
+/// Response for POST /token/ata/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AssociatedTokenAddressBatchResponse {
+    pub wallet: String,
+    /// One account per mint, in request order
+    pub accounts: Vec<MintAssociatedTokenAddress>,
+}
+
+/// A wallet's associated token account for one mint
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MintAssociatedTokenAddress {
+    pub mint: String,
+    pub address: String,
+}
+
+/// Request for POST /pubkey/on-curve
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OnCurveRequest {
+    pub pubkey: String,
+}
+
+/// Query parameters for GET /pubkey/on-curve
+#[derive(Serialize, Deserialize, Debug, Clone, IntoParams)]
+pub struct OnCurveQuery {
+    #[serde(default)]
+    pub pubkey: String,
+}
+
+/// Response for /pubkey/on-curve
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct OnCurveResponse {
+    pub pubkey: String,
+    /// True for an ed25519 point, which has a secret key; false for a PDA, which can't sign
+    pub on_curve: bool,
+}
+
+/// Request for POST /pubkey/normalize
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NormalizePubkeyRequest {
+    pub pubkey: String,
+    /// "base58", "hex", "base64" or "base64url"; detected from `pubkey` when omitted
+    #[serde(default, alias = "publicKeyEncoding")]
+    pub public_key_encoding: Option<String>,
+}
+
+/// Response for POST /pubkey/normalize
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct NormalizePubkeyResponse {
+    /// The canonical base58 address
+    pub pubkey: String,
+    /// The 32 raw bytes as lowercase hex
+    pub hex: String,
+    /// The 32 raw bytes as standard, padded base64
+    pub base64: String,
+    /// The encoding `pubkey` was read as
+    pub input_encoding: String,
+}
+
 /// Request for POST /token/create
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct CreateTokenRequest {
-    #[serde(rename = "mintAuthority")]
+    #[serde(rename = "mintAuthority", alias = "mint_authority", alias = "mintauthority")]
     pub mint_authority: String,
     pub mint: String,
-    pub decimals: u8,
+    /// Required unless the server has a TOKEN_DEFAULT_DECIMALS
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Absent: the server's TOKEN_DEFAULT_FREEZE_AUTHORITY, by default the mint authority.
+    /// `null`: no freeze authority. A pubkey: that account is the freeze authority.
+    #[serde(
+        rename = "freezeAuthority",
+        alias = "freeze_authority",
+        alias = "freezeauthority",
+        default,
+        deserialize_with = "present_or_null",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>)]
+    pub freeze_authority: Option<Option<String>>,
+    /// Create the mint without a freeze authority; same as `"freezeAuthority": null`
+    #[serde(default, alias = "disableFreezeAuthority")]
+    pub disable_freeze_authority: bool,
+}
+
+/// Request for POST /token/create/new
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateNewTokenRequest {
+    #[serde(rename = "mintAuthority", alias = "mint_authority", alias = "mintauthority")]
+    pub mint_authority: String,
+    /// Funds the new mint account's rent and signs its creation; defaults to the mint authority
+    #[serde(default)]
+    pub payer: Option<String>,
+    /// Required unless the server has a TOKEN_DEFAULT_DECIMALS
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Same as for /token/create: absent uses the server's default, `null` disables freezing
+    #[serde(
+        rename = "freezeAuthority",
+        alias = "freeze_authority",
+        alias = "freezeauthority",
+        default,
+        deserialize_with = "present_or_null",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>)]
+    pub freeze_authority: Option<Option<String>>,
+    #[serde(default, alias = "disableFreezeAuthority")]
+    pub disable_freeze_authority: bool,
+}
+
+/// Response for POST /token/create/new
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateNewTokenResponse {
+    /// Address of the generated mint
+    pub mint: String,
+    /// Base58 secret key of the generated mint. It must sign the transaction alongside the
+    /// payer, because create_account needs the signature of the account it creates. It isn't
+    /// needed once the mint exists.
+    pub mint_secret: String,
+    /// Lamports create_account moves from the payer into the mint account
+    pub rent_exempt_lamports: u64,
+    /// The system create_account and the initialize_mint instructions, in order
+    pub instructions: Vec<TokenInstructionResponse>,
+}
+
+zeroize_on_drop!(CreateNewTokenResponse: mint_secret);
+
+/// Deserializes a field that was present in the JSON, so `null` becomes `Some(None)` while an
+/// absent field falls back to `None` via `#[serde(default)]`
+fn present_or_null<'de, D, T>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 /// Request for POST /token/mint
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct MintTokenRequest {
     pub mint: String,
     pub destination: String,
     pub authority: String,
+    /// A JSON number, or a decimal string for amounts above 2^53 that JavaScript numbers can't
+    /// hold exactly
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub amount: u64,
+}
+
+/// Deserializes a u64 from either a JSON number or a string of decimal digits, such as
+/// "18446744073709551615"
+///
+/// Errors say "expected u64" like serde's own, so they get the same range message.
+fn u64_from_number_or_string<'de, D>(deserializer: D) -> std::result::Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct NumberOrString;
+
+    impl serde::de::Visitor<'_> for NumberOrString {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("u64 as a number or a string of decimal digits")
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> std::result::Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> std::result::Result<u64, E> {
+            u64::try_from(value).map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> std::result::Result<u64, E> {
+            let invalid = || E::invalid_value(serde::de::Unexpected::Str(value), &self);
+            if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            // Only an out-of-range value can fail to parse here
+            value.parse().map_err(|_| invalid())
+        }
+    }
+
+    deserializer.deserialize_any(NumberOrString)
+}
+
+/// Like `u64_from_number_or_string`, for an optional field; `null` is the same as absent
+fn optional_u64_from_number_or_string<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Amount(#[serde(deserialize_with = "u64_from_number_or_string")] u64);
+
+    Option::<Amount>::deserialize(deserializer).map(|amount| amount.map(|Amount(amount)| amount))
+}
+
+/// One recipient in a POST /token/mint/batch request
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MintRecipient {
+    /// Wallet whose associated token account is credited
+    pub destination: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub amount: u64,
+}
+
+/// Request for POST /token/mint/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MintBatchRequest {
+    pub mint: String,
+    pub authority: String,
+    pub recipients: Vec<MintRecipient>,
+    /// Fee payer, which may differ from the authority, e.g. a relayer sponsoring the fees; when
+    /// set, the instructions are also bundled into transaction messages with it as the first
+    /// account. `payer` is accepted for compatibility.
+    #[serde(default, alias = "payer", alias = "feePayer")]
+    pub fee_payer: Option<String>,
+    /// Blockhash for the bundled messages (base58); defaults to all zeroes, to be replaced
+    /// before signing
+    #[serde(default, alias = "recentBlockhash")]
+    pub recent_blockhash: Option<String>,
+}
+
+/// Response for POST /token/mint/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MintBatchResponse {
+    /// One mint_to instruction per recipient, in request order
+    pub instructions: Vec<TokenInstructionResponse>,
+    /// Base64-encoded transaction messages, only present when a payer was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<Vec<String>>,
+}
+
+/// Request for POST /token/burn-close
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BurnCloseRequest {
+    pub mint: String,
+    /// Token account to empty and close
+    pub account: String,
+    /// Owner of the token account
+    pub authority: String,
+    /// Receives the closed account's rent lamports
+    pub destination: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
     pub amount: u64,
 }
 
+/// Response for POST /token/burn-close: the burn and close_account instructions, in that order
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct BurnCloseResponse {
+    pub instructions: Vec<TokenInstructionResponse>,
+}
+
+/// Request for POST /token/wrap
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WrapSolRequest {
+    /// Wallet that pays for and owns the wrapped SOL account
+    pub owner: String,
+    /// Lamports of SOL to move into the wrapped SOL account
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub lamports: u64,
+}
+
+/// Request for POST /token/unwrap
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UnwrapSolRequest {
+    /// Owner of the wrapped SOL account; receives its lamports
+    pub owner: String,
+    /// Wrapped SOL account to close; defaults to the owner's associated token account
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+/// Request for POST /token/sync-native
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SyncNativeRequest {
+    /// Wrapped SOL token account whose token balance should catch up with its lamports
+    pub account: String,
+}
+
+/// Response for POST /token/wrap and POST /token/unwrap
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct WrappedSolResponse {
+    /// The wrapped SOL token account
+    pub account: String,
+    /// Instructions to include in one transaction, in order
+    pub instructions: Vec<TokenInstructionResponse>,
+}
+
 /// Response for token-related endpoints
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct TokenInstructionResponse {
     pub program_id: String,
     pub accounts: Vec<AccountMeta>,
@@ -66,7 +1071,7 @@ pub struct TokenInstructionResponse {
 }
 
 /// Account metadata for Solana instructions
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct AccountMeta {
     pub pubkey: String,
     pub is_signer: bool,
@@ -74,46 +1079,256 @@ pub struct AccountMeta {
 }
 
 /// Request for POST /message/sign
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct SignMessageRequest {
     pub message: String,
     pub secret: String,
+    /// Prepended to the message before signing: the signed bytes are the UTF-8 prefix followed
+    /// directly by the UTF-8 message, with no separator
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Sign an empty message instead of rejecting it
+    #[serde(default, alias = "allowEmpty")]
+    pub allow_empty: bool,
+}
+
+zeroize_on_drop!(SignMessageRequest: secret);
+
+/// Request for POST /transaction/sign
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SignTransactionRequest {
+    /// Base64-encoded, bincode-serialized transaction message
+    pub message: String,
+    /// Base58-encoded secret keys, one per required signer
+    pub secrets: Vec<String>,
+}
+
+zeroize_on_drop!(SignTransactionRequest: secrets);
+
+/// Response for POST /transaction/sign
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SignTransactionResponse {
+    /// Base64-encoded, bincode-serialized signed transaction, ready for /rpc/send
+    pub transaction: String,
+    /// The fee payer's signature, which identifies the transaction
+    pub signature: String,
+}
+
+/// Request for POST /transaction/sign/partial
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PartialSignTransactionRequest {
+    /// Base64-encoded, bincode-serialized transaction, with or without some signatures
+    pub transaction: String,
+    /// Base58-encoded secret keys of the signers to add
+    pub secrets: Vec<String>,
+}
+
+zeroize_on_drop!(PartialSignTransactionRequest: secrets);
+
+/// Response for POST /transaction/sign/partial
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct PartialSignTransactionResponse {
+    /// Base64-encoded transaction with the new signatures added
+    pub transaction: String,
+    /// Required signers that still haven't signed
+    pub missing: Vec<String>,
+    /// Whether every required signer has now signed
+    pub complete: bool,
+}
+
+/// Request for POST /transaction/verify
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyTransactionRequest {
+    /// Base64-encoded, bincode-serialized transaction
+    pub transaction: String,
+}
+
+/// Response for POST /transaction/verify
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct VerifyTransactionResponse {
+    /// Whether every required signer has a valid signature
+    pub complete: bool,
+    /// Required signers with no signature yet
+    pub missing: Vec<String>,
+    /// Required signers whose signature doesn't match the message
+    pub invalid: Vec<String>,
+}
+
+/// Request for POST /transaction/signature
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionSignatureRequest {
+    /// Base64-encoded, bincode-serialized transaction signed by at least its fee payer
+    pub transaction: String,
+}
+
+/// Response for POST /transaction/signature
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TransactionSignatureResponse {
+    /// The fee payer's signature, base58-encoded: the id the cluster will know the transaction by
+    pub signature: String,
+    pub fee_payer: String,
 }
 
 /// Response for POST /message/sign
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct SignMessageResponse {
     pub signature: String,
     pub pubkey: String,
     pub message: String,
 }
 
+/// Request for POST /message/sign/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SignMessageBatchRequest {
+    pub secret: String,
+    pub messages: Vec<String>,
+    /// Prepended to every message before signing, as for /message/sign
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Sign empty messages instead of rejecting them
+    #[serde(default, alias = "allowEmpty")]
+    pub allow_empty: bool,
+}
+
+zeroize_on_drop!(SignMessageBatchRequest: secret);
+
+/// Response for POST /message/sign/batch
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SignMessageBatchResponse {
+    pub pubkey: String,
+    /// Base58-encoded signatures, one per message, in request order
+    pub signatures: Vec<String>,
+}
+
 /// Request for POST /message/verify
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct VerifyMessageRequest {
     pub message: String,
     pub signature: String,
     pub pubkey: String,
+    /// The prefix the message was signed with, prepended the same way as for /message/sign
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// How pubkey is encoded: "base58" (a Solana address, the default), or the raw 32 bytes as
+    /// "hex", "base64" or "base64url"
+    #[serde(default, alias = "publicKeyEncoding")]
+    pub public_key_encoding: Option<String>,
+    /// How signature is encoded: "base58" (as /message/sign returns it, the default), the raw
+    /// 64 bytes as "hex", "base64" or "base64url", or "auto" to detect base64, base58 or hex
+    #[serde(default, alias = "signatureEncoding")]
+    pub signature_encoding: Option<String>,
+    /// Use ed25519 strict verification (matches the Solana runtime's acceptance set)
+    #[serde(default)]
+    pub strict: bool,
+    /// Verify a signature over an empty message instead of rejecting it
+    #[serde(default, alias = "allowEmpty")]
+    pub allow_empty: bool,
 }
 
 /// Response for POST /message/verify
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct VerifyMessageResponse {
     pub valid: bool,
     pub message: String,
     pub pubkey: String,
 }
 
+/// Response for POST /auth/challenge
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AuthChallengeResponse {
+    /// The text to sign, as-is and without a prefix
+    pub nonce: String,
+    /// Seconds left to redeem the nonce at /auth/verify
+    pub expires_in_secs: u64,
+}
+
+/// Request for POST /auth/verify
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuthVerifyRequest {
+    pub pubkey: String,
+    /// Base58 signature of the nonce by `pubkey`
+    pub signature: String,
+    pub nonce: String,
+}
+
+/// Response for POST /auth/verify
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AuthVerifyResponse {
+    /// Always true; a failed proof is a 401
+    pub verified: bool,
+    /// The wallet shown to hold the key
+    pub pubkey: String,
+}
+
+/// One route's entry in GET /examples
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct RouteExample {
+    /// Sample request body; absent for routes that take none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub request: Option<serde_json::Value>,
+    /// Sample response body, including the success envelope
+    #[schema(value_type = Object)]
+    pub response: serde_json::Value,
+}
+
+/// Response for POST /siws/build
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SiwsBuildResponse {
+    /// The message text for the wallet to sign
+    pub message: String,
+    /// The fields the message was built from, including any nonce and issued-at time filled in
+    pub fields: SiwsMessage,
+}
+
+/// Request for POST /siws/verify
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SiwsVerifyRequest {
+    /// The signed message text, exactly as the wallet signed it
+    pub message: String,
+    /// Base58-encoded signature by the message's address
+    pub signature: String,
+    /// The domain the message must be for
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// The nonce the server issued, which the message must carry
+    #[serde(default)]
+    pub nonce: Option<String>,
+}
+
+/// Response for POST /siws/verify
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SiwsVerifyResponse {
+    /// Whether the signature is valid and every check passed
+    pub valid: bool,
+    /// Why the sign-in is not valid, when it isn't
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The fields parsed from the message
+    pub fields: SiwsMessage,
+}
+
 /// Request for POST /send/sol
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct SendSolRequest {
     pub from: String,
     pub to: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
     pub lamports: u64,
 }
 
 /// Response for POST /send/sol
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct SendSolResponse {
     pub program_id: String,
     pub accounts: Vec<String>,
@@ -121,16 +1336,48 @@ pub struct SendSolResponse {
 }
 
 /// Request for POST /send/token
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct SendTokenRequest {
     pub destination: String,
     pub mint: String,
     pub owner: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
     pub amount: u64,
+    /// Also create the destination wallet's associated token account if it doesn't exist yet,
+    /// paid for by the owner
+    #[serde(default, alias = "createAtaIfMissing")]
+    pub create_ata_if_missing: bool,
+}
+
+/// Request for POST /send/token-with-memo
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendTokenWithMemoRequest {
+    pub destination: String,
+    pub mint: String,
+    pub owner: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub amount: u64,
+    /// Attached to the transfer by a memo instruction signed by the owner, e.g. an exchange
+    /// deposit reference
+    pub memo: String,
+    /// Also create the destination wallet's associated token account if it doesn't exist yet,
+    /// paid for by the owner
+    #[serde(default, alias = "createAtaIfMissing")]
+    pub create_ata_if_missing: bool,
+}
+
+/// Response for POST /send/token-with-memo
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SendTokenWithMemoResponse {
+    /// The optional create-ATA instruction, the memo instruction and the transfer, to include
+    /// in one transaction in this order
+    pub instructions: Vec<TokenInstructionResponse>,
 }
 
 /// Account metadata for send token endpoint (different naming convention)
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct SendTokenAccountMeta {
     pub pubkey: String,
     #[serde(rename = "isSigner")]
@@ -138,17 +1385,275 @@ pub struct SendTokenAccountMeta {
 }
 
 /// Response for POST /send/token
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct SendTokenResponse {
     pub program_id: String,
     pub accounts: Vec<SendTokenAccountMeta>,
     pub instruction_data: String,
+    /// Set when `create_ata_if_missing` is true: the idempotent create-ATA instruction followed by
+    /// the transfer, with full account metas, to include in one transaction in order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<Vec<TokenInstructionResponse>>,
+}
+
+/// Request for POST /stake/create
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateStakeAccountRequest {
+    /// Wallet that funds the new stake account and pays its rent
+    pub from: String,
+    /// Address of the new stake account; must sign the transaction, like any created account
+    #[serde(alias = "stakeAccount")]
+    pub stake_account: String,
+    /// Lamports to stake, on top of the rent-exempt minimum
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub lamports: u64,
+    /// May delegate and deactivate the stake; defaults to `from`
+    #[serde(default)]
+    pub staker: Option<String>,
+    /// May withdraw from the stake account; defaults to `from`
+    #[serde(default)]
+    pub withdrawer: Option<String>,
+}
+
+/// Response for POST /stake/create
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateStakeAccountResponse {
+    pub stake_account: String,
+    /// Lamports moved into the stake account: the staked amount plus the rent-exempt minimum
+    pub lamports: u64,
+    /// Rent-exempt minimum for a stake account, which stays in it while it exists
+    pub rent_exempt_lamports: u64,
+    /// The system create_account and the stake initialize instructions, in order
+    pub instructions: Vec<TokenInstructionResponse>,
+}
+
+/// Request for POST /stake/delegate
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DelegateStakeRequest {
+    #[serde(alias = "stakeAccount")]
+    pub stake_account: String,
+    /// Vote account of the validator to delegate to
+    #[serde(alias = "voteAccount")]
+    pub vote_account: String,
+    /// The stake account's staker authority, which signs the delegation
+    pub staker: String,
+}
+
+/// Request for POST /stake/deactivate
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeactivateStakeRequest {
+    #[serde(alias = "stakeAccount")]
+    pub stake_account: String,
+    /// The stake account's staker authority, which signs the deactivation
+    pub staker: String,
+}
+
+/// Request for POST /stake/withdraw
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WithdrawStakeRequest {
+    #[serde(alias = "stakeAccount")]
+    pub stake_account: String,
+    /// The stake account's withdraw authority, which signs the withdrawal
+    pub withdrawer: String,
+    /// Account that receives the lamports
+    pub destination: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub lamports: u64,
+}
+
+/// Request for POST /token/transfer-fee/create-mint
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTransferFeeMintRequest {
+    #[serde(alias = "mintAuthority")]
+    pub mint_authority: String,
+    pub mint: String,
+    pub decimals: u8,
+    /// Freezes token accounts of the mint; no freeze authority when absent
+    #[serde(default, alias = "freezeAuthority")]
+    pub freeze_authority: Option<String>,
+    /// Fee charged on every transfer, in hundredths of a percent (at most 10000, i.e. 100%)
+    #[serde(alias = "transferFeeBasisPoints")]
+    pub transfer_fee_basis_points: u16,
+    /// Cap on the fee charged on one transfer, in base units of the token
+    #[serde(alias = "maximumFee", deserialize_with = "u64_from_number_or_string")]
+    pub maximum_fee: u64,
+    /// May change the transfer fee later; defaults to the mint authority
+    #[serde(default, alias = "transferFeeConfigAuthority")]
+    pub transfer_fee_config_authority: Option<String>,
+    /// May withdraw fees withheld in token accounts; defaults to the mint authority
+    #[serde(default, alias = "withdrawWithheldAuthority")]
+    pub withdraw_withheld_authority: Option<String>,
+}
+
+/// Response for POST /token/transfer-fee/create-mint
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateTransferFeeMintResponse {
+    pub mint: String,
+    /// Size the mint account must be created with to hold the transfer-fee extension
+    pub space: usize,
+    /// Rent-exempt minimum for a mint account of that size
+    pub rent_exempt_lamports: u64,
+    /// The initialize_transfer_fee_config and initialize_mint instructions, in order, to follow
+    /// a system create_account of `space` bytes owned by the Token-2022 program
+    pub instructions: Vec<TokenInstructionResponse>,
+}
+
+/// Request for POST /token/transfer-fee/transfer
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TransferWithFeeRequest {
+    /// Wallet that receives the tokens; they go to its Token-2022 associated token account
+    pub destination: String,
+    pub mint: String,
+    pub owner: String,
+    #[serde(deserialize_with = "u64_from_number_or_string")]
+    pub amount: u64,
+    /// The mint's decimals, which the token program checks
+    pub decimals: u8,
+    /// Fee the mint charges on this transfer, which the token program checks. Computed from
+    /// `transfer_fee_basis_points` and `maximum_fee` when absent.
+    #[serde(default, deserialize_with = "optional_u64_from_number_or_string")]
+    pub fee: Option<u64>,
+    /// The mint's current transfer fee, used to compute `fee`
+    #[serde(default, alias = "transferFeeBasisPoints")]
+    pub transfer_fee_basis_points: Option<u16>,
+    #[serde(default, alias = "maximumFee", deserialize_with = "optional_u64_from_number_or_string")]
+    pub maximum_fee: Option<u64>,
+}
+
+/// Response for POST /token/transfer-fee/transfer
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TransferWithFeeResponse {
+    /// Fee withheld in the destination account out of `amount`
+    pub fee: u64,
+    pub instruction: TokenInstructionResponse,
+}
+
+/// Request for POST /token/metadata/pointer
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InitializeMetadataPointerRequest {
+    /// Token-2022 mint to add the pointer to, before its initialize_mint
+    pub mint: String,
+    /// May change the pointer later; nobody can when absent
+    #[serde(default)]
+    pub authority: Option<String>,
+    /// Account holding the metadata; defaults to the mint itself
+    #[serde(default, alias = "metadataAddress")]
+    pub metadata_address: Option<String>,
+}
+
+/// Request for POST /token/metadata/initialize
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InitializeTokenMetadataRequest {
+    /// Token-2022 mint whose metadata pointer points at itself
+    pub mint: String,
+    /// The mint authority, which signs the initialization
+    #[serde(alias = "mintAuthority")]
+    pub mint_authority: String,
+    /// May update the metadata later; defaults to the mint authority
+    #[serde(default, alias = "updateAuthority")]
+    pub update_authority: Option<String>,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// Request for POST /token/metadata/update
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateTokenMetadataRequest {
+    pub mint: String,
+    /// The metadata's update authority, which signs the update
+    #[serde(alias = "updateAuthority")]
+    pub update_authority: String,
+    /// "name", "symbol", "uri", or any other key to set as additional metadata
+    pub field: String,
+    pub value: String,
+}
+
+/// Request for POST /token/close-mint/create-mint
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateCloseableMintRequest {
+    #[serde(alias = "mintAuthority")]
+    pub mint_authority: String,
+    pub mint: String,
+    pub decimals: u8,
+    /// Freezes token accounts of the mint; no freeze authority when absent
+    #[serde(default, alias = "freezeAuthority")]
+    pub freeze_authority: Option<String>,
+    /// May close the mint once its supply is zero; defaults to the mint authority
+    #[serde(default, alias = "closeAuthority")]
+    pub close_authority: Option<String>,
+}
+
+/// Response for POST /token/close-mint/create-mint
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateCloseableMintResponse {
+    pub mint: String,
+    /// Size the mint account must be created with to hold the mint-close-authority extension
+    pub space: usize,
+    /// Rent-exempt minimum for a mint account of that size
+    pub rent_exempt_lamports: u64,
+    /// The initialize_mint_close_authority and initialize_mint instructions, in order, to follow
+    /// a system create_account of `space` bytes owned by the Token-2022 program
+    pub instructions: Vec<TokenInstructionResponse>,
+}
+
+/// Request for POST /token/close-mint
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CloseMintRequest {
+    pub mint: String,
+    /// Receives the mint account's rent
+    pub destination: String,
+    /// The mint's close authority, which signs the close
+    #[serde(alias = "closeAuthority")]
+    pub close_authority: String,
+}
+
+/// One operation in a POST /batch request
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BatchCall {
+    /// Optional caller-chosen value echoed in this call's result
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub id: Option<serde_json::Value>,
+    /// The endpoint's path without the leading slash, e.g. "token/create"
+    pub method: String,
+    /// The request body the endpoint takes; may be omitted for "keypair"
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub params: serde_json::Value,
+}
+
+/// The outcome of one BatchCall: the endpoint's own response plus the HTTP status it would
+/// have returned
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct BatchResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub id: Option<serde_json::Value>,
+    pub status: u16,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub data: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json;
 
     #[test]
     fn test_api_response_serialization() {
@@ -171,6 +1676,73 @@ mod tests {
         assert!(json.contains("success"));
         assert!(json.contains("false"));
         assert!(json.contains("Test error message"));
+        assert!(!json.contains("code"));
+
+        let not_found = ApiErrorResponse::error("Not Found").with_status_code(axum::http::StatusCode::NOT_FOUND);
+        assert_eq!(not_found.code.as_deref(), Some("NOT_FOUND"));
+        let busy = ApiErrorResponse::error("busy").with_status_code(axum::http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(busy.code.as_deref(), Some("TOO_MANY_REQUESTS"));
+    }
+
+    #[test]
+    fn test_mint_amount_as_number_or_string() {
+        let parse = |amount: &str| {
+            serde_json::from_str::<MintTokenRequest>(&format!(
+                r#"{{"mint":"m","destination":"d","authority":"a","amount":{}}}"#,
+                amount
+            ))
+            .map(|request| request.amount)
+        };
+
+        assert_eq!(parse("1000").unwrap(), 1000);
+        assert_eq!(parse(r#""1000""#).unwrap(), 1000);
+        // Amounts past 2^53 keep every digit when sent as strings
+        assert_eq!(parse(r#""9007199254740993""#).unwrap(), 9_007_199_254_740_993);
+        assert_eq!(parse(r#""18446744073709551614""#).unwrap(), u64::MAX - 1);
+        assert_eq!(parse(r#""18446744073709551615""#).unwrap(), u64::MAX);
+        assert_eq!(parse("18446744073709551615").unwrap(), u64::MAX);
+
+        for invalid in [
+            r#""18446744073709551616""#,
+            r#""99999999999999999999999""#,
+            "18446744073709551616",
+            "-1",
+            r#""-1""#,
+            r#""+1""#,
+            r#"" 1""#,
+            r#""""#,
+            r#""1.5""#,
+            "1.5",
+            "null",
+        ] {
+            let error = parse(invalid).unwrap_err().to_string();
+            assert!(error.contains("expected u64"), "{}: {}", invalid, error);
+        }
+    }
+
+    #[test]
+    fn test_secret_fields_are_zeroized() {
+        let mut request = SignMessageRequest {
+            message: "hello".to_string(),
+            secret: "secret-key".to_string(),
+            prefix: None,
+            allow_empty: false,
+        };
+        request.zeroize();
+        assert!(request.secret.is_empty());
+        // Only secret fields are wiped
+        assert_eq!(request.message, "hello");
+
+        let mut request = SignTransactionRequest {
+            message: "AA==".to_string(),
+            secrets: vec!["first".to_string(), "second".to_string()],
+        };
+        request.zeroize();
+        assert!(request.secrets.is_empty());
+
+        let mut response = ExportKeypairResponse { pubkey: "pubkey".to_string(), keypair: vec![7; 64] };
+        response.zeroize();
+        assert!(response.keypair.is_empty());
     }
 
     #[test]
@@ -180,7 +1752,48 @@ mod tests {
         
         assert_eq!(request.mint_authority, "test_authority");
         assert_eq!(request.mint, "test_mint");
-        assert_eq!(request.decimals, 9);
+        assert_eq!(request.decimals, Some(9));
+    }
+
+    #[test]
+    fn test_import_keypair_request_deserialization() {
+        let json = r#"{"keypair":[1,2,3]}"#;
+        let request: ImportKeypairRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.keypair, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_token_accounts_request_optional_mint() {
+        let request: TokenAccountsRequest = serde_json::from_str(r#"{"owner":"key"}"#).unwrap();
+        assert!(request.mint.is_none());
+
+        let request: TokenAccountsRequest = serde_json::from_str(r#"{"owner":"key","mint":"mint"}"#).unwrap();
+        assert_eq!(request.mint.as_deref(), Some("mint"));
+    }
+
+    #[test]
+    fn test_create_token_request_freeze_authority() {
+        let absent: CreateTokenRequest = serde_json::from_str(r#"{"mintAuthority":"a","mint":"m","decimals":6}"#).unwrap();
+        assert_eq!(absent.freeze_authority, None);
+
+        let null: CreateTokenRequest = serde_json::from_str(r#"{"mintAuthority":"a","mint":"m","decimals":6,"freezeAuthority":null}"#).unwrap();
+        assert_eq!(null.freeze_authority, Some(None));
+
+        let set: CreateTokenRequest = serde_json::from_str(r#"{"mintAuthority":"a","mint":"m","decimals":6,"freezeAuthority":"f"}"#).unwrap();
+        assert_eq!(set.freeze_authority, Some(Some("f".to_string())));
+    }
+
+    #[test]
+    fn test_api_response_omits_meta_by_default() {
+        let json = serde_json::to_value(ApiResponse::success("ok")).unwrap();
+        assert_eq!(json, serde_json::json!({ "success": true, "data": "ok" }));
+    }
+
+    #[test]
+    fn test_account_info_request_default_encoding() {
+        let request: AccountInfoRequest = serde_json::from_str(r#"{"pubkey":"key"}"#).unwrap();
+        assert_eq!(request.encoding, "base64");
     }
 
     #[test]
@@ -189,6 +1802,7 @@ mod tests {
         let request: VerifyMessageRequest = serde_json::from_str(json).unwrap();
         
         assert_eq!(request.pubkey, "key");
+        assert!(!request.strict);
     }
 
     #[test]
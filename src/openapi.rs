@@ -0,0 +1,150 @@
+use utoipa::OpenApi;
+
+use crate::handlers;
+
+/// OpenAPI 3 document for the API, served at GET /openapi.json
+///
+/// Request and response schemas come from the models' ToSchema derives; every handler with a
+/// route must be listed in `paths` (enforced by the test below).
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Solana HTTP Server",
+        description = "REST API for Solana keypairs, SPL token instructions, message signing and RPC lookups"
+    ),
+    paths(
+        handlers::openapi_handler,
+        handlers::health_handler,
+        handlers::deep_health_handler,
+        handlers::version_handler,
+        handlers::examples_handler,
+        handlers::generate_keypair_handler,
+        handlers::public_only_keypair_handler,
+        handlers::keypair_qr_handler,
+        handlers::entropy_check_handler,
+        handlers::keypair_from_seed_handler,
+        handlers::derive_keypair_batch_handler,
+        handlers::export_keypair_handler,
+        handlers::import_keypair_handler,
+        handlers::inspect_keypair_handler,
+        handlers::rotate_keypair_handler,
+        handlers::funded_keypair_handler,
+        handlers::token_accounts_handler,
+        handlers::account_info_handler,
+        handlers::mint_info_handler,
+        handlers::token_account_info_handler,
+        handlers::rent_handler,
+        handlers::send_transaction_handler,
+        handlers::airdrop_handler,
+        handlers::relay_handler,
+        handlers::simulate_transaction_handler,
+        handlers::signature_status_handler,
+        handlers::vote_accounts_handler,
+        handlers::account_ws_handler,
+        handlers::create_token_handler,
+        handlers::create_new_token_handler,
+        handlers::mint_token_handler,
+        handlers::mint_batch_stream_handler,
+        handlers::burn_close_handler,
+        handlers::wrap_sol_handler,
+        handlers::unwrap_sol_handler,
+        handlers::sync_native_handler,
+        handlers::derive_pda_handler,
+        handlers::derive_pda_query_handler,
+        handlers::derive_pda_batch_stream_handler,
+        handlers::associated_token_address_handler,
+        handlers::associated_token_address_query_handler,
+        handlers::associated_token_address_batch_handler,
+        handlers::on_curve_handler,
+        handlers::on_curve_query_handler,
+        handlers::normalize_pubkey_handler,
+        handlers::create_transfer_fee_mint_handler,
+        handlers::transfer_with_fee_handler,
+        handlers::metadata_pointer_handler,
+        handlers::initialize_token_metadata_handler,
+        handlers::update_token_metadata_handler,
+        handlers::create_closeable_mint_handler,
+        handlers::close_mint_handler,
+        handlers::create_stake_account_handler,
+        handlers::delegate_stake_handler,
+        handlers::deactivate_stake_handler,
+        handlers::withdraw_stake_handler,
+        handlers::sign_message_handler,
+        handlers::sign_message_batch_handler,
+        handlers::verify_message_handler,
+        handlers::auth_challenge_handler,
+        handlers::auth_verify_handler,
+        handlers::siws_build_handler,
+        handlers::siws_verify_handler,
+        handlers::sign_transaction_handler,
+        handlers::partial_sign_transaction_handler,
+        handlers::verify_transaction_handler,
+        handlers::transaction_signature_handler,
+        handlers::send_sol_handler,
+        handlers::send_token_handler,
+        handlers::send_token_with_memo_handler,
+        handlers::batch_stream_handler,
+    ),
+    tags(
+        (name = "keypair", description = "Keypair generation, import and export"),
+        (name = "token", description = "SPL token instructions"),
+        (name = "address", description = "Program-derived address and curve point checks"),
+        (name = "stake", description = "Stake program instructions"),
+        (name = "message", description = "Message signing and verification"),
+        (name = "auth", description = "Proving a wallet holds its key by signing a one-time challenge"),
+        (name = "siws", description = "Sign-In With Solana message building and verification"),
+        (name = "transaction", description = "Transaction signing and verification"),
+        (name = "send", description = "SOL and SPL token transfer instructions"),
+        (name = "rpc", description = "Lookups against the configured Solana cluster"),
+        (name = "relay", description = "Submitting client transactions with the server paying the fees"),
+        (name = "batch", description = "Several operations in one request"),
+        (name = "meta", description = "API description, examples, server health and versions"),
+    )
+)]
+pub struct ApiDoc;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// (method, path) for every `.route(...)` registered in the router (ignoring its tests)
+    pub(crate) fn registered_routes() -> Vec<(String, String)> {
+        let source = include_str!("router/mod.rs");
+        let source = source.split("#[cfg(test)]").next().unwrap();
+        source
+            .split(".route(\"")
+            .skip(1)
+            .map(|route| {
+                let (path, rest) = route.split_once('"').unwrap();
+                let method = rest.trim_start_matches([',', ' ']).split('(').next().unwrap();
+                (method.to_string(), path.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let routes = registered_routes();
+        assert!(!routes.is_empty());
+
+        for (method, path) in routes {
+            assert!(
+                doc["paths"][&path][&method].is_object(),
+                "{} {} is missing from the OpenAPI document",
+                method.to_uppercase(),
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_schemas_are_generated() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = &doc["components"]["schemas"];
+
+        assert!(schemas["SendSolRequest"]["properties"]["lamports"].is_object());
+        assert!(schemas["ApiErrorResponse"]["properties"]["error"].is_object());
+        assert_eq!(doc["openapi"], "3.1.0");
+    }
+}
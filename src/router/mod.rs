@@ -1,26 +1,123 @@
 use axum::{
-    routing::post,
+    routing::{get, post},
     Router,
     middleware::{self, Next},
-    response::Response,
+    response::{IntoResponse, Response},
     http::Request,
-    body::Body,
+    body::{Body, HttpBody},
+    extract::{ConnectInfo, State},
 };
-use tower_http::cors::CorsLayer;
-use tracing::info;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::{info, warn};
 use axum::body::{to_bytes, Body as AxumBody};
+use axum::http::header;
 use bytes::Bytes;
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+
+use crate::config::{Config, HmacConfig};
+use crate::state::AppState;
+use crate::models::{ApiErrorResponse, ApiResponse, ResponseMeta};
+use crate::utils::errors::AppError;
+use crate::utils::rate_limit::RateLimiter;
+use crate::utils::validation;
+use crate::utils::idempotency::{
+    Begin, CachedResponse, IdempotencyCache, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER, MAX_KEY_LEN,
+};
 
 use crate::handlers::{
+    openapi_handler,
+    not_found_handler,
+    health_handler,
+    deep_health_handler,
+    version_handler,
+    examples_handler,
     generate_keypair_handler,
+    public_only_keypair_handler,
+    keypair_qr_handler,
+    entropy_check_handler,
+    keypair_from_seed_handler,
+    derive_keypair_batch_handler,
+    export_keypair_handler,
+    import_keypair_handler,
+    inspect_keypair_handler,
+    rotate_keypair_handler,
+    funded_keypair_handler,
+    token_accounts_handler,
+    account_info_handler,
+    mint_info_handler,
+    token_account_info_handler,
+    rent_handler,
+    send_transaction_handler,
+    airdrop_handler,
+    relay_handler,
+    simulate_transaction_handler,
+    signature_status_handler,
+    vote_accounts_handler,
+    account_ws_handler,
     create_token_handler,
+    create_new_token_handler,
     mint_token_handler,
+    mint_batch_stream_handler,
+    burn_close_handler,
+    wrap_sol_handler,
+    unwrap_sol_handler,
+    sync_native_handler,
+    derive_pda_handler,
+    derive_pda_query_handler,
+    derive_pda_batch_stream_handler,
+    associated_token_address_handler,
+    associated_token_address_query_handler,
+    associated_token_address_batch_handler,
+    on_curve_handler,
+    on_curve_query_handler,
+    normalize_pubkey_handler,
+    create_transfer_fee_mint_handler,
+    create_closeable_mint_handler,
+    close_mint_handler,
+    transfer_with_fee_handler,
+    metadata_pointer_handler,
+    initialize_token_metadata_handler,
+    update_token_metadata_handler,
+    create_stake_account_handler,
+    delegate_stake_handler,
+    deactivate_stake_handler,
+    withdraw_stake_handler,
     sign_message_handler,
+    sign_message_batch_handler,
+    sign_transaction_handler,
+    partial_sign_transaction_handler,
+    verify_transaction_handler,
+    transaction_signature_handler,
     verify_message_handler,
+    auth_challenge_handler,
+    auth_verify_handler,
+    siws_build_handler,
+    siws_verify_handler,
     send_sol_handler,
     send_token_handler,
+    send_token_with_memo_handler,
+    batch_stream_handler,
 };
 
+/// Copy of `headers` with the credentials (X-API-Key and X-Signature) replaced by
+/// `<redacted>`, so the request log can't be used to replay them
+fn redact_credentials(headers: &axum::http::HeaderMap) -> axum::http::HeaderMap {
+    let mut headers = headers.clone();
+    for name in [API_KEY_HEADER, SIGNATURE_HEADER] {
+        // Replaces every value the header was sent with
+        if headers.contains_key(name) {
+            headers.insert(name, header::HeaderValue::from_static("<redacted>"));
+        }
+    }
+    headers
+}
+
 /// Generate a curl command from the request details
 fn generate_curl_command(
     method: &str,
@@ -60,7 +157,7 @@ async fn logging_middleware(
 ) -> Response {
     let method = req.method().clone();
     let uri = req.uri().clone();
-    let headers = req.headers().clone();
+    let headers = redact_credentials(req.headers());
     
     // Extract the body for curl logging
     let (parts, body) = req.into_parts();
@@ -114,37 +211,1609 @@ async fn logging_middleware(
     response
 }
 
+/// Whether the query string asks for response diagnostics (`meta=true`)
+fn wants_meta(query: Option<&str>) -> bool {
+    query.is_some_and(|query| query.split('&').any(|pair| pair == "meta=true"))
+}
+
+/// Middleware that adds a `meta` object (server timestamp and latency) to successful JSON
+/// responses when the request has `?meta=true`; other responses pass through untouched
+async fn meta_middleware(
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    if !wants_meta(req.uri().query()) {
+        return next.run(req).await;
+    }
+
+    let response = next.run(req).await;
+    let is_json = response.headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !response.status().is_success() || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body_bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => Bytes::new(),
+    };
+
+    let body_bytes = match serde_json::from_slice::<ApiResponse<serde_json::Value>>(&body_bytes) {
+        Ok(mut api_response) => {
+            api_response.meta = Some(ResponseMeta {
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_millis() as u64),
+                latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+            });
+            match serde_json::to_vec(&api_response) {
+                Ok(bytes) => {
+                    // The body grew, so the handler's Content-Length no longer applies
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                    Bytes::from(bytes)
+                }
+                Err(_) => body_bytes,
+            }
+        }
+        Err(_) => body_bytes,
+    };
+
+    Response::from_parts(parts, AxumBody::from(body_bytes))
+}
+
+/// Whether the response should drop the `{ success, data }` envelope: `raw=true` or `raw=false`
+/// in the query string, otherwise the server's default
+fn wants_raw(query: Option<&str>, envelope: bool) -> bool {
+    query
+        .and_then(|query| query.split('&').rev().find_map(|pair| match pair {
+            "raw=true" => Some(true),
+            "raw=false" => Some(false),
+            _ => None,
+        }))
+        .unwrap_or(!envelope)
+}
+
+/// Middleware that unwraps JSON responses for clients that prefer plain REST bodies
+///
+/// A success returns its `data` alone and an error returns `{ error, code }`, with the HTTP
+/// status saying which it is. Bodies that aren't in the envelope pass through untouched.
+async fn raw_response_middleware(
+    State(envelope): State<bool>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !wants_raw(req.uri().query(), envelope) {
+        return next.run(req).await;
+    }
+
+    let response = next.run(req).await;
+    let is_json = response.headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body_bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => Bytes::new(),
+    };
+
+    let unwrapped = if parts.status.is_success() {
+        serde_json::from_slice::<ApiResponse<serde_json::Value>>(&body_bytes)
+            .ok()
+            .and_then(|api_response| serde_json::to_vec(&api_response.data).ok())
+    } else {
+        serde_json::from_slice::<ApiErrorResponse>(&body_bytes)
+            .ok()
+            .and_then(|error| serde_json::to_vec(&serde_json::json!({ "error": error.error, "code": error.code })).ok())
+    };
+    let body_bytes = match unwrapped {
+        Some(bytes) => {
+            // The body shrank, so the handler's Content-Length no longer applies
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Bytes::from(bytes)
+        }
+        None => body_bytes,
+    };
+
+    Response::from_parts(parts, AxumBody::from(body_bytes))
+}
+
+/// Middleware that abandons a request still running after its route's timeout, answering 504
+/// instead
+///
+/// The handler's future is dropped, so async work such as RPC calls stops at its next await.
+/// An Idempotency-Key held by the request is released, so the client can retry with it.
+async fn timeout_middleware(
+    State(config): State<Arc<Config>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let route = req.uri().path().to_string();
+    let timeout = config.timeout_for(&route);
+    match tokio::time::timeout(timeout, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!("{} did not complete within {:?}; abandoned", route, timeout);
+            AppError::GatewayTimeout(format!("Request did not complete within {}s", timeout.as_secs_f64())).into_response()
+        }
+    }
+}
+
+/// Whether the query string asks for instructions in the @solana/web3.js shape (`shape=web3js`)
+///
+/// `shape=default` asks for the usual shape; any other shape is an error.
+fn wants_web3js_shape(query: Option<&str>) -> std::result::Result<bool, AppError> {
+    let shape = query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter_map(|pair| pair.strip_prefix("shape="))
+        .next_back();
+    match shape {
+        None | Some("default") => Ok(false),
+        Some("web3js") => Ok(true),
+        Some(other) => Err(AppError::ValidationError(format!("shape must be default or web3js, got '{}'", other))),
+    }
+}
+
+/// Rewrites every instruction in `value` into the JSON a web3.js `TransactionInstruction`
+/// takes: `{ programId, keys: [{ pubkey, isSigner, isWritable }], data: [bytes] }`
+///
+/// Only objects with full account metas are instructions; the /send/sol and /send/token
+/// top-level account lists don't say which accounts are writable, so they are left as they are.
+fn to_web3js_instructions(value: &mut serde_json::Value) {
+    use serde_json::{json, Value};
+
+    let web3js = |object: &serde_json::Map<String, Value>| -> Option<Value> {
+        let program_id = object.get("program_id")?.as_str()?;
+        let data = validation::decode_base64(object.get("instruction_data")?.as_str()?).ok()?;
+        let keys = object
+            .get("accounts")?
+            .as_array()?
+            .iter()
+            .map(|account| {
+                Some(json!({
+                    "pubkey": account.get("pubkey")?.as_str()?,
+                    "isSigner": account.get("is_signer")?.as_bool()?,
+                    "isWritable": account.get("is_writable")?.as_bool()?,
+                }))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(json!({ "programId": program_id, "keys": keys, "data": data }))
+    };
+
+    match value {
+        Value::Object(object) => match web3js(object) {
+            Some(instruction) => *value = instruction,
+            None => object.values_mut().for_each(to_web3js_instructions),
+        },
+        Value::Array(values) => values.iter_mut().for_each(to_web3js_instructions),
+        _ => {}
+    }
+}
+
+/// Middleware that reshapes the instructions in successful JSON responses for web3.js when the
+/// request has `?shape=web3js`; other responses pass through untouched
+async fn web3js_shape_middleware(
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    match wants_web3js_shape(req.uri().query()) {
+        Ok(true) => {}
+        Ok(false) => return next.run(req).await,
+        Err(e) => return e.into_response(),
+    }
+
+    let response = next.run(req).await;
+    let is_json = response.headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !response.status().is_success() || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body_bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => Bytes::new(),
+    };
+
+    let body_bytes = match serde_json::from_slice::<ApiResponse<serde_json::Value>>(&body_bytes) {
+        Ok(mut api_response) => {
+            to_web3js_instructions(&mut api_response.data);
+            match serde_json::to_vec(&api_response) {
+                Ok(bytes) => {
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                    Bytes::from(bytes)
+                }
+                Err(_) => body_bytes,
+            }
+        }
+        Err(_) => body_bytes,
+    };
+
+    Response::from_parts(parts, AxumBody::from(body_bytes))
+}
+
+/// MIME type clients send in Accept to get MessagePack instead of JSON
+const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Whether the Accept header asks for MessagePack (`application/msgpack` or the older
+/// `application/x-msgpack`)
+fn wants_msgpack(headers: &axum::http::HeaderMap) -> bool {
+    headers.get_all(header::ACCEPT).iter().any(|value| {
+        value.to_str().is_ok_and(|accept| {
+            accept.split(',').any(|media_type| {
+                let media_type = media_type.split(';').next().unwrap_or("").trim();
+                media_type.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE)
+                    || media_type.eq_ignore_ascii_case("application/x-msgpack")
+            })
+        })
+    })
+}
+
+/// Middleware that re-encodes JSON responses (success and error envelopes alike) as
+/// MessagePack when the client sends `Accept: application/msgpack`; JSON stays the default
+async fn msgpack_middleware(
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let msgpack = wants_msgpack(req.headers());
+    let mut response = next.run(req).await;
+    response.headers_mut().append(header::VARY, header::ACCEPT.into());
+
+    let is_json = response.headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !msgpack || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body_bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => Bytes::new(),
+    };
+
+    let encoded = serde_json::from_slice::<serde_json::Value>(&body_bytes)
+        .ok()
+        .and_then(|value| rmp_serde::to_vec_named(&value).ok());
+
+    match encoded {
+        Some(bytes) => {
+            parts.headers.insert(header::CONTENT_TYPE, header::HeaderValue::from_static(MSGPACK_CONTENT_TYPE));
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, AxumBody::from(bytes))
+        }
+        None => Response::from_parts(parts, AxumBody::from(body_bytes)),
+    }
+}
+
+/// Middleware that gives axum's bodiless 405 (a known path with the wrong HTTP method) the
+/// standard error envelope
+///
+/// axum adds the Allow header listing the route's methods once the response leaves the route,
+/// whatever the body, so only the body is replaced here.
+async fn method_not_allowed_middleware(
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let response = next.run(req).await;
+    if response.status() != axum::http::StatusCode::METHOD_NOT_ALLOWED {
+        return response;
+    }
+
+    AppError::MethodNotAllowed(format!(
+        "{} is not allowed on {}; see the Allow header for the methods this route accepts",
+        method, path
+    ))
+    .into_response()
+}
+
+/// Middleware for write endpoints that honours an `Idempotency-Key` header: a retried request
+/// (same key, route and body) gets the original successful response back instead of being run
+/// again, and a retry that arrives while the original is still running gets a 409
+async fn idempotency_middleware(
+    State(cache): State<Arc<IdempotencyCache>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(req).await;
+    };
+    let key = match key.to_str().map(str::trim) {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => key.to_string(),
+        _ => {
+            return AppError::ValidationError(format!(
+                "Idempotency-Key must be 1 to {} visible ASCII characters",
+                MAX_KEY_LEN
+            ))
+            .into_response();
+        }
+    };
+
+    let route = req.uri().path().to_string();
+    let (parts, body) = req.into_parts();
+    let body_bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => Bytes::new(),
+    };
+
+    let pending = match cache.begin(&key, &route, &body_bytes) {
+        Begin::Started(pending) => pending,
+        Begin::InFlight => {
+            return AppError::Conflict("A request with this Idempotency-Key is still in progress".to_string())
+                .into_response();
+        }
+        Begin::Replay(cached) => {
+            info!("Replaying {} response for Idempotency-Key {}", route, key);
+            let mut response = Response::new(AxumBody::from(cached.body));
+            *response.status_mut() = cached.status;
+            if let Some(content_type) = cached.content_type {
+                response.headers_mut().insert(header::CONTENT_TYPE, content_type);
+            }
+            response.headers_mut().insert(IDEMPOTENT_REPLAYED_HEADER, header::HeaderValue::from_static("true"));
+            return response;
+        }
+    };
+
+    let response = next.run(Request::from_parts(parts, AxumBody::from(body_bytes))).await;
+    // Failures aren't cached (dropping `pending` releases the key) so they can be retried
+    if !response.status().is_success() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body_bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, AxumBody::empty()),
+    };
+    pending.complete(CachedResponse {
+        status: parts.status,
+        content_type: parts.headers.get(header::CONTENT_TYPE).cloned(),
+        body: body_bytes.clone(),
+    });
+
+    Response::from_parts(parts, AxumBody::from(body_bytes))
+}
+
+/// Header clients send their API key in when `api_keys` are configured
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Compares two keys in time that depends only on their lengths, so response timing doesn't
+/// reveal how much of a guessed key matched
+fn keys_match(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Header HMAC-signing clients send the hex HMAC-SHA256 of `{X-Timestamp}.{body}` in
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// Header with the Unix time, in seconds, that a signed request was made
+pub const TIMESTAMP_HEADER: &str = "x-timestamp";
+
+/// Largest body a signed request may have, as it is read in full to check the signature
+const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Checks a request's X-Signature against the shared secret and its X-Timestamp against the
+/// server's clock, handing back the request with its body restored
+async fn verify_request_signature(hmac: &HmacConfig, req: Request<Body>) -> Result<Request<Body>, AppError> {
+    let Some(secret) = &hmac.secret else {
+        return Ok(req);
+    };
+    let unauthorized = |message: &str| AppError::Unauthorized(message.to_string());
+
+    let Some(signature) = req.headers().get(SIGNATURE_HEADER) else {
+        return Err(unauthorized("Missing X-Signature header"));
+    };
+    let signature = signature
+        .to_str()
+        .ok()
+        .and_then(validation::decode_hex)
+        .ok_or_else(|| unauthorized("X-Signature header must be a hex HMAC-SHA256"))?;
+
+    let timestamp = req.headers().get(TIMESTAMP_HEADER).and_then(|value| value.to_str().ok()).map(str::to_string);
+    let Some((timestamp, seconds)) = timestamp.and_then(|text| text.parse::<u64>().ok().map(|seconds| (text, seconds))) else {
+        return Err(unauthorized("X-Timestamp header must be the request's Unix time in seconds"));
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    if now.abs_diff(seconds) > hmac.max_skew.as_secs() {
+        return Err(AppError::Unauthorized(format!(
+            "X-Timestamp is more than {}s from the server's clock",
+            hmac.max_skew.as_secs()
+        )));
+    }
+
+    let (parts, body) = req.into_parts();
+    let body_bytes = to_bytes(body, MAX_SIGNED_BODY_BYTES)
+        .await
+        .map_err(|_| AppError::BadRequest(format!("Signed request bodies may be at most {} bytes", MAX_SIGNED_BODY_BYTES)))?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(&body_bytes);
+    // verify_slice compares in constant time
+    mac.verify_slice(&signature).map_err(|_| unauthorized("Invalid request signature"))?;
+
+    Ok(Request::from_parts(parts, AxumBody::from(body_bytes)))
+}
+
+/// Middleware that rejects unauthenticated requests with a 401 when API keys or HMAC signing
+/// are configured
+///
+/// A request with an X-Signature header is checked against the HMAC secret; any other request
+/// needs a valid X-API-Key header.
+async fn api_key_middleware(
+    State(config): State<Arc<Config>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if config.hmac.is_enabled() && (req.headers().contains_key(SIGNATURE_HEADER) || config.api_keys.is_empty()) {
+        return match verify_request_signature(&config.hmac, req).await {
+            Ok(req) => next.run(req).await,
+            Err(e) => {
+                warn!("Rejected signed request: {}", e);
+                e.into_response()
+            }
+        };
+    }
+    if config.api_keys.is_empty() {
+        return next.run(req).await;
+    }
+
+    let given = req.headers().get(API_KEY_HEADER).map(|value| value.as_bytes());
+    let authorized = given.is_some_and(|given| {
+        config.api_keys.iter().fold(false, |found, key| keys_match(given, key.as_bytes()) | found)
+    });
+    if !authorized {
+        let message = if given.is_some() { "Invalid API key" } else { "Missing X-API-Key header" };
+        return AppError::Unauthorized(message.to_string()).into_response();
+    }
+
+    next.run(req).await
+}
+
+/// Middleware that answers 429 Too Many Requests, with a Retry-After header, once a client has
+/// used its per-minute budget
+///
+/// Clients are told apart by API key when one is sent, otherwise by peer IP address.
+async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiter>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let client = match req.headers().get(API_KEY_HEADER).and_then(|value| value.to_str().ok()) {
+        Some(key) => format!("key:{}", key),
+        None => req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map_or_else(|| "unknown".to_string(), |ConnectInfo(addr)| format!("ip:{}", addr.ip())),
+    };
+
+    if let Err(retry_after) = limiter.check(&client) {
+        let mut response = AppError::RateLimited("Rate limit exceeded, try again later".to_string()).into_response();
+        // Round up so clients never retry a moment too early
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response.headers_mut().insert(header::RETRY_AFTER, header::HeaderValue::from(seconds));
+        return response;
+    }
+
+    next.run(req).await
+}
+
+/// Routes that call the cluster or do heavy work, whose requests `max_concurrent_expensive_requests`
+/// limits. /ws/account is left out, since a socket would hold its permit for as long as it is open.
+const EXPENSIVE_ROUTES: [&str; 16] = [
+    "/rpc/token-accounts",
+    "/rpc/account",
+    "/rpc/mint-info",
+    "/rpc/token-account-info",
+    "/rpc/rent",
+    "/rpc/simulate",
+    "/rpc/signature-status",
+    "/rpc/vote-accounts",
+    "/rpc/send",
+    "/rpc/airdrop",
+    "/relay",
+    "/keypair/funded",
+    "/keypair/entropy-check",
+    "/health/deep",
+    "/batch",
+    "/token/mint/batch",
+];
+
+/// Middleware that turns requests to EXPENSIVE_ROUTES away with a 503 while `limit` of them are
+/// already running, so a burst of them can't starve the cheap routes
+///
+/// Nothing queues: a client that is turned away is told to retry after a second. A permit is
+/// held until the response is sent, so a streamed batch, which does its work as its body is
+/// written, holds it until its last line.
+async fn concurrency_limit_middleware(
+    State((limit, permits)): State<(usize, Arc<Semaphore>)>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !EXPENSIVE_ROUTES.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+    let Ok(permit) = permits.try_acquire_owned() else {
+        warn!("Turned away {}: the limit of {} expensive requests in flight is reached", req.uri().path(), limit);
+        let mut response = AppError::ServiceUnavailable(format!(
+            "Too many expensive requests in progress (limit {}); try again shortly",
+            limit
+        ))
+        .into_response();
+        response.headers_mut().insert(header::RETRY_AFTER, header::HeaderValue::from(1));
+        return response;
+    };
+    let response = next.run(req).await;
+    if response.body().size_hint().exact().is_some() {
+        return response;
+    }
+
+    // The body is a stream, dropped (and the permit with it) once sent or the client goes away
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _held = &permit;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+/// CORS policy from `cors.allowed_origins`: any origin when the list is empty, otherwise only
+/// the listed ones
+fn cors_layer(config: &Config) -> CorsLayer {
+    if config.cors_allowed_origins.is_empty() {
+        return CorsLayer::permissive();
+    }
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(config.cors_allowed_origins.clone()))
+        .allow_methods(Any)
+        .allow_headers(Any)
+}
+
 /// Creates and configures the main application router
-pub fn create_router() -> Router {
-    Router::new()
+pub fn create_router(config: Arc<Config>) -> Router {
+    let state = AppState::new(config.clone());
+    let idempotency_cache = Arc::new(IdempotencyCache::new(config.idempotency_ttl));
+
+    // Endpoints that submit to the cluster, where a client retry must not submit twice
+    let write_routes = Router::new()
+        // POST /rpc/send - Submit a signed transaction
+        .route("/rpc/send", post(send_transaction_handler))
+        // POST /rpc/airdrop - Airdrop SOL to a wallet
+        .route("/rpc/airdrop", post(airdrop_handler))
+        // POST /relay - Pay a transaction's fees with the relayer key and submit it
+        .route("/relay", post(relay_handler))
+        .route_layer(middleware::from_fn_with_state(idempotency_cache, idempotency_middleware));
+
+    // Endpoints that produce secret keys, which some deployments must not serve
+    let keypair_generation_routes = Router::new()
         // POST /keypair - Generate new Solana keypair
         .route("/keypair", post(generate_keypair_handler))
+        // POST /keypair/from-seed - Derive a keypair from a 32-byte seed
+        .route("/keypair/from-seed", post(keypair_from_seed_handler))
+        // POST /keypair/derive/batch - Derive sequential accounts m/44'/501'/i'/0' of a mnemonic
+        .route("/keypair/derive/batch", post(derive_keypair_batch_handler))
+        // POST /keypair/funded - Generate a keypair and airdrop SOL to it
+        .route("/keypair/funded", post(funded_keypair_handler))
+        // POST /token/create/new - Generate a mint keypair and the instructions creating the mint
+        .route("/token/create/new", post(create_new_token_handler));
+
+    let router = Router::new()
+        // GET /openapi.json - OpenAPI 3 description of the API
+        .route("/openapi.json", get(openapi_handler))
+        // GET /health/deep - Whether the RPC node is reachable and healthy, for readiness probes
+        .route("/health/deep", get(deep_health_handler))
+        // GET /version - Server version and the Solana crate versions compiled in
+        .route("/version", get(version_handler))
+        // GET /examples - Sample request and response for every JSON route
+        .route("/examples", get(examples_handler))
+        // POST /keypair/public-only - Generate a keypair, discard its secret, return the public key
+        .route("/keypair/public-only", post(public_only_keypair_handler))
+        // GET /keypair/qr - Render a public key as an SVG QR code
+        .route("/keypair/qr", get(keypair_qr_handler))
+        // GET /keypair/entropy-check - Statistical self-test of the keypair generator
+        .route("/keypair/entropy-check", get(entropy_check_handler))
+        // POST /keypair/export - Export secret key as solana-keygen byte array
+        .route("/keypair/export", post(export_keypair_handler))
+        // POST /keypair/import - Import solana-keygen byte array keypair
+        .route("/keypair/import", post(import_keypair_handler))
+        // POST /keypair/inspect - Decode a secret key's length and public keys
+        .route("/keypair/inspect", post(inspect_keypair_handler))
+        // POST /keypair/rotate - Re-sign messages signed by an old key with a new key
+        .route("/keypair/rotate", post(rotate_keypair_handler))
+        // POST /rpc/token-accounts - List a wallet's SPL token accounts
+        .route("/rpc/token-accounts", post(token_accounts_handler))
+        // POST /rpc/account - Look up an account's lamports, owner and data
+        .route("/rpc/account", post(account_info_handler))
+        // POST /rpc/mint-info - Read an SPL token mint's supply, decimals and authorities
+        .route("/rpc/mint-info", post(mint_info_handler))
+        // POST /rpc/token-account-info - Read an SPL token account's balance, delegate and state
+        .route("/rpc/token-account-info", post(token_account_info_handler))
+        // POST /rpc/rent - Rent-exempt minimum for an account size
+        .route("/rpc/rent", post(rent_handler))
+        // POST /rpc/simulate - Simulate a transaction without submitting it
+        .route("/rpc/simulate", post(simulate_transaction_handler))
+        // POST /rpc/signature-status - Confirmation status of a submitted transaction
+        .route("/rpc/signature-status", post(signature_status_handler))
+        // POST /rpc/vote-accounts - Current and delinquent validators' vote accounts
+        .route("/rpc/vote-accounts", post(vote_accounts_handler))
+        // GET /ws/account?pubkey=... - Stream account changes over a WebSocket
+        .route("/ws/account", get(account_ws_handler))
         // POST /token/create - Create SPL token mint instruction
         .route("/token/create", post(create_token_handler))
         // POST /token/mint - Create SPL token mint_to instruction
         .route("/token/mint", post(mint_token_handler))
+        // POST /token/mint/batch - Create mint_to instructions for many recipients (?stream=ndjson)
+        .route("/token/mint/batch", post(mint_batch_stream_handler))
+        // POST /token/burn-close - Create burn + close_account instructions
+        .route("/token/burn-close", post(burn_close_handler))
+        // POST /token/wrap - Create instructions to wrap SOL as an SPL token
+        .route("/token/wrap", post(wrap_sol_handler))
+        // POST /token/unwrap - Create close_account instruction to unwrap wrapped SOL
+        .route("/token/unwrap", post(unwrap_sol_handler))
+        // POST /token/sync-native - Create sync_native instruction for a wrapped SOL account
+        .route("/token/sync-native", post(sync_native_handler))
+        // POST|GET /pda/derive - Find a program-derived address and its bump seed
+        .route("/pda/derive", post(derive_pda_handler).get(derive_pda_query_handler))
+        // POST /pda/derive/batch - Find many program-derived addresses at once (?stream=ndjson)
+        .route("/pda/derive/batch", post(derive_pda_batch_stream_handler))
+        // POST|GET /token/ata - Derive a wallet's associated token account for a mint
+        .route("/token/ata", post(associated_token_address_handler).get(associated_token_address_query_handler))
+        // POST /token/ata/batch - Derive a wallet's associated token accounts for many mints
+        .route("/token/ata/batch", post(associated_token_address_batch_handler))
+        // POST|GET /pubkey/on-curve - Check whether an address is on the ed25519 curve
+        .route("/pubkey/on-curve", post(on_curve_handler).get(on_curve_query_handler))
+        // POST /pubkey/normalize - Convert an address between base58, hex and base64
+        .route("/pubkey/normalize", post(normalize_pubkey_handler))
+        // POST /token/transfer-fee/create-mint - Initialize a Token-2022 mint with a transfer fee
+        .route("/token/transfer-fee/create-mint", post(create_transfer_fee_mint_handler))
+        // POST /token/transfer-fee/transfer - Create Token-2022 transfer_checked_with_fee instruction
+        .route("/token/transfer-fee/transfer", post(transfer_with_fee_handler))
+        // POST /token/metadata/pointer - Create Token-2022 metadata-pointer initialize instruction
+        .route("/token/metadata/pointer", post(metadata_pointer_handler))
+        // POST /token/metadata/initialize - Create token-metadata initialize instruction
+        .route("/token/metadata/initialize", post(initialize_token_metadata_handler))
+        // POST /token/metadata/update - Create token-metadata update_field instruction
+        .route("/token/metadata/update", post(update_token_metadata_handler))
+        // POST /token/close-mint/create-mint - Initialize a Token-2022 mint with a close authority
+        .route("/token/close-mint/create-mint", post(create_closeable_mint_handler))
+        // POST /token/close-mint - Create Token-2022 close_account instruction for a mint
+        .route("/token/close-mint", post(close_mint_handler))
+        // POST /stake/create - Create and initialize a stake account, funded with rent plus the stake
+        .route("/stake/create", post(create_stake_account_handler))
+        // POST /stake/delegate - Delegate a stake account to a vote account
+        .route("/stake/delegate", post(delegate_stake_handler))
+        // POST /stake/deactivate - Deactivate a delegated stake account
+        .route("/stake/deactivate", post(deactivate_stake_handler))
+        // POST /stake/withdraw - Withdraw lamports from a stake account
+        .route("/stake/withdraw", post(withdraw_stake_handler))
         // POST /message/sign - Sign a message with secret key
         .route("/message/sign", post(sign_message_handler))
+        // POST /message/sign/batch - Sign many messages with one secret key
+        .route("/message/sign/batch", post(sign_message_batch_handler))
         // POST /message/verify - Verify a message signature
         .route("/message/verify", post(verify_message_handler))
+        // POST /auth/challenge - Issue a single-use nonce for a wallet to sign
+        .route("/auth/challenge", post(auth_challenge_handler))
+        // POST /auth/verify - Check a signed nonce and redeem it
+        .route("/auth/verify", post(auth_verify_handler))
+        // POST /siws/build - Build a Sign-In With Solana message
+        .route("/siws/build", post(siws_build_handler))
+        // POST /siws/verify - Verify a signed Sign-In With Solana message
+        .route("/siws/verify", post(siws_verify_handler))
+        // POST /transaction/sign - Sign a transaction message with its required signers
+        .route("/transaction/sign", post(sign_transaction_handler))
+        // POST /transaction/sign/partial - Add signatures to a partially signed transaction
+        .route("/transaction/sign/partial", post(partial_sign_transaction_handler))
+        // POST /transaction/verify - Check a transaction's signatures are present and valid
+        .route("/transaction/verify", post(verify_transaction_handler))
+        // POST /transaction/signature - Signature (transaction id) of a signed transaction
+        .route("/transaction/signature", post(transaction_signature_handler))
         // POST /send/sol - Create SOL transfer instruction
         .route("/send/sol", post(send_sol_handler))
         // POST /send/token - Create SPL token transfer instruction
         .route("/send/token", post(send_token_handler))
+        // POST /send/token-with-memo - Create memo + SPL token transfer instructions
+        .route("/send/token-with-memo", post(send_token_with_memo_handler))
+        // POST /batch - Run several operations in one request (?stream=ndjson)
+        .route("/batch", post(batch_stream_handler))
+        .merge(write_routes);
+
+    // Left out entirely when disabled, so the routes 404 like any other unknown path
+    let router = if config.enable_keypair_generation {
+        router.merge(keypair_generation_routes)
+    } else {
+        router
+    }
+    .with_state(state.clone());
+
+    // Concurrency is limited after rate limiting, so a client over its rate doesn't take a slot
+    let router = match config.max_concurrent_expensive_requests {
+        Some(limit) => router.layer(middleware::from_fn_with_state(
+            (limit, Arc::new(Semaphore::new(limit))),
+            concurrency_limit_middleware,
+        )),
+        None => router,
+    };
+
+    // Rate limiting runs after authentication, so unauthenticated requests don't use up budget
+    let router = match config.rate_limit_per_minute {
+        Some(limit) => router.layer(middleware::from_fn_with_state(
+            Arc::new(RateLimiter::new(limit)),
+            rate_limit_middleware,
+        )),
+        None => router,
+    };
+
+    // GET /health - Server status and configured cluster, open to load balancer probes
+    let public_routes = Router::new()
+        .route("/health", get(health_handler))
+        .with_state(state);
+
+    router
+        .layer(middleware::from_fn_with_state(config.clone(), api_key_middleware))
+        // Merged after authentication and rate limiting so health probes need neither
+        .merge(public_routes)
+        // Unknown paths get a JSON 404 rather than an empty body
+        .fallback(not_found_handler)
+        // Give 405s for a wrong HTTP method a JSON body
+        .layer(middleware::from_fn(method_not_allowed_middleware))
+        // Abandon requests that run longer than their route's configured timeout, with a JSON 504
+        .layer(middleware::from_fn_with_state(config.clone(), timeout_middleware))
+        // Reshape instructions for web3.js on ?shape=web3js
+        .layer(middleware::from_fn(web3js_shape_middleware))
+        // Drop the { success, data } envelope for ?raw=true, or by default with RESPONSE_ENVELOPE=false
+        .layer(middleware::from_fn_with_state(config.response_envelope, raw_response_middleware))
         // Add logging middleware
         .layer(middleware::from_fn(logging_middleware))
-        // Add CORS middleware to allow cross-origin requests
-        .layer(CorsLayer::permissive())
+        // Add response diagnostics for ?meta=true (outermost, so latency covers logging too)
+        .layer(middleware::from_fn(meta_middleware))
+        // Encode responses as MessagePack for Accept: application/msgpack (after meta is added)
+        .layer(middleware::from_fn(msgpack_middleware))
+        // Add CORS middleware for cross-origin requests from the configured origins
+        .layer(cors_layer(&config))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::time::Duration;
+
+    use axum::http::StatusCode;
+
     #[test]
     fn test_router_creation() {
-        let _router = create_router();
         // Basic test to ensure router can be created without panicking
-        assert!(true);
+        let _router = create_router(Arc::new(Config::default()));
+    }
+
+    /// Serves the router on a local port, returning its base URL
+    async fn spawn_router() -> String {
+        spawn_router_with(Config::default()).await
+    }
+
+    /// Serves the router built from `config` on a local port, returning its base URL
+    async fn spawn_router_with(config: Config) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = create_router(Arc::new(config)).into_make_service_with_connect_info::<SocketAddr>();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    /// POSTs to `path` on a fresh router and returns the JSON response
+    async fn post_keypair(path: &str) -> (StatusCode, serde_json::Value) {
+        let response = reqwest::Client::new()
+            .post(format!("{}{}", spawn_router().await, path))
+            .send()
+            .await
+            .unwrap();
+        let status = StatusCode::from_u16(response.status().as_u16()).unwrap();
+        (status, response.json().await.unwrap())
+    }
+
+    #[test]
+    fn test_wants_meta() {
+        assert!(wants_meta(Some("meta=true")));
+        assert!(wants_meta(Some("pubkey=abc&meta=true")));
+        assert!(!wants_meta(Some("meta=false")));
+        assert!(!wants_meta(Some("nometa=true")));
+        assert!(!wants_meta(None));
+    }
+
+    #[test]
+    fn test_wants_web3js_shape() {
+        assert!(wants_web3js_shape(Some("shape=web3js")).unwrap());
+        assert!(wants_web3js_shape(Some("meta=true&shape=web3js")).unwrap());
+        assert!(!wants_web3js_shape(Some("shape=default")).unwrap());
+        assert!(!wants_web3js_shape(Some("meta=true")).unwrap());
+        assert!(!wants_web3js_shape(None).unwrap());
+        assert!(matches!(
+            wants_web3js_shape(Some("shape=anchor")),
+            Err(AppError::ValidationError(message)) if message == "shape must be default or web3js, got 'anchor'"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_web3js_instruction_shape() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+        let [mint, destination, authority] = [(); 3].map(|_| solana_sdk::pubkey::Pubkey::new_unique().to_string());
+        let body = serde_json::json!({ "mint": mint, "destination": destination, "authority": authority, "amount": 1000 });
+
+        let response = client.post(format!("{}/token/mint?shape=web3js", base_url)).json(&body).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        let instruction = &body["data"];
+        assert_eq!(instruction["programId"], spl_token::id().to_string());
+        assert_eq!(instruction["keys"][0], serde_json::json!({ "pubkey": mint, "isSigner": false, "isWritable": true }));
+        assert_eq!(instruction["keys"][2], serde_json::json!({ "pubkey": authority, "isSigner": true, "isWritable": false }));
+        // mint_to is tag 7 followed by the little-endian amount
+        assert_eq!(instruction["data"], serde_json::json!([7, 232, 3, 0, 0, 0, 0, 0, 0]));
+        assert!(instruction.get("instruction_data").is_none());
+
+        // Nested instruction lists are reshaped too
+        let body = serde_json::json!({ "owner": authority, "lamports": 1_000_000 });
+        let response = client.post(format!("{}/token/wrap?shape=web3js", base_url)).json(&body).send().await.unwrap();
+        let body: serde_json::Value = response.json().await.unwrap();
+        let instructions = body["data"]["instructions"].as_array().unwrap();
+        assert!(instructions.iter().all(|instruction| instruction["programId"].is_string() && instruction["data"].is_array()));
+
+        let response = client.post(format!("{}/token/mint?shape=anchor", base_url)).json(&body).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+    }
+
+    #[test]
+    fn test_wants_raw() {
+        assert!(wants_raw(Some("raw=true"), true));
+        assert!(!wants_raw(Some("raw=false"), false));
+        assert!(wants_raw(Some("meta=true&raw=false&raw=true"), true));
+        assert!(!wants_raw(Some("meta=true"), true));
+        assert!(wants_raw(None, false));
+        assert!(!wants_raw(None, true));
+    }
+
+    #[tokio::test]
+    async fn test_raw_responses() {
+        let (status, body) = post_keypair("/keypair?raw=true").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["pubkey"].is_string() && body.get("success").is_none());
+
+        let base_url = spawn_router_with(Config { response_envelope: false, ..Config::default() }).await;
+        let client = reqwest::Client::new();
+        let response = client.post(format!("{}/keypair", base_url)).send().await.unwrap();
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap().as_object().unwrap().len(), 2);
+        let response = client.post(format!("{}/keypair?raw=false", base_url)).send().await.unwrap();
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["success"], true);
+
+        // Errors keep their status and drop only the success flag
+        let response = client.get(format!("{}/nowhere", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(
+            response.json::<serde_json::Value>().await.unwrap(),
+            serde_json::json!({ "error": "Not Found", "code": "NOT_FOUND" })
+        );
+
+        // Bodies outside the envelope are left alone
+        let response = client.get(format!("{}/openapi.json", base_url)).send().await.unwrap();
+        assert!(response.json::<serde_json::Value>().await.unwrap()["openapi"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_meta_only_when_requested() {
+        let (status, body) = post_keypair("/keypair").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.get("meta").is_none());
+
+        let (status, body) = post_keypair("/keypair?meta=true").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+        assert!(body["data"]["pubkey"].is_string());
+        assert!(body["meta"]["timestamp"].as_u64().unwrap() > 0);
+        assert!(body["meta"]["latency_ms"].as_f64().unwrap() >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_replays_response() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // A write endpoint that counts how often it actually runs
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new()
+            .route("/write", post(move |body: String| async move {
+                let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                if body == "fail" {
+                    return Err(AppError::RpcError("node unavailable".to_string()));
+                }
+                Ok(axum::Json(ApiResponse::success(call)))
+            }))
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(IdempotencyCache::new(Duration::from_secs(60))),
+                idempotency_middleware,
+            ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/write", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let send = |key: Option<&'static str>, body: &'static str| {
+            let mut request = client.post(&url).body(body);
+            if let Some(key) = key {
+                request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+            }
+            request.send()
+        };
+
+        let first = send(Some("abc"), "ok").await.unwrap();
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        assert_eq!(first.json::<serde_json::Value>().await.unwrap()["data"], 1);
+
+        let retry = send(Some("abc"), "ok").await.unwrap();
+        assert_eq!(retry.status().as_u16(), 200);
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(retry.headers()["content-type"], "application/json");
+        assert_eq!(retry.json::<serde_json::Value>().await.unwrap()["data"], 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Without a key, or with a different body, the request runs again
+        assert_eq!(send(None, "ok").await.unwrap().json::<serde_json::Value>().await.unwrap()["data"], 2);
+        assert_eq!(send(Some("abc"), "other").await.unwrap().json::<serde_json::Value>().await.unwrap()["data"], 3);
+
+        // Failures aren't cached
+        assert_eq!(send(Some("failing"), "fail").await.unwrap().status().as_u16(), 502);
+        assert_eq!(send(Some("failing"), "fail").await.unwrap().status().as_u16(), 502);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        assert_eq!(send(Some(" "), "ok").await.unwrap().status().as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_slow_requests_time_out() {
+        let slow = || async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            axum::Json(ApiResponse::success("done"))
+        };
+        let config = Config {
+            request_timeout: Duration::from_millis(100),
+            route_timeouts: [("/patient".to_string(), Duration::from_secs(5))].into(),
+            ..Config::default()
+        };
+        let app = Router::new()
+            .route("/fast", get(|| async { axum::Json(ApiResponse::success("done")) }))
+            .route("/slow", get(slow))
+            .route("/patient", get(slow))
+            .layer(middleware::from_fn_with_state(Arc::new(config), timeout_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let fast = reqwest::get(format!("{}/fast", base_url)).await.unwrap();
+        assert_eq!(fast.status().as_u16(), 200);
+
+        let slow = reqwest::get(format!("{}/slow", base_url)).await.unwrap();
+        assert_eq!(slow.status().as_u16(), 504);
+        let body: serde_json::Value = slow.json().await.unwrap();
+        assert_eq!(body["success"], false);
+        assert_eq!(body["code"], "GATEWAY_TIMEOUT");
+        assert_eq!(body["error"], "Request did not complete within 0.1s");
+
+        // A route's own timeout replaces the default
+        let patient = reqwest::get(format!("{}/patient", base_url)).await.unwrap();
+        assert_eq!(patient.status().as_u16(), 200);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_funded_keypair_survives_slow_confirmation() {
+        use solana_sdk::signature::Signer;
+
+        // A devnet node that accepts the airdrop but never confirms it
+        let node = Router::new().route(
+            "/",
+            post(|axum::Json(request): axum::Json<serde_json::Value>| async move {
+                let result = match request["method"].as_str().unwrap() {
+                    "getVersion" => serde_json::json!({ "solana-core": "1.18.26", "feature-set": 0 }),
+                    "getGenesisHash" => serde_json::json!(solana_sdk::hash::Hash::new_unique().to_string()),
+                    "requestAirdrop" => serde_json::json!(solana_sdk::signature::Signature::from([7u8; 64]).to_string()),
+                    "getSignatureStatuses" => serde_json::json!({ "context": { "slot": 1 }, "value": [null] }),
+                    method => panic!("unexpected RPC method {}", method),
+                };
+                axum::Json(serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let node_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, node).await.unwrap() });
+
+        let mut config = Config {
+            route_timeouts: [("/keypair/funded".to_string(), Duration::from_secs(3))].into(),
+            ..Config::default()
+        };
+        config.rpc.url = node_url;
+        let base_url = spawn_router_with(config).await;
+
+        // The confirmation wait gives up before the route times out, so the key isn't lost to a 504
+        let response = reqwest::Client::new()
+            .post(format!("{}/keypair/funded", base_url))
+            .json(&serde_json::json!({ "sol": 1.0 }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["data"]["confirmed"], false);
+        let secret = body["data"]["secret"].as_str().unwrap();
+        assert_eq!(solana_sdk::signature::Keypair::from_base58_string(secret).pubkey().to_string(), body["data"]["pubkey"]);
+    }
+
+    #[tokio::test]
+    async fn test_expensive_routes_are_limited() {
+        let slow = || async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            axum::Json(ApiResponse::success("done"))
+        };
+        let app = Router::new()
+            .route("/rpc/account", get(slow))
+            .route("/rpc/simulate", get(slow))
+            .route("/pda/derive", get(slow))
+            .layer(middleware::from_fn_with_state((1, Arc::new(Semaphore::new(1))), concurrency_limit_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let get = |path: &'static str| reqwest::get(format!("{}{}", base_url, path));
+
+        // While one expensive request runs, another is turned away but a cheap one isn't
+        let running = tokio::spawn(get("/rpc/account"));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let (busy, cheap) = tokio::join!(get("/rpc/simulate"), get("/pda/derive"));
+        let busy = busy.unwrap();
+        assert_eq!(busy.status().as_u16(), 503);
+        assert_eq!(busy.headers()["retry-after"], "1");
+        let body: serde_json::Value = busy.json().await.unwrap();
+        assert_eq!(body["code"], "SERVICE_UNAVAILABLE");
+        assert_eq!(body["error"], "Too many expensive requests in progress (limit 1); try again shortly");
+        assert_eq!(cheap.unwrap().status().as_u16(), 200);
+
+        // The slot is free again once the first request finishes
+        assert_eq!(running.await.unwrap().unwrap().status().as_u16(), 200);
+        assert_eq!(get("/rpc/simulate").await.unwrap().status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_batches_hold_their_permit() {
+        // Each line takes a while to produce, as the calls of a streamed /batch do
+        let streamed = || async {
+            let lines = futures::stream::iter(0..3).then(|line| async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok(line)
+            });
+            crate::utils::ndjson::ndjson_response(lines)
+        };
+        let app = Router::new()
+            .route("/batch", get(streamed))
+            .layer(middleware::from_fn_with_state((1, Arc::new(Semaphore::new(1))), concurrency_limit_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let get = || reqwest::get(format!("{}/batch", base_url));
+
+        // The status line is out, but the body is still streaming
+        let first = get().await.unwrap();
+        assert_eq!(first.status().as_u16(), 200);
+        assert_eq!(get().await.unwrap().status().as_u16(), 503);
+
+        assert_eq!(first.text().await.unwrap(), "0\n1\n2\n");
+        assert_eq!(get().await.unwrap().status().as_u16(), 200);
+    }
+
+    #[test]
+    fn test_expensive_routes_are_routes() {
+        let routes = crate::openapi::tests::registered_routes();
+        for route in EXPENSIVE_ROUTES {
+            assert!(routes.iter().any(|(_, path)| path == route), "{}", route);
+        }
+    }
+
+    #[test]
+    fn test_credentials_are_redacted_from_logs() {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(API_KEY_HEADER, header::HeaderValue::from_static("first-key"));
+        headers.insert(SIGNATURE_HEADER, header::HeaderValue::from_static("deadbeef"));
+        headers.insert(TIMESTAMP_HEADER, header::HeaderValue::from_static("1700000000"));
+        headers.insert(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"));
+
+        let redacted = redact_credentials(&headers);
+        let curl = generate_curl_command("POST", "/message/sign", &redacted, "{}", "localhost:3000");
+        let logged = format!("{:?}", redacted);
+        for line in [&curl, &logged] {
+            assert!(!line.contains("first-key") && !line.contains("deadbeef"), "{}", line);
+        }
+        assert!(curl.contains("-H \"x-api-key: <redacted>\""));
+        assert!(curl.contains("-H \"x-signature: <redacted>\""));
+        // The timestamp isn't a secret, and the rest of the request is replayable as before
+        assert!(curl.contains("-H \"x-timestamp: 1700000000\""));
+        assert!(curl.contains("-H \"content-type: application/json\""));
+    }
+
+    #[tokio::test]
+    async fn test_api_keys() {
+        let base_url = spawn_router_with(Config {
+            api_keys: vec!["first-key".to_string(), "second-key".to_string()],
+            ..Config::default()
+        }).await;
+        let client = reqwest::Client::new();
+        let post = |key: Option<&'static str>| {
+            let mut request = client.post(format!("{}/keypair", base_url));
+            if let Some(key) = key {
+                request = request.header(API_KEY_HEADER, key);
+            }
+            request.send()
+        };
+
+        // Health probes don't need a key
+        let response = client.get(format!("{}/health", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["data"]["cluster"], "devnet");
+
+        let response = post(None).await.unwrap();
+        assert_eq!(response.status().as_u16(), 401);
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["error"], "Missing X-API-Key header");
+        assert_eq!(post(Some("second-ke")).await.unwrap().status().as_u16(), 401);
+        assert_eq!(post(Some("second-key")).await.unwrap().status().as_u16(), 200);
+        assert_eq!(post(Some("first-key")).await.unwrap().status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_hmac_signed_requests() {
+        use crate::config::HmacConfig;
+        use zeroize::Zeroizing;
+
+        let secret = "integration-secret-0123456789abcdef";
+        let hmac = HmacConfig { secret: Some(Zeroizing::new(secret.to_string())), ..HmacConfig::default() };
+        let base_url = spawn_router_with(Config { hmac: hmac.clone(), ..Config::default() }).await;
+        let client = reqwest::Client::new();
+        let sign = |timestamp: &str, body: &str| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(format!("{}.{}", timestamp, body).as_bytes());
+            mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>()
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs().to_string();
+        let body = format!(r#"{{"pubkey":"{}"}}"#, solana_sdk::pubkey::Pubkey::new_unique());
+        let send = |timestamp: String, signature: String, body: String| {
+            client.post(format!("{}/pubkey/on-curve", base_url))
+                .header("content-type", "application/json")
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(SIGNATURE_HEADER, signature)
+                .body(body)
+                .send()
+        };
+        let error = |response: reqwest::Response| async move {
+            assert_eq!(response.status().as_u16(), 401);
+            response.json::<serde_json::Value>().await.unwrap()["error"].as_str().unwrap().to_string()
+        };
+
+        let response = send(now.clone(), sign(&now, &body), body.clone()).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert!(response.json::<serde_json::Value>().await.unwrap()["data"]["on_curve"].is_boolean());
+
+        // A tampered body, a stale timestamp or a missing signature is rejected
+        let tampered = body.replace("pubkey", "pubkey ");
+        assert_eq!(error(send(now.clone(), sign(&now, &body), tampered).await.unwrap()).await, "Invalid request signature");
+        let stale = (now.parse::<u64>().unwrap() - 301).to_string();
+        assert_eq!(
+            error(send(stale.clone(), sign(&stale, &body), body.clone()).await.unwrap()).await,
+            "X-Timestamp is more than 300s from the server's clock"
+        );
+        let response = client.post(format!("{}/keypair", base_url)).send().await.unwrap();
+        assert_eq!(error(response).await, "Missing X-Signature header");
+
+        // With API keys too, either one is accepted
+        let base_url = spawn_router_with(Config { hmac, api_keys: vec!["key".to_string()], ..Config::default() }).await;
+        let keyed = client.post(format!("{}/keypair", base_url)).header(API_KEY_HEADER, "key").send().await.unwrap();
+        assert_eq!(keyed.status().as_u16(), 200);
+        let signed = client.post(format!("{}/keypair", base_url))
+            .header(TIMESTAMP_HEADER, &now)
+            .header(SIGNATURE_HEADER, sign(&now, ""))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(signed.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let base_url = spawn_router_with(Config {
+            rate_limit_per_minute: Some(2),
+            ..Config::default()
+        }).await;
+        let client = reqwest::Client::new();
+
+        for _ in 0..2 {
+            let response = client.post(format!("{}/keypair", base_url)).send().await.unwrap();
+            assert_eq!(response.status().as_u16(), 200);
+        }
+        let response = client.post(format!("{}/keypair", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 429);
+        let retry_after: u64 = response.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+        assert!((1..=60).contains(&retry_after));
+
+        // A client sending an API key has its own budget
+        let response = client.post(format!("{}/keypair", base_url)).header(API_KEY_HEADER, "any").send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowed_origins() {
+        let base_url = spawn_router_with(Config {
+            cors_allowed_origins: vec![header::HeaderValue::from_static("https://app.example.com")],
+            ..Config::default()
+        }).await;
+        let client = reqwest::Client::new();
+        let allowed_origin = |origin: &'static str| {
+            let request = client.post(format!("{}/keypair", base_url)).header("origin", origin).send();
+            async move { request.await.unwrap().headers().get("access-control-allow-origin").cloned() }
+        };
+
+        assert_eq!(allowed_origin("https://app.example.com").await.unwrap(), "https://app.example.com");
+        assert!(allowed_origin("https://evil.example.com").await.is_none());
+    }
+
+    /// Sends every GET /examples request to its route and checks the handler's response matches
+    /// the example, or for RPC-backed routes, that the request got past validation to the node
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_examples_match_handlers() {
+        use crate::examples::{RANDOM_ROUTES, RPC_ROUTES, STATEFUL_ROUTES};
+        use crate::services::rpc::RetryPolicy;
+
+        // Nothing listens on port 1, so RPC-backed routes fail fast once their request is valid
+        let mut config = Config::default();
+        config.rpc.url = "http://127.0.0.1:1".to_string();
+        config.rpc.retry = RetryPolicy { max_retries: 0, ..RetryPolicy::default() };
+        let base_url = spawn_router_with(config).await;
+        let client = reqwest::Client::new();
+
+        let examples: serde_json::Value = client.get(format!("{}/examples", base_url)).send().await.unwrap().json().await.unwrap();
+        let examples = examples["data"].as_object().unwrap();
+        // Every route but the API description, this one, the QR image and the WebSocket stream
+        // has an example
+        assert_eq!(examples.len(), crate::openapi::tests::registered_routes().len() - 4);
+
+        for (route, example) in examples {
+            let (method, path) = route.split_once(' ').unwrap();
+            let mut request = match method {
+                "GET" => client.get(format!("{}{}", base_url, path)),
+                _ => client.post(format!("{}{}", base_url, path)),
+            };
+            if let Some(body) = example.get("request") {
+                request = request.json(body);
+            }
+            let response = request.send().await.unwrap();
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap();
+
+            if RPC_ROUTES.contains(&path) {
+                assert!(status.is_server_error(), "{}: {} {}", route, status, body);
+            } else if STATEFUL_ROUTES.contains(&path) {
+                assert_eq!(status.as_u16(), 401, "{}: {}", route, body);
+            } else if RANDOM_ROUTES.contains(&path) {
+                let keys = |value: &serde_json::Value| value["data"].as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+                assert_eq!(keys(&body), keys(&example["response"]), "{}", route);
+            } else {
+                assert_eq!(body, example["response"], "{}", route);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_route_returns_json_404() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+
+        for request in [client.get(format!("{}/bogus", base_url)), client.post(format!("{}/token/bogus", base_url))] {
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status().as_u16(), 404);
+            assert_eq!(
+                response.json::<serde_json::Value>().await.unwrap(),
+                serde_json::json!({ "success": false, "error": "Not Found", "code": "NOT_FOUND" })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wrong_method_returns_json_405_with_allow() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+
+        let response = client.get(format!("{}/token/create", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 405);
+        assert_eq!(response.headers()["allow"], "POST");
+        assert_eq!(
+            response.json::<serde_json::Value>().await.unwrap(),
+            serde_json::json!({
+                "success": false,
+                "error": "GET is not allowed on /token/create; see the Allow header for the methods this route accepts",
+                "code": "METHOD_NOT_ALLOWED"
+            })
+        );
+
+        let response = client.post(format!("{}/health", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 405);
+        assert_eq!(response.headers()["allow"], "GET,HEAD");
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["code"], "METHOD_NOT_ALLOWED");
+    }
+
+    #[tokio::test]
+    async fn test_get_variants_match_post_and_are_cacheable() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+        let (wallet, mint) = (solana_sdk::pubkey::Pubkey::new_unique(), solana_sdk::pubkey::Pubkey::new_unique());
+
+        let requests = [
+            ("/pda/derive", format!("program_id={}&seeds=vault,pubkey:{}", mint, wallet), serde_json::json!({ "program_id": mint.to_string(), "seeds": ["vault", format!("pubkey:{}", wallet)] })),
+            ("/token/ata", format!("wallet={}&mint={}", wallet, mint), serde_json::json!({ "wallet": wallet.to_string(), "mint": mint.to_string() })),
+            ("/pubkey/on-curve", format!("pubkey={}", wallet), serde_json::json!({ "pubkey": wallet.to_string() })),
+        ];
+        for (path, query, body) in requests {
+            let get = client.get(format!("{}{}?{}", base_url, path, query)).send().await.unwrap();
+            assert_eq!(get.status().as_u16(), 200, "{}", path);
+            assert_eq!(get.headers()["cache-control"], "public, max-age=31536000, immutable");
+            let get: serde_json::Value = get.json().await.unwrap();
+
+            let post = client.post(format!("{}{}", base_url, path)).json(&body).send().await.unwrap();
+            assert!(post.headers().get("cache-control").is_none());
+            assert_eq!(post.json::<serde_json::Value>().await.unwrap(), get, "{}", path);
+        }
+
+        // Errors aren't cached
+        let response = client.get(format!("{}/token/ata?wallet={}", base_url, wallet)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        assert!(response.headers().get("cache-control").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_keypair_qr_is_svg() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+
+        let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        let response = client.get(format!("{}/keypair/qr?pubkey={}", base_url, pubkey)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.headers()["content-type"], "image/svg+xml");
+        assert!(response.text().await.unwrap().contains("<svg"));
+
+        let response = client.get(format!("{}/keypair/qr?pubkey=invalid", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "BAD_REQUEST");
+    }
+
+    #[tokio::test]
+    async fn test_non_json_body_is_unsupported_media_type() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+
+        let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        for content_type in [Some("application/x-www-form-urlencoded"), Some("text/plain"), None] {
+            let mut request = client.post(format!("{}/message/verify", base_url)).body(format!("message=hi&signature=sig&pubkey={}", pubkey));
+            if let Some(content_type) = content_type {
+                request = request.header("content-type", content_type);
+            }
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status().as_u16(), 415, "{:?}", content_type);
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["code"], "UNSUPPORTED_MEDIA_TYPE");
+            assert_eq!(body["error"], "Content-Type must be application/json");
+            assert_eq!(body["success"], false);
+        }
+
+        // JSON with a charset parameter, or a +json suffix, is still JSON
+        for content_type in ["application/json; charset=utf-8", "application/problem+json"] {
+            let response = client
+                .post(format!("{}/message/verify", base_url))
+                .header("content-type", content_type)
+                .body("{}")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), 400, "{}", content_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keypair_generation_can_be_disabled() {
+        let base_url = spawn_router_with(Config {
+            enable_keypair_generation: false,
+            ..Config::default()
+        }).await;
+        let client = reqwest::Client::new();
+
+        for path in ["/keypair", "/keypair/from-seed", "/keypair/derive/batch", "/keypair/funded", "/token/create/new"] {
+            let response = client.post(format!("{}{}", base_url, path)).json(&serde_json::json!({})).send().await.unwrap();
+            assert_eq!(response.status().as_u16(), 404, "{}", path);
+        }
+
+        // Instruction building and key conversion stay available
+        let response = client.post(format!("{}/keypair/export", base_url)).json(&serde_json::json!({ "secret": "" })).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_routes_report_rpc_not_configured() {
+        let mut config = Config::default();
+        config.rpc.url = String::new();
+        config.rpc.enabled = false;
+        let base_url = spawn_router_with(config).await;
+        let client = reqwest::Client::new();
+        let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+        for (path, body) in [
+            ("/rpc/account", serde_json::json!({ "pubkey": pubkey })),
+            ("/rpc/token-accounts", serde_json::json!({ "owner": pubkey })),
+            ("/rpc/vote-accounts", serde_json::json!({})),
+            ("/rpc/airdrop", serde_json::json!({ "pubkey": pubkey, "sol": 1.0 })),
+            ("/keypair/funded", serde_json::json!({ "sol": 1.0 })),
+        ] {
+            let response = client.post(format!("{}{}", base_url, path)).json(&body).send().await.unwrap();
+            assert_eq!(response.status().as_u16(), 503, "{}", path);
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["code"], "RPC_NOT_CONFIGURED", "{}", path);
+        }
+
+        // Offline routes, and rent with its local fallback, keep working
+        let response = client.post(format!("{}/rpc/rent", base_url)).json(&serde_json::json!({ "data_len": 165 })).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["data"]["source"], "local");
+        let response = client.post(format!("{}/keypair", base_url)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_token_policy_limits() {
+        use crate::config::TokenPolicy;
+
+        let base_url = spawn_router_with(Config {
+            token: TokenPolicy { max_decimals: 6, max_mint_amount: 1_000, ..TokenPolicy::default() },
+            ..Config::default()
+        }).await;
+        let client = reqwest::Client::new();
+        let post = |path: &str, body: serde_json::Value| client.post(format!("{}{}", base_url, path)).json(&body).send();
+        let (mint, wallet) = (solana_sdk::pubkey::Pubkey::new_unique().to_string(), solana_sdk::pubkey::Pubkey::new_unique().to_string());
+
+        let create = |decimals: u8| serde_json::json!({ "mintAuthority": wallet, "mint": mint, "decimals": decimals });
+        assert_eq!(post("/token/create", create(6)).await.unwrap().status().as_u16(), 200);
+        let response = post("/token/create", create(7)).await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["error"], "decimals must be between 0 and 6");
+
+        let mint_to = |amount: u64| serde_json::json!({ "mint": mint, "destination": wallet, "authority": wallet, "amount": amount });
+        assert_eq!(post("/token/mint", mint_to(1_000)).await.unwrap().status().as_u16(), 200);
+        assert_eq!(post("/token/mint", mint_to(1_001)).await.unwrap().status().as_u16(), 400);
+
+        let batch = serde_json::json!({
+            "mint": mint,
+            "authority": wallet,
+            "recipients": [{ "destination": wallet, "amount": 5 }, { "destination": wallet, "amount": 1_001 }],
+        });
+        let response = post("/token/mint/batch", batch).await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["error"], "recipients[1].amount must be at most 1000, got 1001");
+    }
+
+    #[tokio::test]
+    async fn test_batches_stream_as_ndjson() {
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+        let post = |path: &str, body: serde_json::Value| client.post(format!("{}{}", base_url, path)).json(&body).send();
+        let lines = |body: String| -> Vec<serde_json::Value> {
+            body.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+        };
+        let program_id = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+        // Streamed batches may be larger than MAX_BATCH_SIZE, and each entry's failure is its own line
+        let mut entries: Vec<_> = (0..600).map(|i| serde_json::json!({ "program_id": program_id, "seeds": [format!("entry-{}", i)] })).collect();
+        entries[3] = serde_json::json!({ "program_id": "not-a-program", "seeds": [] });
+        let response = post("/pda/derive/batch?stream=ndjson", serde_json::json!({ "entries": entries })).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+        let results = lines(response.text().await.unwrap());
+        assert_eq!(results.len(), 600);
+        assert!(results[0]["address"].is_string());
+        assert!(results[3]["error"].as_str().unwrap().contains("program_id"));
+
+        let wallet = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        let mint_batch = serde_json::json!({
+            "mint": program_id,
+            "authority": wallet,
+            "recipients": [{ "destination": wallet, "amount": 5 }, { "destination": wallet, "amount": 6 }],
+        });
+        let response = post("/token/mint/batch?stream=ndjson", mint_batch.clone()).await.unwrap();
+        let instructions = lines(response.text().await.unwrap());
+        assert_eq!(instructions.len(), 2);
+        assert!(instructions[1]["instruction_data"].is_string());
+
+        let calls = serde_json::json!([{ "method": "pubkey/on-curve", "params": { "pubkey": wallet } }, { "method": "nope" }]);
+        let results = lines(post("/batch?stream=ndjson", calls).await.unwrap().text().await.unwrap());
+        assert_eq!((results[0]["success"].as_bool(), results[1]["status"].as_u64()), (Some(true), Some(404)));
+
+        // Errors found before streaming starts are ordinary JSON errors
+        let mut bundled = mint_batch.clone();
+        bundled["fee_payer"] = serde_json::json!(wallet);
+        let response = post("/token/mint/batch?stream=ndjson", bundled).await.unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["error"], "fee_payer and recent_blockhash can't be used with stream=ndjson");
+        let response = post("/token/mint/batch?stream=csv", mint_batch).await.unwrap();
+        assert_eq!(response.json::<serde_json::Value>().await.unwrap()["error"], "stream must be ndjson, got 'csv'");
+    }
+
+    #[test]
+    fn test_keys_match() {
+        assert!(keys_match(b"secret", b"secret"));
+        assert!(!keys_match(b"secret", b"secreT"));
+        assert!(!keys_match(b"secret", b"secret!"));
     }
-} 
+
+    #[test]
+    fn test_wants_msgpack() {
+        let accept = |value: &str| {
+            let mut headers = axum::http::HeaderMap::new();
+            headers.insert(header::ACCEPT, value.parse().unwrap());
+            headers
+        };
+
+        assert!(wants_msgpack(&accept("application/msgpack")));
+        assert!(wants_msgpack(&accept("application/json;q=0.5, application/x-msgpack")));
+        assert!(!wants_msgpack(&accept("application/json")));
+        assert!(!wants_msgpack(&axum::http::HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_msgpack_responses() {
+        use crate::models::KeypairResponse;
+
+        let base_url = spawn_router().await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!("{}/keypair", base_url))
+            .header("accept", MSGPACK_CONTENT_TYPE)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-type"], MSGPACK_CONTENT_TYPE);
+        let body: ApiResponse<KeypairResponse> = rmp_serde::from_slice(&response.bytes().await.unwrap()).unwrap();
+        assert!(body.success);
+        assert!(!body.data.pubkey.is_empty());
+
+        // Error envelopes are encoded too
+        let response = client
+            .post(format!("{}/message/sign", base_url))
+            .header("accept", MSGPACK_CONTENT_TYPE)
+            .json(&serde_json::json!({ "message": "hi", "secret": "" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        let body: ApiErrorResponse = rmp_serde::from_slice(&response.bytes().await.unwrap()).unwrap();
+        assert!(!body.success);
+
+        // JSON stays the default
+        let response = client.post(format!("{}/keypair", base_url)).send().await.unwrap();
+        assert_eq!(response.headers()["content-type"], "application/json");
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+use solana_sdk::signature::{Keypair, Signer};
+use zeroize::Zeroizing;
+
+use crate::models::{EntropyCheck, EntropyCheckResponse};
+
+/// Keypairs generated when no sample size is requested
+pub const DEFAULT_ENTROPY_SAMPLE: usize = 256;
+
+/// Bounds on the requested sample size: enough seed bytes for the byte distribution test to be
+/// meaningful, and few enough keypairs that a check stays quick
+pub const MIN_ENTROPY_SAMPLE: usize = 64;
+pub const MAX_ENTROPY_SAMPLE: usize = 1024;
+
+/// How many standard deviations from the expected value a statistic may stray before the check
+/// fails. A sound generator fails at this distance far less often than once in a million runs.
+const TOLERANCE_SIGMAS: f64 = 6.0;
+
+/// Generates `sample` keypairs the way /keypair does and checks their seeds for signs of a
+/// broken random number generator
+///
+/// The checks are not a proof of randomness; they catch catastrophic failures such as a
+/// constant, repeating or heavily biased generator.
+pub fn check_keypair_entropy(sample: usize) -> EntropyCheckResponse {
+    run_checks((0..sample).map(|_| Keypair::new()))
+}
+
+/// Runs every check over the given keypairs' seeds, which are zeroized as soon as they are counted
+fn run_checks(keypairs: impl Iterator<Item = Keypair>) -> EntropyCheckResponse {
+    let mut pubkeys = HashSet::new();
+    let mut byte_counts = [0u64; 256];
+    let mut ones = 0u64;
+    let mut sample_size = 0;
+
+    for keypair in keypairs {
+        sample_size += 1;
+        pubkeys.insert(keypair.pubkey());
+        let bytes = Zeroizing::new(keypair.to_bytes());
+        for &byte in &bytes[..32] {
+            byte_counts[byte as usize] += 1;
+            ones += u64::from(byte.count_ones());
+        }
+    }
+
+    let checks = vec![
+        unique_keys(pubkeys.len(), sample_size),
+        byte_distribution(&byte_counts),
+        bit_balance(ones, sample_size as u64 * 256),
+    ];
+    EntropyCheckResponse {
+        passed: checks.iter().all(|check| check.passed),
+        sample_size,
+        checks,
+    }
+}
+
+/// Every keypair must be distinct; a single repeat means the generator is not random at all
+fn unique_keys(distinct: usize, sample_size: usize) -> EntropyCheck {
+    EntropyCheck {
+        name: "unique_keys".to_string(),
+        passed: distinct == sample_size,
+        detail: format!("{} distinct public keys out of {}", distinct, sample_size),
+    }
+}
+
+/// Chi-square test that every byte value appears in the seeds about equally often
+fn byte_distribution(counts: &[u64; 256]) -> EntropyCheck {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / 256.0;
+    let statistic: f64 = counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum();
+
+    // Chi-square with 255 degrees of freedom has mean 255 and variance 2 * 255
+    let degrees_of_freedom = 255.0_f64;
+    let limit = degrees_of_freedom + TOLERANCE_SIGMAS * (2.0 * degrees_of_freedom).sqrt();
+    EntropyCheck {
+        name: "byte_distribution".to_string(),
+        passed: statistic <= limit,
+        detail: format!(
+            "chi-square {:.1} over {} seed bytes (limit {:.1})",
+            statistic, total, limit
+        ),
+    }
+}
+
+/// Monobit test that about half of all seed bits are set
+fn bit_balance(ones: u64, bits: u64) -> EntropyCheck {
+    let half = bits as f64 / 2.0;
+    let allowed = TOLERANCE_SIGMAS * (bits as f64).sqrt() / 2.0;
+    EntropyCheck {
+        name: "bit_balance".to_string(),
+        passed: (ones as f64 - half).abs() <= allowed,
+        detail: format!(
+            "{:.2}% of {} seed bits set (allowed {:.2}% to {:.2}%)",
+            100.0 * ones as f64 / bits as f64,
+            bits,
+            100.0 * (half - allowed) / bits as f64,
+            100.0 * (half + allowed) / bits as f64
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::keypair::keypair_from_seed;
+
+    #[test]
+    fn test_generated_keypairs_pass() {
+        let report = check_keypair_entropy(DEFAULT_ENTROPY_SAMPLE);
+        assert!(report.passed, "{:#?}", report);
+        assert_eq!(report.sample_size, DEFAULT_ENTROPY_SAMPLE);
+        let names: Vec<_> = report.checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(names, ["unique_keys", "byte_distribution", "bit_balance"]);
+    }
+
+    #[test]
+    fn test_broken_generators_fail() {
+        // A constant generator fails every check
+        let report = run_checks((0..MIN_ENTROPY_SAMPLE).map(|_| keypair_from_seed(&[7; 32]).unwrap()));
+        assert!(!report.passed);
+        assert!(report.checks.iter().all(|check| !check.passed), "{:#?}", report);
+        assert_eq!(report.checks[0].detail, "1 distinct public keys out of 64");
+
+        // Distinct seeds that never set the top bit of a byte are caught by the statistics alone
+        let report = run_checks((0..MIN_ENTROPY_SAMPLE).map(|i| {
+            let mut seed = [0u8; 32];
+            for (j, byte) in seed.iter_mut().enumerate() {
+                *byte = ((i * 32 + j) % 128) as u8;
+            }
+            seed[0] = i as u8;
+            keypair_from_seed(&seed).unwrap()
+        }));
+        let passed: Vec<_> = report.checks.iter().map(|check| check.passed).collect();
+        assert_eq!(passed, [true, false, false], "{:#?}", report);
+    }
+}
@@ -1 +1,7 @@
-pub mod solana; 
+pub mod solana;
+pub mod rpc;
+pub mod pubsub;
+pub mod packing;
+pub mod siws;
+pub mod qr;
+pub mod entropy;
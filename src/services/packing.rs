@@ -0,0 +1,163 @@
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signature,
+};
+
+use crate::utils::errors::{AppError, Result};
+
+/// Largest serialized transaction the network accepts (1232 bytes: an IPv6 MTU minus headers)
+pub const MAX_TRANSACTION_SIZE: usize = PACKET_DATA_SIZE;
+
+/// Serialized size of the signed transaction carrying `message`, counting one 64-byte signature
+/// per required signer
+pub fn transaction_size(message: &Message) -> usize {
+    let signatures = usize::from(message.header.num_required_signatures);
+    compact_len_size(signatures) + signatures * std::mem::size_of::<Signature>() + message.serialize().len()
+}
+
+/// Bytes taken by a compact-u16 ("short_vec") length prefix
+fn compact_len_size(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+/// Packs instructions, in order, into as few messages as possible without any signed
+/// transaction exceeding MAX_TRANSACTION_SIZE
+///
+/// Each message is filled greedily before starting the next. A transaction only grows as
+/// instructions are added, so for an order-preserving split this gives the fewest messages.
+/// Fails if a single instruction doesn't fit in a transaction on its own.
+pub fn pack_instructions(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    recent_blockhash: &Hash,
+) -> Result<Vec<Message>> {
+    let mut messages = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+
+    for (index, instruction) in instructions.iter().enumerate() {
+        current.push(instruction.clone());
+        if transaction_size(&Message::new(&current, Some(payer))) <= MAX_TRANSACTION_SIZE {
+            continue;
+        }
+
+        current.pop();
+        let single = std::slice::from_ref(instruction);
+        if current.is_empty() || transaction_size(&Message::new(single, Some(payer))) > MAX_TRANSACTION_SIZE {
+            return Err(AppError::ValidationError(format!(
+                "Instruction {} does not fit in a {}-byte transaction on its own",
+                index, MAX_TRANSACTION_SIZE
+            )));
+        }
+        messages.push(Message::new_with_blockhash(&current, Some(payer), recent_blockhash));
+        current = single.to_vec();
+    }
+
+    if !current.is_empty() {
+        messages.push(Message::new_with_blockhash(&current, Some(payer), recent_blockhash));
+    }
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::transaction::Transaction;
+
+    /// An instruction with no accounts and `data_len` bytes of data, for sizing tests
+    fn instruction(program_id: &Pubkey, data_len: usize) -> Instruction {
+        Instruction::new_with_bytes(*program_id, &vec![7; data_len], vec![])
+    }
+
+    #[test]
+    fn test_transaction_size_matches_serialized_transaction() {
+        let payer = Pubkey::new_unique();
+        let instructions = [
+            instruction(&Pubkey::new_unique(), 10),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &[],
+                5,
+            ).unwrap(),
+        ];
+        let message = Message::new(&instructions, Some(&payer));
+
+        let transaction = Transaction::new_unsigned(message.clone());
+        assert_eq!(transaction_size(&message), bincode::serialized_size(&transaction).unwrap() as usize);
+    }
+
+    #[test]
+    fn test_single_instruction_at_the_limit() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let overhead = transaction_size(&Message::new(&[instruction(&program_id, 0)], Some(&payer)));
+
+        // Data lengths of 128 and up take a two-byte length prefix; the overhead above has one
+        let data_len = MAX_TRANSACTION_SIZE - overhead - 1;
+        let fits = instruction(&program_id, data_len);
+        assert_eq!(transaction_size(&Message::new(std::slice::from_ref(&fits), Some(&payer))), MAX_TRANSACTION_SIZE);
+        assert_eq!(pack_instructions(&[fits], &payer, &Hash::default()).unwrap().len(), 1);
+
+        let too_big = instruction(&program_id, data_len + 1);
+        let result = pack_instructions(&[instruction(&program_id, 1), too_big], &payer, &Hash::default());
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("Instruction 1 ")));
+    }
+
+    #[test]
+    fn test_packing_boundary() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let first = instruction(&program_id, 500);
+
+        // Size the second instruction so the pair lands exactly on the limit
+        let pair_overhead = transaction_size(&Message::new(&[first.clone(), instruction(&program_id, 200)], Some(&payer))) - 200;
+        let second_len = MAX_TRANSACTION_SIZE - pair_overhead;
+        let pair = [first.clone(), instruction(&program_id, second_len)];
+        assert_eq!(transaction_size(&Message::new(&pair, Some(&payer))), MAX_TRANSACTION_SIZE);
+
+        let blockhash = Hash::new_unique();
+        let messages = pack_instructions(&pair, &payer, &blockhash).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].recent_blockhash, blockhash);
+
+        // One byte more splits the pair
+        let messages = pack_instructions(&[first, instruction(&program_id, second_len + 1)], &payer, &blockhash).unwrap();
+        assert_eq!(messages.iter().map(|m| m.instructions.len()).collect::<Vec<_>>(), [1, 1]);
+    }
+
+    #[test]
+    fn test_packing_keeps_order_and_fills_messages() {
+        let payer = Pubkey::new_unique();
+        let instructions: Vec<_> = (0..100)
+            .map(|i| instruction(&Pubkey::new_unique(), i % 50))
+            .collect();
+
+        let messages = pack_instructions(&instructions, &payer, &Hash::default()).unwrap();
+        assert!(messages.len() > 1);
+
+        let mut next = 0;
+        for (index, message) in messages.iter().enumerate() {
+            assert!(transaction_size(message) <= MAX_TRANSACTION_SIZE);
+            let count = message.instructions.len();
+
+            // Every message but the last is full: the next instruction would not have fit
+            if index + 1 < messages.len() {
+                let overfull = &instructions[next..next + count + 1];
+                assert!(transaction_size(&Message::new(overfull, Some(&payer))) > MAX_TRANSACTION_SIZE);
+            }
+            next += count;
+        }
+        assert_eq!(next, instructions.len());
+        assert!(pack_instructions(&[], &payer, &Hash::default()).unwrap().is_empty());
+    }
+}
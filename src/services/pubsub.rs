@@ -0,0 +1,139 @@
+use axum::extract::ws::{Message, WebSocket};
+use futures::StreamExt;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_config::RpcAccountInfoConfig,
+};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{info, warn};
+
+use crate::models::{ApiErrorResponse, ApiResponse};
+use crate::config::RpcConfig;
+use crate::utils::errors::{AppError, Result, serialization_error};
+
+/// Proxies Solana's WebSocket subscriptions to API clients
+pub struct PubsubService {
+    /// None when no WebSocket URL is set and RPC is turned off
+    url: Option<String>,
+}
+
+impl PubsubService {
+    /// Uses the configured WebSocket URL, or derives one from the RPC URL
+    pub fn new(config: &RpcConfig) -> Self {
+        let url = config.ws_url.clone().or_else(|| config.enabled.then(|| ws_url_for(&config.url)));
+
+        Self { url }
+    }
+
+    /// Fails with RpcNotConfigured when there's no endpoint to subscribe to, so the route can
+    /// report it before upgrading the connection
+    pub fn ensure_configured(&self) -> Result<()> {
+        match self.url {
+            Some(_) => Ok(()),
+            None => Err(crate::services::rpc::rpc_not_configured()),
+        }
+    }
+
+    /// Subscribes to `pubkey` and forwards every account notification to `socket` as JSON
+    /// until either side closes, then unsubscribes
+    ///
+    /// The HTTP upgrade has already happened, so failures are reported to the client as an
+    /// error envelope followed by a close frame.
+    pub async fn forward_account_updates(&self, mut socket: WebSocket, pubkey: Pubkey) {
+        if let Err(e) = self.stream_account_updates(&mut socket, &pubkey).await {
+            warn!("Account subscription for {} ended with an error: {}", pubkey, e);
+            if let Ok(text) = serde_json::to_string(&ApiErrorResponse::error(&e.to_string())) {
+                let _ = socket.send(Message::Text(text)).await;
+            }
+        }
+        let _ = socket.send(Message::Close(None)).await;
+    }
+
+    async fn stream_account_updates(&self, socket: &mut WebSocket, pubkey: &Pubkey) -> Result<()> {
+        let url = self.url.as_deref().ok_or_else(crate::services::rpc::rpc_not_configured)?;
+        let client = PubsubClient::new(url)
+            .await
+            .map_err(|e| AppError::RpcError(format!("Failed to connect to {}: {}", url, e)))?;
+
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        };
+        let (mut notifications, unsubscribe) = client
+            .account_subscribe(pubkey, Some(config))
+            .await
+            .map_err(|e| AppError::RpcError(format!("accountSubscribe failed: {}", e)))?;
+
+        info!("Subscribed to account updates for {}", pubkey);
+
+        let result = loop {
+            tokio::select! {
+                notification = notifications.next() => {
+                    let Some(notification) = notification else {
+                        break Err(AppError::RpcError("Subscription closed by the RPC node".to_string()));
+                    };
+                    let text = match serde_json::to_string(&ApiResponse::success(notification)) {
+                        Ok(text) => text,
+                        Err(e) => break Err(serialization_error(e)),
+                    };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break Ok(());
+                    }
+                }
+                message = socket.recv() => {
+                    // Anything other than a close is ignored; axum answers pings itself
+                    match message {
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break Ok(()),
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        };
+
+        unsubscribe().await;
+        drop(notifications);
+        if let Err(e) = client.shutdown().await {
+            warn!("Failed to shut down pubsub client: {}", e);
+        }
+        info!("Unsubscribed from account updates for {}", pubkey);
+
+        result
+    }
+}
+
+/// Maps an HTTP(S) RPC URL to the matching WebSocket URL (http → ws, https → wss)
+fn ws_url_for(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_configured() {
+        assert!(PubsubService::new(&RpcConfig::default()).ensure_configured().is_ok());
+
+        let mut config = RpcConfig::default();
+        config.url = String::new();
+        config.enabled = false;
+        assert!(matches!(PubsubService::new(&config).ensure_configured(), Err(AppError::RpcNotConfigured(_))));
+        // A WebSocket URL of its own still allows subscriptions
+        config.ws_url = Some("wss://example.com".to_string());
+        assert!(PubsubService::new(&config).ensure_configured().is_ok());
+    }
+
+    #[test]
+    fn test_ws_url_for() {
+        assert_eq!(ws_url_for("https://api.devnet.solana.com"), "wss://api.devnet.solana.com");
+        assert_eq!(ws_url_for("http://127.0.0.1:8899"), "ws://127.0.0.1:8899");
+        assert_eq!(ws_url_for("wss://example.com"), "wss://example.com");
+    }
+}
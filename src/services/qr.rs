@@ -0,0 +1,46 @@
+use qrcode::{render::svg, EcLevel, QrCode};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::utils::errors::{AppError, Result};
+
+/// Smallest width and height of a rendered code, in pixels, so it scans from a phone screen
+pub const MIN_QR_DIMENSION: u32 = 256;
+
+/// Renders the base58 address of `pubkey` as an SVG QR code
+///
+/// The code holds the bare address, which is what wallets expect when scanning a receive
+/// address. Medium error correction keeps it scannable when partly obscured or printed small.
+pub fn pubkey_svg(pubkey: &Pubkey) -> Result<String> {
+    let code = QrCode::with_error_correction_level(pubkey.to_string(), EcLevel::M)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to encode QR code: {}", e)))?;
+
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(MIN_QR_DIMENSION, MIN_QR_DIMENSION)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pubkey_svg_encodes_address() {
+        let pubkey = Pubkey::new_unique();
+        let svg = pubkey_svg(&pubkey).unwrap();
+        assert!(svg.starts_with("<?xml"), "{}", &svg[..40]);
+        assert!(svg.contains("<svg"));
+        assert!(svg.ends_with("</svg>"));
+
+        let dimension = |attribute: &str| -> u32 {
+            let start = svg.find(&format!("{}=\"", attribute)).unwrap() + attribute.len() + 2;
+            svg[start..].split('"').next().unwrap().parse().unwrap()
+        };
+        assert!(dimension("width") >= MIN_QR_DIMENSION);
+        assert!(dimension("height") >= MIN_QR_DIMENSION);
+
+        // The same address always renders the same code, and another address a different one
+        assert_eq!(pubkey_svg(&pubkey).unwrap(), svg);
+        assert_ne!(pubkey_svg(&Pubkey::new_unique()).unwrap(), svg);
+    }
+}
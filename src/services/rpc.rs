@@ -0,0 +1,1440 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use solana_account_decoder::{parse_token::UiTokenAmount, UiAccount, UiAccountData, UiAccountEncoding};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient as NonblockingRpcClient,
+    rpc_client::{RpcClient, RpcClientConfig},
+    rpc_config::{RpcGetVoteAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_request::{RpcError, RpcRequest, TokenAccountsFilter},
+    rpc_response::{Response as RpcResponse, RpcKeyedAccount, RpcVoteAccountInfo},
+};
+use serde::Deserialize;
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::Transaction,
+};
+use solana_rpc_client::http_sender::HttpSender;
+use solana_transaction_status::{TransactionConfirmationStatus, UiTransactionEncoding};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use tracing::{debug, info, warn};
+
+use crate::config::RpcConfig;
+use crate::models::{
+    AccountInfoResponse, MintInfoResponse, SignatureStatusResponse, SimulateTransactionResponse, TokenAccountBalance,
+    TokenAccountInfoResponse, VoteAccountInfo, VoteAccountsResponse,
+};
+use crate::utils::errors::{AppError, Result, serialization_error};
+use crate::utils::pagination::paginate;
+
+/// Default RPC endpoint used when neither an RPC URL nor a cluster is configured
+pub const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+
+/// How long a single RPC request may take before it is abandoned
+const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// JSON-RPC error code a node returns while it is unhealthy or behind the cluster
+const RPC_NODE_UNHEALTHY: i64 = -32005;
+
+/// Genesis hash of mainnet-beta, used to recognise mainnet behind any RPC URL
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dm2Nfd";
+
+/// Consecutive transient failures after which an endpoint is skipped, and for how long
+const ENDPOINT_FAILURE_THRESHOLD: u32 = 3;
+const ENDPOINT_DOWN_FOR: Duration = Duration::from_secs(30);
+
+/// Longest an airdrop confirmation is waited for, and how long to wait between polls
+pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Which RPC client implementation RpcService uses (selected with `rpc.client`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RpcClientKind {
+    /// solana_client's blocking RpcClient, driven through tokio's blocking thread pool
+    Blocking,
+    /// solana_client's async RpcClient, awaited directly on the runtime
+    #[default]
+    Nonblocking,
+}
+
+impl FromStr for RpcClientKind {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "blocking" => Ok(RpcClientKind::Blocking),
+            "nonblocking" => Ok(RpcClientKind::Nonblocking),
+            other => Err(AppError::ValidationError(format!(
+                "Invalid RPC client kind '{}': expected 'blocking' or 'nonblocking'",
+                other
+            ))),
+        }
+    }
+}
+
+/// How transient RPC failures are retried (configured with `rpc.max_retries` and
+/// `rpc.retry_base_ms`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt, so a call is made at most max_retries + 1 times
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub base_delay: Duration,
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Backoff before retry number `retry` (1-based): base_delay * 2^(retry - 1), capped at max_delay
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+/// How many RPC calls may be in flight at once, and how long a request waits for a free
+/// connection (configured with `rpc.pool_size` and `rpc.pool_timeout_ms`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Concurrent calls to the node, and the idle HTTP connections kept open for reuse
+    pub size: usize,
+    /// How long a call waits for a free connection before failing with 503
+    pub acquire_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            size: 32,
+            acquire_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// The underlying RPC client for the selected RpcClientKind
+#[derive(Clone)]
+enum RpcBackend {
+    Blocking(Arc<RpcClient>),
+    Nonblocking(Arc<NonblockingRpcClient>),
+}
+
+/// Tracks an endpoint's recent transient failures, marking it down for ENDPOINT_DOWN_FOR once
+/// ENDPOINT_FAILURE_THRESHOLD happen in a row
+#[derive(Default)]
+struct EndpointHealth {
+    consecutive_failures: AtomicU32,
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl EndpointHealth {
+    fn is_down(&self) -> bool {
+        self.down_until.lock().unwrap().is_some_and(|until| Instant::now() < until)
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.down_until.lock().unwrap() = None;
+    }
+
+    /// Counts a transient failure, returning true if it marked the endpoint down
+    fn record_failure(&self) -> bool {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures < ENDPOINT_FAILURE_THRESHOLD {
+            return false;
+        }
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.down_until.lock().unwrap() = Some(Instant::now() + ENDPOINT_DOWN_FOR);
+        true
+    }
+}
+
+/// One RPC URL with its client and health
+struct RpcEndpoint {
+    url: String,
+    backend: RpcBackend,
+    health: EndpointHealth,
+}
+
+/// The error every RPC-backed route returns when RPC is turned off
+pub fn rpc_not_configured() -> AppError {
+    AppError::RpcNotConfigured(
+        "No Solana RPC endpoint is configured (rpc.url is empty), so this route is unavailable".to_string(),
+    )
+}
+
+/// Runs the same RPC call on whichever backend is configured, failing over between endpoints
+/// and retrying transient failures according to the service's RetryPolicy
+///
+/// Each attempt tries the endpoints that aren't marked down in configured order, moving on to
+/// the next one after a transient failure; permanent failures are returned straight away, as
+/// every endpoint would give the same answer. The retry delay only applies once every endpoint
+/// has failed. Each call first takes a connection from the service's pool (see `acquire_connection`).
+/// Both clients expose identically named methods, so `$call` is written once against `$client`.
+/// Blocking calls are moved onto tokio's blocking pool (once per attempt), so anything `$call`
+/// captures must be Copy (e.g. Pubkeys/Signatures) rather than borrowed. Owned values such as a
+/// Transaction are listed in `clone(...)` and cloned into each blocking attempt.
+macro_rules! rpc_call {
+    ($self:ident, |$client:ident| $call:expr) => {
+        rpc_call!($self, clone(), |$client| $call)
+    };
+    ($self:ident, clone($($captured:ident),*), |$client:ident| $call:expr) => {{
+        let mut attempt: u32 = 0;
+        'attempts: loop {
+            if !$self.enabled {
+                break Err(rpc_not_configured());
+            }
+            attempt += 1;
+            let mut last_err = None;
+            for (index, endpoint) in $self.available_endpoints() {
+                // Held for this call only, so a call waiting out a retry delay frees its connection
+                let permit = $self.acquire_connection().await?;
+                let result: std::result::Result<_, Box<ClientError>> = match &endpoint.backend {
+                    RpcBackend::Nonblocking($client) => $call.await.map_err(Box::new),
+                    RpcBackend::Blocking(client) => {
+                        let $client = client.clone();
+                        $(let $captured = $captured.clone();)*
+                        tokio::task::spawn_blocking(move || $call.map_err(Box::new))
+                            .await
+                            .map_err(|e| AppError::InternalServerError(format!("RPC task failed: {}", e)))?
+                    }
+                };
+                drop(permit);
+
+                match result {
+                    Ok(value) => {
+                        endpoint.health.record_success();
+                        if index == 0 {
+                            debug!("RPC call served by {}", endpoint.url);
+                        } else {
+                            info!("RPC call served by fallback endpoint {}", endpoint.url);
+                        }
+                        break 'attempts Ok(value);
+                    }
+                    Err(err) if is_retryable(&err) => {
+                        warn!("RPC endpoint {} failed on attempt {} ({})", endpoint.url, attempt, err);
+                        if endpoint.health.record_failure() {
+                            warn!(
+                                "RPC endpoint {} marked down for {:?} after {} consecutive failures",
+                                endpoint.url, ENDPOINT_DOWN_FOR, ENDPOINT_FAILURE_THRESHOLD
+                            );
+                        }
+                        last_err = Some(err);
+                    }
+                    Err(err) => break 'attempts Err(rpc_error(*err, attempt)),
+                }
+            }
+
+            let err = last_err.expect("every attempt tries at least one endpoint");
+            if attempt > $self.retry.max_retries {
+                break Err(rpc_error(*err, attempt));
+            }
+            let delay = $self.retry.delay(attempt);
+            warn!("RPC attempt {} failed on every endpoint, retrying in {:?}", attempt, delay);
+            tokio::time::sleep(delay).await;
+        }
+    }};
+}
+
+/// RPC service for the endpoints that talk to a Solana cluster
+///
+/// Built once at startup and shared by every request; clones share the same underlying
+/// clients, their HTTP connection pools, endpoint health and the limit on concurrent calls.
+#[derive(Clone)]
+pub struct RpcService {
+    /// The configured URL followed by its fallbacks, in the order they are tried
+    endpoints: Arc<[RpcEndpoint]>,
+    /// Commitment for reads, passed explicitly with each read. The client is built with the
+    /// configured commitment as its default, which airdrops, sends and confirmations use
+    commitment: CommitmentConfig,
+    retry: RetryPolicy,
+    pool: PoolConfig,
+    /// One permit per connection in the pool
+    connections: Arc<Semaphore>,
+    /// False when RPC is turned off, so every call fails with RpcNotConfigured
+    enabled: bool,
+}
+
+impl RpcService {
+    /// Creates a new RpcService for the configured endpoints, client kind, retry policy and pool
+    ///
+    /// Reads use the configured commitment unless overridden with `with_commitment`.
+    pub fn new(config: &RpcConfig) -> Self {
+        let urls: Vec<&str> = std::iter::once(config.url.as_str())
+            .chain(config.fallback_urls.iter().map(String::as_str))
+            .collect();
+        Self {
+            retry: config.retry,
+            enabled: config.enabled,
+            ..Self::build(&urls, config.client, config.commitment, config.pool)
+        }
+    }
+
+    /// Creates a new RpcService for the given URL using the default client kind
+    #[cfg(test)]
+    pub fn with_url(url: &str) -> Self {
+        Self::with_client(url, RpcClientKind::default())
+    }
+
+    /// Creates a new RpcService for the given URL and client kind
+    #[cfg(test)]
+    pub fn with_client(url: &str, kind: RpcClientKind) -> Self {
+        Self::build(&[url], kind, CommitmentConfig::confirmed(), PoolConfig::default())
+    }
+
+    /// A handle on the same client whose reads use `commitment` (e.g. from a request's
+    /// `commitment` field); None keeps the current one
+    pub fn with_commitment(&self, commitment: Option<CommitmentConfig>) -> Self {
+        Self {
+            commitment: commitment.unwrap_or(self.commitment),
+            ..self.clone()
+        }
+    }
+
+    fn build(urls: &[&str], kind: RpcClientKind, commitment: CommitmentConfig, pool: PoolConfig) -> Self {
+        // The same settings HttpSender::new_with_timeout uses, plus an idle pool sized to match
+        // the number of concurrent calls so every permit can reuse an open connection
+        let http = reqwest::Client::builder()
+            .default_headers(HttpSender::default_headers())
+            .timeout(RPC_TIMEOUT)
+            .pool_idle_timeout(RPC_TIMEOUT)
+            .pool_max_idle_per_host(pool.size)
+            .build()
+            .expect("build RPC HTTP client");
+
+        let endpoints = urls
+            .iter()
+            .map(|url| {
+                let sender = HttpSender::new_with_client(*url, http.clone());
+                let client_config = RpcClientConfig::with_commitment(commitment);
+                let backend = match kind {
+                    RpcClientKind::Blocking => {
+                        RpcBackend::Blocking(Arc::new(RpcClient::new_sender(sender, client_config)))
+                    }
+                    RpcClientKind::Nonblocking => {
+                        RpcBackend::Nonblocking(Arc::new(NonblockingRpcClient::new_sender(sender, client_config)))
+                    }
+                };
+                RpcEndpoint { url: url.to_string(), backend, health: EndpointHealth::default() }
+            })
+            .collect();
+
+        Self {
+            endpoints,
+            commitment,
+            retry: RetryPolicy::default(),
+            pool,
+            connections: Arc::new(Semaphore::new(pool.size)),
+            enabled: true,
+        }
+    }
+
+    /// Waits up to the pool's acquire timeout for a free connection
+    ///
+    /// Fails with ServiceUnavailable (503) when every connection stays busy, so a burst of
+    /// requests is turned away promptly instead of queueing behind a slow node.
+    async fn acquire_connection(&self) -> Result<SemaphorePermit<'_>> {
+        let busy = || {
+            AppError::ServiceUnavailable(format!(
+                "All {} RPC connections are busy; try again shortly",
+                self.pool.size
+            ))
+        };
+        tokio::time::timeout(self.pool.acquire_timeout, self.connections.acquire())
+            .await
+            .map_err(|_| busy())?
+            .map_err(|_| busy())
+    }
+
+    /// The endpoints to try, with their position in the configured order
+    ///
+    /// Endpoints marked down are skipped; if every endpoint is down they are all tried anyway,
+    /// since failing without asking any node can only be worse.
+    fn available_endpoints(&self) -> Vec<(usize, &RpcEndpoint)> {
+        let up: Vec<_> = self.endpoints.iter().enumerate().filter(|(_, endpoint)| !endpoint.health.is_down()).collect();
+        if up.is_empty() {
+            self.endpoints.iter().enumerate().collect()
+        } else {
+            up
+        }
+    }
+
+    /// Returns which client implementation this service uses
+    #[cfg(test)]
+    pub fn client_kind(&self) -> RpcClientKind {
+        match self.endpoints[0].backend {
+            RpcBackend::Blocking(_) => RpcClientKind::Blocking,
+            RpcBackend::Nonblocking(_) => RpcClientKind::Nonblocking,
+        }
+    }
+
+    /// Returns true if the configured endpoints serve mainnet-beta
+    ///
+    /// URLs naming mainnet are rejected without a network call; anything else (private or
+    /// provider URLs) is identified by comparing the cluster's genesis hash with mainnet-beta's.
+    pub async fn is_mainnet(&self) -> Result<bool> {
+        if self.endpoints.iter().any(|endpoint| endpoint.url.contains("mainnet")) {
+            return Ok(true);
+        }
+
+        let genesis_hash = rpc_call!(self, |client| client.get_genesis_hash())?;
+        Ok(is_mainnet_genesis_hash(&genesis_hash))
+    }
+
+    /// Requests an airdrop of `lamports` to `pubkey`, refusing to do so on mainnet
+    pub async fn request_airdrop(&self, pubkey: &Pubkey, lamports: u64) -> Result<Signature> {
+        if self.is_mainnet().await? {
+            return Err(AppError::ValidationError("Airdrops are not available on mainnet".to_string()));
+        }
+
+        let pubkey = *pubkey;
+        rpc_call!(self, |client| client.request_airdrop(&pubkey, lamports))
+    }
+
+    /// Submits a signed transaction, running preflight at the service's commitment, and returns
+    /// its signature
+    ///
+    /// Retrying a send is safe: the cluster deduplicates transactions by signature.
+    pub async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let transaction = transaction.clone();
+        rpc_call!(self, clone(transaction), |client| client.send_transaction(&transaction))
+    }
+
+    /// Lists the SPL token accounts owned by `owner`, optionally restricted to one mint
+    pub async fn get_token_accounts_by_owner(
+        &self,
+        owner: &Pubkey,
+        mint: Option<Pubkey>,
+    ) -> Result<Vec<TokenAccountBalance>> {
+        let owner = *owner;
+        let commitment = self.commitment;
+        let accounts = rpc_call!(self, |client| client.get_token_accounts_by_owner_with_commitment(
+            &owner,
+            match mint {
+                Some(mint) => TokenAccountsFilter::Mint(mint),
+                None => TokenAccountsFilter::ProgramId(spl_token::id()),
+            },
+            commitment,
+        ))?;
+
+        accounts.value.iter().map(parse_token_account).collect()
+    }
+
+    /// Asks the endpoints whether their node is healthy; an error means none is reachable and
+    /// caught up with the cluster
+    pub async fn get_health(&self) -> Result<()> {
+        rpc_call!(self, |client| client.get_health())
+    }
+
+    /// The slot the cluster has reached at the read commitment
+    pub async fn get_slot(&self) -> Result<u64> {
+        let commitment = self.commitment;
+        rpc_call!(self, |client| client.get_slot_with_commitment(commitment))
+    }
+
+    /// Asks the cluster for the minimum balance that keeps an account of `data_len` bytes rent exempt
+    pub async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> Result<u64> {
+        // Sent directly: the clients' helper for this call doesn't pass a commitment
+        let commitment = self.commitment.commitment;
+        rpc_call!(self, |client| client.send(
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            serde_json::json!([data_len, { "commitment": commitment }]),
+        ))
+    }
+
+    /// Fetches an account with its data in the requested encoding, returning NotFound if the
+    /// account doesn't exist
+    pub async fn get_account_info(&self, pubkey: &Pubkey, encoding: UiAccountEncoding) -> Result<AccountInfoResponse> {
+        // getAccountInfo is called directly: get_account_with_config decodes into an Account,
+        // which drops jsonParsed data
+        let pubkey = *pubkey;
+        let commitment = self.commitment.commitment;
+        let response: RpcResponse<Option<UiAccount>> = rpc_call!(self, |client| client.send(
+            RpcRequest::GetAccountInfo,
+            serde_json::json!([pubkey.to_string(), { "encoding": encoding, "commitment": commitment }]),
+        ))?;
+
+        let account = response.value
+            .ok_or_else(|| AppError::NotFound(format!("Account not found: {}", pubkey)))?;
+
+        // Nodes fall back to base64 for accounts they can't parse, so report what was returned
+        let (encoding, data) = match account.data {
+            UiAccountData::Json(parsed) => ("jsonParsed", serde_json::to_value(parsed).map_err(serialization_error)?),
+            UiAccountData::Binary(data, _) | UiAccountData::LegacyBinary(data) => ("base64", serde_json::Value::String(data)),
+        };
+
+        Ok(AccountInfoResponse {
+            pubkey: pubkey.to_string(),
+            lamports: account.lamports,
+            owner: account.owner,
+            executable: account.executable,
+            encoding: encoding.to_string(),
+            data,
+        })
+    }
+
+    /// Fetches and unpacks an SPL token mint, rejecting accounts that aren't initialized mints
+    pub async fn get_mint_info(&self, mint: &Pubkey) -> Result<MintInfoResponse> {
+        let account = self.get_raw_account(mint).await?;
+        let not_a_mint = |reason: &str| {
+            AppError::ValidationError(format!("Account {} is not an SPL token mint: {}", mint, reason))
+        };
+
+        if account.owner != spl_token::id() {
+            return Err(not_a_mint(&format!("owned by {}", account.owner)));
+        }
+        // unpack checks both the 82-byte length and the is_initialized flag
+        let state = Mint::unpack(&account.data).map_err(|_| {
+            if account.data.len() == Mint::LEN {
+                not_a_mint("not initialized")
+            } else {
+                not_a_mint("data is not a mint")
+            }
+        })?;
+
+        Ok(MintInfoResponse {
+            mint: mint.to_string(),
+            supply: state.supply,
+            decimals: state.decimals,
+            mint_authority: Option::<Pubkey>::from(state.mint_authority).map(|pubkey| pubkey.to_string()),
+            freeze_authority: Option::<Pubkey>::from(state.freeze_authority).map(|pubkey| pubkey.to_string()),
+            is_initialized: state.is_initialized,
+        })
+    }
+
+    /// Fetches and unpacks an SPL token account, rejecting accounts that aren't initialized
+    /// token accounts
+    pub async fn get_token_account_info(&self, account: &Pubkey) -> Result<TokenAccountInfoResponse> {
+        let raw = self.get_raw_account(account).await?;
+        let not_a_token_account = |reason: &str| {
+            AppError::ValidationError(format!("Account {} is not an SPL token account: {}", account, reason))
+        };
+
+        if raw.owner != spl_token::id() {
+            return Err(not_a_token_account(&format!("owned by {}", raw.owner)));
+        }
+        // unpack checks both the 165-byte length and that the state isn't Uninitialized
+        let state = TokenAccount::unpack(&raw.data).map_err(|_| {
+            if raw.data.len() == TokenAccount::LEN {
+                not_a_token_account("not initialized")
+            } else {
+                not_a_token_account("data is not a token account")
+            }
+        })?;
+
+        Ok(TokenAccountInfoResponse {
+            account: account.to_string(),
+            mint: state.mint.to_string(),
+            owner: state.owner.to_string(),
+            amount: state.amount,
+            delegate: Option::<Pubkey>::from(state.delegate).map(|pubkey| pubkey.to_string()),
+            delegated_amount: state.delegated_amount,
+            state: match state.state {
+                AccountState::Frozen => "frozen",
+                AccountState::Initialized | AccountState::Uninitialized => "initialized",
+            }.to_string(),
+        })
+    }
+
+    /// Fetches an account's raw data, returning NotFound if it doesn't exist
+    async fn get_raw_account(&self, pubkey: &Pubkey) -> Result<Account> {
+        let pubkey = *pubkey;
+        let commitment = self.commitment;
+        let response = rpc_call!(self, |client| client.get_account_with_commitment(&pubkey, commitment))?;
+        response.value.ok_or_else(|| AppError::NotFound(format!("Account not found: {}", pubkey)))
+    }
+
+    /// Runs a transaction against the cluster's current state without submitting it
+    ///
+    /// A transaction that would fail is not an error here: the failure is reported in the
+    /// response's `err` alongside the logs that lead up to it.
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        sig_verify: bool,
+        replace_recent_blockhash: bool,
+    ) -> Result<SimulateTransactionResponse> {
+        let transaction = transaction.clone();
+        let config = RpcSimulateTransactionConfig {
+            sig_verify,
+            replace_recent_blockhash,
+            commitment: Some(self.commitment),
+            encoding: Some(UiTransactionEncoding::Base64),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let response = rpc_call!(self, clone(transaction, config), |client| {
+            client.simulate_transaction_with_config(&transaction, config.clone())
+        })?;
+
+        let result = response.value;
+        Ok(SimulateTransactionResponse {
+            err: result.err.map(|err| err.to_string()),
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+        })
+    }
+
+    /// Looks up a transaction's confirmation status in the node's recent status cache
+    pub async fn get_signature_status(&self, signature: &Signature) -> Result<SignatureStatusResponse> {
+        let signature = *signature;
+        let response = rpc_call!(self, |client| client.get_signature_statuses(&[signature]))?;
+        let status = response.value.into_iter().next().flatten();
+
+        Ok(SignatureStatusResponse {
+            signature: signature.to_string(),
+            found: status.is_some(),
+            confirmation_status: status.as_ref().and_then(|status| status.confirmation_status.as_ref()).map(|level| {
+                match level {
+                    TransactionConfirmationStatus::Processed => "processed",
+                    TransactionConfirmationStatus::Confirmed => "confirmed",
+                    TransactionConfirmationStatus::Finalized => "finalized",
+                }
+                .to_string()
+            }),
+            slot: status.as_ref().map(|status| status.slot),
+            confirmations: status.as_ref().and_then(|status| status.confirmations),
+            err: status.and_then(|status| status.err).map(|err| err.to_string()),
+        })
+    }
+
+    /// Lists the cluster's current and delinquent vote accounts, each sorted by activated stake
+    /// (largest first) and then cut to `limit` accounts after skipping `offset`
+    ///
+    /// The node always returns the full lists, which run to thousands of accounts on mainnet,
+    /// so paging only bounds the response, not the RPC call.
+    pub async fn get_vote_accounts(
+        &self,
+        vote_pubkey: Option<&Pubkey>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<VoteAccountsResponse> {
+        let config = RpcGetVoteAccountsConfig {
+            vote_pubkey: vote_pubkey.map(|pubkey| pubkey.to_string()),
+            commitment: Some(self.commitment),
+            ..RpcGetVoteAccountsConfig::default()
+        };
+        let status = rpc_call!(self, clone(config), |client| client.get_vote_accounts_with_config(config.clone()))?;
+
+        let page = |mut accounts: Vec<RpcVoteAccountInfo>| {
+            accounts.sort_by(|a, b| b.activated_stake.cmp(&a.activated_stake).then_with(|| a.vote_pubkey.cmp(&b.vote_pubkey)));
+            let (accounts, pagination) = paginate(accounts, offset, limit);
+            let accounts: Vec<_> = accounts
+                .into_iter()
+                .map(|account| VoteAccountInfo {
+                    vote_pubkey: account.vote_pubkey,
+                    node_pubkey: account.node_pubkey,
+                    activated_stake: account.activated_stake,
+                    commission: account.commission,
+                    epoch_vote_account: account.epoch_vote_account,
+                    last_vote: account.last_vote,
+                    root_slot: account.root_slot,
+                })
+                .collect();
+            (accounts, pagination)
+        };
+
+        let (current, current_page) = page(status.current);
+        let (delinquent, delinquent_page) = page(status.delinquent);
+        Ok(VoteAccountsResponse {
+            total_current: current_page.total,
+            total_delinquent: delinquent_page.total,
+            current,
+            delinquent,
+            pagination: if current_page.total >= delinquent_page.total { current_page } else { delinquent_page },
+        })
+    }
+
+    /// Polls the cluster until the signature is confirmed, returning false if that doesn't
+    /// happen within `timeout`
+    pub async fn wait_for_confirmation(&self, signature: &Signature, timeout: Duration) -> Result<bool> {
+        let poll = async {
+            loop {
+                let signature = *signature;
+                if rpc_call!(self, |client| client.confirm_transaction(&signature))? {
+                    return Ok(true);
+                }
+                tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, poll)
+            .await
+            .unwrap_or(Ok(false))
+    }
+
+}
+
+/// The `info` object of a jsonParsed SPL token account
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ParsedTokenAccountInfo {
+    mint: String,
+    token_amount: UiTokenAmount,
+}
+
+/// Helper function to pull mint, amount and decimals out of a jsonParsed token account
+fn parse_token_account(keyed_account: &RpcKeyedAccount) -> Result<TokenAccountBalance> {
+    let unexpected = || AppError::RpcError(format!("Unexpected token account data for {}", keyed_account.pubkey));
+
+    let UiAccountData::Json(parsed) = &keyed_account.account.data else {
+        return Err(unexpected());
+    };
+    let info: ParsedTokenAccountInfo = serde_json::from_value(parsed.parsed["info"].clone())
+        .map_err(|_| unexpected())?;
+    let amount = info.token_amount.amount.parse::<u64>().map_err(|_| unexpected())?;
+
+    Ok(TokenAccountBalance {
+        account: keyed_account.pubkey.clone(),
+        mint: info.mint,
+        amount,
+        decimals: info.token_amount.decimals,
+    })
+}
+
+/// Returns true if the genesis hash belongs to mainnet-beta
+fn is_mainnet_genesis_hash(genesis_hash: &Hash) -> bool {
+    genesis_hash.to_string() == MAINNET_GENESIS_HASH
+}
+
+/// Returns true for failures worth retrying: rate limits, timeouts, connection and server
+/// errors, and unhealthy nodes. Anything else (bad params, invalid pubkeys,
+/// transaction errors) fails the same way every time.
+fn is_retryable(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(e) => {
+            e.is_timeout()
+                || e.is_connect()
+                || e.status().is_some_and(|status| status.as_u16() == 429 || status.is_server_error())
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => *code == RPC_NODE_UNHEALTHY,
+        _ => false,
+    }
+}
+
+/// Helper function to convert RPC client errors, recording how many attempts were made
+fn rpc_error(err: ClientError, attempts: u32) -> AppError {
+    AppError::RpcError(format!("{} (attempts: {})", err, attempts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use serde_json::{json, Value};
+
+    /// Serves JSON-RPC responses produced by `respond(call_number, request)` on a local port,
+    /// returning its URL and a counter of the requests received (getVersion is answered
+    /// automatically and not counted)
+    async fn spawn_rpc_node<F>(respond: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(usize, &Value) -> (StatusCode, Value) + Clone + Send + Sync + 'static,
+    {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| async move {
+                // The client checks the node version before some calls; answer it uncounted
+                if request["method"] == "getVersion" {
+                    let version = json!({ "solana-core": "1.18.26", "feature-set": 0 });
+                    return (StatusCode::OK, Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": version })));
+                }
+                let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                let (status, body) = respond(call, &request);
+                (status, Json(body))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, calls)
+    }
+
+    /// Serves a JSON-RPC getGenesisHash response on a local port and returns its URL
+    async fn spawn_genesis_hash_node(genesis_hash: String) -> String {
+        let (url, _) = spawn_rpc_node(move |_, request| {
+            (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": genesis_hash }))
+        }).await;
+        url
+    }
+
+    /// A RetryPolicy with millisecond delays so retry tests run quickly
+    fn fast_retry(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_is_mainnet_by_url() {
+        let service = RpcService::with_url("https://api.mainnet-beta.solana.com");
+        assert!(service.is_mainnet().await.unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_is_mainnet_by_genesis_hash() {
+        // A provider URL that doesn't mention mainnet but serves the mainnet-beta genesis hash
+        let url = spawn_genesis_hash_node(MAINNET_GENESIS_HASH.to_string()).await;
+        assert!(RpcService::with_url(&url).is_mainnet().await.unwrap());
+
+        let url = spawn_genesis_hash_node(Hash::new_unique().to_string()).await;
+        assert!(!RpcService::with_url(&url).is_mainnet().await.unwrap());
+    }
+
+    #[test]
+    fn test_rpc_client_kind_from_str() {
+        assert_eq!("blocking".parse::<RpcClientKind>().unwrap(), RpcClientKind::Blocking);
+        assert_eq!(" Nonblocking ".parse::<RpcClientKind>().unwrap(), RpcClientKind::Nonblocking);
+        assert!("async".parse::<RpcClientKind>().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_blocking_and_nonblocking_clients_agree() {
+        let url = spawn_genesis_hash_node(MAINNET_GENESIS_HASH.to_string()).await;
+
+        for kind in [RpcClientKind::Blocking, RpcClientKind::Nonblocking] {
+            let service = RpcService::with_client(&url, kind);
+            assert_eq!(service.client_kind(), kind);
+            assert!(service.is_mainnet().await.unwrap());
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_exhausted_pool_returns_service_unavailable() {
+        let url = spawn_genesis_hash_node(Hash::new_unique().to_string()).await;
+        let pool = PoolConfig { size: 1, acquire_timeout: Duration::from_millis(20) };
+        let service = RpcService::build(&[&url], RpcClientKind::default(), CommitmentConfig::confirmed(), pool);
+
+        // Another request holds the only connection; handles from with_commitment share the pool
+        let held = service.acquire_connection().await.unwrap();
+        let result = service.with_commitment(None).is_mainnet().await;
+        assert!(matches!(result, Err(AppError::ServiceUnavailable(msg)) if msg.contains("All 1 RPC connections")));
+
+        drop(held);
+        assert!(!service.is_mainnet().await.unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_airdrop_rejected_on_mainnet() {
+        let url = spawn_genesis_hash_node(MAINNET_GENESIS_HASH.to_string()).await;
+        let service = RpcService::with_url(&url);
+
+        let result = service.request_airdrop(&Pubkey::new_unique(), 1_000_000_000).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+        };
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(350));
+        assert_eq!(policy.delay(40), Duration::from_millis(350));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transient_failures_are_retried() {
+        // Two 503s, then the genesis hash
+        let (url, calls) = spawn_rpc_node(|call, request| {
+            if call <= 2 {
+                (StatusCode::SERVICE_UNAVAILABLE, json!({}))
+            } else {
+                (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": MAINNET_GENESIS_HASH }))
+            }
+        }).await;
+
+        for kind in [RpcClientKind::Blocking, RpcClientKind::Nonblocking] {
+            calls.store(0, Ordering::SeqCst);
+            let mut service = RpcService::with_client(&url, kind);
+            service.retry = fast_retry(3);
+
+            assert!(service.is_mainnet().await.unwrap());
+            assert_eq!(calls.load(Ordering::SeqCst), 3);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_retries_exhausted_reports_attempts() {
+        let (url, calls) = spawn_rpc_node(|_, _| (StatusCode::SERVICE_UNAVAILABLE, json!({}))).await;
+        let mut service = RpcService::with_url(&url);
+        service.retry = fast_retry(2);
+
+        let result = service.is_mainnet().await;
+        assert!(matches!(result, Err(AppError::RpcError(msg)) if msg.contains("attempts: 3")));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_permanent_failures_are_not_retried() {
+        let (url, calls) = spawn_rpc_node(|_, request| {
+            (StatusCode::OK, json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32602, "message": "Invalid param: WrongSize" }
+            }))
+        }).await;
+        let mut service = RpcService::with_url(&url);
+        service.retry = fast_retry(3);
+
+        let result = service.is_mainnet().await;
+        assert!(matches!(result, Err(AppError::RpcError(msg)) if msg.contains("attempts: 1")));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fallback_endpoints_serve_when_primary_fails() {
+        let (primary, primary_calls) = spawn_rpc_node(|_, _| (StatusCode::SERVICE_UNAVAILABLE, json!({}))).await;
+        let (fallback, fallback_calls) = spawn_rpc_node(|_, request| {
+            (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": MAINNET_GENESIS_HASH }))
+        }).await;
+        let mut service =
+            RpcService::build(&[&primary, &fallback], RpcClientKind::default(), CommitmentConfig::confirmed(), PoolConfig::default());
+        service.retry = fast_retry(0);
+
+        // Each call fails over within its single attempt, until the primary is marked down
+        for call in 1..=ENDPOINT_FAILURE_THRESHOLD as usize {
+            assert!(service.is_mainnet().await.unwrap());
+            assert_eq!(primary_calls.load(Ordering::SeqCst), call);
+            assert_eq!(fallback_calls.load(Ordering::SeqCst), call);
+        }
+        assert!(service.endpoints[0].health.is_down());
+        assert!(!service.endpoints[1].health.is_down());
+
+        // Clones share endpoint health, so the primary is skipped while it is down
+        assert!(service.with_commitment(None).is_mainnet().await.unwrap());
+        assert_eq!(primary_calls.load(Ordering::SeqCst), ENDPOINT_FAILURE_THRESHOLD as usize);
+
+        // With every endpoint down, all of them are tried rather than failing outright
+        *service.endpoints[1].health.down_until.lock().unwrap() = Some(Instant::now() + ENDPOINT_DOWN_FOR);
+        assert!(service.is_mainnet().await.unwrap());
+        assert_eq!(primary_calls.load(Ordering::SeqCst), ENDPOINT_FAILURE_THRESHOLD as usize + 1);
+        assert!(!service.endpoints[1].health.is_down(), "a success clears the mark");
+    }
+
+    #[test]
+    fn test_endpoint_health() {
+        let health = EndpointHealth::default();
+        assert!(!health.record_failure());
+        // A success resets the run of failures
+        health.record_success();
+        for _ in 1..ENDPOINT_FAILURE_THRESHOLD {
+            assert!(!health.record_failure());
+            assert!(!health.is_down());
+        }
+        assert!(health.record_failure());
+        assert!(health.is_down());
+
+        // The mark expires on its own
+        *health.down_until.lock().unwrap() = Some(Instant::now() - Duration::from_millis(1));
+        assert!(!health.is_down());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_send_transaction_retries_and_returns_signature() {
+        use solana_sdk::{signature::{Keypair, Signer}, system_instruction};
+
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], Hash::new_unique());
+        let signature = transaction.signatures[0];
+
+        // One 503, then the node accepts the transaction
+        let (url, calls) = spawn_rpc_node(move |call, request| {
+            assert_eq!(request["method"], "sendTransaction");
+            if call == 1 {
+                (StatusCode::SERVICE_UNAVAILABLE, json!({}))
+            } else {
+                (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": signature.to_string() }))
+            }
+        }).await;
+
+        for kind in [RpcClientKind::Blocking, RpcClientKind::Nonblocking] {
+            calls.store(0, Ordering::SeqCst);
+            let mut service = RpcService::with_client(&url, kind);
+            service.retry = fast_retry(3);
+
+            assert_eq!(service.send_transaction(&transaction).await.unwrap(), signature);
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+        }
+    }
+
+    /// A jsonParsed token account as returned by getTokenAccountsByOwner
+    fn token_account_json(account: &Pubkey, mint: &Pubkey, amount: &str, decimals: u8) -> Value {
+        json!({
+            "pubkey": account.to_string(),
+            "account": {
+                "lamports": 2039280,
+                "owner": spl_token::id().to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": 165,
+                "data": {
+                    "program": "spl-token",
+                    "space": 165,
+                    "parsed": {
+                        "type": "account",
+                        "info": {
+                            "mint": mint.to_string(),
+                            "owner": Pubkey::new_unique().to_string(),
+                            "state": "initialized",
+                            "isNative": false,
+                            "tokenAmount": {
+                                "amount": amount,
+                                "decimals": decimals,
+                                "uiAmount": null,
+                                "uiAmountString": amount
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_token_accounts_by_owner() {
+        let account = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let accounts = json!([token_account_json(&account, &mint, "18446744073709551615", 6)]);
+        let (url, _) = spawn_rpc_node(move |_, request| {
+            assert_eq!(request["method"], "getTokenAccountsByOwner");
+            (StatusCode::OK, json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "context": { "slot": 1 }, "value": accounts }
+            }))
+        }).await;
+
+        let service = RpcService::with_url(&url);
+        let balances = service.get_token_accounts_by_owner(&Pubkey::new_unique(), Some(mint)).await.unwrap();
+
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].account, account.to_string());
+        assert_eq!(balances[0].mint, mint.to_string());
+        assert_eq!(balances[0].amount, u64::MAX);
+        assert_eq!(balances[0].decimals, 6);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_token_accounts_by_owner_empty() {
+        let (url, _) = spawn_rpc_node(|_, request| {
+            (StatusCode::OK, json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "context": { "slot": 1 }, "value": [] }
+            }))
+        }).await;
+
+        let service = RpcService::with_url(&url);
+        let balances = service.get_token_accounts_by_owner(&Pubkey::new_unique(), None).await.unwrap();
+        assert!(balances.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_vote_accounts() {
+        let vote_pubkey = Pubkey::new_unique();
+        let vote_account = |stake: u64| json!({
+            "votePubkey": Pubkey::new_unique().to_string(),
+            "nodePubkey": Pubkey::new_unique().to_string(),
+            "activatedStake": stake,
+            "commission": 5,
+            "epochVoteAccount": true,
+            "epochCredits": [[100, 2000, 1000]],
+            "lastVote": 250,
+            "rootSlot": 218
+        });
+        let current = json!([vote_account(10), vote_account(30), vote_account(20)]);
+        let delinquent = json!([vote_account(0)]);
+        let (url, _) = spawn_rpc_node(move |_, request| {
+            assert_eq!(request["method"], "getVoteAccounts");
+            let filtered = request["params"][0]["votePubkey"].as_str().is_some();
+            (StatusCode::OK, json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {
+                    "current": if filtered { json!([current[0]]) } else { current.clone() },
+                    "delinquent": if filtered { json!([]) } else { delinquent.clone() }
+                }
+            }))
+        }).await;
+        let service = RpcService::with_url(&url);
+
+        // Sorted by stake, then paged independently within each list
+        let accounts = service.get_vote_accounts(None, 1, 1).await.unwrap();
+        assert_eq!((accounts.total_current, accounts.total_delinquent), (3, 1));
+        assert_eq!((accounts.pagination.total, accounts.pagination.next_offset), (3, Some(2)));
+        assert_eq!(accounts.current.len(), 1);
+        assert_eq!(accounts.current[0].activated_stake, 20);
+        assert_eq!(accounts.current[0].commission, 5);
+        assert!(accounts.delinquent.is_empty());
+
+        let accounts = service.get_vote_accounts(None, 0, 100).await.unwrap();
+        let stakes: Vec<_> = accounts.current.iter().map(|account| account.activated_stake).collect();
+        assert_eq!(stakes, [30, 20, 10]);
+        assert_eq!(accounts.delinquent[0].last_vote, 250);
+        assert_eq!(accounts.pagination.next_offset, None);
+
+        // The filter is passed to the node
+        let accounts = service.get_vote_accounts(Some(&vote_pubkey), 0, 100).await.unwrap();
+        assert_eq!((accounts.total_current, accounts.total_delinquent), (1, 0));
+    }
+
+    /// Serves a getAccountInfo response whose value is `account`
+    async fn spawn_account_node(account: Value) -> String {
+        let (url, _) = spawn_rpc_node(move |_, request| {
+            assert_eq!(request["method"], "getAccountInfo");
+            (StatusCode::OK, json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "context": { "slot": 1 }, "value": account }
+            }))
+        }).await;
+        url
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_account_info_base64() {
+        let url = spawn_account_node(json!({
+            "lamports": 42,
+            "owner": "11111111111111111111111111111111",
+            "executable": false,
+            "rentEpoch": 0,
+            "space": 3,
+            "data": ["AQID", "base64"]
+        })).await;
+
+        let service = RpcService::with_url(&url);
+        let account = service.get_account_info(&Pubkey::new_unique(), UiAccountEncoding::Base64).await.unwrap();
+
+        assert_eq!(account.lamports, 42);
+        assert_eq!(account.owner, "11111111111111111111111111111111");
+        assert_eq!(account.encoding, "base64");
+        assert_eq!(account.data, json!("AQID"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_account_info_json_parsed() {
+        let token_account = token_account_json(&Pubkey::new_unique(), &Pubkey::new_unique(), "5", 0);
+        let url = spawn_account_node(token_account["account"].clone()).await;
+
+        let service = RpcService::with_url(&url);
+        let account = service.get_account_info(&Pubkey::new_unique(), UiAccountEncoding::JsonParsed).await.unwrap();
+
+        assert_eq!(account.encoding, "jsonParsed");
+        assert_eq!(account.data["program"], "spl-token");
+        assert_eq!(account.data["parsed"]["info"]["tokenAmount"]["amount"], "5");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_account_info_not_found() {
+        let url = spawn_account_node(Value::Null).await;
+
+        let service = RpcService::with_url(&url);
+        let result = service.get_account_info(&Pubkey::new_unique(), UiAccountEncoding::Base64).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_simulate_transaction() {
+        let payer = Pubkey::new_unique();
+        let instruction = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let transaction = Transaction::new_unsigned(solana_sdk::message::Message::new(&[instruction], Some(&payer)));
+
+        let (url, _) = spawn_rpc_node(|_, request| {
+            assert_eq!(request["method"], "simulateTransaction");
+            let config = &request["params"][1];
+            assert_eq!(config["encoding"], "base64");
+            assert_eq!(config["sigVerify"], false);
+            assert_eq!(config["replaceRecentBlockhash"], true);
+            assert_eq!(config["commitment"], "processed");
+
+            let value = json!({
+                "err": { "InstructionError": [0, { "Custom": 1 }] },
+                "logs": ["Program 11111111111111111111111111111111 invoke [1]", "Transfer: insufficient lamports 0, need 1"],
+                "accounts": null,
+                "unitsConsumed": 150,
+                "returnData": null
+            });
+            (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "context": { "slot": 1 }, "value": value } }))
+        }).await;
+
+        for kind in [RpcClientKind::Blocking, RpcClientKind::Nonblocking] {
+            let service = RpcService::with_client(&url, kind).with_commitment(Some(CommitmentConfig::processed()));
+            let simulation = service.simulate_transaction(&transaction, false, true).await.unwrap();
+
+            assert!(simulation.err.unwrap().contains("custom program error: 0x1"));
+            assert_eq!(simulation.logs.len(), 2);
+            assert_eq!(simulation.units_consumed, Some(150));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_signature_status() {
+        let statuses = [
+            json!({ "slot": 72, "confirmations": 3, "err": null, "status": { "Ok": null }, "confirmationStatus": "confirmed" }),
+            json!({
+                "slot": 80,
+                "confirmations": null,
+                "err": { "InstructionError": [0, { "Custom": 1 }] },
+                "status": { "Err": { "InstructionError": [0, { "Custom": 1 }] } },
+                "confirmationStatus": "finalized"
+            }),
+            Value::Null,
+        ];
+        let (url, _) = spawn_rpc_node(move |call, request| {
+            assert_eq!(request["method"], "getSignatureStatuses");
+            let value = json!([statuses[call - 1]]);
+            (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "context": { "slot": 90 }, "value": value } }))
+        }).await;
+
+        let service = RpcService::with_url(&url);
+        let signature = Signature::from([1u8; 64]);
+
+        let status = service.get_signature_status(&signature).await.unwrap();
+        assert!(status.found);
+        assert_eq!(status.signature, signature.to_string());
+        assert_eq!(status.confirmation_status.as_deref(), Some("confirmed"));
+        assert_eq!((status.slot, status.confirmations), (Some(72), Some(3)));
+        assert!(status.err.is_none());
+
+        let status = service.get_signature_status(&signature).await.unwrap();
+        assert_eq!(status.confirmation_status.as_deref(), Some("finalized"));
+        assert_eq!(status.confirmations, None);
+        assert!(status.err.unwrap().contains("custom program error: 0x1"));
+
+        let status = service.get_signature_status(&signature).await.unwrap();
+        assert!(!status.found);
+        assert!(status.confirmation_status.is_none() && status.slot.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_minimum_balance_for_rent_exemption() {
+        let (url, _) = spawn_rpc_node(|_, request| {
+            assert_eq!(request["method"], "getMinimumBalanceForRentExemption");
+            assert_eq!(request["params"][0], 165);
+            (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": 2039280 }))
+        }).await;
+
+        let service = RpcService::with_url(&url);
+        assert_eq!(service.get_minimum_balance_for_rent_exemption(165).await.unwrap(), 2039280);
+    }
+
+    #[tokio::test]
+    async fn test_get_health_and_slot() {
+        let (url, _) = spawn_rpc_node(|_, request| match request["method"].as_str() {
+            Some("getHealth") => (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": "ok" })),
+            Some("getSlot") => {
+                assert_eq!(request["params"][0]["commitment"], "confirmed");
+                (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": 281_234_567 }))
+            }
+            method => panic!("unexpected {:?}", method),
+        }).await;
+
+        let service = RpcService::with_url(&url);
+        service.get_health().await.unwrap();
+        assert_eq!(service.get_slot().await.unwrap(), 281_234_567);
+
+        // A node that has fallen behind says so, but still reports its slot
+        let (url, _) = spawn_rpc_node(|_, request| match request["method"].as_str() {
+            Some("getHealth") => (StatusCode::OK, json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": RPC_NODE_UNHEALTHY, "message": "Node is behind by 120 slots", "data": { "numSlotsBehind": 120 } }
+            })),
+            _ => (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": 281_234_447 })),
+        }).await;
+        let service = RpcService { retry: fast_retry(0), ..RpcService::with_url(&url) };
+        let error = service.get_health().await.unwrap_err();
+        assert!(error.to_string().contains("Node is behind by 120 slots"), "{}", error);
+        assert_eq!(service.get_slot().await.unwrap(), 281_234_447);
+    }
+
+    /// Serves a base64 getAccountInfo response for an account owned by `owner` holding `data`
+    async fn spawn_raw_account_node(owner: Pubkey, data: Vec<u8>) -> String {
+        use base64::{Engine as _, engine::general_purpose};
+
+        spawn_account_node(json!({
+            "lamports": 1461600,
+            "owner": owner.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+            "data": [general_purpose::STANDARD.encode(&data), "base64"]
+        })).await
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_mint_info() {
+        let mint_authority = Pubkey::new_unique();
+        let state = Mint {
+            mint_authority: Some(mint_authority).into(),
+            supply: u64::MAX,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        };
+        let mut data = vec![0; Mint::LEN];
+        state.pack_into_slice(&mut data);
+
+        let url = spawn_raw_account_node(spl_token::id(), data).await;
+        let mint = Pubkey::new_unique();
+        let info = RpcService::with_url(&url).get_mint_info(&mint).await.unwrap();
+
+        assert_eq!(info.mint, mint.to_string());
+        assert_eq!(info.supply, u64::MAX);
+        assert_eq!(info.decimals, 6);
+        assert_eq!(info.mint_authority, Some(mint_authority.to_string()));
+        assert_eq!(info.freeze_authority, None);
+        assert!(info.is_initialized);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_mint_info_rejects_non_mints() {
+        let mint = Pubkey::new_unique();
+
+        // Owned by another program
+        let url = spawn_raw_account_node(Pubkey::new_unique(), vec![0; Mint::LEN]).await;
+        let result = RpcService::with_url(&url).get_mint_info(&mint).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("owned by")));
+
+        // Token program account that was never initialized
+        let url = spawn_raw_account_node(spl_token::id(), vec![0; Mint::LEN]).await;
+        let result = RpcService::with_url(&url).get_mint_info(&mint).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("not initialized")));
+
+        // A token account rather than a mint
+        let url = spawn_raw_account_node(spl_token::id(), vec![0; spl_token::state::Account::LEN]).await;
+        let result = RpcService::with_url(&url).get_mint_info(&mint).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("not a mint")));
+
+        let url = spawn_account_node(Value::Null).await;
+        let result = RpcService::with_url(&url).get_mint_info(&mint).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_token_account_info() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let state = TokenAccount {
+            mint,
+            owner,
+            amount: 1_000,
+            delegate: Some(delegate).into(),
+            state: AccountState::Frozen,
+            is_native: None.into(),
+            delegated_amount: 250,
+            close_authority: None.into(),
+        };
+        let mut data = vec![0; TokenAccount::LEN];
+        state.pack_into_slice(&mut data);
+
+        let url = spawn_raw_account_node(spl_token::id(), data).await;
+        let account = Pubkey::new_unique();
+        let info = RpcService::with_url(&url).get_token_account_info(&account).await.unwrap();
+
+        assert_eq!(info.account, account.to_string());
+        assert_eq!(info.mint, mint.to_string());
+        assert_eq!(info.owner, owner.to_string());
+        assert_eq!(info.amount, 1_000);
+        assert_eq!(info.delegate, Some(delegate.to_string()));
+        assert_eq!(info.delegated_amount, 250);
+        assert_eq!(info.state, "frozen");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_token_account_info_rejects_non_token_accounts() {
+        let account = Pubkey::new_unique();
+
+        let url = spawn_raw_account_node(Pubkey::new_unique(), vec![0; TokenAccount::LEN]).await;
+        let result = RpcService::with_url(&url).get_token_account_info(&account).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("owned by")));
+
+        let url = spawn_raw_account_node(spl_token::id(), vec![0; TokenAccount::LEN]).await;
+        let result = RpcService::with_url(&url).get_token_account_info(&account).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("not initialized")));
+
+        // A mint rather than a token account
+        let url = spawn_raw_account_node(spl_token::id(), vec![0; Mint::LEN]).await;
+        let result = RpcService::with_url(&url).get_token_account_info(&account).await;
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.contains("not a token account")));
+    }
+
+    #[test]
+    fn test_with_commitment_shares_the_client() {
+        for kind in [RpcClientKind::Blocking, RpcClientKind::Nonblocking] {
+            let service = RpcService::with_client("http://127.0.0.1:8899", kind);
+            let finalized = service.with_commitment(Some(CommitmentConfig::finalized()));
+            assert_eq!(finalized.commitment, CommitmentConfig::finalized());
+            assert_eq!(service.with_commitment(None).commitment, service.commitment);
+
+            let shared = match (&service.endpoints[0].backend, &finalized.endpoints[0].backend) {
+                (RpcBackend::Blocking(a), RpcBackend::Blocking(b)) => Arc::ptr_eq(a, b),
+                (RpcBackend::Nonblocking(a), RpcBackend::Nonblocking(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            };
+            assert!(shared);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_commitment_is_sent_with_reads() {
+        let (url, _) = spawn_rpc_node(|_, request| {
+            let commitment = match request["method"].as_str().unwrap() {
+                "getAccountInfo" => &request["params"][1]["commitment"],
+                "getMinimumBalanceForRentExemption" => &request["params"][1]["commitment"],
+                "getTokenAccountsByOwner" => &request["params"][2]["commitment"],
+                method => panic!("unexpected call {}", method),
+            };
+            assert_eq!(commitment, "finalized");
+            let result = match request["method"].as_str().unwrap() {
+                "getAccountInfo" | "getTokenAccountsByOwner" => {
+                    let value = if request["method"] == "getAccountInfo" { Value::Null } else { json!([]) };
+                    json!({ "context": { "slot": 1 }, "value": value })
+                }
+                _ => json!(890880),
+            };
+            (StatusCode::OK, json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }).await;
+
+        for kind in [RpcClientKind::Blocking, RpcClientKind::Nonblocking] {
+            let service = RpcService::with_client(&url, kind).with_commitment(Some(CommitmentConfig::finalized()));
+            assert_eq!(service.client_kind(), kind);
+
+            let result = service.get_account_info(&Pubkey::new_unique(), UiAccountEncoding::Base64).await;
+            assert!(matches!(result, Err(AppError::NotFound(_))));
+            assert!(matches!(service.get_mint_info(&Pubkey::new_unique()).await, Err(AppError::NotFound(_))));
+            assert_eq!(service.get_minimum_balance_for_rent_exemption(0).await.unwrap(), 890880);
+            assert!(service.get_token_accounts_by_owner(&Pubkey::new_unique(), None).await.unwrap().is_empty());
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Sign-In With Solana (SIWS) messages
+//!
+//! The text layout follows the wallet-standard `solana:signIn` feature, which adapts EIP-4361
+//! (Sign-In With Ethereum) to Solana addresses:
+//!
+//! ```text
+//! ${domain} wants you to sign in with your Solana account:
+//! ${address}
+//!
+//! ${statement}
+//!
+//! URI: ${uri}
+//! Version: ${version}
+//! Chain ID: ${chain_id}
+//! Nonce: ${nonce}
+//! Issued At: ${issued_at}
+//! Expiration Time: ${expiration_time}
+//! Not Before: ${not_before}
+//! Request ID: ${request_id}
+//! Resources:
+//! - ${resources[0]}
+//! ```
+//!
+//! Everything after the address is optional. The statement paragraph is left out when there is
+//! no statement, and the field lines when none of the fields are set.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use utoipa::ToSchema;
+
+use crate::utils::errors::{AppError, Result};
+
+/// Ends the first line of every SIWS message, after the domain
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+
+/// Fewest characters a nonce may have, as in EIP-4361
+pub const MIN_NONCE_LEN: usize = 8;
+
+/// Length of the nonces `generate_nonce` returns
+const GENERATED_NONCE_LEN: usize = 16;
+
+/// The fields of a Sign-In With Solana message
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SiwsMessage {
+    /// The domain asking for the sign-in, e.g. "example.com"
+    pub domain: String,
+    /// Base58 address of the signing account
+    pub address: String,
+    /// Human-readable statement shown to the user, on a single line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, alias = "chainId", skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    /// At least 8 alphanumeric characters, chosen by the server to prevent replays
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// RFC 3339 timestamp
+    #[serde(default, alias = "issuedAt", skip_serializing_if = "Option::is_none")]
+    pub issued_at: Option<String>,
+    /// RFC 3339 timestamp after which the sign-in is no longer valid
+    #[serde(default, alias = "expirationTime", skip_serializing_if = "Option::is_none")]
+    pub expiration_time: Option<String>,
+    /// RFC 3339 timestamp before which the sign-in is not yet valid
+    #[serde(default, alias = "notBefore", skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<String>,
+    #[serde(default, alias = "requestId", skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+}
+
+impl SiwsMessage {
+    /// Fills in a random nonce and an issued-at time of `now`, where they aren't already set
+    pub fn with_defaults(mut self, now: DateTime<Utc>) -> Self {
+        self.nonce.get_or_insert_with(generate_nonce);
+        self.issued_at
+            .get_or_insert_with(|| now.to_rfc3339_opts(SecondsFormat::Millis, true));
+        self
+    }
+
+    /// Checks every field can be written to, and read back from, the message text
+    pub fn validate(&self) -> Result<()> {
+        single_line(&self.domain, "domain")?;
+        if self.domain.is_empty() {
+            return Err(AppError::ValidationError("domain is required".to_string()));
+        }
+        Pubkey::from_str(&self.address)
+            .map_err(|_| AppError::InvalidPublicKey(format!("Invalid address: {}", self.address)))?;
+
+        let optional = [
+            (&self.statement, "statement"),
+            (&self.uri, "uri"),
+            (&self.version, "version"),
+            (&self.chain_id, "chain_id"),
+            (&self.nonce, "nonce"),
+            (&self.issued_at, "issued_at"),
+            (&self.expiration_time, "expiration_time"),
+            (&self.not_before, "not_before"),
+            (&self.request_id, "request_id"),
+        ];
+        for (value, field_name) in optional {
+            if let Some(value) = value {
+                if value.is_empty() {
+                    return Err(AppError::ValidationError(format!(
+                        "{} must not be empty; omit it instead",
+                        field_name
+                    )));
+                }
+                single_line(value, field_name)?;
+            }
+        }
+        for resource in &self.resources {
+            single_line(resource, "resources")?;
+        }
+
+        if let Some(nonce) = &self.nonce {
+            if nonce.len() < MIN_NONCE_LEN || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(AppError::ValidationError(format!(
+                    "nonce must be at least {} alphanumeric characters",
+                    MIN_NONCE_LEN
+                )));
+            }
+        }
+        for (value, field_name) in [
+            (&self.issued_at, "issued_at"),
+            (&self.expiration_time, "expiration_time"),
+            (&self.not_before, "not_before"),
+        ] {
+            if let Some(value) = value {
+                parse_timestamp(value, field_name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The message text a wallet signs, in the layout described in the module docs
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}{}\n{}", self.domain, HEADER_SUFFIX, self.address);
+        if let Some(statement) = &self.statement {
+            text.push_str("\n\n");
+            text.push_str(statement);
+        }
+
+        let mut fields: Vec<String> = self
+            .tagged_fields()
+            .into_iter()
+            .filter_map(|(tag, value)| value.as_ref().map(|value| format!("{}{}", tag, value)))
+            .collect();
+        if !self.resources.is_empty() {
+            fields.push(RESOURCES_TAG.to_string());
+            fields.extend(self.resources.iter().map(|resource| format!("- {}", resource)));
+        }
+        if !fields.is_empty() {
+            text.push_str("\n\n");
+            text.push_str(&fields.join("\n"));
+        }
+        text
+    }
+
+    /// Parses message text in the layout described in the module docs
+    ///
+    /// Only text that `to_text` would produce for the parsed fields is accepted, so a message
+    /// has exactly one reading.
+    pub fn parse(text: &str) -> Result<Self> {
+        let malformed = |reason: &str| AppError::ValidationError(format!("Malformed SIWS message: {}", reason));
+
+        let (header, rest) = text.split_once('\n').ok_or_else(|| malformed("missing address line"))?;
+        let domain = header
+            .strip_suffix(HEADER_SUFFIX)
+            .ok_or_else(|| malformed("first line must end with \"wants you to sign in with your Solana account:\""))?;
+        let (address, rest) = rest.split_once('\n').unwrap_or((rest, ""));
+
+        let mut message = SiwsMessage {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            ..Default::default()
+        };
+
+        let mut paragraphs = Vec::new();
+        if !rest.is_empty() {
+            let rest = rest.strip_prefix('\n').ok_or_else(|| malformed("expected a blank line after the address"))?;
+            paragraphs = rest.split("\n\n").collect();
+        }
+        let is_fields = |paragraph: &str| {
+            TAGS.iter().any(|tag| paragraph.starts_with(tag)) || paragraph.starts_with(RESOURCES_TAG)
+        };
+        let (statement, fields) = match paragraphs.as_slice() {
+            [] => (None, None),
+            [fields] if is_fields(fields) => (None, Some(*fields)),
+            [statement] => (Some(*statement), None),
+            [statement, fields] => (Some(*statement), Some(*fields)),
+            _ => return Err(malformed("unexpected blank line")),
+        };
+        message.statement = statement.map(str::to_string);
+
+        if let Some(fields) = fields {
+            let mut lines = fields.lines().peekable();
+            for (tag, slot) in TAGS.iter().zip(message.tagged_slots()) {
+                if let Some(value) = lines.peek().and_then(|line| line.strip_prefix(tag)) {
+                    *slot = Some(value.to_string());
+                    lines.next();
+                }
+            }
+            if lines.peek() == Some(&RESOURCES_TAG) {
+                lines.next();
+                for line in lines.by_ref() {
+                    let resource = line.strip_prefix("- ").ok_or_else(|| malformed("resources must be \"- \" lines"))?;
+                    message.resources.push(resource.to_string());
+                }
+                if message.resources.is_empty() {
+                    return Err(malformed("Resources: with no resources"));
+                }
+            }
+            if let Some(line) = lines.next() {
+                return Err(malformed(&format!("unexpected line \"{}\"", line)));
+            }
+        }
+
+        message.validate()?;
+        if message.to_text() != text {
+            return Err(malformed("text is not in the canonical layout"));
+        }
+        Ok(message)
+    }
+
+    /// Checks the message was issued for `expected_domain` with `expected_nonce`, where given,
+    /// and that `now` is inside its validity window, returning why not if it isn't
+    pub fn check(
+        &self,
+        expected_domain: Option<&str>,
+        expected_nonce: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> std::result::Result<(), String> {
+        if let Some(domain) = expected_domain {
+            if self.domain != domain {
+                return Err(format!("Message is for domain {}, expected {}", self.domain, domain));
+            }
+        }
+        if let Some(nonce) = expected_nonce {
+            if self.nonce.as_deref() != Some(nonce) {
+                return Err("Message nonce does not match".to_string());
+            }
+        }
+        // validate() has already checked the timestamps parse
+        if let Some(expiration_time) = &self.expiration_time {
+            if parse_timestamp(expiration_time, "expiration_time").is_ok_and(|expires| expires <= now) {
+                return Err(format!("Message expired at {}", expiration_time));
+            }
+        }
+        if let Some(not_before) = &self.not_before {
+            if parse_timestamp(not_before, "not_before").is_ok_and(|starts| starts > now) {
+                return Err(format!("Message is not valid until {}", not_before));
+            }
+        }
+        Ok(())
+    }
+
+    /// The single-value fields after the statement, in message order
+    fn tagged_fields(&self) -> [(&'static str, &Option<String>); 8] {
+        [
+            (TAGS[0], &self.uri),
+            (TAGS[1], &self.version),
+            (TAGS[2], &self.chain_id),
+            (TAGS[3], &self.nonce),
+            (TAGS[4], &self.issued_at),
+            (TAGS[5], &self.expiration_time),
+            (TAGS[6], &self.not_before),
+            (TAGS[7], &self.request_id),
+        ]
+    }
+
+    /// Mutable access to the fields `tagged_fields` lists, in the same order
+    fn tagged_slots(&mut self) -> [&mut Option<String>; 8] {
+        [
+            &mut self.uri,
+            &mut self.version,
+            &mut self.chain_id,
+            &mut self.nonce,
+            &mut self.issued_at,
+            &mut self.expiration_time,
+            &mut self.not_before,
+            &mut self.request_id,
+        ]
+    }
+}
+
+/// Line prefixes of the single-value fields, in message order
+const TAGS: [&str; 8] = [
+    "URI: ",
+    "Version: ",
+    "Chain ID: ",
+    "Nonce: ",
+    "Issued At: ",
+    "Expiration Time: ",
+    "Not Before: ",
+    "Request ID: ",
+];
+
+/// Line introducing the resource list
+const RESOURCES_TAG: &str = "Resources:";
+
+/// A random alphanumeric nonce from the operating system's random number generator
+pub fn generate_nonce() -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(GENERATED_NONCE_LEN)
+        .map(char::from)
+        .collect()
+}
+
+fn single_line(value: &str, field_name: &str) -> Result<()> {
+    if value.contains(['\n', '\r']) {
+        return Err(AppError::ValidationError(format!("{} must be a single line", field_name)));
+    }
+    Ok(())
+}
+
+fn parse_timestamp(value: &str, field_name: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|_| AppError::ValidationError(format!("{} must be an RFC 3339 timestamp", field_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_message() -> SiwsMessage {
+        SiwsMessage {
+            domain: "example.com".to_string(),
+            address: "11111111111111111111111111111112".to_string(),
+            statement: Some("Sign in to Example".to_string()),
+            uri: Some("https://example.com/login".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some("mainnet".to_string()),
+            nonce: Some("32891756".to_string()),
+            issued_at: Some("2024-01-01T00:00:00.000Z".to_string()),
+            expiration_time: Some("2024-01-01T00:10:00.000Z".to_string()),
+            not_before: Some("2024-01-01T00:00:00.000Z".to_string()),
+            request_id: Some("request-1".to_string()),
+            resources: vec!["https://example.com/a".to_string(), "ipfs://b".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_to_text_layout() {
+        let expected = "example.com wants you to sign in with your Solana account:\n\
+            11111111111111111111111111111112\n\
+            \n\
+            Sign in to Example\n\
+            \n\
+            URI: https://example.com/login\n\
+            Version: 1\n\
+            Chain ID: mainnet\n\
+            Nonce: 32891756\n\
+            Issued At: 2024-01-01T00:00:00.000Z\n\
+            Expiration Time: 2024-01-01T00:10:00.000Z\n\
+            Not Before: 2024-01-01T00:00:00.000Z\n\
+            Request ID: request-1\n\
+            Resources:\n\
+            - https://example.com/a\n\
+            - ipfs://b";
+        assert_eq!(full_message().to_text(), expected);
+
+        let minimal = SiwsMessage {
+            domain: "example.com".to_string(),
+            address: "11111111111111111111111111111112".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            minimal.to_text(),
+            "example.com wants you to sign in with your Solana account:\n11111111111111111111111111111112"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trips() {
+        let full = full_message();
+        let without_statement = SiwsMessage { statement: None, ..full.clone() };
+        let statement_only = SiwsMessage {
+            domain: full.domain.clone(),
+            address: full.address.clone(),
+            statement: full.statement.clone(),
+            ..Default::default()
+        };
+        let nonce_only = SiwsMessage {
+            domain: full.domain.clone(),
+            address: full.address.clone(),
+            nonce: full.nonce.clone(),
+            ..Default::default()
+        };
+        for message in [full, without_statement, statement_only, nonce_only] {
+            assert_eq!(SiwsMessage::parse(&message.to_text()).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_text() {
+        let text = full_message().to_text();
+        let malformed = [
+            text.replace("wants you to sign in with your Solana account:", "wants you to sign in:"),
+            text.replace("11111111111111111111111111111112", "not-an-address"),
+            // Fields out of order
+            text.replace("Version: 1\nChain ID: mainnet", "Chain ID: mainnet\nVersion: 1"),
+            text.replace("Nonce: 32891756", "Nonce: short"),
+            text.replace("Issued At: 2024-01-01T00:00:00.000Z", "Issued At: yesterday"),
+            format!("{}\nTrailing: line", text),
+            format!("{}\n\nAnother paragraph", text),
+            text.replace("- ipfs://b", "ipfs://b"),
+        ];
+        for text in malformed {
+            assert!(SiwsMessage::parse(&text).is_err(), "accepted {:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_multiline_fields() {
+        let message = SiwsMessage { statement: Some("line one\nline two".to_string()), ..full_message() };
+        assert!(matches!(message.validate(), Err(AppError::ValidationError(_))));
+        let message = SiwsMessage { version: Some(String::new()), ..full_message() };
+        assert!(matches!(message.validate(), Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_with_defaults() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let message = SiwsMessage { nonce: None, issued_at: None, ..full_message() }.with_defaults(now);
+        assert_eq!(message.nonce.as_ref().unwrap().len(), GENERATED_NONCE_LEN);
+        assert_eq!(message.issued_at.as_deref(), Some("2024-01-01T00:00:00.000Z"));
+        assert!(message.validate().is_ok());
+
+        // Given values are kept
+        assert_eq!(full_message().with_defaults(now), full_message());
+    }
+
+    #[test]
+    fn test_check() {
+        let message = full_message();
+        let at = |timestamp: &str| DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc);
+        let during = at("2024-01-01T00:05:00Z");
+
+        assert_eq!(message.check(Some("example.com"), Some("32891756"), during), Ok(()));
+        assert_eq!(message.check(None, None, during), Ok(()));
+        assert!(message.check(Some("evil.com"), None, during).is_err());
+        assert!(message.check(None, Some("00000000"), during).is_err());
+        assert!(message.check(None, None, at("2024-01-01T00:10:00Z")).is_err());
+        assert!(message.check(None, None, at("2023-12-31T23:59:59Z")).is_err());
+    }
+}
@@ -1,37 +1,101 @@
+use std::ops::Range;
 use std::str::FromStr;
 
+use bip39::{Mnemonic, Seed};
 use solana_sdk::{
+    derivation_path::DerivationPath,
+    hash::Hash,
+    message::Message,
+    program_pack::Pack,
     pubkey::Pubkey,
-    signature::{Keypair, Signer, Signature},
+    rent::Rent,
+    signature::{keypair_from_seed, Keypair, Signer, Signature},
+    signer::keypair::keypair_from_seed_and_derivation_path,
     instruction::Instruction,
+    stake::{
+        self,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
     system_instruction,
+    transaction::Transaction,
 };
 use spl_token::{
-    instruction::{initialize_mint, mint_to, transfer},
+    instruction::{burn, close_account, initialize_mint, mint_to, sync_native, transfer},
+    native_mint,
+    state::Mint,
 };
+use spl_token_2022::{
+    extension::{metadata_pointer, transfer_fee::{self, TransferFee}, ExtensionType},
+    state::Mint as Token2022Mint,
+};
+use spl_token_metadata_interface::state::Field;
 use bs58;
-use base64::{Engine as _, engine::general_purpose};
-use ed25519_dalek::{Verifier, PublicKey as Ed25519PublicKey, ed25519::signature::Signature as Ed25519Signature};
+use rand::{rngs::OsRng, RngCore};
+use zeroize::Zeroizing;
+use ed25519_dalek::{Verifier, PublicKey as Ed25519PublicKey, SecretKey as Ed25519SecretKey, ed25519::signature::Signature as Ed25519Signature};
 
+use crate::config::BinaryEncoding;
 use crate::models::{
     KeypairResponse, 
+    PublicKeyResponse,
+    DerivedKeypair,
+    ExportKeypairResponse,
+    InspectKeypairResponse,
+    PdaResponse,
+    AssociatedTokenAddressResponse,
+    OnCurveResponse,
+    NormalizePubkeyResponse,
     TokenInstructionResponse, 
+    CreateNewTokenResponse,
+    BurnCloseResponse,
+    MintBatchResponse,
+    WrappedSolResponse,
+    CreateStakeAccountResponse,
+    CreateTransferFeeMintResponse,
+    CreateCloseableMintResponse,
+    TransferWithFeeResponse,
     AccountMeta, 
     SignMessageResponse, 
+    SignMessageBatchResponse,
+    RotateKeypairResponse,
+    SignTransactionResponse,
+    PartialSignTransactionResponse,
+    VerifyTransactionResponse,
+    TransactionSignatureResponse,
     VerifyMessageResponse,
     SendSolResponse,
     SendTokenResponse,
+    SendTokenWithMemoResponse,
     SendTokenAccountMeta,
 };
-use crate::utils::errors::{AppError, Result, base58_decode_error};
+use crate::services::packing::pack_instructions;
+use crate::utils::errors::{AppError, Result, base58_decode_error, bincode_error};
 
 /// Solana service for interacting with the Solana blockchain
-pub struct SolanaService;
+pub struct SolanaService {
+    binary_encoding: BinaryEncoding,
+}
+
+/// A Token-2022 mint's transfer-fee extension settings
+#[derive(Debug, Clone, Copy)]
+pub struct TransferFeeSettings {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+    /// May change the fee later
+    pub config_authority: Pubkey,
+    /// May withdraw fees withheld in token accounts
+    pub withdraw_withheld_authority: Pubkey,
+}
 
 impl SolanaService {
     /// Creates a new SolanaService instance
     pub fn new() -> Self {
-        Self
+        Self::with_binary_encoding(BinaryEncoding::default())
+    }
+
+    /// A service that encodes instruction data, messages and transactions with `binary_encoding`
+    pub fn with_binary_encoding(binary_encoding: BinaryEncoding) -> Self {
+        Self { binary_encoding }
     }
 
     /// Generates a new Solana keypair
@@ -39,7 +103,7 @@ impl SolanaService {
         let keypair = Keypair::new();
         
         let pubkey = keypair.pubkey().to_string();
-        let secret = bs58::encode(&keypair.to_bytes()).into_string();
+        let secret = bs58::encode(Zeroizing::new(keypair.to_bytes()).as_ref()).into_string();
 
         Ok(KeypairResponse {
             pubkey,
@@ -47,11 +111,165 @@ impl SolanaService {
         })
     }
 
+    /// Generates a keypair and returns only its public key; the secret is wiped from memory
+    /// before returning and can't be recovered
+    ///
+    /// The seed is drawn into a buffer that zeroizes itself on drop, and the Keypair derived from
+    /// it holds an ed25519-dalek SecretKey, which also zeroizes itself when dropped.
+    pub fn generate_public_key_only(&self) -> Result<PublicKeyResponse> {
+        let mut seed = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(seed.as_mut());
+
+        let keypair = keypair_from_seed(seed.as_ref())
+            .map_err(|e| AppError::InvalidKeypair(e.to_string()))?;
+        let pubkey = keypair.pubkey();
+        drop(keypair);
+
+        Ok(PublicKeyResponse {
+            pubkey: pubkey.to_string(),
+        })
+    }
+
+    /// Derives the keypair for a 32-byte Ed25519 seed, so the same seed always gives the same keypair
+    pub fn keypair_from_seed(&self, seed: &[u8; 32]) -> Result<KeypairResponse> {
+        let keypair = keypair_from_seed(seed)
+            .map_err(|e| AppError::InvalidKeypair(e.to_string()))?;
+
+        Ok(KeypairResponse {
+            pubkey: keypair.pubkey().to_string(),
+            secret: bs58::encode(Zeroizing::new(keypair.to_bytes()).as_ref()).into_string(),
+        })
+    }
+
+    /// Derives the keypairs at m/44'/501'/i'/0' for every account index `i` in `accounts`, in
+    /// order, as Phantom, Solflare and `solana-keygen recover 'prompt://?key=i/0'` do
+    ///
+    /// The BIP39 seed and each derived Keypair zeroize themselves when dropped.
+    pub fn derive_keypairs(&self, mnemonic: &Mnemonic, passphrase: &str, accounts: Range<u32>) -> Result<Vec<DerivedKeypair>> {
+        let seed = Seed::new(mnemonic, passphrase);
+
+        accounts
+            .map(|index| {
+                let path = DerivationPath::new_bip44(Some(index), Some(0));
+                let keypair = keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(path))
+                    .map_err(|e| AppError::InvalidKeypair(e.to_string()))?;
+                Ok(DerivedKeypair {
+                    index,
+                    derivation_path: format!("m/44'/501'/{}'/0'", index),
+                    pubkey: keypair.pubkey().to_string(),
+                    secret: bs58::encode(Zeroizing::new(keypair.to_bytes()).as_ref()).into_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Exports a base58 secret key as the 64-byte array used by solana-keygen keypair files
+    pub fn export_keypair(&self, secret_key: &str) -> Result<ExportKeypairResponse> {
+        // Decode the secret key from base58
+        let secret_bytes = Zeroizing::new(
+            bs58::decode(secret_key)
+                .into_vec()
+                .map_err(base58_decode_error)?,
+        );
+
+        let keypair = self.keypair_from_bytes(&secret_bytes)?;
+
+        Ok(ExportKeypairResponse {
+            pubkey: keypair.pubkey().to_string(),
+            keypair: Zeroizing::new(keypair.to_bytes()).to_vec(),
+        })
+    }
+
+    /// Imports a solana-keygen keypair byte array and returns it in base58 form
+    pub fn import_keypair(&self, keypair_bytes: &[u8]) -> Result<KeypairResponse> {
+        let keypair = self.keypair_from_bytes(keypair_bytes)?;
+
+        Ok(KeypairResponse {
+            pubkey: keypair.pubkey().to_string(),
+            secret: bs58::encode(Zeroizing::new(keypair.to_bytes()).as_ref()).into_string(),
+        })
+    }
+
+    /// Decodes a base58 secret key and reports its length and public keys, never its secret bytes
+    ///
+    /// Unlike the signing endpoints this accepts malformed keys, so that it can say what is wrong
+    /// with them; only text that isn't base58 is an error.
+    pub fn inspect_keypair(&self, secret_key: &str) -> Result<InspectKeypairResponse> {
+        let bytes = Zeroizing::new(
+            bs58::decode(secret_key)
+                .into_vec()
+                .map_err(base58_decode_error)?,
+        );
+
+        let derived_pubkey = bytes.get(..32).map(|secret| {
+            let secret = Ed25519SecretKey::from_bytes(secret).expect("any 32 bytes are an ed25519 secret key");
+            Ed25519PublicKey::from(&secret).to_bytes()
+        });
+        let embedded_pubkey = match bytes.len() {
+            64 => Some(<[u8; 32]>::try_from(&bytes[32..]).expect("32 bytes after the secret half")),
+            _ => None,
+        };
+        let pubkey_matches = embedded_pubkey.is_some() && embedded_pubkey == derived_pubkey;
+        let problem = match bytes.len() {
+            64 if !pubkey_matches => Some("Public key does not match secret key".to_string()),
+            64 => None,
+            32 => Some("Key is 32 bytes: a secret seed without its public half; secret keys are 64 bytes".to_string()),
+            length => Some(format!("Key is {} bytes; secret keys are 64 bytes", length)),
+        };
+
+        Ok(InspectKeypairResponse {
+            byte_length: bytes.len(),
+            derived_pubkey: derived_pubkey.map(|pubkey| Pubkey::new_from_array(pubkey).to_string()),
+            embedded_pubkey: embedded_pubkey.map(|pubkey| Pubkey::new_from_array(pubkey).to_string()),
+            pubkey_matches,
+            valid: problem.is_none(),
+            problem,
+        })
+    }
+
+    /// Finds the program-derived address of `seeds` under `program_id`, with its bump seed
+    pub fn derive_pda(&self, program_id: &Pubkey, seeds: &[Vec<u8>]) -> Result<PdaResponse> {
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        let (address, bump) = Pubkey::try_find_program_address(&seeds, program_id).ok_or_else(|| {
+            AppError::ValidationError(format!("No bump seed gives an off-curve address for these seeds under {}", program_id))
+        })?;
+
+        Ok(PdaResponse { address: address.to_string(), bump })
+    }
+
+    /// Derives `wallet`'s associated token account for `mint` under `token_program`
+    pub fn derive_ata(&self, wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> AssociatedTokenAddressResponse {
+        let address = spl_associated_token_account::get_associated_token_address_with_program_id(wallet, mint, token_program);
+        AssociatedTokenAddressResponse { address: address.to_string() }
+    }
+
+    /// Reports whether `pubkey` is an ed25519 curve point; program-derived addresses never are
+    pub fn on_curve(&self, pubkey: &Pubkey) -> OnCurveResponse {
+        OnCurveResponse { pubkey: pubkey.to_string(), on_curve: pubkey.is_on_curve() }
+    }
+
+    /// Writes `pubkey`, read as `input_encoding`, out as base58, hex and base64
+    pub fn normalize_pubkey(&self, pubkey: &Pubkey, input_encoding: &str) -> NormalizePubkeyResponse {
+        NormalizePubkeyResponse {
+            pubkey: pubkey.to_string(),
+            hex: pubkey.to_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
+            base64: BinaryEncoding::Base64.encode(pubkey.to_bytes()),
+            input_encoding: input_encoding.to_string(),
+        }
+    }
+
+    /// Computes the rent-exempt minimum for `data_len` bytes from the default Rent sysvar values,
+    /// for when no RPC endpoint is configured
+    pub fn rent_exempt_minimum(&self, data_len: usize) -> u64 {
+        Rent::default().minimum_balance(data_len)
+    }
+
     /// Creates an SPL token mint instruction
     pub fn create_token_mint(
         &self,
         mint_authority: &str,
         mint: &str,
+        freeze_authority: Option<&str>,
         decimals: u8,
     ) -> Result<TokenInstructionResponse> {
         // Parse public keys
@@ -61,18 +279,285 @@ impl SolanaService {
         let mint_pubkey = Pubkey::from_str(mint)
             .map_err(|_| AppError::InvalidPublicKey(mint.to_string()))?;
 
+        // The native mint already exists (wrapped SOL); initializing it would always fail on chain
+        if mint_pubkey == native_mint::id() {
+            return Err(AppError::ValidationError(format!(
+                "mint {} is the native SOL mint, which already exists and can't be created; \
+                 use /token/wrap to hold SOL as wrapped SOL tokens",
+                mint_pubkey
+            )));
+        }
+
+        let freeze_authority_pubkey = freeze_authority
+            .map(|freeze_authority| {
+                Pubkey::from_str(freeze_authority)
+                    .map_err(|_| AppError::InvalidPublicKey(freeze_authority.to_string()))
+            })
+            .transpose()?;
+
         // Create the initialize_mint instruction
         let instruction = initialize_mint(
             &spl_token::id(),
             &mint_pubkey,
             &mint_authority_pubkey,
-            Some(&mint_authority_pubkey), // freeze authority (same as mint authority)
+            freeze_authority_pubkey.as_ref(), // None disables freezing for this mint
+            decimals,
+        ).map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+
+        self.instruction_to_response(instruction)
+    }
+
+    /// Generates a new mint keypair and creates the instructions that make it an SPL token mint: a
+    /// system create_account, funded by `payer`, then initialize_mint. The mint's secret key is
+    /// returned because the mint must sign the create_account.
+    pub fn create_new_token_mint(
+        &self,
+        payer: &Pubkey,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> Result<CreateNewTokenResponse> {
+        let mint = Keypair::new();
+        let rent_exempt_lamports = self.rent_exempt_minimum(Mint::LEN);
+
+        let instructions = vec![
+            system_instruction::create_account(payer, &mint.pubkey(), rent_exempt_lamports, Mint::LEN as u64, &spl_token::id()),
+            initialize_mint(&spl_token::id(), &mint.pubkey(), mint_authority, freeze_authority, decimals)
+                .map_err(|e| AppError::TokenOperationFailed(e.to_string()))?,
+        ];
+
+        Ok(CreateNewTokenResponse {
+            mint: mint.pubkey().to_string(),
+            mint_secret: bs58::encode(Zeroizing::new(mint.to_bytes()).as_ref()).into_string(),
+            rent_exempt_lamports,
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| self.instruction_to_response(instruction))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Creates the instructions initializing a Token-2022 mint with the transfer-fee extension:
+    /// initialize_transfer_fee_config, which must come first, then initialize_mint. Also returns
+    /// the account size and rent the caller's create_account needs for the extension.
+    pub fn create_transfer_fee_mint(
+        &self,
+        mint: &Pubkey,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+        fee: TransferFeeSettings,
+    ) -> Result<CreateTransferFeeMintResponse> {
+        let space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[ExtensionType::TransferFeeConfig])
+            .map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+
+        let instructions = vec![
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                mint,
+                Some(&fee.config_authority),
+                Some(&fee.withdraw_withheld_authority),
+                fee.basis_points,
+                fee.maximum_fee,
+            ),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                mint,
+                mint_authority,
+                freeze_authority,
+                decimals,
+            ),
+        ];
+
+        Ok(CreateTransferFeeMintResponse {
+            mint: mint.to_string(),
+            space,
+            rent_exempt_lamports: self.rent_exempt_minimum(space),
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| {
+                    instruction
+                        .map_err(|e| AppError::TokenOperationFailed(e.to_string()))
+                        .and_then(|instruction| self.instruction_to_response(instruction))
+                })
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Creates the instructions initializing a Token-2022 mint with the mint-close-authority
+    /// extension: initialize_mint_close_authority, which must come first, then initialize_mint.
+    /// Also returns the account size and rent the caller's create_account needs for the extension.
+    pub fn create_closeable_mint(
+        &self,
+        mint: &Pubkey,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+        close_authority: &Pubkey,
+    ) -> Result<CreateCloseableMintResponse> {
+        let space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[ExtensionType::MintCloseAuthority])
+            .map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+
+        let instructions = vec![
+            spl_token_2022::instruction::initialize_mint_close_authority(
+                &spl_token_2022::id(),
+                mint,
+                Some(close_authority),
+            ),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                mint,
+                mint_authority,
+                freeze_authority,
+                decimals,
+            ),
+        ];
+
+        Ok(CreateCloseableMintResponse {
+            mint: mint.to_string(),
+            space,
+            rent_exempt_lamports: self.rent_exempt_minimum(space),
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| {
+                    instruction
+                        .map_err(|e| AppError::TokenOperationFailed(e.to_string()))
+                        .and_then(|instruction| self.instruction_to_response(instruction))
+                })
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Creates the Token-2022 close_account instruction that closes a mint with the
+    /// mint-close-authority extension, sending its rent to `destination`. The token program
+    /// rejects it while the mint's supply is above zero.
+    pub fn close_mint(&self, mint: &Pubkey, destination: &Pubkey, close_authority: &Pubkey) -> Result<TokenInstructionResponse> {
+        let instruction = spl_token_2022::instruction::close_account(&spl_token_2022::id(), mint, destination, close_authority, &[])
+            .map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+        self.instruction_to_response(instruction)
+    }
+
+    /// Computes the fee a Token-2022 mint charges on a transfer of `amount`: the basis points
+    /// rounded up, capped at `maximum_fee`
+    pub fn transfer_fee(&self, amount: u64, basis_points: u16, maximum_fee: u64) -> Result<u64> {
+        TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        }
+        .calculate_fee(amount)
+        .ok_or_else(|| AppError::ValidationError("amount is too large to compute a transfer fee for".to_string()))
+    }
+
+    /// Creates a Token-2022 transfer_checked_with_fee instruction between the owner's and the
+    /// destination wallet's Token-2022 associated token accounts. The token program rejects the
+    /// transfer unless `fee` matches what the mint charges.
+    pub fn transfer_with_fee(
+        &self,
+        owner: &Pubkey,
+        destination: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+        decimals: u8,
+        fee: u64,
+    ) -> Result<TransferWithFeeResponse> {
+        let source_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            owner,
+            mint,
+            &spl_token_2022::id(),
+        );
+        let destination_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            destination,
+            mint,
+            &spl_token_2022::id(),
+        );
+
+        let instruction = transfer_fee::instruction::transfer_checked_with_fee(
+            &spl_token_2022::id(),
+            &source_ata,
+            mint,
+            &destination_ata,
+            owner,
+            &[],
+            amount,
             decimals,
+            fee,
+        ).map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+
+        Ok(TransferWithFeeResponse {
+            fee,
+            instruction: self.instruction_to_response(instruction)?,
+        })
+    }
+
+    /// Creates a Token-2022 metadata-pointer initialize instruction, which must come before the
+    /// mint's initialize_mint. The pointer defaults to the mint itself, where
+    /// initialize_token_metadata stores the metadata.
+    pub fn initialize_metadata_pointer(
+        &self,
+        mint: &Pubkey,
+        authority: Option<&Pubkey>,
+        metadata_address: Option<&Pubkey>,
+    ) -> Result<TokenInstructionResponse> {
+        let instruction = metadata_pointer::instruction::initialize(
+            &spl_token_2022::id(),
+            mint,
+            authority.copied(),
+            Some(*metadata_address.unwrap_or(mint)),
         ).map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
 
         self.instruction_to_response(instruction)
     }
 
+    /// Creates a token-metadata initialize instruction storing the name, symbol and uri in the
+    /// Token-2022 mint itself, signed by the mint authority. The program grows the mint account
+    /// to fit, so it must already hold the extra rent.
+    pub fn initialize_token_metadata(
+        &self,
+        mint: &Pubkey,
+        mint_authority: &Pubkey,
+        update_authority: &Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<TokenInstructionResponse> {
+        self.instruction_to_response(spl_token_metadata_interface::instruction::initialize(
+            &spl_token_2022::id(),
+            mint,
+            update_authority,
+            mint,
+            mint_authority,
+            name,
+            symbol,
+            uri,
+        ))
+    }
+
+    /// Creates a token-metadata update_field instruction for the metadata stored in a Token-2022
+    /// mint. `field` is "name", "symbol", "uri", or any other key to set as additional metadata.
+    pub fn update_token_metadata(
+        &self,
+        mint: &Pubkey,
+        update_authority: &Pubkey,
+        field: &str,
+        value: String,
+    ) -> Result<TokenInstructionResponse> {
+        let field = match field {
+            "name" => Field::Name,
+            "symbol" => Field::Symbol,
+            "uri" => Field::Uri,
+            key => Field::Key(key.to_string()),
+        };
+
+        self.instruction_to_response(spl_token_metadata_interface::instruction::update_field(
+            &spl_token_2022::id(),
+            mint,
+            update_authority,
+            field,
+            value,
+        ))
+    }
+
     /// Creates an SPL token mint_to instruction
     pub fn mint_token(
         &self,
@@ -91,14 +576,97 @@ impl SolanaService {
         let authority_pubkey = Pubkey::from_str(authority)
             .map_err(|_| AppError::InvalidPublicKey(authority.to_string()))?;
 
+        self.mint_to_response(&mint_pubkey, &destination_pubkey, &authority_pubkey, amount)
+    }
+
+    /// Creates the mint_to instruction crediting `destination`'s associated token account, as
+    /// for one recipient of `mint_token_batch`
+    pub fn mint_to_response(
+        &self,
+        mint: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Result<TokenInstructionResponse> {
+        let instruction = self.mint_to_instruction(mint, destination, authority, amount)?;
+        self.instruction_to_response(instruction)
+    }
+
+    /// Creates mint_to instructions for each (destination, amount) recipient and, when `bundle`
+    /// gives a fee payer and recent blockhash, packs them into as few base64-encoded transaction
+    /// messages as fit the transaction size limit
+    pub fn mint_token_batch(
+        &self,
+        mint: &Pubkey,
+        authority: &Pubkey,
+        recipients: &[(Pubkey, u64)],
+        bundle: Option<(&Pubkey, &Hash)>,
+    ) -> Result<MintBatchResponse> {
+        let instructions = recipients
+            .iter()
+            .map(|(destination, amount)| self.mint_to_instruction(mint, destination, authority, *amount))
+            .collect::<Result<Vec<_>>>()?;
+
+        let messages = bundle
+            .map(|(payer, recent_blockhash)| -> Result<Vec<String>> {
+                Ok(pack_instructions(&instructions, payer, recent_blockhash)?
+                    .iter()
+                    .map(|message| self.binary_encoding.encode(message.serialize()))
+                    .collect())
+            })
+            .transpose()?;
+
+        Ok(MintBatchResponse {
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| self.instruction_to_response(instruction))
+                .collect::<Result<Vec<_>>>()?,
+            messages,
+        })
+    }
+
+    /// Builds a mint_to instruction crediting the destination wallet's associated token account
+    fn mint_to_instruction(
+        &self,
+        mint: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Result<Instruction> {
         // Calculate associated token account for the destination
-        let destination_ata = spl_associated_token_account::get_associated_token_address(&destination_pubkey, &mint_pubkey);
+        let destination_ata = spl_associated_token_account::get_associated_token_address(destination, mint);
 
-        // Create the mint_to instruction
-        let instruction = mint_to(
+        mint_to(
             &spl_token::id(),
-            &mint_pubkey,
+            mint,
             &destination_ata,
+            authority,
+            &[],
+            amount,
+        ).map_err(|e| AppError::TokenOperationFailed(e.to_string()))
+    }
+
+    /// Creates an SPL token burn instruction for `amount` base units held in `account`
+    pub fn burn_token(
+        &self,
+        mint: &str,
+        account: &str,
+        authority: &str,
+        amount: u64,
+    ) -> Result<TokenInstructionResponse> {
+        let mint_pubkey = Pubkey::from_str(mint)
+            .map_err(|_| AppError::InvalidPublicKey(mint.to_string()))?;
+
+        let account_pubkey = Pubkey::from_str(account)
+            .map_err(|_| AppError::InvalidPublicKey(account.to_string()))?;
+
+        let authority_pubkey = Pubkey::from_str(authority)
+            .map_err(|_| AppError::InvalidPublicKey(authority.to_string()))?;
+
+        let instruction = burn(
+            &spl_token::id(),
+            &account_pubkey,
+            &mint_pubkey,
             &authority_pubkey,
             &[],
             amount,
@@ -107,24 +675,171 @@ impl SolanaService {
         self.instruction_to_response(instruction)
     }
 
-    /// Signs a message with the provided secret key
+    /// Creates an SPL token close_account instruction sending the account's rent to `destination`
+    pub fn close_token_account(
+        &self,
+        account: &str,
+        destination: &str,
+        authority: &str,
+    ) -> Result<TokenInstructionResponse> {
+        let account_pubkey = Pubkey::from_str(account)
+            .map_err(|_| AppError::InvalidPublicKey(account.to_string()))?;
+
+        let destination_pubkey = Pubkey::from_str(destination)
+            .map_err(|_| AppError::InvalidPublicKey(destination.to_string()))?;
+
+        let authority_pubkey = Pubkey::from_str(authority)
+            .map_err(|_| AppError::InvalidPublicKey(authority.to_string()))?;
+
+        let instruction = close_account(
+            &spl_token::id(),
+            &account_pubkey,
+            &destination_pubkey,
+            &authority_pubkey,
+            &[],
+        ).map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+
+        self.instruction_to_response(instruction)
+    }
+
+    /// Creates the burn then close_account instructions that empty and close a token account
+    ///
+    /// The token program only closes accounts with a zero balance, so `amount` must be the
+    /// account's full remaining balance for the transaction to succeed.
+    pub fn burn_and_close(
+        &self,
+        mint: &str,
+        account: &str,
+        authority: &str,
+        destination: &str,
+        amount: u64,
+    ) -> Result<BurnCloseResponse> {
+        let burn = self.burn_token(mint, account, authority, amount)?;
+        let close = self.close_token_account(account, destination, authority)?;
+
+        Ok(BurnCloseResponse {
+            instructions: vec![burn, close],
+        })
+    }
+
+    /// Creates the instructions that wrap `lamports` of `owner`'s SOL as SPL tokens: create the
+    /// owner's wrapped SOL associated token account (if it doesn't exist yet), transfer the
+    /// lamports into it and sync_native so its token balance includes them
+    pub fn wrap_sol(&self, owner: &Pubkey, lamports: u64) -> Result<WrappedSolResponse> {
+        let account = spl_associated_token_account::get_associated_token_address(owner, &native_mint::id());
+
+        let instructions = vec![
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                owner,
+                owner,
+                &native_mint::id(),
+                &spl_token::id(),
+            ),
+            system_instruction::transfer(owner, &account, lamports),
+            self.sync_native_instruction(&account)?,
+        ];
+
+        Ok(WrappedSolResponse {
+            account: account.to_string(),
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| self.instruction_to_response(instruction))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Creates a sync_native instruction, which sets a wrapped SOL account's token balance to
+    /// its lamports minus rent after SOL has been transferred into it
+    pub fn sync_native(&self, account: &Pubkey) -> Result<TokenInstructionResponse> {
+        self.instruction_to_response(self.sync_native_instruction(account)?)
+    }
+
+    fn sync_native_instruction(&self, account: &Pubkey) -> Result<Instruction> {
+        sync_native(&spl_token::id(), account).map_err(|e| AppError::TokenOperationFailed(e.to_string()))
+    }
+
+    /// Creates the instructions for a new stake account: a system create_account funded by
+    /// `from` with `lamports` plus the rent-exempt minimum, then the stake program's initialize
+    /// with the given staker and withdrawer and no lockup
+    pub fn create_stake_account(
+        &self,
+        from: &Pubkey,
+        stake_account: &Pubkey,
+        staker: &Pubkey,
+        withdrawer: &Pubkey,
+        lamports: u64,
+    ) -> Result<CreateStakeAccountResponse> {
+        let rent_exempt_lamports = self.rent_exempt_minimum(StakeStateV2::size_of());
+        let total = lamports
+            .checked_add(rent_exempt_lamports)
+            .ok_or_else(|| AppError::ValidationError("lamports is too large".to_string()))?;
+
+        let authorized = Authorized { staker: *staker, withdrawer: *withdrawer };
+        let instructions = stake::instruction::create_account(from, stake_account, &authorized, &Lockup::default(), total);
+
+        Ok(CreateStakeAccountResponse {
+            stake_account: stake_account.to_string(),
+            lamports: total,
+            rent_exempt_lamports,
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| self.instruction_to_response(instruction))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Creates a stake program delegate instruction, signed by the staker authority
+    pub fn delegate_stake(&self, stake_account: &Pubkey, vote_account: &Pubkey, staker: &Pubkey) -> Result<TokenInstructionResponse> {
+        self.instruction_to_response(stake::instruction::delegate_stake(stake_account, staker, vote_account))
+    }
+
+    /// Creates a stake program deactivate instruction, signed by the staker authority; the
+    /// stake cools down over the following epoch boundary before it can be withdrawn
+    pub fn deactivate_stake(&self, stake_account: &Pubkey, staker: &Pubkey) -> Result<TokenInstructionResponse> {
+        self.instruction_to_response(stake::instruction::deactivate_stake(stake_account, staker))
+    }
+
+    /// Creates a stake program withdraw instruction, signed by the withdraw authority
+    pub fn withdraw_stake(
+        &self,
+        stake_account: &Pubkey,
+        withdrawer: &Pubkey,
+        destination: &Pubkey,
+        lamports: u64,
+    ) -> Result<TokenInstructionResponse> {
+        self.instruction_to_response(stake::instruction::withdraw(stake_account, withdrawer, destination, lamports, None))
+    }
+
+    /// Creates the close_account instruction that unwraps a wrapped SOL account, returning its
+    /// whole balance (wrapped SOL and rent) to `owner` as native SOL
+    ///
+    /// `account` defaults to the owner's wrapped SOL associated token account.
+    pub fn unwrap_sol(&self, owner: &Pubkey, account: Option<&Pubkey>) -> Result<WrappedSolResponse> {
+        let account = account
+            .copied()
+            .unwrap_or_else(|| spl_associated_token_account::get_associated_token_address(owner, &native_mint::id()));
+
+        let instruction = close_account(&spl_token::id(), &account, owner, owner, &[])
+            .map_err(|e| AppError::TokenOperationFailed(e.to_string()))?;
+
+        Ok(WrappedSolResponse {
+            account: account.to_string(),
+            instructions: vec![self.instruction_to_response(instruction)?],
+        })
+    }
+
+    /// Signs a message with the provided secret key, after prepending `prefix` if one is given
     pub fn sign_message(
         &self,
         message: &str,
+        prefix: Option<&str>,
         secret_key: &str,
     ) -> Result<SignMessageResponse> {
-        // Decode the secret key from base58
-        let secret_bytes = bs58::decode(secret_key)
-            .into_vec()
-            .map_err(base58_decode_error)?;
-
-        // Create keypair from secret key bytes
-        let keypair = Keypair::from_bytes(&secret_bytes)
-            .map_err(|_| AppError::InvalidSecretKey("Invalid secret key format".to_string()))?;
+        let keypair = self.keypair_from_secret(secret_key)?;
 
         // Sign the message
-        let message_bytes = message.as_bytes();
-        let signature = keypair.sign_message(message_bytes);
+        let message_bytes = Self::signed_message_bytes(message, prefix);
+        let signature = keypair.sign_message(&message_bytes);
 
         // Encode signature as base58
         let signature_base58 = bs58::encode(signature.as_ref()).into_string();
@@ -136,46 +851,336 @@ impl SolanaService {
         })
     }
 
-    /// Verifies a message signature
-    pub fn verify_message(
+    /// Signs each message with the one secret key, which is decoded a single time, after
+    /// prepending `prefix` to each if one is given
+    pub fn sign_messages(
         &self,
-        message: &str,
-        signature_base58: &str,
-        pubkey: &str,
-    ) -> Result<VerifyMessageResponse> {
-        // Decode signature from base58
-        let signature_bytes = bs58::decode(signature_base58)
-            .into_vec()
-            .map_err(|_| AppError::InvalidSignature("Invalid signature format".to_string()))?;
+        messages: &[String],
+        prefix: Option<&str>,
+        secret_key: &str,
+    ) -> Result<SignMessageBatchResponse> {
+        let keypair = self.keypair_from_secret(secret_key)?;
 
-        // Check if signature has the correct length (64 bytes for ed25519)
-        if signature_bytes.len() != 64 {
-            return Err(AppError::InvalidSignature("Invalid signature length".to_string()));
-        }
+        let signatures = messages
+            .iter()
+            .map(|message| {
+                let signature = keypair.sign_message(&Self::signed_message_bytes(message, prefix));
+                bs58::encode(signature.as_ref()).into_string()
+            })
+            .collect();
 
-        // Parse public key
-        let pubkey_parsed = Pubkey::from_str(pubkey)
-            .map_err(|_| AppError::InvalidPublicKey(format!("Invalid pubkey: {}", pubkey)))?;
+        Ok(SignMessageBatchResponse {
+            pubkey: keypair.pubkey().to_string(),
+            signatures,
+        })
+    }
 
-        // Create signature from bytes
-        let signature = Signature::try_from(signature_bytes.as_slice())
-            .map_err(|_| AppError::InvalidSignature("Invalid signature format".to_string()))?;
+    /// Checks each message was signed by the old secret's key, then signs it again with the new
+    /// secret, after prepending `prefix` to each if one is given
+    pub fn rotate_signatures(
+        &self,
+        messages: &[(&str, Signature)],
+        prefix: Option<&str>,
+        old_secret: &str,
+        new_secret: &str,
+    ) -> Result<RotateKeypairResponse> {
+        let old_keypair = self.keypair_from_secret(old_secret)?;
+        let new_keypair = self.keypair_from_secret(new_secret)?;
+        if old_keypair.pubkey() == new_keypair.pubkey() {
+            return Err(AppError::ValidationError("new_secret must be a different key from old_secret".to_string()));
+        }
 
-        // Verify using ed25519-dalek for compatibility
-        let message_bytes = message.as_bytes();
-        let valid = self.verify_ed25519_signature(&pubkey_parsed, message_bytes, &signature)?;
+        let mut signatures = Vec::with_capacity(messages.len());
+        for (index, (message, signature)) in messages.iter().enumerate() {
+            let message_bytes = Self::signed_message_bytes(message, prefix);
+            if !self.verify_ed25519_signature(&old_keypair.pubkey(), &message_bytes, signature, false)? {
+                return Err(AppError::ValidationError(format!(
+                    "messages[{}].signature is not a signature of the message by {}",
+                    index,
+                    old_keypair.pubkey()
+                )));
+            }
+            signatures.push(bs58::encode(new_keypair.sign_message(&message_bytes).as_ref()).into_string());
+        }
 
-        Ok(VerifyMessageResponse {
-            valid,
-            message: message.to_string(),
-            pubkey: pubkey.to_string(),
+        Ok(RotateKeypairResponse {
+            old_pubkey: old_keypair.pubkey().to_string(),
+            new_pubkey: new_keypair.pubkey().to_string(),
+            signatures,
         })
     }
 
-    /// Creates a SOL transfer instruction
-    pub fn send_sol(
+    /// Signs a transaction message with every one of its required signers, returning the
+    /// base64-encoded transaction ready for submission
+    ///
+    /// Every secret must belong to a required signer and every required signer must be given.
+    pub fn sign_transaction(
         &self,
-        from: &str,
+        message: Message,
+        secret_keys: &[String],
+    ) -> Result<SignTransactionResponse> {
+        let keypairs = self.required_signer_keypairs(&message, secret_keys)?;
+
+        let required = &message.account_keys[..usize::from(message.header.num_required_signatures)];
+        let missing: Vec<String> = required
+            .iter()
+            .filter(|signer| !keypairs.iter().any(|keypair| keypair.pubkey() == **signer))
+            .map(Pubkey::to_string)
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Missing secret for required signer(s): {}",
+                missing.join(", ")
+            )));
+        }
+
+        let recent_blockhash = message.recent_blockhash;
+        let mut transaction = Transaction::new_unsigned(message);
+        let signers: Vec<&Keypair> = keypairs.iter().collect();
+        transaction
+            .try_sign(&signers, recent_blockhash)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to sign transaction: {}", e)))?;
+
+        let serialized = bincode::serialize(&transaction).map_err(bincode_error)?;
+        Ok(SignTransactionResponse {
+            transaction: self.binary_encoding.encode(serialized),
+            signature: transaction.signatures[0].to_string(),
+        })
+    }
+
+    /// Adds signatures from `secret_keys` to a partially signed transaction, keeping the
+    /// signatures it already has, and reports which required signers have still not signed
+    pub fn partial_sign_transaction(
+        &self,
+        mut transaction: Transaction,
+        secret_keys: &[String],
+    ) -> Result<PartialSignTransactionResponse> {
+        let keypairs = self.required_signer_keypairs(&transaction.message, secret_keys)?;
+
+        // Signing against the message's own blockhash leaves the existing signatures in place
+        let recent_blockhash = transaction.message.recent_blockhash;
+        let signers: Vec<&Keypair> = keypairs.iter().collect();
+        transaction
+            .try_partial_sign(&signers, recent_blockhash)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to sign transaction: {}", e)))?;
+
+        let required_signers = usize::from(transaction.message.header.num_required_signatures);
+        let missing: Vec<String> = transaction
+            .signatures
+            .iter()
+            .zip(&transaction.message.account_keys)
+            .take(required_signers)
+            .filter(|(signature, _)| **signature == Signature::default())
+            .map(|(_, signer)| signer.to_string())
+            .collect();
+
+        let serialized = bincode::serialize(&transaction).map_err(bincode_error)?;
+        Ok(PartialSignTransactionResponse {
+            transaction: self.binary_encoding.encode(serialized),
+            complete: missing.is_empty(),
+            missing,
+        })
+    }
+
+    /// Checks every required signature on a transaction against its message, reporting signers
+    /// whose signature is missing (all zeroes) or doesn't verify
+    pub fn verify_transaction(&self, transaction: &Transaction) -> Result<VerifyTransactionResponse> {
+        let message_bytes = transaction.message.serialize();
+        let required_signers = usize::from(transaction.message.header.num_required_signatures);
+
+        let mut missing = Vec::new();
+        let mut invalid = Vec::new();
+        let signers = transaction.signatures.iter().zip(&transaction.message.account_keys).take(required_signers);
+        for (signature, signer) in signers {
+            if *signature == Signature::default() {
+                missing.push(signer.to_string());
+            } else if !self.verify_ed25519_signature(signer, &message_bytes, signature, true)? {
+                invalid.push(signer.to_string());
+            }
+        }
+
+        Ok(VerifyTransactionResponse {
+            complete: missing.is_empty() && invalid.is_empty(),
+            missing,
+            invalid,
+        })
+    }
+
+    /// Checks a transaction is safe for the relayer to pay for, then adds the relayer's fee
+    /// payer signature
+    ///
+    /// The relayer must be the fee payer and play no other part: an instruction that names it
+    /// could spend its lamports. Every instruction must call one of `allowed_programs`, and
+    /// every other required signer must already have signed, so nothing is submitted that the
+    /// cluster would reject at the relayer's expense.
+    pub fn sign_relayed_transaction(
+        &self,
+        mut transaction: Transaction,
+        relayer: &Keypair,
+        allowed_programs: &[Pubkey],
+    ) -> Result<Transaction> {
+        let message = &transaction.message;
+        let relayer_pubkey = relayer.pubkey();
+        if message.account_keys.first() != Some(&relayer_pubkey) {
+            return Err(AppError::ValidationError(format!(
+                "Transaction fee payer must be the relayer {}",
+                relayer_pubkey
+            )));
+        }
+
+        for (index, instruction) in message.instructions.iter().enumerate() {
+            let program_id = instruction.program_id(&message.account_keys);
+            if !allowed_programs.contains(program_id) {
+                return Err(AppError::ValidationError(format!(
+                    "Instruction {} calls program {}, which is not allowed for relaying",
+                    index, program_id
+                )));
+            }
+            // The fee payer is always the first account key
+            if instruction.accounts.contains(&0) {
+                return Err(AppError::ValidationError(format!(
+                    "Instruction {} uses the relayer {}; relayed transactions may only use it to pay fees",
+                    index, relayer_pubkey
+                )));
+            }
+        }
+
+        let recent_blockhash = message.recent_blockhash;
+        transaction
+            .try_partial_sign(&[relayer], recent_blockhash)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to sign transaction: {}", e)))?;
+
+        let verified = self.verify_transaction(&transaction)?;
+        if !verified.invalid.is_empty() {
+            return Err(AppError::InvalidSignature(format!(
+                "Signature(s) do not match the transaction message: {}",
+                verified.invalid.join(", ")
+            )));
+        }
+        if !verified.missing.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Transaction is missing signature(s) from: {}",
+                verified.missing.join(", ")
+            )));
+        }
+
+        Ok(transaction)
+    }
+
+    /// The signature identifying a transaction: its first one, made by the fee payer
+    ///
+    /// Only the fee payer needs to have signed, and that signature must be valid for the message,
+    /// so the id returned is the one the transaction will have once it is broadcast.
+    pub fn transaction_signature(&self, transaction: &Transaction) -> Result<TransactionSignatureResponse> {
+        let (Some(signature), Some(fee_payer)) = (transaction.signatures.first(), transaction.message.account_keys.first()) else {
+            return Err(AppError::ValidationError("Transaction has no signatures".to_string()));
+        };
+        if *signature == Signature::default() {
+            return Err(AppError::ValidationError(format!("Fee payer {} has not signed the transaction", fee_payer)));
+        }
+        if !self.verify_ed25519_signature(fee_payer, &transaction.message.serialize(), signature, true)? {
+            return Err(AppError::InvalidSignature(format!(
+                "Fee payer {} signature does not match the transaction message",
+                fee_payer
+            )));
+        }
+
+        Ok(TransactionSignatureResponse {
+            signature: signature.to_string(),
+            fee_payer: fee_payer.to_string(),
+        })
+    }
+
+    /// Decodes each secret key, checking it belongs to one of the message's required signers and
+    /// isn't repeated
+    fn required_signer_keypairs(&self, message: &Message, secret_keys: &[String]) -> Result<Vec<Keypair>> {
+        let keypairs = secret_keys
+            .iter()
+            .map(|secret_key| self.keypair_from_secret(secret_key))
+            .collect::<Result<Vec<_>>>()?;
+
+        let required = &message.account_keys[..usize::from(message.header.num_required_signatures)];
+        for (index, keypair) in keypairs.iter().enumerate() {
+            let pubkey = keypair.pubkey();
+            if !required.contains(&pubkey) {
+                return Err(AppError::ValidationError(format!(
+                    "secrets[{}] ({}) is not a required signer for this message",
+                    index, pubkey
+                )));
+            }
+            if keypairs[..index].iter().any(|earlier| earlier.pubkey() == pubkey) {
+                return Err(AppError::ValidationError(format!(
+                    "secrets[{}] ({}) was already provided",
+                    index, pubkey
+                )));
+            }
+        }
+
+        Ok(keypairs)
+    }
+
+    /// Verifies a message signature
+    ///
+    /// By default this uses ed25519-dalek's `verify`. Both modes reject a non-canonical `s` scalar.
+    /// `verify` compares the recomputed `R` with the signature's `R` bytes, so it also rejects
+    /// non-canonical `R` encodings, but it accepts small-order `R` and public key points.
+    /// With `strict` set, `verify_strict` is used: it additionally rejects small-order `R` and
+    /// public keys (as the Solana runtime does), but compares `R` as decompressed points, so a
+    /// non-canonical `R` encoding can pass.
+    ///
+    /// In either mode the all-zeros signature, and any signature under the all-zeros key, is
+    /// invalid.
+    pub fn verify_message(
+        &self,
+        message: &str,
+        prefix: Option<&str>,
+        signature_base58: &str,
+        pubkey: &str,
+        strict: bool,
+    ) -> Result<VerifyMessageResponse> {
+        // Decode signature from base58
+        let signature_bytes = bs58::decode(signature_base58)
+            .into_vec()
+            .map_err(|_| AppError::InvalidSignature("Invalid signature format".to_string()))?;
+
+        // Check if signature has the correct length (64 bytes for ed25519)
+        if signature_bytes.len() != 64 {
+            return Err(AppError::InvalidSignature("Invalid signature length".to_string()));
+        }
+
+        // Parse public key
+        let pubkey_parsed = Pubkey::from_str(pubkey)
+            .map_err(|_| AppError::InvalidPublicKey(format!("Invalid pubkey: {}", pubkey)))?;
+
+        // Create signature from bytes
+        let signature = Signature::try_from(signature_bytes.as_slice())
+            .map_err(|_| AppError::InvalidSignature("Invalid signature format".to_string()))?;
+
+        // Verify using ed25519-dalek for compatibility
+        let message_bytes = Self::signed_message_bytes(message, prefix);
+        let valid = self.verify_ed25519_signature(&pubkey_parsed, &message_bytes, &signature, strict)?;
+
+        Ok(VerifyMessageResponse {
+            valid,
+            message: message.to_string(),
+            pubkey: pubkey.to_string(),
+        })
+    }
+
+    /// The bytes a message signature covers: the UTF-8 prefix, if any, followed directly by the
+    /// UTF-8 message
+    fn signed_message_bytes(message: &str, prefix: Option<&str>) -> Vec<u8> {
+        let prefix = prefix.unwrap_or_default();
+        let mut bytes = Vec::with_capacity(prefix.len() + message.len());
+        bytes.extend_from_slice(prefix.as_bytes());
+        bytes.extend_from_slice(message.as_bytes());
+        bytes
+    }
+
+    /// Creates a SOL transfer instruction
+    pub fn send_sol(
+        &self,
+        from: &str,
         to: &str,
         lamports: u64,
     ) -> Result<SendSolResponse> {
@@ -202,13 +1207,19 @@ impl SolanaService {
         })
     }
 
-    /// Creates an SPL token transfer instruction
+    /// Creates an SPL token transfer instruction from the owner's associated token account to the
+    /// destination wallet's
+    ///
+    /// With `create_ata_if_missing`, the response also lists an idempotent instruction creating
+    /// the destination's associated token account, paid for by the owner, ahead of the transfer.
+    /// The idempotent form succeeds when the account already exists, so it is always safe to include.
     pub fn send_token(
         &self,
         destination: &str,
         mint: &str,
         owner: &str,
         amount: u64,
+        create_ata_if_missing: bool,
     ) -> Result<SendTokenResponse> {
         // Parse public keys
         let destination_pubkey = Pubkey::from_str(destination)
@@ -256,16 +1267,113 @@ impl SolanaService {
             },
         ];
 
+        let program_id = instruction.program_id.to_string();
+        let instruction_data = self.binary_encoding.encode(&instruction.data);
+        let instructions = if create_ata_if_missing {
+            let create_ata = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &owner_pubkey,
+                &destination_pubkey,
+                &mint_pubkey,
+                &spl_token::id(),
+            );
+            Some(vec![
+                self.instruction_to_response(create_ata)?,
+                self.instruction_to_response(instruction)?,
+            ])
+        } else {
+            None
+        };
+
         Ok(SendTokenResponse {
-            program_id: instruction.program_id.to_string(),
+            program_id,
             accounts,
-            instruction_data: general_purpose::STANDARD.encode(&instruction.data),
+            instruction_data,
+            instructions,
+        })
+    }
+
+    /// Creates a memo instruction followed by an SPL token transfer from the owner's associated
+    /// token account to the destination wallet's, optionally preceded by an idempotent
+    /// instruction creating the destination account
+    ///
+    /// The memo is signed by the owner, who signs the transfer anyway, and sits directly before
+    /// the transfer so indexers that read the memo preceding a transfer attribute it correctly.
+    pub fn send_token_with_memo(
+        &self,
+        destination: &Pubkey,
+        mint: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        memo: &str,
+        create_ata_if_missing: bool,
+    ) -> Result<SendTokenWithMemoResponse> {
+        let source_ata = spl_associated_token_account::get_associated_token_address(owner, mint);
+        let dest_ata = spl_associated_token_account::get_associated_token_address(destination, mint);
+
+        let mut instructions = Vec::with_capacity(3);
+        if create_ata_if_missing {
+            instructions.push(spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                owner,
+                destination,
+                mint,
+                &spl_token::id(),
+            ));
+        }
+        instructions.push(spl_memo::build_memo(memo.as_bytes(), &[owner]));
+        instructions.push(
+            transfer(&spl_token::id(), &source_ata, &dest_ata, owner, &[], amount)
+                .map_err(|e| AppError::TokenOperationFailed(e.to_string()))?,
+        );
+
+        Ok(SendTokenWithMemoResponse {
+            instructions: instructions
+                .into_iter()
+                .map(|instruction| self.instruction_to_response(instruction))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Helper function to build a Keypair from 64 raw bytes (secret half followed by public half),
+    /// rejecting arrays whose public half doesn't belong to the secret half
+    fn keypair_from_bytes(&self, bytes: &[u8]) -> Result<Keypair> {
+        if bytes.len() != 64 {
+            return Err(AppError::InvalidKeypair("Invalid keypair length: must be 64 bytes".to_string()));
+        }
+
+        let secret = Ed25519SecretKey::from_bytes(&bytes[..32])
+            .map_err(|_| AppError::InvalidKeypair("Invalid keypair format".to_string()))?;
+        if Ed25519PublicKey::from(&secret).as_bytes() != &bytes[32..] {
+            return Err(AppError::InvalidKeypair("Public key does not match secret key".to_string()));
+        }
+
+        Keypair::from_bytes(bytes)
+            .map_err(|_| AppError::InvalidKeypair("Invalid keypair format".to_string()))
+    }
+
+    /// Decodes a base58 secret key (64-byte keypair), reporting problems as InvalidSecretKey
+    fn keypair_from_secret(&self, secret_key: &str) -> Result<Keypair> {
+        // Decode the secret key from base58
+        let secret_bytes = Zeroizing::new(
+            bs58::decode(secret_key)
+                .into_vec()
+                .map_err(base58_decode_error)?,
+        );
+
+        // Create keypair from secret key bytes, checking the 64-byte length and that the
+        // public half matches the secret half rather than relying on Keypair::from_bytes
+        self.keypair_from_bytes(&secret_bytes).map_err(|e| match e {
+            AppError::InvalidKeypair(msg) => AppError::InvalidSecretKey(msg),
+            other => other,
         })
     }
 
     /// Helper function to convert Solana Instruction to our response format
+    ///
+    /// Accounts keep the instruction builder's order, which is the order the program reads them
+    /// in, so they must never be sorted or deduplicated here. The tests pin that order and the
+    /// signer/writable flags for each SPL instruction against the program's ABI.
     fn instruction_to_response(&self, instruction: Instruction) -> Result<TokenInstructionResponse> {
-        // Convert accounts
+        // Convert accounts, preserving their order
         let accounts: Vec<AccountMeta> = instruction.accounts
             .into_iter()
             .map(|acc| AccountMeta {
@@ -275,8 +1383,7 @@ impl SolanaService {
             })
             .collect();
 
-        // Encode instruction data as base64
-        let instruction_data = general_purpose::STANDARD.encode(&instruction.data);
+        let instruction_data = self.binary_encoding.encode(&instruction.data);
 
         Ok(TokenInstructionResponse {
             program_id: instruction.program_id.to_string(),
@@ -291,7 +1398,15 @@ impl SolanaService {
         pubkey: &Pubkey,
         message: &[u8],
         signature: &Signature,
+        strict: bool,
     ) -> Result<bool> {
+        // No one holds a key for the all-zeros address (the system program), and non-strict
+        // verification accepts the all-zeros signature under it for some messages, so neither
+        // ever verifies. Strict verification also refuses the other small-order keys.
+        if *pubkey == Pubkey::default() || *signature == Signature::default() {
+            return Ok(false);
+        }
+
         // Convert Solana pubkey to ed25519-dalek public key
         let ed25519_pubkey = Ed25519PublicKey::from_bytes(pubkey.as_ref())
             .map_err(|_| AppError::InvalidPublicKey("Invalid public key for verification".to_string()))?;
@@ -301,23 +1416,20 @@ impl SolanaService {
             .map_err(|_| AppError::InvalidSignature("Invalid signature format".to_string()))?;
 
         // Verify the signature
-        let is_valid = ed25519_pubkey.verify(message, &ed25519_signature).is_ok();
+        let is_valid = if strict {
+            ed25519_pubkey.verify_strict(message, &ed25519_signature).is_ok()
+        } else {
+            ed25519_pubkey.verify(message, &ed25519_signature).is_ok()
+        };
         
         Ok(is_valid)
     }
 
     /// Validates if a string is a valid base58-encoded Solana public key
+    #[cfg(test)]
     pub fn is_valid_pubkey(&self, pubkey_str: &str) -> bool {
         Pubkey::from_str(pubkey_str).is_ok()
     }
-
-    /// Validates if a string is a valid base58-encoded Solana secret key
-    pub fn is_valid_secret_key(&self, secret_key_str: &str) -> bool {
-        match bs58::decode(secret_key_str).into_vec() {
-            Ok(bytes) => bytes.len() == 64, // Solana secret keys are 64 bytes
-            Err(_) => false,
-        }
-    }
 }
 
 impl Default for SolanaService {
@@ -329,6 +1441,7 @@ impl Default for SolanaService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::{Engine as _, engine::general_purpose};
 
     #[test]
     fn test_generate_keypair() {
@@ -341,6 +1454,16 @@ mod tests {
         assert!(!keypair_response.secret.is_empty());
     }
 
+    #[test]
+    fn test_generate_public_key_only() {
+        let service = SolanaService::new();
+        let first = service.generate_public_key_only().unwrap();
+        let second = service.generate_public_key_only().unwrap();
+
+        assert!(service.is_valid_pubkey(&first.pubkey));
+        assert_ne!(first.pubkey, second.pubkey);
+    }
+
     #[test]
     fn test_sign_and_verify_message() {
         let service = SolanaService::new();
@@ -350,7 +1473,7 @@ mod tests {
         let message = "Hello, Solana!";
         
         // Sign the message
-        let sign_result = service.sign_message(message, &keypair_response.secret);
+        let sign_result = service.sign_message(message, None, &keypair_response.secret);
         assert!(sign_result.is_ok());
         
         let sign_response = sign_result.unwrap();
@@ -359,9 +1482,11 @@ mod tests {
         
         // Verify the signature
         let verify_result = service.verify_message(
-            message, 
+            message,
+            None,
             &sign_response.signature, 
-            &sign_response.pubkey
+            &sign_response.pubkey,
+            false,
         );
         assert!(verify_result.is_ok());
         let verify_response = verify_result.unwrap();
@@ -369,6 +1494,150 @@ mod tests {
         assert_eq!(verify_response.message, message);
     }
 
+    #[test]
+    fn test_all_zeros_key_and_signature_never_verify() {
+        let service = SolanaService::new();
+        let keypair = Keypair::new();
+        let zeros_signature = Signature::default().to_string();
+        // The all-zeros key is the system program's address, and is on the curve
+        assert_eq!(Pubkey::default(), solana_sdk::system_program::id());
+        assert!(service.on_curve(&Pubkey::default()).on_curve);
+
+        // Without the explicit check, non-strict verification accepts the all-zeros signature
+        // under the all-zeros key for about a quarter of messages
+        for message in (0..64).map(|i| format!("message {}", i)) {
+            for strict in [false, true] {
+                let verify = |signature: &str, pubkey: &str| {
+                    service.verify_message(&message, None, signature, pubkey, strict).unwrap().valid
+                };
+                assert!(!verify(&zeros_signature, &Pubkey::default().to_string()), "{} (strict: {})", message, strict);
+                assert!(!verify(&zeros_signature, &keypair.pubkey().to_string()));
+                let signature = keypair.sign_message(message.as_bytes()).to_string();
+                assert!(!verify(&signature, &Pubkey::default().to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefixed_message_signature() {
+        let service = SolanaService::new();
+        let keypair_response = service.generate_keypair().unwrap();
+        let prefix = "example.com wants you to sign in:\n";
+        let signed = service.sign_message("nonce 42", Some(prefix), &keypair_response.secret).unwrap();
+        // The echoed message excludes the prefix
+        assert_eq!(signed.message, "nonce 42");
+
+        // The signature covers exactly prefix || message
+        let keypair = Keypair::from_base58_string(&keypair_response.secret);
+        let expected = keypair.sign_message(format!("{}nonce 42", prefix).as_bytes());
+        assert_eq!(signed.signature, expected.to_string());
+
+        let verify = |message: &str, prefix: Option<&str>| {
+            service.verify_message(message, prefix, &signed.signature, &signed.pubkey, false).unwrap().valid
+        };
+        assert!(verify("nonce 42", Some(prefix)));
+        assert!(!verify("nonce 42", None));
+        assert!(!verify("nonce 42", Some("other.com wants you to sign in:\n")));
+        // Without a separator, moving bytes between prefix and message gives the same signed bytes
+        assert!(verify(&format!("{}nonce 42", prefix), None));
+    }
+
+    #[test]
+    fn test_rotate_signatures() {
+        let service = SolanaService::new();
+        let old_secret = service.generate_keypair().unwrap().secret.clone();
+        let new_secret = service.generate_keypair().unwrap().secret.clone();
+        let prefix = Some("example.com attests:\n");
+        let signed = |message: &str| {
+            let signature = service.sign_message(message, prefix, &old_secret).unwrap().signature;
+            Signature::from_str(&signature).unwrap()
+        };
+        let messages = [("first", signed("first")), ("second", signed("second"))];
+
+        let rotated = service.rotate_signatures(&messages, prefix, &old_secret, &new_secret).unwrap();
+        assert_eq!(rotated.old_pubkey, Keypair::from_base58_string(&old_secret).pubkey().to_string());
+        assert_eq!(rotated.new_pubkey, Keypair::from_base58_string(&new_secret).pubkey().to_string());
+        for ((message, _), signature) in messages.iter().zip(&rotated.signatures) {
+            assert!(service.verify_message(message, prefix, signature, &rotated.new_pubkey, false).unwrap().valid);
+        }
+
+        // A signature over another message, or under another prefix, is not the old key's
+        let swapped = [("first", signed("second"))];
+        let result = service.rotate_signatures(&swapped, prefix, &old_secret, &new_secret);
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("messages[0].signature is not")));
+        let result = service.rotate_signatures(&messages, None, &old_secret, &new_secret);
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = service.rotate_signatures(&messages, prefix, &old_secret, &old_secret);
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "new_secret must be a different key from old_secret"));
+    }
+
+    #[test]
+    fn test_message_bytes_are_signed_unchanged() {
+        let service = SolanaService::new();
+        let keypair_response = service.generate_keypair().unwrap();
+        let keypair = Keypair::from_base58_string(&keypair_response.secret);
+
+        // Control characters, line endings, a byte order mark, combining marks and emoji are all
+        // signed as their exact UTF-8 bytes, with no trimming or normalization
+        for message in ["", " ", "\0nul\0", "line\r\nbreak\n", "\u{feff}bom", "e\u{301}", "caf\u{e9}", "\u{1f680}"] {
+            let signed = service.sign_message(message, None, &keypair_response.secret).unwrap();
+            assert_eq!(signed.signature, keypair.sign_message(message.as_bytes()).to_string(), "{:?}", message);
+            assert_eq!(signed.message, message);
+        }
+
+        // Canonically equivalent strings are different bytes, so their signatures differ
+        let composed = service.sign_message("caf\u{e9}", None, &keypair_response.secret).unwrap();
+        assert!(!service.verify_message("cafe\u{301}", None, &composed.signature, &composed.pubkey, false).unwrap().valid);
+    }
+
+    #[test]
+    fn test_sign_message_wrong_secret_length() {
+        let service = SolanaService::new();
+        let short_secret = bs58::encode([7u8; 32]).into_string();
+
+        let result = service.sign_message("hello", None, &short_secret);
+        assert!(matches!(result, Err(AppError::InvalidSecretKey(_))));
+    }
+
+    #[test]
+    fn test_sign_message_mismatched_secret() {
+        let service = SolanaService::new();
+        let mut bytes = Keypair::new().to_bytes();
+        bytes[32..].copy_from_slice(&Keypair::new().pubkey().to_bytes());
+        let mismatched_secret = bs58::encode(bytes).into_string();
+
+        let result = service.sign_message("hello", None, &mismatched_secret);
+        assert!(matches!(result, Err(AppError::InvalidSecretKey(_))));
+    }
+
+    #[test]
+    fn test_strict_verification() {
+        let service = SolanaService::new();
+        let keypair_response = service.generate_keypair().unwrap();
+        let sign_response = service.sign_message("strict", None, &keypair_response.secret).unwrap();
+
+        // Canonical signatures are accepted in strict mode
+        let strict_result = service
+            .verify_message("strict", None, &sign_response.signature, &sign_response.pubkey, true)
+            .unwrap();
+        assert!(strict_result.valid);
+
+        // The identity point is a small-order public key: lenient verification accepts a
+        // signature with R = identity and s = 0 for it, strict verification must not
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let weak_pubkey = Pubkey::new_from_array(identity).to_string();
+        let mut weak_signature = [0u8; 64];
+        weak_signature[0] = 1;
+        let weak_signature = bs58::encode(weak_signature).into_string();
+
+        let lenient = service.verify_message("anything", None, &weak_signature, &weak_pubkey, false).unwrap();
+        assert!(lenient.valid);
+        let strict = service.verify_message("anything", None, &weak_signature, &weak_pubkey, true).unwrap();
+        assert!(!strict.valid);
+    }
+
     #[test]
     fn test_invalid_signature_verification() {  
         let service = SolanaService::new();
@@ -379,8 +1648,10 @@ mod tests {
         
         let verify_result = service.verify_message(
             "test message",
+            None,
             &invalid_signature,
-            &keypair_response.pubkey
+            &keypair_response.pubkey,
+            false,
         );
         
         // Should succeed but return valid: false
@@ -388,6 +1659,146 @@ mod tests {
         assert!(!verify_result.unwrap().valid);
     }
 
+    #[test]
+    fn test_keypair_from_seed() {
+        let service = SolanaService::new();
+        // RFC 8032 section 7.1, test 1
+        let seed = [
+            0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c, 0xc4,
+            0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+        ];
+        let public_key = [
+            0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64, 0x07, 0x3a,
+            0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+        ];
+
+        let keypair = service.keypair_from_seed(&seed).unwrap();
+        assert_eq!(keypair.pubkey, Pubkey::new_from_array(public_key).to_string());
+
+        // Deterministic, and the secret is the seed followed by the public key
+        assert_eq!(service.keypair_from_seed(&seed).unwrap().secret, keypair.secret);
+        let secret = bs58::decode(&keypair.secret).into_vec().unwrap();
+        assert_eq!(secret[..32], seed);
+        assert_eq!(secret[32..], public_key);
+    }
+
+    #[test]
+    fn test_derive_keypairs() {
+        use solana_sdk::signer::keypair::generate_seed_from_seed_phrase_and_passphrase;
+
+        let service = SolanaService::new();
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::from_phrase(phrase, bip39::Language::English).unwrap();
+
+        let keypairs = service.derive_keypairs(&mnemonic, "", 0..3).unwrap();
+        let indexes: Vec<_> = keypairs.iter().map(|keypair| (keypair.index, keypair.derivation_path.as_str())).collect();
+        assert_eq!(indexes, [(0, "m/44'/501'/0'/0'"), (1, "m/44'/501'/1'/0'"), (2, "m/44'/501'/2'/0'")]);
+        // The first account Phantom shows for this phrase
+        assert_eq!(keypairs[0].pubkey, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+
+        // The same keys solana-keygen derives from the phrase, each secret matching its pubkey
+        let seed = generate_seed_from_seed_phrase_and_passphrase(phrase, "");
+        for keypair in &keypairs {
+            let expected = keypair_from_seed_and_derivation_path(&seed, Some(DerivationPath::new_bip44(Some(keypair.index), Some(0)))).unwrap();
+            assert_eq!(keypair.pubkey, expected.pubkey().to_string());
+            assert_eq!(Keypair::from_base58_string(&keypair.secret).pubkey(), expected.pubkey());
+        }
+
+        // A range starting later picks up where the first left off
+        let later = service.derive_keypairs(&mnemonic, "", 2..4).unwrap();
+        assert_eq!(later[0].pubkey, keypairs[2].pubkey);
+        assert_ne!(later[1].pubkey, keypairs[2].pubkey);
+
+        // The passphrase gives different wallets
+        let with_passphrase = service.derive_keypairs(&mnemonic, "secret", 0..1).unwrap();
+        assert_ne!(with_passphrase[0].pubkey, keypairs[0].pubkey);
+    }
+
+    #[test]
+    fn test_export_import_keypair_round_trip() {
+        let service = SolanaService::new();
+        let keypair_response = service.generate_keypair().unwrap();
+
+        let exported = service.export_keypair(&keypair_response.secret).unwrap();
+        assert_eq!(exported.keypair.len(), 64);
+        assert_eq!(exported.pubkey, keypair_response.pubkey);
+
+        let imported = service.import_keypair(&exported.keypair).unwrap();
+        assert_eq!(imported.pubkey, keypair_response.pubkey);
+        assert_eq!(imported.secret, keypair_response.secret);
+    }
+
+    #[test]
+    fn test_import_keypair_invalid() {
+        let service = SolanaService::new();
+
+        // Wrong length
+        assert!(matches!(service.import_keypair(&[1u8; 32]), Err(AppError::InvalidKeypair(_))));
+
+        // Public half that doesn't match the secret half
+        let mut bytes = Keypair::new().to_bytes();
+        bytes[63] ^= 0xff;
+        assert!(matches!(service.import_keypair(&bytes), Err(AppError::InvalidKeypair(_))));
+    }
+
+    #[test]
+    fn test_inspect_keypair() {
+        let service = SolanaService::new();
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey().to_string();
+
+        let report = service.inspect_keypair(&keypair.to_base58_string()).unwrap();
+        assert_eq!(report.byte_length, 64);
+        assert_eq!(report.derived_pubkey.as_deref(), Some(pubkey.as_str()));
+        assert_eq!(report.embedded_pubkey.as_deref(), Some(pubkey.as_str()));
+        assert!(report.pubkey_matches && report.valid);
+        assert_eq!(report.problem, None);
+
+        // A corrupted public half is caught, and both public keys are shown
+        let mut bytes = keypair.to_bytes();
+        bytes[63] ^= 0xff;
+        let report = service.inspect_keypair(&bs58::encode(bytes).into_string()).unwrap();
+        assert_eq!(report.derived_pubkey.as_deref(), Some(pubkey.as_str()));
+        assert_eq!(report.embedded_pubkey, Some(Pubkey::try_from(&bytes[32..]).unwrap().to_string()));
+        assert!(!report.pubkey_matches && !report.valid);
+        assert_eq!(report.problem.as_deref(), Some("Public key does not match secret key"));
+
+        // A bare 32-byte seed still derives its public key
+        let report = service.inspect_keypair(&bs58::encode(&bytes[..32]).into_string()).unwrap();
+        assert_eq!(report.derived_pubkey.as_deref(), Some(pubkey.as_str()));
+        assert_eq!(report.embedded_pubkey, None);
+        assert!(!report.valid);
+
+        let report = service.inspect_keypair(&bs58::encode([1u8; 10]).into_string()).unwrap();
+        assert_eq!((report.byte_length, report.derived_pubkey), (10, None));
+        assert_eq!(report.problem.as_deref(), Some("Key is 10 bytes; secret keys are 64 bytes"));
+
+        assert!(matches!(service.inspect_keypair("0OIl"), Err(AppError::DeserializationError(_))));
+    }
+
+    #[test]
+    fn test_derived_addresses() {
+        let service = SolanaService::new();
+        let program_id = Pubkey::new_unique();
+        let seeds = vec![b"vault".to_vec(), program_id.to_bytes().to_vec()];
+
+        let pda = service.derive_pda(&program_id, &seeds).unwrap();
+        let (expected, bump) = Pubkey::find_program_address(&[b"vault", program_id.as_ref()], &program_id);
+        assert_eq!((pda.address, pda.bump), (expected.to_string(), bump));
+        assert!(!service.on_curve(&expected).on_curve);
+        assert!(service.on_curve(&Keypair::new().pubkey()).on_curve);
+
+        let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(
+            service.derive_ata(&wallet, &mint, &spl_token::id()).address,
+            spl_associated_token_account::get_associated_token_address(&wallet, &mint).to_string()
+        );
+        assert_ne!(
+            service.derive_ata(&wallet, &mint, &spl_token_2022::id()).address,
+            service.derive_ata(&wallet, &mint, &spl_token::id()).address
+        );
+    }
+
     #[test]
     fn test_pubkey_validation() {
         let service = SolanaService::new();
@@ -407,7 +1818,7 @@ mod tests {
         let mint_authority = "11111111111111111111111111111112";
         let mint = "11111111111111111111111111111113";
         
-        let result = service.create_token_mint(mint_authority, mint, 9);
+        let result = service.create_token_mint(mint_authority, mint, Some(mint_authority), 9);
         assert!(result.is_ok());
         
         let response = result.unwrap();
@@ -416,6 +1827,656 @@ mod tests {
         assert!(!response.instruction_data.is_empty());
     }
 
+    /// (pubkey, is_signer, is_writable) for each of an instruction's accounts, in order
+    fn account_metas(response: &TokenInstructionResponse) -> Vec<(String, bool, bool)> {
+        response.accounts.iter().map(|a| (a.pubkey.clone(), a.is_signer, a.is_writable)).collect()
+    }
+
+    #[test]
+    fn test_initialize_mint_account_order() {
+        let service = SolanaService::new();
+        let mint_authority = Pubkey::new_unique().to_string();
+        let mint = Pubkey::new_unique().to_string();
+
+        // InitializeMint reads [writable mint, rent sysvar]; the authorities travel in the data
+        // and need not sign
+        let response = service.create_token_mint(&mint_authority, &mint, Some(&mint_authority), 9).unwrap();
+        assert_eq!(account_metas(&response), [
+            (mint, false, true),
+            (solana_sdk::sysvar::rent::id().to_string(), false, false),
+        ]);
+    }
+
+    #[test]
+    fn test_mint_to_account_order() {
+        let service = SolanaService::new();
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let destination_ata = spl_associated_token_account::get_associated_token_address(&owner, &mint);
+
+        // MintTo reads [writable mint, writable destination token account, signing authority]
+        let response = service.mint_token(&mint.to_string(), &owner.to_string(), &authority.to_string(), 1).unwrap();
+        assert_eq!(account_metas(&response), [
+            (mint.to_string(), false, true),
+            (destination_ata.to_string(), false, true),
+            (authority.to_string(), true, false),
+        ]);
+    }
+
+    #[test]
+    fn test_token_mint_freeze_authority() {
+        let service = SolanaService::new();
+        let mint_authority = Pubkey::new_unique();
+        let freeze_authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique().to_string();
+
+        // InitializeMint data: tag, decimals, mint authority, then COption<freeze authority>
+        let data = |freeze: Option<&str>| {
+            let response = service.create_token_mint(&mint_authority.to_string(), &mint, freeze, 6).unwrap();
+            general_purpose::STANDARD.decode(&response.instruction_data).unwrap()
+        };
+
+        let with_freeze = data(Some(&freeze_authority.to_string()));
+        assert_eq!(with_freeze[34], 1);
+        assert_eq!(with_freeze[35..], freeze_authority.to_bytes());
+
+        let without_freeze = data(None);
+        assert_eq!(without_freeze.len(), 35);
+        assert_eq!(without_freeze[34], 0);
+    }
+
+
+    #[test]
+    fn test_send_token_with_memo_fits_one_transaction() {
+        use crate::services::packing::{transaction_size, MAX_TRANSACTION_SIZE};
+        use crate::utils::validation::MAX_MEMO_LEN;
+
+        let service = SolanaService::new();
+        let (destination, mint, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let memo = "m".repeat(MAX_MEMO_LEN);
+        let response = service.send_token_with_memo(&destination, &mint, &owner, 5, &memo, true).unwrap();
+        assert_eq!(response.instructions.len(), 3);
+
+        // Rebuild the instructions from the response, as a client would, and check the largest
+        // memo still leaves the transaction within the size limit
+        let instructions: Vec<Instruction> = response
+            .instructions
+            .iter()
+            .map(|instruction| Instruction {
+                program_id: Pubkey::from_str(&instruction.program_id).unwrap(),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|account| solana_sdk::instruction::AccountMeta {
+                        pubkey: Pubkey::from_str(&account.pubkey).unwrap(),
+                        is_signer: account.is_signer,
+                        is_writable: account.is_writable,
+                    })
+                    .collect(),
+                data: general_purpose::STANDARD.decode(&instruction.instruction_data).unwrap(),
+            })
+            .collect();
+        assert_eq!(instructions[1].program_id, spl_memo::id());
+        assert_eq!(instructions[1].data, memo.as_bytes());
+        let message = Message::new(&instructions, Some(&owner));
+        assert!(transaction_size(&message) <= MAX_TRANSACTION_SIZE, "{}", transaction_size(&message));
+    }
+
+    #[test]
+    fn test_create_new_token_mint() {
+        let service = SolanaService::new();
+        let payer = Pubkey::new_unique();
+        let mint_authority = Pubkey::new_unique();
+
+        let response = service.create_new_token_mint(&payer, &mint_authority, None, 6).unwrap();
+        let secret = bs58::decode(&response.mint_secret).into_vec().unwrap();
+        let mint = Keypair::from_bytes(&secret).unwrap().pubkey().to_string();
+        assert_eq!(response.mint, mint);
+        assert_eq!(response.rent_exempt_lamports, Rent::default().minimum_balance(Mint::LEN));
+
+        // create_account is signed by the payer and the new mint, and initialize_mint follows it
+        let [create, initialize] = &response.instructions[..] else { panic!("expected two instructions") };
+        assert_eq!(create.program_id, solana_sdk::system_program::id().to_string());
+        assert_eq!(account_metas(create), [(payer.to_string(), true, true), (mint.clone(), true, true)]);
+        assert_eq!(initialize.program_id, spl_token::id().to_string());
+        assert_eq!(initialize.accounts[0].pubkey, mint);
+
+        // Every call generates a different mint
+        let other = service.create_new_token_mint(&payer, &mint_authority, None, 6).unwrap();
+        assert_ne!(other.mint, response.mint);
+    }
+
+    #[test]
+    fn test_create_token_mint_rejects_native_mint() {
+        let service = SolanaService::new();
+        let mint_authority = Pubkey::new_unique().to_string();
+
+        let result = service.create_token_mint(&mint_authority, "So11111111111111111111111111111111111111112", None, 9);
+        match result {
+            Err(AppError::ValidationError(message)) => assert!(message.contains("/token/wrap"), "{}", message),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+    #[test]
+    fn test_token_mint_instruction_max_amount() {
+        let service = SolanaService::new();
+        let mint = Pubkey::new_unique().to_string();
+        let owner = Pubkey::new_unique().to_string();
+
+        let response = service.mint_token(&mint, &owner, &owner, u64::MAX).unwrap();
+        let data = general_purpose::STANDARD.decode(&response.instruction_data).unwrap();
+
+        // MintTo is the one-byte tag followed by the little-endian amount
+        assert_eq!(data[1..], u64::MAX.to_le_bytes());
+    }
+
+    #[test]
+    fn test_mint_token_batch() {
+        use solana_sdk::message::Message;
+        use crate::services::packing::{transaction_size, MAX_TRANSACTION_SIZE};
+
+        let service = SolanaService::new();
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let recipients: Vec<_> = (1..=40).map(|amount| (Pubkey::new_unique(), amount)).collect();
+
+        let response = service.mint_token_batch(&mint, &authority, &recipients, None).unwrap();
+        assert_eq!(response.instructions.len(), recipients.len());
+        assert!(response.messages.is_none());
+        let (destination, amount) = recipients[3];
+        let ata = spl_associated_token_account::get_associated_token_address(&destination, &mint);
+        assert_eq!(response.instructions[3].accounts[1].pubkey, ata.to_string());
+        assert_eq!(general_purpose::STANDARD.decode(&response.instructions[3].instruction_data).unwrap()[1..], amount.to_le_bytes());
+
+        let blockhash = Hash::new_unique();
+        let response = service.mint_token_batch(&mint, &authority, &recipients, Some((&payer, &blockhash))).unwrap();
+        let messages: Vec<Message> = response.messages.unwrap()
+            .iter()
+            .map(|message| bincode::deserialize(&general_purpose::STANDARD.decode(message).unwrap()).unwrap())
+            .collect();
+        assert!(messages.len() > 1);
+        assert_eq!(messages.iter().map(|m| m.instructions.len()).sum::<usize>(), recipients.len());
+
+        // Each message is signed by both payer and authority and still fits in a transaction
+        for message in &messages {
+            assert_eq!(message.account_keys[0], payer);
+            assert_eq!(message.recent_blockhash, blockhash);
+            assert_eq!(message.header.num_required_signatures, 2);
+            assert!(transaction_size(message) <= MAX_TRANSACTION_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_sign_transaction() {
+        let service = SolanaService::new();
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let secret = |keypair: &Keypair| bs58::encode(keypair.to_bytes()).into_string();
+
+        // The payer and the mint authority must both sign
+        let instructions = [
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            mint_to(&spl_token::id(), &Pubkey::new_unique(), &Pubkey::new_unique(), &authority.pubkey(), &[], 5).unwrap(),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &Hash::new_unique());
+
+        let response = service.sign_transaction(message.clone(), &[secret(&authority), secret(&payer)]).unwrap();
+        let transaction = crate::utils::validation::validate_signed_transaction(&response.transaction).unwrap();
+        transaction.verify().unwrap();
+        assert_eq!(transaction.message, message);
+        assert_eq!(response.signature, transaction.signatures[0].to_string());
+
+        let missing = service.sign_transaction(message.clone(), &[secret(&payer)]);
+        assert!(matches!(missing, Err(AppError::ValidationError(msg)) if msg.contains(&authority.pubkey().to_string())));
+
+        let stranger = Keypair::new();
+        let extra = service.sign_transaction(message.clone(), &[secret(&payer), secret(&authority), secret(&stranger)]);
+        assert!(matches!(extra, Err(AppError::ValidationError(msg)) if msg.starts_with("secrets[2]")));
+
+        let duplicate = service.sign_transaction(message, &[secret(&payer), secret(&payer)]);
+        assert!(matches!(duplicate, Err(AppError::ValidationError(msg)) if msg.contains("already provided")));
+    }
+
+    #[test]
+    fn test_partial_sign_transaction() {
+        let service = SolanaService::new();
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let secret = |keypair: &Keypair| bs58::encode(keypair.to_bytes()).into_string();
+        let decode = |transaction: &str| -> Transaction {
+            bincode::deserialize(&general_purpose::STANDARD.decode(transaction).unwrap()).unwrap()
+        };
+
+        let instructions = [
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            mint_to(&spl_token::id(), &Pubkey::new_unique(), &Pubkey::new_unique(), &authority.pubkey(), &[], 5).unwrap(),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &Hash::new_unique());
+        let unsigned = general_purpose::STANDARD.encode(bincode::serialize(&Transaction::new_unsigned(message)).unwrap());
+
+        // The authority signs first, offline
+        let first = service.partial_sign_transaction(decode(&unsigned), &[secret(&authority)]).unwrap();
+        assert!(!first.complete);
+        assert_eq!(first.missing, [payer.pubkey().to_string()]);
+        let authority_signature = decode(&first.transaction).signatures[1];
+        assert_ne!(authority_signature, Signature::default());
+
+        // Then the payer; the authority's signature is kept
+        let second = service.partial_sign_transaction(decode(&first.transaction), &[secret(&payer)]).unwrap();
+        assert!(second.complete);
+        assert!(second.missing.is_empty());
+        let transaction = crate::utils::validation::validate_signed_transaction(&second.transaction).unwrap();
+        assert_eq!(transaction.signatures[1], authority_signature);
+        transaction.verify().unwrap();
+
+        let stranger = service.partial_sign_transaction(decode(&unsigned), &[secret(&Keypair::new())]);
+        assert!(matches!(stranger, Err(AppError::ValidationError(msg)) if msg.contains("not a required signer")));
+    }
+
+    #[test]
+    fn test_transaction_signature() {
+        let service = SolanaService::new();
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+
+        let instructions = [
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            mint_to(&spl_token::id(), &Pubkey::new_unique(), &Pubkey::new_unique(), &authority.pubkey(), &[], 5).unwrap(),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &Hash::new_unique());
+        let mut transaction = Transaction::new_unsigned(message);
+        assert!(matches!(service.transaction_signature(&transaction), Err(AppError::ValidationError(m)) if m.contains("has not signed")));
+
+        // The fee payer's signature is the id even before the other signers have signed
+        transaction.partial_sign(&[&payer], transaction.message.recent_blockhash);
+        let result = service.transaction_signature(&transaction).unwrap();
+        assert_eq!(result.signature, transaction.signatures[0].to_string());
+        assert_eq!(result.fee_payer, payer.pubkey().to_string());
+
+        transaction.partial_sign(&[&authority], transaction.message.recent_blockhash);
+        assert_eq!(service.transaction_signature(&transaction).unwrap().signature, result.signature);
+
+        transaction.signatures[0] = payer.sign_message(b"something else");
+        assert!(matches!(service.transaction_signature(&transaction), Err(AppError::InvalidSignature(_))));
+
+        let empty = Transaction { signatures: Vec::new(), message: Message::default() };
+        assert!(matches!(service.transaction_signature(&empty), Err(AppError::ValidationError(m)) if m == "Transaction has no signatures"));
+    }
+
+    #[test]
+    fn test_verify_transaction() {
+        let service = SolanaService::new();
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+
+        let instructions = [
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            mint_to(&spl_token::id(), &Pubkey::new_unique(), &Pubkey::new_unique(), &authority.pubkey(), &[], 5).unwrap(),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &Hash::new_unique());
+        let mut transaction = Transaction::new_unsigned(message);
+
+        let result = service.verify_transaction(&transaction).unwrap();
+        assert!(!result.complete);
+        assert_eq!(result.missing, [payer.pubkey().to_string(), authority.pubkey().to_string()]);
+        assert!(result.invalid.is_empty());
+
+        transaction.partial_sign(&[&payer], transaction.message.recent_blockhash);
+        // The authority's slot holds a signature, but over a different message
+        transaction.signatures[1] = authority.sign_message(b"something else");
+        let result = service.verify_transaction(&transaction).unwrap();
+        assert!(!result.complete);
+        assert!(result.missing.is_empty());
+        assert_eq!(result.invalid, [authority.pubkey().to_string()]);
+
+        transaction.partial_sign(&[&authority], transaction.message.recent_blockhash);
+        let result = service.verify_transaction(&transaction).unwrap();
+        assert!(result.complete);
+        assert!(result.missing.is_empty() && result.invalid.is_empty());
+    }
+
+    #[test]
+    fn test_burn_and_close_instructions() {
+        let service = SolanaService::new();
+        let mint = Pubkey::new_unique().to_string();
+        let account = Pubkey::new_unique().to_string();
+        let authority = Pubkey::new_unique().to_string();
+        let destination = Pubkey::new_unique().to_string();
+
+        let response = service.burn_and_close(&mint, &account, &authority, &destination, 500).unwrap();
+        let [burn, close] = response.instructions.as_slice() else {
+            panic!("expected two instructions");
+        };
+
+        // Burn (tag 8): account, mint, authority
+        let burn_data = general_purpose::STANDARD.decode(&burn.instruction_data).unwrap();
+        assert_eq!(burn_data[0], 8);
+        assert_eq!(burn_data[1..], 500u64.to_le_bytes());
+        assert_eq!(burn.accounts.iter().map(|a| a.pubkey.as_str()).collect::<Vec<_>>(), [&account, &mint, &authority]);
+
+        // CloseAccount (tag 9): account, destination, authority
+        let close_data = general_purpose::STANDARD.decode(&close.instruction_data).unwrap();
+        assert_eq!(close_data, [9]);
+        assert_eq!(close.accounts.iter().map(|a| a.pubkey.as_str()).collect::<Vec<_>>(), [&account, &destination, &authority]);
+        assert!(close.accounts[2].is_signer);
+    }
+
+    #[test]
+    fn test_wrap_sol_instructions() {
+        let service = SolanaService::new();
+        let owner = Pubkey::new_unique();
+        let account = spl_associated_token_account::get_associated_token_address(&owner, &native_mint::id());
+
+        let response = service.wrap_sol(&owner, 1_000_000).unwrap();
+        assert_eq!(response.account, account.to_string());
+        let [create, fund, sync] = response.instructions.as_slice() else {
+            panic!("expected three instructions");
+        };
+
+        // CreateIdempotent (tag 1) for the owner's native mint account
+        assert_eq!(create.program_id, spl_associated_token_account::id().to_string());
+        assert_eq!(general_purpose::STANDARD.decode(&create.instruction_data).unwrap(), [1]);
+        assert_eq!(create.accounts[2].pubkey, owner.to_string());
+        assert_eq!(create.accounts[3].pubkey, native_mint::id().to_string());
+
+        // System transfer (tag 2) into the wrapped SOL account
+        let fund_data = general_purpose::STANDARD.decode(&fund.instruction_data).unwrap();
+        assert_eq!(fund_data[..4], 2u32.to_le_bytes());
+        assert_eq!(fund_data[4..], 1_000_000u64.to_le_bytes());
+        assert_eq!(fund.accounts[1].pubkey, account.to_string());
+
+        // SyncNative (tag 17)
+        assert_eq!(general_purpose::STANDARD.decode(&sync.instruction_data).unwrap(), [17]);
+        assert_eq!(sync.accounts[0].pubkey, account.to_string());
+    }
+
+    #[test]
+    fn test_stake_instructions() {
+        use solana_sdk::stake::instruction::StakeInstruction;
+
+        let service = SolanaService::new();
+        let [from, stake_account, staker, withdrawer, vote, destination] = [(); 6].map(|_| Pubkey::new_unique());
+        let decode = |instruction: &TokenInstructionResponse| -> StakeInstruction {
+            assert_eq!(instruction.program_id, stake::program::id().to_string());
+            bincode::deserialize(&general_purpose::STANDARD.decode(&instruction.instruction_data).unwrap()).unwrap()
+        };
+
+        // The account is funded with the stake plus rent for the 200-byte stake state
+        let created = service.create_stake_account(&from, &stake_account, &staker, &withdrawer, 5_000_000_000).unwrap();
+        assert_eq!(created.rent_exempt_lamports, 2_282_880);
+        assert_eq!(created.lamports, 5_002_282_880);
+        let [create, initialize] = created.instructions.as_slice() else {
+            panic!("expected two instructions");
+        };
+        let create_data = general_purpose::STANDARD.decode(&create.instruction_data).unwrap();
+        assert_eq!(create_data[..4], 0u32.to_le_bytes());
+        assert_eq!(create_data[4..12], created.lamports.to_le_bytes());
+        assert!(create.accounts[1].is_signer, "the new stake account signs its creation");
+        assert_eq!(
+            decode(initialize),
+            StakeInstruction::Initialize(Authorized { staker, withdrawer }, Lockup::default())
+        );
+        assert!(service.create_stake_account(&from, &stake_account, &staker, &withdrawer, u64::MAX).is_err());
+
+        let delegate = service.delegate_stake(&stake_account, &vote, &staker).unwrap();
+        assert_eq!(decode(&delegate), StakeInstruction::DelegateStake);
+        assert_eq!(delegate.accounts[1].pubkey, vote.to_string());
+        assert!(delegate.accounts.iter().any(|meta| meta.pubkey == staker.to_string() && meta.is_signer));
+
+        let deactivate = service.deactivate_stake(&stake_account, &staker).unwrap();
+        assert_eq!(decode(&deactivate), StakeInstruction::Deactivate);
+
+        let withdraw = service.withdraw_stake(&stake_account, &withdrawer, &destination, 42).unwrap();
+        assert_eq!(decode(&withdraw), StakeInstruction::Withdraw(42));
+        assert_eq!(withdraw.accounts[1].pubkey, destination.to_string());
+        assert!(withdraw.accounts.iter().any(|meta| meta.pubkey == withdrawer.to_string() && meta.is_signer));
+    }
+
+    #[test]
+    fn test_transfer_fee_instructions() {
+        use solana_sdk::program_option::COption;
+        use spl_token_2022::{extension::transfer_fee::instruction::TransferFeeInstruction, instruction::TokenInstruction};
+
+        let service = SolanaService::new();
+        let [mint, authority, fee_authority, withdraw_authority, owner, destination] = [(); 6].map(|_| Pubkey::new_unique());
+        let data = |instruction: &TokenInstructionResponse| {
+            assert_eq!(instruction.program_id, spl_token_2022::id().to_string());
+            general_purpose::STANDARD.decode(&instruction.instruction_data).unwrap()
+        };
+
+        let fee = TransferFeeSettings {
+            basis_points: 50,
+            maximum_fee: 5_000,
+            config_authority: fee_authority,
+            withdraw_withheld_authority: withdraw_authority,
+        };
+        let created = service.create_transfer_fee_mint(&mint, &authority, None, 6, fee).unwrap();
+        // 82-byte mint, padding to the 165-byte account length, the account type and the extension
+        assert_eq!(created.space, 278);
+        assert_eq!(created.rent_exempt_lamports, service.rent_exempt_minimum(278));
+        let [config, initialize] = created.instructions.as_slice() else {
+            panic!("expected two instructions");
+        };
+        assert_eq!(
+            TokenInstruction::unpack(&data(config)).unwrap(),
+            TokenInstruction::TransferFeeExtension(TransferFeeInstruction::InitializeTransferFeeConfig {
+                transfer_fee_config_authority: COption::Some(fee_authority),
+                withdraw_withheld_authority: COption::Some(withdraw_authority),
+                transfer_fee_basis_points: 50,
+                maximum_fee: 5_000,
+            })
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&data(initialize)).unwrap(),
+            TokenInstruction::InitializeMint { decimals: 6, mint_authority: authority, freeze_authority: COption::None }
+        );
+
+        // 0.5% of 1_000_001 rounds up to 5_001, then the cap applies
+        assert_eq!(service.transfer_fee(1_000, 50, 5_000).unwrap(), 5);
+        assert_eq!(service.transfer_fee(1_001, 50, 5_000).unwrap(), 6);
+        assert_eq!(service.transfer_fee(1_000_001, 50, 5_000).unwrap(), 5_000);
+
+        let transfer = service.transfer_with_fee(&owner, &destination, &mint, 1_000, 6, 5).unwrap();
+        assert_eq!(transfer.fee, 5);
+        assert_eq!(
+            TokenInstruction::unpack(&data(&transfer.instruction)).unwrap(),
+            TokenInstruction::TransferFeeExtension(TransferFeeInstruction::TransferCheckedWithFee {
+                amount: 1_000,
+                decimals: 6,
+                fee: 5,
+            })
+        );
+        let expected_source = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &owner,
+            &mint,
+            &spl_token_2022::id(),
+        );
+        let accounts: Vec<_> = transfer.instruction.accounts.iter().map(|meta| meta.pubkey.clone()).collect();
+        assert_eq!(accounts[0], expected_source.to_string());
+        assert_eq!(accounts[1], mint.to_string());
+        assert_ne!(
+            accounts[2],
+            spl_associated_token_account::get_associated_token_address(&destination, &mint).to_string(),
+            "Token-2022 accounts are derived with the Token-2022 program id"
+        );
+        assert!(transfer.instruction.accounts[3].is_signer);
+    }
+
+    #[test]
+    fn test_closeable_mint_instructions() {
+        use solana_sdk::program_option::COption;
+        use spl_token_2022::instruction::TokenInstruction;
+
+        let service = SolanaService::new();
+        let [mint, authority, close_authority, destination] = [(); 4].map(|_| Pubkey::new_unique());
+        let data = |instruction: &TokenInstructionResponse| {
+            assert_eq!(instruction.program_id, spl_token_2022::id().to_string());
+            general_purpose::STANDARD.decode(&instruction.instruction_data).unwrap()
+        };
+
+        let created = service.create_closeable_mint(&mint, &authority, None, 6, &close_authority).unwrap();
+        // 165-byte base, the account type, and the extension's 4-byte header and 32-byte authority
+        assert_eq!(created.space, 202);
+        assert_eq!(created.rent_exempt_lamports, service.rent_exempt_minimum(202));
+        let [close_config, initialize] = created.instructions.as_slice() else {
+            panic!("expected two instructions");
+        };
+        assert_eq!(
+            TokenInstruction::unpack(&data(close_config)).unwrap(),
+            TokenInstruction::InitializeMintCloseAuthority { close_authority: COption::Some(close_authority) }
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&data(initialize)).unwrap(),
+            TokenInstruction::InitializeMint { decimals: 6, mint_authority: authority, freeze_authority: COption::None }
+        );
+
+        let close = service.close_mint(&mint, &destination, &close_authority).unwrap();
+        assert_eq!(TokenInstruction::unpack(&data(&close)).unwrap(), TokenInstruction::CloseAccount);
+        let accounts: Vec<_> = close.accounts.iter().map(|meta| (meta.pubkey.clone(), meta.is_signer, meta.is_writable)).collect();
+        assert_eq!(
+            accounts,
+            [
+                (mint.to_string(), false, true),
+                (destination.to_string(), false, true),
+                (close_authority.to_string(), true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_metadata_instructions() {
+        use spl_token_metadata_interface::instruction::{Initialize, TokenMetadataInstruction, UpdateField};
+
+        let service = SolanaService::new();
+        let [mint, authority, update_authority, metadata] = [(); 4].map(|_| Pubkey::new_unique());
+        let data = |instruction: &TokenInstructionResponse| {
+            assert_eq!(instruction.program_id, spl_token_2022::id().to_string());
+            general_purpose::STANDARD.decode(&instruction.instruction_data).unwrap()
+        };
+
+        // The pointer defaults to the mint. After the extension and instruction tags, the data is
+        // the authority then the metadata address, with all zeroes for none.
+        let pointer = data(&service.initialize_metadata_pointer(&mint, Some(&authority), None).unwrap());
+        assert_eq!(pointer[2..34], authority.to_bytes());
+        assert_eq!(pointer[34..66], mint.to_bytes());
+        let pointer = data(&service.initialize_metadata_pointer(&mint, None, Some(&metadata)).unwrap());
+        assert_eq!(pointer[2..34], [0; 32]);
+        assert_eq!(pointer[34..66], metadata.to_bytes());
+
+        let initialize = service
+            .initialize_token_metadata(&mint, &authority, &update_authority, "Token".into(), "TKN".into(), "https://example.com/token.json".into())
+            .unwrap();
+        assert_eq!(
+            TokenMetadataInstruction::unpack(&data(&initialize)).unwrap(),
+            TokenMetadataInstruction::Initialize(Initialize {
+                name: "Token".into(),
+                symbol: "TKN".into(),
+                uri: "https://example.com/token.json".into(),
+            })
+        );
+        let accounts: Vec<_> = initialize.accounts.iter().map(|meta| (meta.pubkey.clone(), meta.is_signer)).collect();
+        assert_eq!(
+            accounts,
+            [(mint.to_string(), false), (update_authority.to_string(), false), (mint.to_string(), false), (authority.to_string(), true)]
+        );
+
+        for (field, expected) in [("symbol", Field::Symbol), ("website", Field::Key("website".into()))] {
+            let update = service.update_token_metadata(&mint, &update_authority, field, "value".into()).unwrap();
+            assert_eq!(
+                TokenMetadataInstruction::unpack(&data(&update)).unwrap(),
+                TokenMetadataInstruction::UpdateField(UpdateField { field: expected, value: "value".into() })
+            );
+            assert!(update.accounts[1].is_signer);
+        }
+    }
+
+    #[test]
+    fn test_sign_relayed_transaction() {
+        let service = SolanaService::new();
+        let relayer = Keypair::new();
+        let user = Keypair::new();
+        let allowed = [solana_sdk::system_program::id(), spl_memo::id()];
+        let blockhash = Hash::new_unique();
+        let unsigned = |instructions: &[Instruction], fee_payer: &Pubkey| {
+            Transaction::new_unsigned(Message::new_with_blockhash(instructions, Some(fee_payer), &blockhash))
+        };
+        let user_signed = |instructions: &[Instruction]| {
+            let mut transaction = unsigned(instructions, &relayer.pubkey());
+            transaction.partial_sign(&[&user], blockhash);
+            transaction
+        };
+        let error = |transaction| service.sign_relayed_transaction(transaction, &relayer, &allowed).unwrap_err().to_string();
+        let transfer = system_instruction::transfer(&user.pubkey(), &Pubkey::new_unique(), 1);
+
+        // The relayer adds its fee payer signature, completing the transaction
+        let relayed = service.sign_relayed_transaction(user_signed(std::slice::from_ref(&transfer)), &relayer, &allowed).unwrap();
+        assert!(relayed.verify().is_ok());
+        assert_eq!(relayed.message.account_keys[0], relayer.pubkey());
+
+        let mut other_payer = unsigned(std::slice::from_ref(&transfer), &user.pubkey());
+        other_payer.sign(&[&user], blockhash);
+        assert!(error(other_payer).contains("fee payer must be the relayer"));
+
+        // Spending the relayer's lamports is refused, even though the relayer would sign
+        let drain = system_instruction::transfer(&relayer.pubkey(), &user.pubkey(), 1_000_000_000);
+        assert!(error(user_signed(&[transfer.clone(), drain])).starts_with(&format!("Validation error: Instruction 1 uses the relayer {}", relayer.pubkey())));
+
+        let token_transfer = spl_token::instruction::transfer(&spl_token::id(), &Pubkey::new_unique(), &Pubkey::new_unique(), &user.pubkey(), &[], 1).unwrap();
+        assert_eq!(
+            error(user_signed(&[token_transfer])),
+            format!("Validation error: Instruction 0 calls program {}, which is not allowed for relaying", spl_token::id())
+        );
+
+        let missing = unsigned(&[transfer], &relayer.pubkey());
+        assert_eq!(error(missing), format!("Validation error: Transaction is missing signature(s) from: {}", user.pubkey()));
+    }
+
+    #[test]
+    fn test_sync_native_instruction() {
+        let service = SolanaService::new();
+        let account = Pubkey::new_unique();
+
+        let instruction = service.sync_native(&account).unwrap();
+        assert_eq!(instruction.program_id, spl_token::id().to_string());
+        assert_eq!(general_purpose::STANDARD.decode(&instruction.instruction_data).unwrap(), [17]);
+        let [meta] = instruction.accounts.as_slice() else {
+            panic!("expected one account");
+        };
+        assert_eq!(meta.pubkey, account.to_string());
+        assert!(meta.is_writable && !meta.is_signer);
+    }
+
+    #[test]
+    fn test_unwrap_sol_instruction() {
+        let service = SolanaService::new();
+        let owner = Pubkey::new_unique();
+
+        let response = service.unwrap_sol(&owner, None).unwrap();
+        let default_account = spl_associated_token_account::get_associated_token_address(&owner, &native_mint::id());
+        assert_eq!(response.account, default_account.to_string());
+        let [close] = response.instructions.as_slice() else {
+            panic!("expected one instruction");
+        };
+        // CloseAccount (tag 9): account, destination, authority
+        assert_eq!(general_purpose::STANDARD.decode(&close.instruction_data).unwrap(), [9]);
+        let expected = [default_account, owner, owner].map(|key| key.to_string());
+        assert_eq!(close.accounts.iter().map(|a| a.pubkey.clone()).collect::<Vec<_>>(), expected);
+
+        let other = Pubkey::new_unique();
+        let response = service.unwrap_sol(&owner, Some(&other)).unwrap();
+        assert_eq!(response.account, other.to_string());
+    }
+
+    #[test]
+    fn test_rent_exempt_minimum() {
+        let service = SolanaService::new();
+        assert_eq!(service.rent_exempt_minimum(0), 890880);
+        // An SPL token account
+        assert_eq!(service.rent_exempt_minimum(165), 2039280);
+    }
+
     #[test]
     fn test_send_sol_instruction() {
         let service = SolanaService::new();
@@ -443,4 +2504,84 @@ mod tests {
         let result = service.send_sol(from, to, 0);
         assert!(result.is_err());
     }
-} 
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Messages including empty, arbitrary Unicode, raw bytes (lossily decoded, so control
+        /// characters and NULs appear) and large inputs
+        fn message() -> impl Strategy<Value = String> {
+            prop_oneof![
+                Just(String::new()),
+                any::<String>(),
+                prop::collection::vec(any::<u8>(), 0..512).prop_map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+                prop::collection::vec(any::<char>(), 16_384..65_536).prop_map(|chars| chars.into_iter().collect()),
+            ]
+        }
+
+        /// A random keypair, built from a generated seed so failures shrink and reproduce
+        fn secret() -> impl Strategy<Value = String> {
+            any::<[u8; 32]>().prop_map(|seed| SolanaService::new().keypair_from_seed(&seed).unwrap().secret.clone())
+        }
+
+        fn is_valid(service: &SolanaService, message: &str, signature: &str, pubkey: &str, strict: bool) -> bool {
+            // Tampered signatures may fail to parse, which counts as not valid
+            matches!(service.verify_message(message, None, signature, pubkey, strict), Ok(response) if response.valid)
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(64))]
+
+            #[test]
+            fn sign_then_verify_is_valid(message in message(), secret in secret(), strict in any::<bool>()) {
+                let service = SolanaService::new();
+                let signed = service.sign_message(&message, None, &secret).unwrap();
+
+                prop_assert!(is_valid(&service, &message, &signed.signature, &signed.pubkey, strict));
+            }
+
+            #[test]
+            fn tampered_signature_is_invalid(message in message(), secret in secret(), index in 0..64usize, bit in 0..8u8) {
+                let service = SolanaService::new();
+                let signed = service.sign_message(&message, None, &secret).unwrap();
+
+                let mut signature = bs58::decode(&signed.signature).into_vec().unwrap();
+                signature[index] ^= 1 << bit;
+                let signature = bs58::encode(signature).into_string();
+
+                prop_assert!(!is_valid(&service, &message, &signature, &signed.pubkey, false));
+                prop_assert!(!is_valid(&service, &message, &signature, &signed.pubkey, true));
+            }
+
+            #[test]
+            fn tampered_message_is_invalid(message in message(), secret in secret(), index in any::<prop::sample::Index>(), bit in 0..8u8) {
+                let service = SolanaService::new();
+                let signed = service.sign_message(&message, None, &secret).unwrap();
+
+                // Flip one bit of the message; if that breaks UTF-8, append a byte instead
+                let mut bytes = message.clone().into_bytes();
+                let tampered = if bytes.is_empty() {
+                    "\0".to_string()
+                } else {
+                    let position = index.index(bytes.len());
+                    bytes[position] ^= 1 << bit;
+                    String::from_utf8(bytes).unwrap_or_else(|_| format!("{}\0", message))
+                };
+                prop_assert_ne!(&tampered, &message);
+
+                prop_assert!(!is_valid(&service, &tampered, &signed.signature, &signed.pubkey, false));
+            }
+
+            #[test]
+            fn other_pubkey_is_invalid(message in message(), secret in secret(), other in secret()) {
+                prop_assume!(secret != other);
+                let service = SolanaService::new();
+                let signed = service.sign_message(&message, None, &secret).unwrap();
+                let other_pubkey = service.sign_message("", None, &other).unwrap().pubkey;
+
+                prop_assert!(!is_valid(&service, &message, &signed.signature, &other_pubkey, false));
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use tracing::error;
+
+use crate::config::Config;
+use crate::services::pubsub::PubsubService;
+use crate::services::rpc::RpcService;
+use crate::services::solana::SolanaService;
+use crate::utils::audit::AuditLog;
+use crate::utils::challenge::ChallengeStore;
+
+/// Everything handlers share, built once at startup and cloned cheaply into each request
+///
+/// The RPC client, and the HTTP connection pool inside it, lives here rather than being
+/// created per request, so concurrent requests reuse open connections to the node.
+#[derive(Clone)]
+pub struct AppState {
+    pub config: Arc<Config>,
+    pub solana: Arc<SolanaService>,
+    pub rpc: Arc<RpcService>,
+    pub pubsub: Arc<PubsubService>,
+    /// Nonces issued by /auth/challenge and not yet redeemed
+    pub challenges: Arc<ChallengeStore>,
+    /// Record of key generation, signing and verification
+    pub audit: Arc<AuditLog>,
+}
+
+impl AppState {
+    /// Builds the shared services from the configuration; no network calls are made
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            solana: Arc::new(SolanaService::with_binary_encoding(config.binary_encoding)),
+            rpc: Arc::new(RpcService::new(&config.rpc)),
+            pubsub: Arc::new(PubsubService::new(&config.rpc)),
+            challenges: Arc::new(ChallengeStore::new(config.challenge_ttl)),
+            // Validating the configuration already opened the sink once, so this only fails if
+            // the file was removed or made unwritable since; stdout keeps the events
+            audit: Arc::new(AuditLog::open(&config.audit_log).unwrap_or_else(|e| {
+                error!("Failed to open the audit log, writing audit events to stdout instead: {}", e);
+                AuditLog::to_writer(std::io::stdout())
+            })),
+            config,
+        }
+    }
+}
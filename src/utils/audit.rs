@@ -0,0 +1,172 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::Mutex;
+
+use axum::http::StatusCode;
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use tracing::error;
+
+use crate::config::AuditSink;
+use crate::utils::errors::AppError;
+
+/// Outcome of an audited operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditResult {
+    Success,
+    /// A verification ran, and the signature didn't check out
+    Invalid,
+    /// The operation was rejected or failed, with the status the client got
+    Failure(StatusCode),
+}
+
+impl AuditResult {
+    pub fn failure(error: &AppError) -> Self {
+        AuditResult::Failure(error.status())
+    }
+
+    /// Success or Invalid, for a verification that ran
+    pub fn verified(valid: bool) -> Self {
+        if valid { AuditResult::Success } else { AuditResult::Invalid }
+    }
+}
+
+/// One line of the audit log
+///
+/// Only public keys are recorded. Nothing derived from a secret, nor any error message (which
+/// could quote the input), is ever written.
+#[derive(Serialize)]
+struct AuditEvent<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    timestamp: String,
+    operation: &'a str,
+    pubkeys: &'a [&'a str],
+    result: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+}
+
+/// Append-only record of every key generation, signing and verification, one JSON object per
+/// line, written to the configured `audit_log`
+pub struct AuditLog {
+    writer: Option<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl AuditLog {
+    /// Opens the sink, appending to a file sink if it already exists
+    pub fn open(sink: &AuditSink) -> std::io::Result<Self> {
+        Ok(match sink {
+            AuditSink::Off => Self { writer: None },
+            AuditSink::Stdout => Self::to_writer(std::io::stdout()),
+            AuditSink::File(path) => Self::to_writer(OpenOptions::new().create(true).append(true).open(path)?),
+        })
+    }
+
+    /// An audit log writing to `writer`
+    pub fn to_writer(writer: impl Write + Send + 'static) -> Self {
+        Self { writer: Some(Mutex::new(Box::new(writer))) }
+    }
+
+    /// Records that `operation` involving `pubkeys` ended with `result`
+    pub fn record(&self, operation: &str, pubkeys: &[&str], result: AuditResult) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+        let (result, status) = match result {
+            AuditResult::Success => ("success", None),
+            AuditResult::Invalid => ("invalid", None),
+            AuditResult::Failure(status) => ("failure", Some(status.as_u16())),
+        };
+        let event = AuditEvent {
+            kind: "audit",
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            operation,
+            pubkeys,
+            result,
+            status,
+        };
+
+        // One write per line, under the lock, so concurrent events never interleave
+        let mut line = serde_json::to_vec(&event).expect("audit events serialize");
+        line.push(b'\n');
+        let mut writer = writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(e) = writer.write_all(&line).and_then(|()| writer.flush()) {
+            error!("Failed to write audit event for {}: {}", operation, e);
+        }
+    }
+
+    /// An audit log kept in memory, and the buffer its lines are written to
+    #[cfg(test)]
+    pub(crate) fn in_memory() -> (Self, std::sync::Arc<Mutex<Vec<u8>>>) {
+        use std::sync::Arc;
+
+        struct Shared(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Shared {
+            fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(bytes)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        (Self::to_writer(Shared(buffer.clone())), buffer)
+    }
+}
+
+impl std::fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLog").field("enabled", &self.writer.is_some()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_json_lines() {
+        let (audit, buffer) = AuditLog::in_memory();
+        audit.record("message.sign", &["pubkey-one"], AuditResult::Success);
+        audit.record("message.verify", &["pubkey-two"], AuditResult::Invalid);
+        audit.record("transaction.sign", &[], AuditResult::Failure(StatusCode::BAD_REQUEST));
+
+        let buffer = buffer.lock().unwrap();
+        let events: Vec<serde_json::Value> = std::str::from_utf8(&buffer)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["type"], "audit");
+        assert_eq!(events[0]["operation"], "message.sign");
+        assert_eq!(events[0]["pubkeys"], serde_json::json!(["pubkey-one"]));
+        assert_eq!(events[0]["result"], "success");
+        assert!(events[0].get("status").is_none());
+        assert!(chrono::DateTime::parse_from_rfc3339(events[0]["timestamp"].as_str().unwrap()).is_ok());
+        assert_eq!(events[1]["result"], "invalid");
+        assert_eq!((&events[2]["result"], &events[2]["status"]), (&serde_json::json!("failure"), &serde_json::json!(400)));
+    }
+
+    #[test]
+    fn test_file_sink_appends() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        for operation in ["keypair.generate", "message.sign"] {
+            let audit = AuditLog::open(&AuditSink::File(path.clone())).unwrap();
+            audit.record(operation, &[], AuditResult::Success);
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let operations: Vec<String> = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["operation"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(operations, ["keypair.generate", "message.sign"]);
+    }
+}
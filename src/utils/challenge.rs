@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::services::siws::generate_nonce;
+
+/// Most unredeemed challenges kept at once, so a client requesting challenges in a loop can't
+/// grow the store without bound
+pub const MAX_OUTSTANDING_CHALLENGES: usize = 100_000;
+
+/// Why a nonce could not be redeemed
+#[derive(Debug, PartialEq, Eq)]
+pub enum RedeemError {
+    /// Never issued, or already redeemed
+    Unknown,
+    /// Issued, but not redeemed within the TTL
+    Expired,
+}
+
+/// In-memory store of the nonces handed out by /auth/challenge
+///
+/// Each nonce can be redeemed once, within the TTL of being issued. Redeeming removes it, so a
+/// signed challenge can't be replayed. Nonces are lost on restart.
+pub struct ChallengeStore {
+    ttl: Duration,
+    issued: Mutex<HashMap<String, Instant>>,
+}
+
+impl ChallengeStore {
+    /// Creates a store whose nonces expire `ttl` after they are issued
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            issued: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How long an issued nonce stays redeemable
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Issues a new random nonce, or None while MAX_OUTSTANDING_CHALLENGES are unredeemed
+    pub fn issue(&self) -> Option<String> {
+        let now = Instant::now();
+        let mut issued = self.issued.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        issued.retain(|_, since| now.duration_since(*since) < self.ttl);
+        if issued.len() >= MAX_OUTSTANDING_CHALLENGES {
+            return None;
+        }
+
+        let nonce = generate_nonce();
+        issued.insert(nonce.clone(), now);
+        Some(nonce)
+    }
+
+    /// Redeems `nonce`, which then can't be redeemed again
+    pub fn redeem(&self, nonce: &str) -> Result<(), RedeemError> {
+        let mut issued = self.issued.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match issued.remove(nonce) {
+            Some(since) if since.elapsed() < self.ttl => Ok(()),
+            Some(_) => Err(RedeemError::Expired),
+            None => Err(RedeemError::Unknown),
+        }
+    }
+}
+
+impl std::fmt::Debug for ChallengeStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChallengeStore").field("ttl", &self.ttl).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_is_redeemed_once() {
+        let store = ChallengeStore::new(Duration::from_secs(60));
+        let nonce = store.issue().unwrap();
+        assert_ne!(store.issue().unwrap(), nonce);
+
+        assert_eq!(store.redeem(&nonce), Ok(()));
+        assert_eq!(store.redeem(&nonce), Err(RedeemError::Unknown));
+        assert_eq!(store.redeem("never-issued"), Err(RedeemError::Unknown));
+    }
+
+    #[test]
+    fn test_nonces_expire() {
+        let store = ChallengeStore::new(Duration::ZERO);
+        let nonce = store.issue().unwrap();
+        assert_eq!(store.redeem(&nonce), Err(RedeemError::Expired));
+    }
+}
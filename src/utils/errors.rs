@@ -26,6 +26,7 @@ pub enum AppError {
     InvalidSignature(String),
     
     #[error("Signature verification failed")]
+    #[allow(dead_code)]
     SignatureVerificationFailed,
     
     #[error("Token operation failed: {0}")]
@@ -42,26 +43,121 @@ pub enum AppError {
     
     #[error("Validation error: {0}")]
     ValidationError(String),
+    
+    #[error("RPC error: {0}")]
+    RpcError(String),
+    
+    #[error("Not found: {0}")]
+    NotFound(String),
+    
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    #[error("RPC not configured: {0}")]
+    RpcNotConfigured(String),
+
+    #[error("Method not allowed: {0}")]
+    MethodNotAllowed(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Timed out: {0}")]
+    GatewayTimeout(String),
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::InvalidKeypair(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::InvalidPublicKey(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::InvalidSecretKey(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::InvalidSignature(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::SignatureVerificationFailed => (StatusCode::BAD_REQUEST, "Signature verification failed".to_string()),
-            AppError::TokenOperationFailed(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            AppError::SerializationError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            AppError::DeserializationError(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::InternalServerError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
+impl AppError {
+    /// The HTTP status this error is reported with
+    ///
+    /// This is the one place errors are mapped to statuses. There is deliberately no catch-all
+    /// arm, so a new variant doesn't compile until it is given a status here.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_)
+            | AppError::InvalidKeypair(_)
+            | AppError::InvalidPublicKey(_)
+            | AppError::InvalidSecretKey(_)
+            | AppError::InvalidSignature(_)
+            | AppError::SignatureVerificationFailed
+            | AppError::DeserializationError(_)
+            | AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::TokenOperationFailed(_)
+            | AppError::SerializationError(_)
+            | AppError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::RpcError(_) => StatusCode::BAD_GATEWAY,
+            AppError::ServiceUnavailable(_) | AppError::RpcNotConfigured(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::GatewayTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+        }
+    }
+
+    /// The response `code` for errors that need one more specific than their status's name
+    pub fn specific_code(&self) -> Option<&'static str> {
+        match self {
+            AppError::RpcNotConfigured(_) => Some("RPC_NOT_CONFIGURED"),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status and client-facing message this error is reported with
+    pub fn into_parts(self) -> (StatusCode, String) {
+        let status = self.status();
+        let message = match self {
+            AppError::SignatureVerificationFailed => "Signature verification failed".to_string(),
+            AppError::BadRequest(msg)
+            | AppError::InvalidKeypair(msg)
+            | AppError::InvalidPublicKey(msg)
+            | AppError::InvalidSecretKey(msg)
+            | AppError::InvalidSignature(msg)
+            | AppError::TokenOperationFailed(msg)
+            | AppError::SerializationError(msg)
+            | AppError::DeserializationError(msg)
+            | AppError::InternalServerError(msg)
+            | AppError::ValidationError(msg)
+            | AppError::RpcError(msg)
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::RateLimited(msg)
+            | AppError::ServiceUnavailable(msg)
+            | AppError::RpcNotConfigured(msg)
+            | AppError::MethodNotAllowed(msg)
+            | AppError::UnsupportedMediaType(msg)
+            | AppError::GatewayTimeout(msg) => msg,
         };
+        (status, message)
+    }
 
-        let body = Json(ApiErrorResponse::error(&error_message));
-        (status, body).into_response()
+    /// The HTTP status and the error body this error is reported with
+    pub fn into_body(self) -> (StatusCode, ApiErrorResponse) {
+        let specific_code = self.specific_code();
+        let (status, error_message) = self.into_parts();
+        let mut body = ApiErrorResponse::error(&error_message).with_status_code(status);
+        if let Some(code) = specific_code {
+            body.code = Some(code.to_string());
+        }
+        (status, body)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, body) = self.into_body();
+        (status, Json(body)).into_response()
     }
 }
 
@@ -102,6 +198,120 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn test_rpc_error_into_response() {
+        let response = AppError::RpcError("node unavailable".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[test]
+    fn test_unauthorized_and_rate_limited_into_response() {
+        let response = AppError::Unauthorized("Missing X-API-Key header".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = AppError::RateLimited("Rate limit exceeded".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn test_service_unavailable_into_response() {
+        let response = AppError::ServiceUnavailable("All RPC connections are busy".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn test_conflict_into_response() {
+        let response = AppError::Conflict("Request in progress".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_not_found_into_response() {
+        let response = AppError::NotFound("Account not found".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// The example that follows `previous` in a walk over every variant, starting from `None`
+    ///
+    /// The match has no catch-all arm, so a new variant doesn't compile until it is added to
+    /// the walk, and test_every_variant_status then fails until its status is in EXPECTED.
+    fn next_variant(previous: Option<&AppError>) -> Option<AppError> {
+        let example = || "example".to_string();
+        match previous {
+            None => Some(AppError::BadRequest(example())),
+            Some(AppError::BadRequest(_)) => Some(AppError::InvalidKeypair(example())),
+            Some(AppError::InvalidKeypair(_)) => Some(AppError::InvalidPublicKey(example())),
+            Some(AppError::InvalidPublicKey(_)) => Some(AppError::InvalidSecretKey(example())),
+            Some(AppError::InvalidSecretKey(_)) => Some(AppError::InvalidSignature(example())),
+            Some(AppError::InvalidSignature(_)) => Some(AppError::SignatureVerificationFailed),
+            Some(AppError::SignatureVerificationFailed) => Some(AppError::TokenOperationFailed(example())),
+            Some(AppError::TokenOperationFailed(_)) => Some(AppError::SerializationError(example())),
+            Some(AppError::SerializationError(_)) => Some(AppError::DeserializationError(example())),
+            Some(AppError::DeserializationError(_)) => Some(AppError::InternalServerError(example())),
+            Some(AppError::InternalServerError(_)) => Some(AppError::ValidationError(example())),
+            Some(AppError::ValidationError(_)) => Some(AppError::RpcError(example())),
+            Some(AppError::RpcError(_)) => Some(AppError::NotFound(example())),
+            Some(AppError::NotFound(_)) => Some(AppError::Conflict(example())),
+            Some(AppError::Conflict(_)) => Some(AppError::Unauthorized(example())),
+            Some(AppError::Unauthorized(_)) => Some(AppError::RateLimited(example())),
+            Some(AppError::RateLimited(_)) => Some(AppError::ServiceUnavailable(example())),
+            Some(AppError::ServiceUnavailable(_)) => Some(AppError::RpcNotConfigured(example())),
+            Some(AppError::RpcNotConfigured(_)) => Some(AppError::MethodNotAllowed(example())),
+            Some(AppError::MethodNotAllowed(_)) => Some(AppError::UnsupportedMediaType(example())),
+            Some(AppError::UnsupportedMediaType(_)) => Some(AppError::GatewayTimeout(example())),
+            Some(AppError::GatewayTimeout(_)) => None,
+        }
+    }
+
+    /// Every variant's expected status and response `code`, by variant name
+    const EXPECTED: [(&str, StatusCode, &str); 21] = [
+        ("BadRequest", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("InvalidKeypair", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("InvalidPublicKey", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("InvalidSecretKey", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("InvalidSignature", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("SignatureVerificationFailed", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("TokenOperationFailed", StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_SERVER_ERROR"),
+        ("SerializationError", StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_SERVER_ERROR"),
+        ("DeserializationError", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("InternalServerError", StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_SERVER_ERROR"),
+        ("ValidationError", StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+        ("RpcError", StatusCode::BAD_GATEWAY, "BAD_GATEWAY"),
+        ("NotFound", StatusCode::NOT_FOUND, "NOT_FOUND"),
+        ("Conflict", StatusCode::CONFLICT, "CONFLICT"),
+        ("Unauthorized", StatusCode::UNAUTHORIZED, "UNAUTHORIZED"),
+        ("RateLimited", StatusCode::TOO_MANY_REQUESTS, "TOO_MANY_REQUESTS"),
+        ("ServiceUnavailable", StatusCode::SERVICE_UNAVAILABLE, "SERVICE_UNAVAILABLE"),
+        ("RpcNotConfigured", StatusCode::SERVICE_UNAVAILABLE, "RPC_NOT_CONFIGURED"),
+        ("MethodNotAllowed", StatusCode::METHOD_NOT_ALLOWED, "METHOD_NOT_ALLOWED"),
+        ("UnsupportedMediaType", StatusCode::UNSUPPORTED_MEDIA_TYPE, "UNSUPPORTED_MEDIA_TYPE"),
+        ("GatewayTimeout", StatusCode::GATEWAY_TIMEOUT, "GATEWAY_TIMEOUT"),
+    ];
+
+    #[tokio::test]
+    async fn test_every_variant_status() {
+        let mut variants = Vec::new();
+        while let Some(next) = next_variant(variants.last()) {
+            variants.push(next);
+        }
+        assert_eq!(variants.len(), EXPECTED.len());
+
+        for error in variants {
+            let debug = format!("{:?}", error);
+            let name = debug.split('(').next().unwrap();
+            let &(_, status, code) = EXPECTED
+                .iter()
+                .find(|(variant, _, _)| *variant == name)
+                .unwrap_or_else(|| panic!("no expected status for AppError::{}", name));
+
+            let response = error.into_response();
+            assert_eq!(response.status(), status, "status of AppError::{}", name);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: ApiErrorResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body.code.as_deref(), Some(code), "code of AppError::{}", name);
+            assert!(!body.success);
+        }
+    }
+
     #[test]
     fn test_error_display() {
         let error = AppError::InvalidPublicKey("test key".to_string());
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::http::{HeaderValue, StatusCode};
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+
+/// Header clients set to make a write request safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header added to responses replayed from the cache
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest Idempotency-Key accepted
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Identifies one idempotent request: the client's key plus a hash of the route and body, so
+/// reusing a key for a different request doesn't replay the wrong response
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    key: String,
+    request_hash: [u8; 32],
+}
+
+/// A successful response kept for replay
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub content_type: Option<HeaderValue>,
+    pub body: Bytes,
+}
+
+/// What to do with an incoming request
+#[derive(Debug)]
+pub enum Begin<'a> {
+    /// First time this request is seen: run it, then `complete` the pending entry
+    Started(PendingRequest<'a>),
+    /// The same request is still running (e.g. a client retried after a timeout)
+    InFlight,
+    /// The same request already succeeded: return this instead of running it again
+    Replay(CachedResponse),
+}
+
+enum Entry {
+    InFlight { since: Instant },
+    Completed { since: Instant, response: CachedResponse },
+}
+
+impl Entry {
+    fn since(&self) -> Instant {
+        match self {
+            Entry::InFlight { since } | Entry::Completed { since, .. } => *since,
+        }
+    }
+}
+
+/// In-memory cache of responses to requests sent with an Idempotency-Key
+///
+/// Only successful responses are kept, so a request that failed (including with a transient RPC
+/// error) can be retried with the same key. Entries expire after the TTL.
+pub struct IdempotencyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+}
+
+impl IdempotencyCache {
+    /// Creates a cache whose entries expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Looks up a request, marking it in flight if it hasn't been seen
+    pub fn begin(&self, key: &str, route: &str, body: &[u8]) -> Begin<'_> {
+        let cache_key = CacheKey {
+            key: key.to_string(),
+            request_hash: Sha256::new()
+                .chain_update(route.as_bytes())
+                .chain_update([0])
+                .chain_update(body)
+                .finalize()
+                .into(),
+        };
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.retain(|_, entry| now.duration_since(entry.since()) < self.ttl);
+
+        match entries.get(&cache_key) {
+            Some(Entry::Completed { response, .. }) => Begin::Replay(response.clone()),
+            Some(Entry::InFlight { .. }) => Begin::InFlight,
+            None => {
+                entries.insert(cache_key.clone(), Entry::InFlight { since: now });
+                Begin::Started(PendingRequest { cache: self, cache_key: Some(cache_key) })
+            }
+        }
+    }
+}
+
+/// A request marked in flight by `IdempotencyCache::begin`
+///
+/// Dropping it without calling `complete` (the request failed, or the client went away and the
+/// handler was cancelled) removes the entry, so the same key can be retried.
+#[derive(Debug)]
+pub struct PendingRequest<'a> {
+    cache: &'a IdempotencyCache,
+    cache_key: Option<CacheKey>,
+}
+
+impl PendingRequest<'_> {
+    /// Stores the successful response for replay
+    pub fn complete(mut self, response: CachedResponse) {
+        if let Some(cache_key) = self.cache_key.take() {
+            let mut entries = self.cache.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            entries.insert(cache_key, Entry::Completed { since: Instant::now(), response });
+        }
+    }
+}
+
+impl Drop for PendingRequest<'_> {
+    fn drop(&mut self) {
+        if let Some(cache_key) = self.cache_key.take() {
+            let mut entries = self.cache.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            entries.remove(&cache_key);
+        }
+    }
+}
+
+impl std::fmt::Debug for IdempotencyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdempotencyCache").field("ttl", &self.ttl).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            content_type: Some(HeaderValue::from_static("application/json")),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_completed_request_is_replayed() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+
+        let Begin::Started(pending) = cache.begin("key", "/rpc/send", b"{}") else {
+            panic!("first request should start");
+        };
+        assert!(matches!(cache.begin("key", "/rpc/send", b"{}"), Begin::InFlight));
+
+        pending.complete(response("first"));
+        let Begin::Replay(replayed) = cache.begin("key", "/rpc/send", b"{}") else {
+            panic!("completed request should be replayed");
+        };
+        assert_eq!(replayed.body, "first");
+    }
+
+    #[test]
+    fn test_key_is_scoped_to_route_and_body() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let Begin::Started(pending) = cache.begin("key", "/rpc/send", b"{}") else {
+            panic!("first request should start");
+        };
+        pending.complete(response("first"));
+
+        assert!(matches!(cache.begin("key", "/rpc/send", b"{\"other\":1}"), Begin::Started(_)));
+        assert!(matches!(cache.begin("key", "/rpc/airdrop", b"{}"), Begin::Started(_)));
+        assert!(matches!(cache.begin("other", "/rpc/send", b"{}"), Begin::Started(_)));
+    }
+
+    #[test]
+    fn test_dropped_request_can_be_retried() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let Begin::Started(pending) = cache.begin("key", "/rpc/send", b"{}") else {
+            panic!("first request should start");
+        };
+
+        drop(pending);
+        assert!(matches!(cache.begin("key", "/rpc/send", b"{}"), Begin::Started(_)));
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache = IdempotencyCache::new(Duration::ZERO);
+        let Begin::Started(pending) = cache.begin("key", "/rpc/send", b"{}") else {
+            panic!("first request should start");
+        };
+        pending.complete(response("first"));
+
+        assert!(matches!(cache.begin("key", "/rpc/send", b"{}"), Begin::Started(_)));
+    }
+}
@@ -1,5 +1,11 @@
 pub mod validation;
 pub mod errors;
+pub mod idempotency;
+pub mod ndjson;
+pub mod pagination;
+pub mod audit;
+pub mod challenge;
+pub mod rate_limit;
 
 // Re-export commonly used items for convenience - commented out to avoid unused warnings
 // pub use errors::{AppError, Result};
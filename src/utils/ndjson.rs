@@ -0,0 +1,72 @@
+use std::convert::Infallible;
+
+use axum::{
+    body::{Body, Bytes},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use futures::{future, Stream, StreamExt};
+use serde::Serialize;
+use tracing::error;
+
+use crate::utils::errors::{serialization_error, Result};
+
+/// Content type of a newline-delimited JSON response
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Streams `items` as newline-delimited JSON, one object per line, each serialized only as it
+/// is sent so the whole response is never held in memory
+///
+/// The `200` status has gone out before the first item is produced, so an item that fails is
+/// written as an error line (the usual `{"success": false, "error", "code"}` body) and ends
+/// the stream.
+pub fn ndjson_response<T, S>(items: S) -> Response
+where
+    T: Serialize,
+    S: Stream<Item = Result<T>> + Send + 'static,
+{
+    let lines = items.scan(false, |failed, item| {
+        if *failed {
+            return future::ready(None);
+        }
+        let line = item.and_then(|item| serde_json::to_vec(&item).map_err(serialization_error));
+        let mut line = line.unwrap_or_else(|e| {
+            error!("Ending NDJSON stream: {}", e);
+            *failed = true;
+            serde_json::to_vec(&e.into_body().1).expect("error bodies serialize")
+        });
+        line.push(b'\n');
+        future::ready(Some(Ok::<_, Infallible>(Bytes::from(line))))
+    });
+
+    ([(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)], Body::from_stream(lines)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::errors::AppError;
+
+    async fn lines(response: Response) -> Vec<serde_json::Value> {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        std::str::from_utf8(&body).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_items_are_lines() {
+        let response = ndjson_response(futures::stream::iter([1, 2, 3].map(Ok)));
+        assert_eq!(response.headers()[header::CONTENT_TYPE], NDJSON_CONTENT_TYPE);
+        assert_eq!(lines(response).await, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_a_failed_item_ends_the_stream() {
+        let items = [Ok(1), Err(AppError::TokenOperationFailed("boom".to_string())), Ok(3)];
+        let lines = lines(ndjson_response(futures::stream::iter(items))).await;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], 1);
+        assert_eq!(lines[1]["success"], false);
+        assert_eq!(lines[1]["error"], "boom");
+        assert_eq!(lines[1]["code"], "INTERNAL_SERVER_ERROR");
+    }
+}
@@ -0,0 +1,38 @@
+use crate::models::Pagination;
+
+/// Cuts `items` to the page of at most `limit` items after skipping `offset`, with the metadata
+/// describing that page
+///
+/// `items` must already be in a stable order, or consecutive pages can overlap or miss items.
+pub fn paginate<T>(items: Vec<T>, offset: usize, limit: usize) -> (Vec<T>, Pagination) {
+    let total = items.len();
+    let end = offset.saturating_add(limit);
+    let pagination = Pagination {
+        offset,
+        limit,
+        total,
+        next_offset: (end < total).then_some(end),
+    };
+    (items.into_iter().skip(offset).take(limit).collect(), pagination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let (page, pagination) = paginate((0..5).collect(), 0, 2);
+        assert_eq!(page, [0, 1]);
+        assert_eq!((pagination.total, pagination.next_offset), (5, Some(2)));
+
+        let (page, pagination) = paginate((0..5).collect(), 4, 2);
+        assert_eq!(page, [4]);
+        assert_eq!(pagination.next_offset, None);
+
+        // Past the end is an empty last page, not an error
+        let (page, pagination) = paginate((0..5).collect::<Vec<_>>(), usize::MAX, 2);
+        assert!(page.is_empty());
+        assert_eq!((pagination.offset, pagination.next_offset), (usize::MAX, None));
+    }
+}
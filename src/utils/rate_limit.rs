@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Length of one rate-limit window
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Fixed-window request counter per client (API key or IP address)
+///
+/// Each client may make `limit` requests per minute, counted from its first request in the
+/// window. Windows that have ended are dropped as new requests arrive, so idle clients don't
+/// accumulate.
+pub struct RateLimiter {
+    limit: u32,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// Allows `limit` requests per client per minute
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts a request from `client`, returning how long it must wait if it is over the limit
+    pub fn check(&self, client: &str) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let mut windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        windows.retain(|_, (started, _)| now.duration_since(*started) < WINDOW);
+
+        let (started, count) = windows.entry(client.to_string()).or_insert((now, 0));
+        if *count >= self.limit {
+            return Err(WINDOW.saturating_sub(now.duration_since(*started)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_per_client_and_window() {
+        let limiter = RateLimiter::new(2);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start).is_ok());
+        assert!(limiter.check_at("a", start + Duration::from_secs(1)).is_ok());
+        let retry_after = limiter.check_at("a", start + Duration::from_secs(15)).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(45));
+
+        // Other clients have their own budget
+        assert!(limiter.check_at("b", start + Duration::from_secs(15)).is_ok());
+
+        // A new window starts a minute after the first request
+        assert!(limiter.check_at("a", start + WINDOW).is_ok());
+    }
+}
@@ -1,11 +1,68 @@
-use solana_sdk::pubkey::Pubkey;
+use solana_account_decoder::UiAccountEncoding;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    message::Message,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    sanitize::Sanitize,
+    signature::Signature,
+    system_instruction::MAX_PERMITTED_DATA_LENGTH,
+    transaction::Transaction,
+};
+use std::collections::HashMap;
+use std::ops::Range;
 use std::str::FromStr;
+use base64::{Engine as _, engine::general_purpose};
+use bip39::{Language, Mnemonic};
 use bs58;
+use serde::de::DeserializeOwned;
+use spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+use zeroize::Zeroizing;
 
-use super::errors::{AppError, Result};
+use super::errors::{AppError, Result, base64_decode_error};
+use crate::services::entropy::{DEFAULT_ENTROPY_SAMPLE, MAX_ENTROPY_SAMPLE, MIN_ENTROPY_SAMPLE};
+
+/// Strips the leading/trailing whitespace and newlines that copy-paste tends to add;
+/// interior characters are left alone so a key with a space in the middle still fails
+pub fn normalize_input(value: &str) -> &str {
+    value.trim()
+}
+
+/// Remembers the public keys already parsed while validating one request, so a key repeated
+/// across a batch (the same destination minted to many times, say) is only decoded once
+///
+/// Only keys that parse are cached; an invalid key is validated, and reported with its own
+/// field name, every time it appears.
+#[derive(Debug, Default)]
+pub struct PubkeyCache {
+    parsed: HashMap<String, Pubkey>,
+}
+
+impl PubkeyCache {
+    /// Validates `key` like `validate_pubkey`, returning the cached key when it was seen before
+    pub fn validate(&mut self, key: &str, field_name: &str) -> Result<Pubkey> {
+        if let Some(pubkey) = self.parsed.get(key) {
+            return Ok(*pubkey);
+        }
+        let pubkey = validate_pubkey(key, field_name)?;
+        self.parsed.insert(key.to_string(), pubkey);
+        Ok(pubkey)
+    }
+
+    /// Number of distinct valid keys seen so far
+    pub fn len(&self) -> usize {
+        self.parsed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parsed.is_empty()
+    }
+}
 
 /// Validates that a string is a valid base58-encoded Solana public key
 pub fn validate_pubkey(key: &str, field_name: &str) -> Result<Pubkey> {
+    let key = normalize_input(key);
     if key.is_empty() {
         return Err(AppError::ValidationError(format!("{} is required", field_name)));
     }
@@ -41,14 +98,323 @@ pub fn validate_pubkey(key: &str, field_name: &str) -> Result<Pubkey> {
         })
 }
 
+/// Validates a public key given as an authority, which must be able to sign: the all-zeros
+/// address is the system program's, and no key can sign for it
+pub fn validate_authority(key: &str, field_name: &str) -> Result<Pubkey> {
+    let pubkey = validate_pubkey(key, field_name)?;
+    if pubkey == Pubkey::default() {
+        return Err(AppError::ValidationError(format!(
+            "{} can't be {}, the all-zeros address, which no key can sign for",
+            field_name, pubkey
+        )));
+    }
+    Ok(pubkey)
+}
+
 /// Validates that an amount is positive (greater than 0)
 pub fn validate_positive_amount(amount: u64, field_name: &str) -> Result<u64> {
     if amount == 0 {
-        return Err(AppError::ValidationError("Amount must be greater than 0".to_string()));
+        return Err(AppError::ValidationError(format!("{} must be greater than 0", field_name)));
     }
     Ok(amount)
 }
 
+/// Validates a SOL amount and converts it to lamports
+pub fn validate_sol_amount(sol: f64) -> Result<u64> {
+    if !sol.is_finite() || sol <= 0.0 {
+        return Err(AppError::ValidationError("sol must be greater than 0".to_string()));
+    }
+
+    let lamports = (sol * LAMPORTS_PER_SOL as f64).round();
+    if lamports < 1.0 {
+        return Err(AppError::ValidationError("sol must be at least 1 lamport".to_string()));
+    }
+    if lamports >= u64::MAX as f64 {
+        return Err(AppError::ValidationError("sol amount is too large".to_string()));
+    }
+
+    Ok(lamports as u64)
+}
+
+/// Validates an account data length against the runtime's 10 MiB account size limit
+pub fn validate_data_len(data_len: u64) -> Result<usize> {
+    if data_len > MAX_PERMITTED_DATA_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "data_len must be at most {} bytes",
+            MAX_PERMITTED_DATA_LENGTH
+        )));
+    }
+    Ok(data_len as usize)
+}
+
+/// Validates the sample size of an entropy check, defaulting to DEFAULT_ENTROPY_SAMPLE
+pub fn validate_entropy_sample(sample: Option<usize>) -> Result<usize> {
+    match sample.unwrap_or(DEFAULT_ENTROPY_SAMPLE) {
+        sample @ MIN_ENTROPY_SAMPLE..=MAX_ENTROPY_SAMPLE => Ok(sample),
+        _ => Err(AppError::ValidationError(format!(
+            "sample must be between {} and {}",
+            MIN_ENTROPY_SAMPLE, MAX_ENTROPY_SAMPLE
+        ))),
+    }
+}
+
+/// Validates a 32-byte Ed25519 seed given as 64 hex characters or base64
+///
+/// The seed and the intermediate decoded bytes are zeroized when dropped.
+pub fn validate_seed(seed: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let seed = normalize_input(seed);
+    if seed.is_empty() {
+        return Err(AppError::ValidationError("seed is required".to_string()));
+    }
+
+    // 64 hex characters can't be 32 bytes of base64 (that is 43 or 44 characters), so there's no ambiguity
+    let bytes = Zeroizing::new(if seed.len() == 64 && seed.bytes().all(|b| b.is_ascii_hexdigit()) {
+        decode_hex(seed).ok_or_else(|| AppError::ValidationError("seed is not valid hex".to_string()))?
+    } else {
+        decode_base64(seed)
+            .map_err(|_| AppError::ValidationError("seed must be 32 bytes encoded as hex, base64 or base64url".to_string()))?
+    });
+
+    let mut decoded = Zeroizing::new([0u8; 32]);
+    if bytes.len() != decoded.len() {
+        return Err(AppError::ValidationError(format!("seed must be exactly 32 bytes, got {}", bytes.len())));
+    }
+    decoded.copy_from_slice(&bytes);
+    Ok(decoded)
+}
+
+/// Most keypairs one /keypair/derive/batch request derives
+pub const MAX_DERIVED_KEYPAIRS: u32 = 100;
+
+/// Validates a BIP39 English mnemonic: its word count, every word and the checksum
+pub fn validate_mnemonic(mnemonic: &str) -> Result<Mnemonic> {
+    let mnemonic = normalize_input(mnemonic);
+    if mnemonic.is_empty() {
+        return Err(AppError::ValidationError("mnemonic is required".to_string()));
+    }
+    // The bip39 errors name what is wrong without quoting the phrase
+    Mnemonic::from_phrase(mnemonic, Language::English)
+        .map_err(|e| AppError::ValidationError(format!("mnemonic is not a valid BIP39 English phrase: {}", e)))
+}
+
+/// Validates the account indexes `start_index..start_index + count` of a derivation, which must
+/// all be below 2^31, the hardened indexes m/44'/501'/i'/0' uses
+pub fn validate_derivation_range(start_index: u32, count: u32) -> Result<Range<u32>> {
+    if !(1..=MAX_DERIVED_KEYPAIRS).contains(&count) {
+        return Err(AppError::ValidationError(format!(
+            "count must be between 1 and {}",
+            MAX_DERIVED_KEYPAIRS
+        )));
+    }
+    match start_index.checked_add(count) {
+        Some(end) if end <= HARDENED_INDEX_LIMIT => Ok(start_index..end),
+        _ => Err(AppError::ValidationError(format!(
+            "start_index + count must be at most {}, the number of hardened account indexes",
+            HARDENED_INDEX_LIMIT
+        ))),
+    }
+}
+
+/// Hardened BIP32 child indexes are 0..2^31
+const HARDENED_INDEX_LIMIT: u32 = 1 << 31;
+
+/// Decodes standard base64, or unpadded base64url as JWT and other web tooling produce it
+///
+/// The two alphabets differ only in `+/` versus `-_`, so no value decodes to different bytes
+/// under each.
+pub fn decode_base64(value: &str) -> std::result::Result<Vec<u8>, base64::DecodeError> {
+    general_purpose::STANDARD
+        .decode(value)
+        .or_else(|e| general_purpose::URL_SAFE_NO_PAD.decode(value).map_err(|_| e))
+}
+
+/// Decodes `value`, sent in `field_name`, from a binary `encoding` other than base58: "hex",
+/// "base64" (standard, padded) or "base64url" (unpadded)
+fn decode_binary(value: &str, encoding: &str, field_name: &str) -> Result<Vec<u8>> {
+    let value = normalize_input(value);
+    if value.is_empty() {
+        return Err(AppError::ValidationError(format!("{} is required", field_name)));
+    }
+    let decoded = match encoding {
+        "hex" => decode_hex(value),
+        "base64" => general_purpose::STANDARD.decode(value).ok(),
+        _ => general_purpose::URL_SAFE_NO_PAD.decode(value).ok(),
+    };
+    decoded.ok_or_else(|| AppError::ValidationError(format!("{} is not valid {}", field_name, encoding)))
+}
+
+/// Decodes a hex string of either case; None if it has an odd length or a non-hex character
+pub fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Validates an Ed25519 public key in `encoding`: "base58" (a Solana address, the default),
+/// or the raw 32 bytes as "hex", "base64" or "base64url" as other ed25519 tooling produces them
+pub fn validate_encoded_pubkey(key: &str, encoding: Option<&str>, field_name: &str) -> Result<Pubkey> {
+    let bytes = match encoding.map(normalize_input) {
+        None | Some("base58") => return validate_pubkey(key, field_name),
+        Some(encoding @ ("hex" | "base64" | "base64url")) => decode_binary(key, encoding, field_name)?,
+        Some(other) => {
+            return Err(AppError::ValidationError(format!(
+                "public_key_encoding must be base58, hex, base64 or base64url, got '{}'",
+                other
+            )))
+        }
+    };
+
+    Pubkey::try_from(bytes.as_slice()).map_err(|_| {
+        AppError::ValidationError(format!("{} must be exactly 32 bytes, got {}", field_name, bytes.len()))
+    })
+}
+
+/// Decodes an Ed25519 public key written in any of base58, hex, base64 or base64url, returning
+/// it with the encoding it was read as
+///
+/// The encodings are tried in that order and the first to give 32 bytes wins. A Solana address
+/// is never 64 characters, so hex can't be mistaken for one, but a key that happens to be both
+/// valid base58 and base64url is read as base58; name the encoding to be sure. Text that decodes
+/// without being 32 bytes is reported with the length of its first decoding.
+pub fn detect_encoded_pubkey(key: &str, field_name: &str) -> Result<(Pubkey, &'static str)> {
+    let key = normalize_input(key);
+    if key.is_empty() {
+        return Err(AppError::ValidationError(format!("{} is required", field_name)));
+    }
+    let decodings = [
+        ("base58", bs58::decode(key).into_vec().ok()),
+        ("hex", decode_hex(key)),
+        ("base64", general_purpose::STANDARD.decode(key).ok()),
+        ("base64url", general_purpose::URL_SAFE_NO_PAD.decode(key).ok()),
+    ];
+
+    let decoded = decodings.iter().filter_map(|(encoding, bytes)| Some((*encoding, bytes.as_deref()?)));
+    if let Some((encoding, pubkey)) = decoded.clone().find_map(|(encoding, bytes)| Some((encoding, Pubkey::try_from(bytes).ok()?))) {
+        return Ok((pubkey, encoding));
+    }
+    Err(AppError::ValidationError(match decoded.clone().next() {
+        Some((encoding, bytes)) => format!("{} must be exactly 32 bytes, got {} as {}", field_name, bytes.len(), encoding),
+        None => format!("{} is not valid base58, hex, base64 or base64url", field_name),
+    }))
+}
+
+/// Decodes a base64, bincode-serialized value sent in `field_name` and runs its sanity checks
+fn decode_wire_format<T: DeserializeOwned + Sanitize>(value: &str, field_name: &str, kind: &str) -> Result<T> {
+    let value = normalize_input(value);
+    if value.is_empty() {
+        return Err(AppError::ValidationError(format!("{} is required", field_name)));
+    }
+
+    let bytes = decode_base64(value).map_err(base64_decode_error)?;
+    let decoded: T = bincode::deserialize(&bytes)
+        .map_err(|e| AppError::DeserializationError(format!("Invalid {}: {}", kind, e)))?;
+
+    decoded
+        .sanitize()
+        .map_err(|e| AppError::ValidationError(format!("Invalid {}: {}", kind, e)))?;
+    Ok(decoded)
+}
+
+/// Decodes a base64, bincode-serialized transaction and checks it is well formed; signatures
+/// may still be missing
+pub fn validate_transaction(transaction: &str) -> Result<Transaction> {
+    decode_wire_format(transaction, "transaction", "transaction")
+}
+
+/// Decodes a base64, bincode-serialized transaction and checks it is well formed and signed
+pub fn validate_signed_transaction(transaction: &str) -> Result<Transaction> {
+    let transaction = validate_transaction(transaction)?;
+    if transaction.signatures.is_empty() || !transaction.is_signed() {
+        return Err(AppError::ValidationError("Transaction is not fully signed".to_string()));
+    }
+
+    Ok(transaction)
+}
+
+/// Decodes a base64, bincode-serialized (legacy) transaction message and checks it is well formed
+pub fn validate_message(message: &str) -> Result<Message> {
+    decode_wire_format(message, "message", "message")
+}
+
+/// Most entries accepted in one batch request
+pub const MAX_BATCH_SIZE: usize = 500;
+
+/// Most entries in a batch whose results are streamed with `?stream=ndjson`, which never holds
+/// them all in memory
+pub const MAX_STREAMED_BATCH_SIZE: usize = 10_000;
+
+/// Validates that a batch has between 1 and MAX_BATCH_SIZE entries
+pub fn validate_batch_len(len: usize, field_name: &str) -> Result<()> {
+    validate_batch_len_up_to(len, MAX_BATCH_SIZE, field_name)
+}
+
+/// Validates that a batch has between 1 and `max` entries
+pub fn validate_batch_len_up_to(len: usize, max: usize, field_name: &str) -> Result<()> {
+    if len == 0 {
+        return Err(AppError::ValidationError(format!("{} must not be empty", field_name)));
+    }
+    if len > max {
+        return Err(AppError::ValidationError(format!(
+            "{} must have at most {} entries, got {}",
+            field_name, max, len
+        )));
+    }
+    Ok(())
+}
+
+/// Validates the `stream` query parameter, returning whether the response is streamed as
+/// newline-delimited JSON ("ndjson", the only format) rather than sent as one JSON body
+pub fn validate_stream(stream: Option<&str>) -> Result<bool> {
+    match stream.map(normalize_input) {
+        None => Ok(false),
+        Some("ndjson") => Ok(true),
+        Some(other) => Err(AppError::ValidationError(format!("stream must be ndjson, got '{}'", other))),
+    }
+}
+
+/// Page size used by paged RPC lists when none is requested, and the largest accepted
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+/// Validates a page size, defaulting to DEFAULT_PAGE_LIMIT
+pub fn validate_page_limit(limit: Option<usize>) -> Result<usize> {
+    match limit.unwrap_or(DEFAULT_PAGE_LIMIT) {
+        limit @ 1..=MAX_PAGE_LIMIT => Ok(limit),
+        _ => Err(AppError::ValidationError(format!("limit must be between 1 and {}", MAX_PAGE_LIMIT))),
+    }
+}
+
+/// Validates a base58-encoded blockhash
+pub fn validate_blockhash(blockhash: &str, field_name: &str) -> Result<Hash> {
+    Hash::from_str(normalize_input(blockhash))
+        .map_err(|_| AppError::ValidationError(format!("Invalid {}: expected a base58-encoded 32-byte hash", field_name)))
+}
+
+/// Validates a commitment level ("processed", "confirmed" or "finalized")
+pub fn validate_commitment(commitment: &str) -> Result<CommitmentConfig> {
+    match normalize_input(commitment) {
+        "processed" => Ok(CommitmentConfig::processed()),
+        "confirmed" => Ok(CommitmentConfig::confirmed()),
+        "finalized" => Ok(CommitmentConfig::finalized()),
+        _ => Err(AppError::ValidationError(
+            "commitment must be \"processed\", \"confirmed\" or \"finalized\"".to_string(),
+        )),
+    }
+}
+
+/// Validates an account data encoding ("base64" or "jsonParsed")
+pub fn validate_account_encoding(encoding: &str) -> Result<UiAccountEncoding> {
+    match encoding {
+        "base64" => Ok(UiAccountEncoding::Base64),
+        "jsonParsed" => Ok(UiAccountEncoding::JsonParsed),
+        _ => Err(AppError::ValidationError("encoding must be \"base64\" or \"jsonParsed\"".to_string())),
+    }
+}
+
 /// Validates that a string is non-empty
 pub fn validate_non_empty_string(value: &str, field_name: &str) -> Result<()> {
     if value.is_empty() {
@@ -57,15 +423,66 @@ pub fn validate_non_empty_string(value: &str, field_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Longest memo accepted, in UTF-8 bytes: short enough that the memo, a token transfer and an
+/// associated token account creation still fit in one transaction
+pub const MAX_MEMO_LEN: usize = 566;
+
+/// Validates that a memo is non-empty and at most MAX_MEMO_LEN bytes of UTF-8
+pub fn validate_memo(memo: &str) -> Result<()> {
+    if memo.is_empty() {
+        return Err(AppError::ValidationError("memo is required".to_string()));
+    }
+    if memo.len() > MAX_MEMO_LEN {
+        return Err(AppError::ValidationError(format!(
+            "memo must be at most {} bytes, got {}",
+            MAX_MEMO_LEN,
+            memo.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Validates a message to sign or verify: it must not be empty unless `allow_empty` is set, since
+/// an empty message usually means the client sent the wrong field
+///
+/// Any other message, including one of only whitespace, is accepted and signed byte for byte.
+pub fn validate_signable_message(message: &str, allow_empty: bool, field_name: &str) -> Result<()> {
+    if message.is_empty() && !allow_empty {
+        return Err(AppError::ValidationError(format!(
+            "{} must not be empty; set allow_empty to sign an empty message",
+            field_name
+        )));
+    }
+    Ok(())
+}
+
+/// Whether a message is non-empty but only whitespace, which is valid to sign but rarely intended
+pub fn is_blank_message(message: &str) -> bool {
+    !message.is_empty() && message.trim().is_empty()
+}
+
+/// Validates an optional message-signing prefix: when given it must not be empty, since an empty
+/// prefix signs the same bytes as no prefix at all
+pub fn validate_message_prefix(prefix: Option<&str>) -> Result<()> {
+    if prefix == Some("") {
+        return Err(AppError::ValidationError(
+            "prefix must not be empty; omit it to sign the message alone".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Validates that a string is a valid base58-encoded secret key (64 bytes when decoded)
 pub fn validate_secret_key(secret_key: &str) -> Result<()> {
     if secret_key.is_empty() {
         return Err(AppError::ValidationError("secret is required".to_string()));
     }
     
-    let decoded = bs58::decode(secret_key)
-        .into_vec()
-        .map_err(|_| AppError::InvalidSecretKey("Invalid secret key format".to_string()))?;
+    let decoded = Zeroizing::new(
+        bs58::decode(secret_key)
+            .into_vec()
+            .map_err(|_| AppError::InvalidSecretKey("Invalid secret key format".to_string()))?,
+    );
     
     if decoded.len() != 64 {
         return Err(AppError::InvalidSecretKey("Invalid secret key length".to_string()));
@@ -74,6 +491,19 @@ pub fn validate_secret_key(secret_key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validates a solana-keygen style keypair byte array (64 bytes: secret half followed by public half)
+pub fn validate_keypair_bytes(keypair: &[u8]) -> Result<()> {
+    if keypair.is_empty() {
+        return Err(AppError::ValidationError("keypair is required".to_string()));
+    }
+
+    if keypair.len() != 64 {
+        return Err(AppError::InvalidKeypair("Invalid keypair length: must be 64 bytes".to_string()));
+    }
+
+    Ok(())
+}
+
 /// Validates that a string is a valid base58-encoded signature
 pub fn validate_signature_format(signature: &str) -> Result<Vec<u8>> {
     if signature.is_empty() {
@@ -91,18 +521,263 @@ pub fn validate_signature_format(signature: &str) -> Result<Vec<u8>> {
     Ok(decoded)
 }
 
-/// Validates decimals for token creation (0-9 is standard range)
-pub fn validate_decimals(decimals: u8) -> Result<u8> {
-    if decimals > 9 {
-        return Err(AppError::ValidationError("decimals must be between 0 and 9".to_string()));
+/// Validates a base58 transaction signature, such as one returned by /rpc/send
+pub fn validate_signature(signature: &str) -> Result<Signature> {
+    let decoded = validate_signature_format(normalize_input(signature))?;
+    Signature::try_from(decoded.as_slice())
+        .map_err(|_| AppError::InvalidSignature("Invalid signature length: must be 64 bytes".to_string()))
+}
+
+/// Validates an Ed25519 signature in `encoding`: "base58" (as /message/sign returns it, the
+/// default), the raw 64 bytes as "hex", "base64" or "base64url", or "auto" to detect which of
+/// base64, base58 and hex it is
+pub fn validate_encoded_signature(signature: &str, encoding: Option<&str>) -> Result<Signature> {
+    let bytes = match encoding.map(normalize_input) {
+        None | Some("base58") => validate_signature_format(signature)?,
+        Some(encoding @ ("hex" | "base64" | "base64url")) => decode_binary(signature, encoding, "signature")?,
+        Some("auto") => return detect_encoded_signature(signature),
+        Some(other) => {
+            return Err(AppError::ValidationError(format!(
+                "signature_encoding must be base58, hex, base64, base64url or auto, got '{}'",
+                other
+            )))
+        }
+    };
+    Signature::try_from(bytes.as_slice())
+        .map_err(|_| AppError::InvalidSignature("Invalid signature length: must be 64 bytes".to_string()))
+}
+
+/// Decodes a 64-byte signature written in padded base64, base58 or hex, trying them in that
+/// order and using the first that gives 64 bytes
+///
+/// Only padded base64 is tried: 64 bytes of it always end in `==`, which neither base58 nor hex
+/// uses, and 128 hex characters are far too long to be 64 bytes of base58. So a well-formed
+/// signature is never read in the wrong encoding. Unpadded base64url is left out because a short
+/// base58 signature can also be 64 bytes of base64url; name that encoding instead. Text that
+/// decodes without being 64 bytes is reported with the length of its first decoding.
+pub fn detect_encoded_signature(signature: &str) -> Result<Signature> {
+    let signature = normalize_input(signature);
+    if signature.is_empty() {
+        return Err(AppError::ValidationError("signature is required".to_string()));
+    }
+    let decodings = [
+        ("base64", general_purpose::STANDARD.decode(signature).ok()),
+        ("base58", bs58::decode(signature).into_vec().ok()),
+        ("hex", decode_hex(signature)),
+    ];
+
+    let decoded = decodings.iter().filter_map(|(encoding, bytes)| Some((*encoding, bytes.as_deref()?)));
+    if let Some(signature) = decoded.clone().find_map(|(_, bytes)| Signature::try_from(bytes).ok()) {
+        return Ok(signature);
+    }
+    Err(AppError::InvalidSignature(match decoded.clone().next() {
+        Some((encoding, bytes)) => format!("Invalid signature length: must be 64 bytes, got {} as {}", bytes.len(), encoding),
+        None => "Invalid signature format: not valid base64, base58 or hex".to_string(),
+    }))
+}
+
+/// Most seeds /pda/derive accepts: MAX_SEEDS less the one find_program_address adds for the bump
+pub const MAX_PDA_SEEDS: usize = solana_sdk::pubkey::MAX_SEEDS - 1;
+
+/// Decodes program-derived address seeds, each plain UTF-8 text or prefixed with how it is
+/// encoded (`utf8:`, `pubkey:`, `hex:` or `base64:`), and checks the count and lengths
+pub fn validate_pda_seeds(seeds: &[String]) -> Result<Vec<Vec<u8>>> {
+    if seeds.len() > MAX_PDA_SEEDS {
+        return Err(AppError::ValidationError(format!(
+            "seeds may have at most {} entries, got {}",
+            MAX_PDA_SEEDS,
+            seeds.len()
+        )));
+    }
+
+    seeds
+        .iter()
+        .enumerate()
+        .map(|(i, seed)| {
+            let field_name = format!("seeds[{}]", i);
+            let bytes = match seed.split_once(':') {
+                Some(("utf8", text)) => text.as_bytes().to_vec(),
+                Some(("pubkey", key)) => validate_pubkey(key, &field_name)?.to_bytes().to_vec(),
+                Some((encoding @ ("hex" | "base64"), value)) => decode_binary(value, encoding, &field_name)?,
+                _ => seed.as_bytes().to_vec(),
+            };
+            if bytes.len() > solana_sdk::pubkey::MAX_SEED_LEN {
+                return Err(AppError::ValidationError(format!(
+                    "{} is {} bytes; seeds are at most {} bytes",
+                    field_name,
+                    bytes.len(),
+                    solana_sdk::pubkey::MAX_SEED_LEN
+                )));
+            }
+            Ok(bytes)
+        })
+        .collect()
+}
+
+/// Validates the token program an associated token account belongs to: the token program
+/// (the default) or Token-2022
+pub fn validate_token_program(token_program: Option<&str>) -> Result<Pubkey> {
+    let Some(token_program) = token_program else {
+        return Ok(spl_token::id());
+    };
+    let program_id = validate_pubkey(token_program, "token_program")?;
+    if program_id != spl_token::id() && program_id != spl_token_2022::id() {
+        return Err(AppError::ValidationError(format!(
+            "token_program must be the token program ({}) or Token-2022 ({}), got {}",
+            spl_token::id(),
+            spl_token_2022::id(),
+            program_id
+        )));
+    }
+    Ok(program_id)
+}
+
+/// Most decimals a token mint may have (0-9 is the standard range)
+pub const MAX_DECIMALS: u8 = 9;
+
+/// Validates decimals for token creation against the deployment's limit (at most MAX_DECIMALS)
+pub fn validate_decimals(decimals: u8, max_decimals: u8) -> Result<u8> {
+    if decimals > max_decimals {
+        return Err(AppError::ValidationError(format!("decimals must be between 0 and {}", max_decimals)));
     }
     Ok(decimals)
 }
 
+/// Validates a mint amount is positive and within the deployment's limit
+pub fn validate_mint_amount(amount: u64, max_mint_amount: u64, field_name: &str) -> Result<u64> {
+    let amount = validate_positive_amount(amount, field_name)?;
+    if amount > max_mint_amount {
+        return Err(AppError::ValidationError(format!(
+            "{} must be at most {}, got {}",
+            field_name, max_mint_amount, amount
+        )));
+    }
+    Ok(amount)
+}
+
+/// Checks every instruction of a message calls a program the server allows; an empty
+/// `allowed_programs` allows any program
+pub fn validate_programs_allowed(message: &Message, allowed_programs: &[Pubkey]) -> Result<()> {
+    if allowed_programs.is_empty() {
+        return Ok(());
+    }
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        let program_id = instruction.program_id(&message.account_keys);
+        if !allowed_programs.contains(program_id) {
+            return Err(AppError::ValidationError(format!(
+                "Instruction {} calls program {}, which is not in this server's allowed_programs",
+                index, program_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks a program an endpoint assembles instructions for is one the server allows
+pub fn validate_program_allowed(program_id: &Pubkey, allowed_programs: &[Pubkey]) -> Result<()> {
+    if allowed_programs.is_empty() || allowed_programs.contains(program_id) {
+        return Ok(());
+    }
+    Err(AppError::ValidationError(format!(
+        "Program {} is not in this server's allowed_programs",
+        program_id
+    )))
+}
+
+/// Longest token metadata name, symbol and uri, in bytes. The token-metadata program only bounds
+/// them by the account size; these are the Metaplex limits wallets and explorers expect.
+pub const MAX_METADATA_NAME_LEN: usize = 32;
+pub const MAX_METADATA_SYMBOL_LEN: usize = 10;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// Validates a token metadata field and its value against the metadata length limits. Any field
+/// other than name, symbol and uri is an additional key, limited like a name, whose value is
+/// limited like a uri.
+pub fn validate_metadata_field(field: &str, value: &str) -> Result<()> {
+    let max_len = match field {
+        "name" => MAX_METADATA_NAME_LEN,
+        "symbol" => MAX_METADATA_SYMBOL_LEN,
+        "uri" => MAX_METADATA_URI_LEN,
+        key => {
+            if key.is_empty() || key.len() > MAX_METADATA_NAME_LEN {
+                return Err(AppError::ValidationError(format!(
+                    "field must be between 1 and {} bytes, got {}",
+                    MAX_METADATA_NAME_LEN,
+                    key.len()
+                )));
+            }
+            MAX_METADATA_URI_LEN
+        }
+    };
+    if value.len() > max_len {
+        return Err(AppError::ValidationError(format!(
+            "{} must be at most {} bytes, got {}",
+            field,
+            max_len,
+            value.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Validates a Token-2022 transfer fee: at most 100% of the transfer, and a nonzero cap when any
+/// fee is charged, since a zero maximum_fee would silently make every fee zero
+pub fn validate_transfer_fee(transfer_fee_basis_points: u16, maximum_fee: u64) -> Result<()> {
+    if transfer_fee_basis_points > MAX_FEE_BASIS_POINTS {
+        return Err(AppError::ValidationError(format!(
+            "transfer_fee_basis_points must be at most {} (100%)",
+            MAX_FEE_BASIS_POINTS
+        )));
+    }
+    if transfer_fee_basis_points > 0 && maximum_fee == 0 {
+        return Err(AppError::ValidationError(
+            "maximum_fee must be greater than 0 when transfer_fee_basis_points is set".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pubkey_cache() {
+        let mut cache = PubkeyCache::default();
+        let key = Pubkey::new_unique();
+
+        assert_eq!(cache.validate(&key.to_string(), "recipients[0].destination").unwrap(), key);
+        assert_eq!(cache.validate(&key.to_string(), "recipients[1].destination").unwrap(), key);
+        assert_eq!(cache.len(), 1);
+
+        // Invalid keys are reported with the field they appeared in each time, and never cached
+        for field in ["recipients[2].destination", "recipients[3].destination"] {
+            match cache.validate("invalid", field) {
+                Err(AppError::InvalidPublicKey(message)) => assert!(message.contains(field), "{}", message),
+                other => panic!("expected an invalid key error, got {:?}", other),
+            }
+        }
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_authority() {
+        let key = Pubkey::new_unique();
+        assert_eq!(validate_authority(&key.to_string(), "mintAuthority").unwrap(), key);
+
+        // The all-zeros address is a valid pubkey, only not as an authority
+        let zeros = Pubkey::default().to_string();
+        assert_eq!(validate_pubkey(&zeros, "mint").unwrap(), Pubkey::default());
+        match validate_authority(&zeros, "mintAuthority") {
+            Err(AppError::ValidationError(message)) => assert_eq!(
+                message,
+                "mintAuthority can't be 11111111111111111111111111111111, the all-zeros address, which no key can sign for"
+            ),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+        assert!(matches!(validate_authority("invalid", "mintAuthority"), Err(AppError::InvalidPublicKey(_))));
+    }
+
     #[test]
     fn test_validate_pubkey_valid() {
         let valid_pubkey = "11111111111111111111111111111112";
@@ -117,6 +792,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_pubkey_paste_artifacts() {
+        let expected = Pubkey::from_str("11111111111111111111111111111112").unwrap();
+        for pasted in [
+            " 11111111111111111111111111111112",
+            "11111111111111111111111111111112\n",
+            "11111111111111111111111111111112\r\n",
+            "\t11111111111111111111111111111112  ",
+        ] {
+            assert_eq!(validate_pubkey(pasted, "test").unwrap(), expected, "{:?}", pasted);
+        }
+
+        // Interior whitespace is not removed
+        assert!(validate_pubkey("1111111111111111 1111111111111112", "test").is_err());
+        // Only whitespace is the same as missing
+        assert!(matches!(validate_pubkey(" \n", "test"), Err(AppError::ValidationError(_))));
+    }
+
     #[test]
     fn test_validate_pubkey_empty() {
         let result = validate_pubkey("", "test");
@@ -133,7 +826,394 @@ mod tests {
     #[test]
     fn test_validate_positive_amount_zero() {
         let result = validate_positive_amount(0, "amount");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(AppError::ValidationError(msg)) if msg == "amount must be greater than 0"));
+    }
+
+    #[test]
+    fn test_validate_token_limits() {
+        assert_eq!(validate_decimals(9, MAX_DECIMALS).unwrap(), 9);
+        assert!(matches!(validate_decimals(10, MAX_DECIMALS), Err(AppError::ValidationError(_))));
+        assert!(matches!(validate_decimals(3, 2), Err(AppError::ValidationError(message)) if message == "decimals must be between 0 and 2"));
+
+        assert_eq!(validate_mint_amount(u64::MAX, u64::MAX, "amount").unwrap(), u64::MAX);
+        assert_eq!(validate_mint_amount(100, 100, "amount").unwrap(), 100);
+        assert!(matches!(validate_mint_amount(101, 100, "amount"), Err(AppError::ValidationError(message)) if message == "amount must be at most 100, got 101"));
+        assert!(validate_mint_amount(0, 100, "amount").is_err());
+    }
+
+    #[test]
+    fn test_validate_keypair_bytes() {
+        assert!(validate_keypair_bytes(&[0u8; 64]).is_ok());
+        assert!(validate_keypair_bytes(&[]).is_err());
+        assert!(validate_keypair_bytes(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_validate_sol_amount() {
+        assert_eq!(validate_sol_amount(1.0).unwrap(), 1_000_000_000);
+        assert_eq!(validate_sol_amount(0.5).unwrap(), 500_000_000);
+        assert!(validate_sol_amount(0.0).is_err());
+        assert!(validate_sol_amount(-1.0).is_err());
+        assert!(validate_sol_amount(f64::NAN).is_err());
+        assert!(validate_sol_amount(1e-12).is_err());
+        assert!(validate_sol_amount(1e30).is_err());
+    }
+
+    #[test]
+    fn test_validate_data_len() {
+        assert_eq!(validate_data_len(0).unwrap(), 0);
+        assert_eq!(validate_data_len(165).unwrap(), 165);
+        assert_eq!(validate_data_len(10 * 1024 * 1024).unwrap(), 10 * 1024 * 1024);
+        assert!(validate_data_len(10 * 1024 * 1024 + 1).is_err());
+        assert!(validate_data_len(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_validate_programs_allowed() {
+        let payer = Pubkey::new_unique();
+        let memo = Pubkey::new_unique();
+        let message = Message::new(
+            &[
+                solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1),
+                solana_sdk::instruction::Instruction::new_with_bytes(memo, b"hello", vec![]),
+            ],
+            Some(&payer),
+        );
+
+        assert!(validate_program_allowed(&memo, &[]).is_ok());
+        assert!(matches!(
+            validate_program_allowed(&memo, &[spl_token::id()]),
+            Err(AppError::ValidationError(message)) if message == format!("Program {} is not in this server's allowed_programs", memo)
+        ));
+
+        assert!(validate_programs_allowed(&message, &[]).is_ok(), "an empty list allows every program");
+        assert!(validate_programs_allowed(&message, &[solana_sdk::system_program::id(), memo]).is_ok());
+        assert!(matches!(
+            validate_programs_allowed(&message, &[solana_sdk::system_program::id()]),
+            Err(AppError::ValidationError(message)) if message == format!("Instruction 1 calls program {}, which is not in this server's allowed_programs", memo)
+        ));
+    }
+
+    #[test]
+    fn test_validate_metadata_field() {
+        assert!(validate_metadata_field("name", &"n".repeat(MAX_METADATA_NAME_LEN)).is_ok());
+        assert!(validate_metadata_field("symbol", "").is_ok());
+        assert!(validate_metadata_field("uri", &"u".repeat(MAX_METADATA_URI_LEN)).is_ok());
+        assert!(matches!(validate_metadata_field("symbol", "ELEVENCHARS"), Err(AppError::ValidationError(message)) if message == "symbol must be at most 10 bytes, got 11"));
+        // Lengths are bytes, which is what the account stores
+        assert!(validate_metadata_field("name", &"é".repeat(17)).is_err());
+
+        assert!(validate_metadata_field("website", &"w".repeat(MAX_METADATA_URI_LEN)).is_ok());
+        assert!(validate_metadata_field("website", &"w".repeat(MAX_METADATA_URI_LEN + 1)).is_err());
+        assert!(matches!(validate_metadata_field("", "value"), Err(AppError::ValidationError(message)) if message == "field must be between 1 and 32 bytes, got 0"));
+    }
+
+    #[test]
+    fn test_validate_transfer_fee() {
+        assert!(validate_transfer_fee(0, 0).is_ok());
+        assert!(validate_transfer_fee(50, 5_000).is_ok());
+        assert!(validate_transfer_fee(MAX_FEE_BASIS_POINTS, 1).is_ok());
+        assert!(matches!(validate_transfer_fee(10_001, 1), Err(AppError::ValidationError(message)) if message == "transfer_fee_basis_points must be at most 10000 (100%)"));
+        assert!(matches!(validate_transfer_fee(50, 0), Err(AppError::ValidationError(message)) if message.starts_with("maximum_fee must be greater than 0")));
+    }
+
+    #[test]
+    fn test_validate_stream() {
+        assert!(!validate_stream(None).unwrap());
+        assert!(validate_stream(Some("ndjson")).unwrap());
+        assert!(matches!(validate_stream(Some("csv")), Err(AppError::ValidationError(message)) if message == "stream must be ndjson, got 'csv'"));
+        assert!(validate_batch_len_up_to(MAX_STREAMED_BATCH_SIZE, MAX_STREAMED_BATCH_SIZE, "entries").is_ok());
+        assert!(validate_batch_len_up_to(MAX_STREAMED_BATCH_SIZE + 1, MAX_STREAMED_BATCH_SIZE, "entries").is_err());
+    }
+
+    #[test]
+    fn test_validate_page_limit() {
+        assert_eq!(validate_page_limit(None).unwrap(), DEFAULT_PAGE_LIMIT);
+        assert_eq!(validate_page_limit(Some(1)).unwrap(), 1);
+        assert_eq!(validate_page_limit(Some(MAX_PAGE_LIMIT)).unwrap(), MAX_PAGE_LIMIT);
+        assert!(matches!(validate_page_limit(Some(0)), Err(AppError::ValidationError(message)) if message == "limit must be between 1 and 500"));
+        assert!(validate_page_limit(Some(MAX_PAGE_LIMIT + 1)).is_err());
+    }
+
+    #[test]
+    fn test_validate_entropy_sample() {
+        assert_eq!(validate_entropy_sample(None).unwrap(), DEFAULT_ENTROPY_SAMPLE);
+        assert_eq!(validate_entropy_sample(Some(64)).unwrap(), 64);
+        assert_eq!(validate_entropy_sample(Some(1024)).unwrap(), 1024);
+        assert!(matches!(validate_entropy_sample(Some(63)), Err(AppError::ValidationError(message)) if message == "sample must be between 64 and 1024"));
+        assert!(validate_entropy_sample(Some(1025)).is_err());
+    }
+
+    #[test]
+    fn test_validate_seed() {
+        let hex = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+        let seed = validate_seed(hex).unwrap();
+        assert_eq!(seed[0], 0x9d);
+        assert_eq!(seed[31], 0x60);
+
+        let base64 = general_purpose::STANDARD.encode(*seed);
+        assert_eq!(validate_seed(&base64).unwrap(), seed);
+        assert_eq!(validate_seed(&general_purpose::URL_SAFE_NO_PAD.encode(*seed)).unwrap(), seed);
+        assert_eq!(validate_seed(&hex.to_uppercase()).unwrap(), seed);
+        assert_eq!(validate_seed(&format!("{}\n", hex)).unwrap(), seed);
+
+        assert!(validate_seed("").is_err());
+        assert!(validate_seed(&hex[..62]).is_err());
+        assert!(validate_seed(&general_purpose::STANDARD.encode([0u8; 31])).is_err());
+        assert!(validate_seed(&general_purpose::STANDARD.encode([0u8; 64])).is_err());
+        assert!(validate_seed("not a seed!").is_err());
+    }
+
+    #[test]
+    fn test_validate_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(validate_mnemonic(phrase).unwrap().phrase(), phrase);
+        assert_eq!(validate_mnemonic(&format!("  {}\n", phrase.replace(' ', "  "))).unwrap().phrase(), phrase);
+
+        assert!(matches!(validate_mnemonic(" "), Err(AppError::ValidationError(message)) if message == "mnemonic is required"));
+        // Every word is in the list, but the last one doesn't carry the checksum
+        let bad_checksum = phrase.replace("about", "abandon");
+        assert!(matches!(validate_mnemonic(&bad_checksum), Err(AppError::ValidationError(message)) if message.ends_with("invalid checksum")));
+        assert!(validate_mnemonic(&phrase.replace("about", "solana")).is_err());
+        assert!(validate_mnemonic("abandon abandon about").is_err());
+    }
+
+    #[test]
+    fn test_validate_derivation_range() {
+        assert_eq!(validate_derivation_range(0, 1).unwrap(), 0..1);
+        assert_eq!(validate_derivation_range(5, MAX_DERIVED_KEYPAIRS).unwrap(), 5..105);
+        assert_eq!(validate_derivation_range((1 << 31) - 1, 1).unwrap(), (1 << 31) - 1..1 << 31);
+
+        assert!(matches!(validate_derivation_range(0, 0), Err(AppError::ValidationError(message)) if message == "count must be between 1 and 100"));
+        assert!(validate_derivation_range(0, MAX_DERIVED_KEYPAIRS + 1).is_err());
+        assert!(validate_derivation_range(1 << 31, 1).is_err());
+        assert!(validate_derivation_range(u32::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_validate_signed_transaction() {
+        use solana_sdk::{hash::Hash, signature::{Keypair, Signer}, system_instruction};
+
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        let encode = |transaction: &Transaction| general_purpose::STANDARD.encode(bincode::serialize(transaction).unwrap());
+
+        // Unsigned
+        assert!(matches!(
+            validate_signed_transaction(&encode(&transaction)),
+            Err(AppError::ValidationError(message)) if message.contains("not fully signed")
+        ));
+
+        transaction.sign(&[&payer], Hash::new_unique());
+        let decoded = validate_signed_transaction(&encode(&transaction)).unwrap();
+        assert_eq!(decoded.signatures, transaction.signatures);
+        let base64url = general_purpose::URL_SAFE_NO_PAD.encode(bincode::serialize(&transaction).unwrap());
+        assert_eq!(validate_signed_transaction(&base64url).unwrap().signatures, transaction.signatures);
+
+        assert!(matches!(validate_signed_transaction(""), Err(AppError::ValidationError(_))));
+        assert!(matches!(validate_signed_transaction("not base64!"), Err(AppError::DeserializationError(_))));
+        assert!(matches!(validate_signed_transaction("AAAA"), Err(AppError::DeserializationError(_))));
+    }
+
+    #[test]
+    fn test_validate_message() {
+        let payer = Pubkey::new_unique();
+        let instruction = solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let message = Message::new(&[instruction], Some(&payer));
+
+        let encoded = general_purpose::STANDARD.encode(message.serialize());
+        assert_eq!(validate_message(&encoded).unwrap(), message);
+
+        // A header claiming more signers than there are accounts
+        let mut malformed = message.clone();
+        malformed.header.num_required_signatures = 9;
+        let encoded = general_purpose::STANDARD.encode(malformed.serialize());
+        assert!(matches!(validate_message(&encoded), Err(AppError::ValidationError(_))));
+
+        assert!(matches!(validate_message(""), Err(AppError::ValidationError(message)) if message == "message is required"));
+        assert!(matches!(validate_message("AAAA"), Err(AppError::DeserializationError(_))));
+    }
+
+    #[test]
+    fn test_validate_batch_len() {
+        assert!(validate_batch_len(1, "recipients").is_ok());
+        assert!(validate_batch_len(MAX_BATCH_SIZE, "recipients").is_ok());
+        assert!(matches!(validate_batch_len(0, "recipients"), Err(AppError::ValidationError(_))));
+        assert!(matches!(
+            validate_batch_len(MAX_BATCH_SIZE + 1, "recipients"),
+            Err(AppError::ValidationError(message)) if message == "recipients must have at most 500 entries, got 501"
+        ));
+    }
+
+    #[test]
+    fn test_validate_signable_message() {
+        assert!(validate_signable_message("hello", false, "message").is_ok());
+        assert!(validate_signable_message(" \n\t", false, "message").is_ok());
+        assert!(validate_signable_message("", true, "message").is_ok());
+        assert!(matches!(
+            validate_signable_message("", false, "messages[2]"),
+            Err(AppError::ValidationError(message)) if message.starts_with("messages[2] must not be empty")
+        ));
+
+        assert!(is_blank_message(" "));
+        assert!(is_blank_message("\r\n\n"));
+        assert!(!is_blank_message(""));
+        assert!(!is_blank_message(" a "));
+        // Zero-width characters aren't whitespace, so they're not treated as blank
+        assert!(!is_blank_message("\u{200b}"));
+    }
+
+    #[test]
+    fn test_validate_memo() {
+        assert!(validate_memo("deposit 1234").is_ok());
+        assert!(validate_memo(&"a".repeat(MAX_MEMO_LEN)).is_ok());
+        assert!(matches!(validate_memo(""), Err(AppError::ValidationError(message)) if message == "memo is required"));
+        // The limit counts bytes, so multi-byte characters reach it sooner
+        assert!(matches!(
+            validate_memo(&"é".repeat(MAX_MEMO_LEN / 2 + 1)),
+            Err(AppError::ValidationError(message)) if message == "memo must be at most 566 bytes, got 568"
+        ));
+    }
+
+    #[test]
+    fn test_validate_blockhash() {
+        let hash = Hash::new_unique();
+        assert_eq!(validate_blockhash(&format!(" {} ", hash), "recent_blockhash").unwrap(), hash);
+        assert!(matches!(validate_blockhash("", "recent_blockhash"), Err(AppError::ValidationError(_))));
+        assert!(matches!(validate_blockhash("abc", "recent_blockhash"), Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_validate_encoded_pubkey() {
+        let pubkey = Pubkey::new_unique();
+        let hex: String = pubkey.to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        let base64 = general_purpose::STANDARD.encode(pubkey.to_bytes());
+
+        assert_eq!(validate_encoded_pubkey(&pubkey.to_string(), None, "pubkey").unwrap(), pubkey);
+        assert_eq!(validate_encoded_pubkey(&pubkey.to_string(), Some("base58"), "pubkey").unwrap(), pubkey);
+        assert_eq!(validate_encoded_pubkey(&hex.to_uppercase(), Some("hex"), "pubkey").unwrap(), pubkey);
+        assert_eq!(validate_encoded_pubkey(&format!(" {}\n", base64), Some("base64"), "pubkey").unwrap(), pubkey);
+
+        let error = |key: &str, encoding| match validate_encoded_pubkey(key, Some(encoding), "pubkey") {
+            Err(AppError::ValidationError(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        };
+        assert_eq!(error(&hex[..62], "hex"), "pubkey must be exactly 32 bytes, got 31");
+        assert_eq!(error(&format!("{}0", hex), "hex"), "pubkey is not valid hex");
+        assert_eq!(error(&general_purpose::STANDARD.encode([1u8; 33]), "base64"), "pubkey must be exactly 32 bytes, got 33");
+        assert_eq!(error(&base64, "base32"), "public_key_encoding must be base58, hex, base64 or base64url, got 'base32'");
+        assert_eq!(error("", "hex"), "pubkey is required");
+
+        let base64url = general_purpose::URL_SAFE_NO_PAD.encode(pubkey.to_bytes());
+        assert_eq!(validate_encoded_pubkey(&base64url, Some("base64url"), "pubkey").unwrap(), pubkey);
+        // Each base64 selector takes only its own form
+        assert_eq!(error(&base64, "base64url"), "pubkey is not valid base64url");
+        assert_eq!(error(&base64url, "base64"), "pubkey is not valid base64");
+    }
+
+    #[test]
+    fn test_detect_encoded_pubkey() {
+        let pubkey = Pubkey::from([0x5a; 32]);
+        let hex: String = pubkey.to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        let base64 = general_purpose::STANDARD.encode(pubkey.to_bytes());
+        let base64url = general_purpose::URL_SAFE_NO_PAD.encode([0xfb; 32]);
+
+        assert_eq!(detect_encoded_pubkey(&pubkey.to_string(), "pubkey").unwrap(), (pubkey, "base58"));
+        assert_eq!(detect_encoded_pubkey(&hex.to_uppercase(), "pubkey").unwrap(), (pubkey, "hex"));
+        assert_eq!(detect_encoded_pubkey(&format!(" {}\n", base64), "pubkey").unwrap(), (pubkey, "base64"));
+        assert_eq!(detect_encoded_pubkey(&base64url, "pubkey").unwrap(), (Pubkey::from([0xfb; 32]), "base64url"));
+
+        let error = |key: &str| match detect_encoded_pubkey(key, "pubkey") {
+            Err(AppError::ValidationError(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        };
+        assert_eq!(error(&pubkey.to_string()[..40]), format!("pubkey must be exactly 32 bytes, got {} as base58", bs58::decode(&pubkey.to_string()[..40]).into_vec().unwrap().len()));
+        assert_eq!(error(&general_purpose::STANDARD.encode([0xfb; 33])), "pubkey must be exactly 32 bytes, got 33 as base64");
+        assert_eq!(error("0x!"), "pubkey is not valid base58, hex, base64 or base64url");
+        assert_eq!(error(" "), "pubkey is required");
+    }
+
+    #[test]
+    fn test_validate_encoded_signature() {
+        let signature = Signature::from([0xfb; 64]);
+        let base64url = general_purpose::URL_SAFE_NO_PAD.encode(signature.as_ref());
+        assert!(base64url.contains('-') && !base64url.ends_with('='));
+
+        assert_eq!(validate_encoded_signature(&signature.to_string(), None).unwrap(), signature);
+        assert_eq!(validate_encoded_signature(&base64url, Some("base64url")).unwrap(), signature);
+        let base64 = general_purpose::STANDARD.encode(signature.as_ref());
+        assert_eq!(validate_encoded_signature(&base64, Some("base64")).unwrap(), signature);
+
+        assert!(matches!(
+            validate_encoded_signature(&general_purpose::URL_SAFE_NO_PAD.encode([1u8; 63]), Some("base64url")),
+            Err(AppError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            validate_encoded_signature(&base64url, Some("base32")),
+            Err(AppError::ValidationError(message)) if message == "signature_encoding must be base58, hex, base64, base64url or auto, got 'base32'"
+        ));
+    }
+
+    #[test]
+    fn test_detect_encoded_signature() {
+        let signature = Signature::from([0xfb; 64]);
+        let hex: String = signature.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+        for encoded in [
+            signature.to_string(),
+            general_purpose::STANDARD.encode(signature.as_ref()),
+            hex.clone(),
+            hex.to_uppercase(),
+        ] {
+            assert_eq!(validate_encoded_signature(&encoded, Some("auto")).unwrap(), signature, "{}", encoded);
+        }
+        // Real signatures, which are mostly 87 or 88 base58 characters, are read the same way
+        let keypair = solana_sdk::signature::Keypair::new();
+        for message in ["a", "b", "c", "d"] {
+            let signature = solana_sdk::signature::Signer::sign_message(&keypair, message.as_bytes());
+            assert_eq!(detect_encoded_signature(&signature.to_string()).unwrap(), signature);
+            assert_eq!(detect_encoded_signature(&general_purpose::STANDARD.encode(signature.as_ref())).unwrap(), signature);
+        }
+
+        let error = |signature: &str| match detect_encoded_signature(signature) {
+            Err(AppError::InvalidSignature(message) | AppError::ValidationError(message)) => message,
+            other => panic!("expected an error, got {:?}", other),
+        };
+        // Unpadded base64url must be named explicitly
+        assert_eq!(
+            error(&general_purpose::URL_SAFE_NO_PAD.encode(signature.as_ref())),
+            "Invalid signature format: not valid base64, base58 or hex"
+        );
+        assert_eq!(error(&bs58::encode([0xfb; 63]).into_string()), "Invalid signature length: must be 64 bytes, got 63 as base58");
+        assert_eq!(error(""), "signature is required");
+    }
+
+    #[test]
+    fn test_validate_signature() {
+        let signature = Signature::from([7u8; 64]);
+        assert_eq!(validate_signature(&format!("{}\n", signature)).unwrap(), signature);
+        assert!(matches!(validate_signature(""), Err(AppError::ValidationError(_))));
+        assert!(matches!(validate_signature("0OIl"), Err(AppError::InvalidSignature(_))));
+        assert!(matches!(validate_signature(&bs58::encode([7u8; 32]).into_string()), Err(AppError::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn test_validate_commitment() {
+        assert_eq!(validate_commitment("processed").unwrap(), CommitmentConfig::processed());
+        assert_eq!(validate_commitment("confirmed").unwrap(), CommitmentConfig::confirmed());
+        assert_eq!(validate_commitment("finalized").unwrap(), CommitmentConfig::finalized());
+        // Deprecated aliases the SDK still parses are not accepted
+        assert!(validate_commitment("max").is_err());
+        assert!(validate_commitment("recent").is_err());
+        assert!(validate_commitment("").is_err());
+    }
+
+    #[test]
+    fn test_validate_account_encoding() {
+        assert_eq!(validate_account_encoding("base64").unwrap(), UiAccountEncoding::Base64);
+        assert_eq!(validate_account_encoding("jsonParsed").unwrap(), UiAccountEncoding::JsonParsed);
+        assert!(validate_account_encoding("base58").is_err());
+        assert!(validate_account_encoding("json").is_err());
     }
 
     #[test]
@@ -147,4 +1227,37 @@ mod tests {
         let result = validate_non_empty_string("", "message");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_pda_seeds() {
+        let mint = Pubkey::new_unique();
+        let seeds = [
+            "metadata".to_string(),
+            format!("pubkey:{}", mint),
+            "hex:01ff".to_string(),
+            "base64:AQI=".to_string(),
+            "utf8:hex:literal".to_string(),
+        ];
+        let decoded = validate_pda_seeds(&seeds).unwrap();
+        assert_eq!(decoded[0], b"metadata");
+        assert_eq!(decoded[1], mint.to_bytes());
+        assert_eq!(decoded[2], [1, 0xff]);
+        assert_eq!(decoded[3], [1, 2]);
+        assert_eq!(decoded[4], b"hex:literal");
+
+        let result = validate_pda_seeds(&["x".repeat(33)]);
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "seeds[0] is 33 bytes; seeds are at most 32 bytes"));
+        let result = validate_pda_seeds(&vec!["a".to_string(); 16]);
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message == "seeds may have at most 15 entries, got 16"));
+        assert!(matches!(validate_pda_seeds(&["hex:zz".to_string()]), Err(AppError::ValidationError(_))));
+        assert!(matches!(validate_pda_seeds(&["pubkey:bad".to_string()]), Err(AppError::InvalidPublicKey(_))));
+    }
+
+    #[test]
+    fn test_validate_token_program() {
+        assert_eq!(validate_token_program(None).unwrap(), spl_token::id());
+        assert_eq!(validate_token_program(Some(&spl_token_2022::id().to_string())).unwrap(), spl_token_2022::id());
+        let result = validate_token_program(Some("11111111111111111111111111111111"));
+        assert!(matches!(result, Err(AppError::ValidationError(message)) if message.starts_with("token_program must be")));
+    }
 } 